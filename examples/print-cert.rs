@@ -39,6 +39,8 @@ fn generalname_to_string(gn: &GeneralName) -> String {
         GeneralName::RFC822Name(n) => format!("RFC822Name:{}", n),
         GeneralName::RegisteredID(oid) => format!("RegisteredID:{}", oid),
         GeneralName::URI(n) => format!("URI:{}", n),
+        GeneralName::SmtpUTF8Mailbox(n) => format!("SmtpUTF8Mailbox:{}", n),
+        GeneralName::UserPrincipalName(n) => format!("UserPrincipalName:{}", n),
         GeneralName::X400Address(obj) => format!("X400Address:{:?}", obj),
     }
 }
@@ -125,6 +127,14 @@ fn print_x509_extension(oid: &Oid, ext: &X509Extension) {
         ParsedExtension::SubjectKeyIdentifier(id) => {
             println!("      X509v3 Subject Key Identifier: {:x}", id);
         }
+        ParsedExtension::UnsupportedExtension { .. } => match ext.debug_dump() {
+            Ok(dump) => {
+                for line in dump.lines() {
+                    println!("      {}", line);
+                }
+            }
+            Err(e) => println!("      <invalid DER value: {}>", e),
+        },
         x => println!("      {:?}", x),
     }
 }
@@ -49,6 +49,14 @@ fn print_x509_extension(oid: &Oid, ext: &X509Extension, level: usize) {
         ParsedExtension::InvalidityDate(date) => {
             println!("{:indent$}Invalidity Date: {}", "", date, indent = level);
         }
+        ParsedExtension::HoldInstructionCode(hold) => {
+            println!(
+                "{:indent$}Hold Instruction Code: {}",
+                "",
+                hold,
+                indent = level
+            );
+        }
         ParsedExtension::AuthorityKeyIdentifier(aki) => {
             println!(
                 "{:indent$}X509v3 Authority Key Identifier:",
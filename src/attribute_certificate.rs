@@ -0,0 +1,623 @@
+//! X.509 attribute certificates
+//!
+//! Attribute certificates are defined in [RFC5755](https://datatracker.ietf.org/doc/html/rfc5755).
+//! An attribute certificate binds privilege attributes (for ex. roles or groups) to a holder,
+//! usually identified by the issuer and serial number of its public key certificate.
+//!
+//! # Example
+//!
+//! To check the roles granted to the holder of a certificate:
+//!
+//! ```rust
+//! use x509_parser::attribute_certificate::AttributeCertificate;
+//! use x509_parser::prelude::*;
+//!
+//! # static AC: &'static [u8] = include_bytes!("../assets/test-ac.der");
+//! # static LEAF: &'static [u8] = include_bytes!("../assets/test-leaf.der");
+//! # fn main() {
+//! let (_, ac) = AttributeCertificate::from_der(AC).expect("could not parse attribute certificate");
+//! let (_, cert) = X509Certificate::from_der(LEAF).expect("could not parse certificate");
+//! assert!(ac.holder().matches(&cert));
+//! for role in ac.roles().expect("invalid role attribute") {
+//!     println!("Role: {}", role.role_name);
+//! }
+//! # }
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::error::{X509Error, X509Result};
+use crate::extensions::*;
+use crate::time::ASN1Time;
+use crate::utils::{format_serial, many0_lazy};
+use crate::x509::{parse_serial, parse_signature_value, AlgorithmIdentifier, X509Version};
+
+#[cfg(feature = "verify")]
+use crate::verify::verify_signature;
+#[cfg(feature = "verify")]
+use crate::x509::SubjectPublicKeyInfo;
+use asn1_rs::{oid, Any, BitString, Class, FromDer, Tag};
+use der_parser::der::*;
+use der_parser::oid::Oid;
+use nom::combinator::{all_consuming, complete, opt};
+use nom::multi::many1;
+use nom::{Err, Offset};
+use std::convert::TryFrom;
+
+/// id-at-role, the role attribute
+pub const OID_AT_ROLE: Oid<'static> = oid!(2.5.4 .72);
+/// id-aca-group, the group attribute
+pub const OID_ACA_GROUP: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .10 .4);
+
+/// An X.509 attribute certificate
+///
+/// <pre>
+/// AttributeCertificate ::= SEQUENCE {
+///     acinfo               AttributeCertificateInfo,
+///     signatureAlgorithm   AlgorithmIdentifier,
+///     signatureValue       BIT STRING }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct AttributeCertificate<'a> {
+    pub acinfo: AttributeCertificateInfo<'a>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature_value: BitString<'a>,
+}
+
+impl<'a> AttributeCertificate<'a> {
+    /// Get the holder of the attribute certificate
+    #[inline]
+    pub fn holder(&self) -> &Holder<'a> {
+        &self.acinfo.holder
+    }
+
+    /// Get the issuer of the attribute certificate
+    #[inline]
+    pub fn issuer(&self) -> &AttCertIssuer<'a> {
+        &self.acinfo.issuer
+    }
+
+    /// Get the raw bytes of the serial number
+    #[inline]
+    pub fn raw_serial(&self) -> &'a [u8] {
+        self.acinfo.raw_serial
+    }
+
+    /// Get a formatted string of the serial number, separated by ':'
+    pub fn raw_serial_as_string(&self) -> String {
+        format_serial(self.acinfo.raw_serial)
+    }
+
+    /// Get the attributes
+    #[inline]
+    pub fn attributes(&self) -> &[AcAttribute<'a>] {
+        &self.acinfo.attributes
+    }
+
+    /// Get the extensions
+    #[inline]
+    pub fn extensions(&self) -> &[X509Extension<'a>] {
+        &self.acinfo.extensions
+    }
+
+    /// Test if the attribute certificate is valid at the given date/time
+    pub fn is_valid_at(&self, time: ASN1Time) -> bool {
+        time >= self.acinfo.validity.not_before && time <= self.acinfo.validity.not_after
+    }
+
+    /// Test if the attribute certificate is currently valid
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_at(ASN1Time::now())
+    }
+
+    /// Return the values of the role attributes
+    ///
+    /// Returns `InvalidAttributeCertificate` if a role value is malformed.
+    pub fn roles(&self) -> Result<Vec<RoleSyntax<'a>>, X509Error> {
+        self.attribute_values(&OID_AT_ROLE)
+            .map(|value| RoleSyntax::from_der(value).map(|(_, role)| role))
+            .collect::<Result<_, _>>()
+            .or(Err(X509Error::InvalidAttributeCertificate))
+    }
+
+    /// Return the values of the group attributes
+    ///
+    /// Returns `InvalidAttributeCertificate` if a group value is malformed.
+    pub fn groups(&self) -> Result<Vec<IetfAttrSyntax<'a>>, X509Error> {
+        self.attribute_values(&OID_ACA_GROUP)
+            .map(|value| IetfAttrSyntax::from_der(value).map(|(_, group)| group))
+            .collect::<Result<_, _>>()
+            .or(Err(X509Error::InvalidAttributeCertificate))
+    }
+
+    /// Test if the certificate grants the role `name` (compared to the URI, DNS name or email
+    /// address of the role names)
+    pub fn has_role(&self, name: &str) -> bool {
+        self.roles()
+            .map(|roles| {
+                roles.iter().any(|role| match role.role_name {
+                    GeneralName::URI(s) | GeneralName::DNSName(s) | GeneralName::RFC822Name(s) => {
+                        s == name
+                    }
+                    _ => false,
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Test if the certificate grants membership of the group `name` (compared to the string
+    /// values of the group attributes)
+    pub fn has_group(&self, name: &str) -> bool {
+        self.groups()
+            .map(|groups| {
+                groups.iter().any(|group| {
+                    group
+                        .values
+                        .iter()
+                        .any(|value| matches!(value, IetfAttrValue::String(s) if *s == name))
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    // Return the raw values of all attributes of type `oid`
+    fn attribute_values<'b>(&'b self, oid: &'b Oid) -> impl Iterator<Item = &'a [u8]> + 'b {
+        self.acinfo
+            .attributes
+            .iter()
+            .filter(move |attr| attr.attr_type == *oid)
+            .flat_map(|attr| attr.values.iter().copied())
+    }
+
+    /// Verify the cryptographic signature of this attribute certificate
+    ///
+    /// `public_key` is the public key of the **issuer** (the attribute authority).
+    ///
+    /// Not all algorithms are supported, this function is limited to what `ring` supports.
+    #[cfg(feature = "verify")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+    pub fn verify_signature(&self, public_key: &SubjectPublicKeyInfo) -> Result<(), X509Error> {
+        verify_signature(
+            public_key,
+            &self.signature_algorithm,
+            &self.signature_value,
+            self.acinfo.raw,
+        )
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for AttributeCertificate<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, acinfo) = AttributeCertificateInfo::from_der(i)?;
+            let (i, signature_algorithm) = AlgorithmIdentifier::from_der(i)?;
+            let (i, signature_value) = parse_signature_value(i)?;
+            let ac = AttributeCertificate {
+                acinfo,
+                signature_algorithm,
+                signature_value,
+            };
+            Ok((i, ac))
+        })(i)
+    }
+}
+
+/// The signed part of an attribute certificate
+///
+/// <pre>
+/// AttributeCertificateInfo ::= SEQUENCE {
+///     version                 AttCertVersion, -- version is v2
+///     holder                  Holder,
+///     issuer                  AttCertIssuer,
+///     signature               AlgorithmIdentifier,
+///     serialNumber            CertificateSerialNumber,
+///     attrCertValidityPeriod  AttCertValidityPeriod,
+///     attributes              SEQUENCE OF Attribute,
+///     issuerUniqueID          UniqueIdentifier OPTIONAL,
+///     extensions              Extensions OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct AttributeCertificateInfo<'a> {
+    pub version: X509Version,
+    pub holder: Holder<'a>,
+    pub issuer: AttCertIssuer<'a>,
+    pub signature: AlgorithmIdentifier<'a>,
+    pub raw_serial: &'a [u8],
+    pub validity: AttCertValidityPeriod,
+    pub attributes: Vec<AcAttribute<'a>>,
+    pub issuer_unique_id: Option<BitString<'a>>,
+    pub extensions: Vec<X509Extension<'a>>,
+    pub(crate) raw: &'a [u8],
+}
+
+impl<'a> AttributeCertificateInfo<'a> {
+    /// Return the raw DER encoding of the signed data
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for AttributeCertificateInfo<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        let start_i = i;
+        parse_der_sequence_defined_g(move |i, _| {
+            let (i, version) = X509Version::from_der(i)?;
+            if version != X509Version::V2 {
+                return Err(Err::Error(X509Error::InvalidVersion));
+            }
+            let (i, holder) = Holder::from_der(i)?;
+            let (i, issuer) = AttCertIssuer::from_der(i)?;
+            let (i, signature) = AlgorithmIdentifier::from_der(i)?;
+            let (i, raw_serial) = parse_serial(i)?;
+            let (i, validity) = AttCertValidityPeriod::from_der(i)?;
+            let (i, attributes) = parse_der_sequence_defined_g(|a, _| {
+                all_consuming(many0_lazy(complete(AcAttribute::from_der)))(a)
+            })(i)?;
+            let (i, issuer_unique_id) = opt(complete(BitString::from_der))(i)
+                .or(Err(X509Error::InvalidAttributeCertificate))?;
+            let (i, extensions) = if i.is_empty() {
+                (i, Vec::new())
+            } else {
+                parse_extension_sequence(i)?
+            };
+            let len = start_i.offset(i);
+            let acinfo = AttributeCertificateInfo {
+                version,
+                holder,
+                issuer,
+                signature,
+                raw_serial,
+                validity,
+                attributes,
+                issuer_unique_id,
+                extensions,
+                raw: &start_i[..len],
+            };
+            Ok((i, acinfo))
+        })(i)
+    }
+}
+
+/// The holder of an attribute certificate
+///
+/// <pre>
+/// Holder ::= SEQUENCE {
+///     baseCertificateID   [0] IssuerSerial OPTIONAL,
+///     entityName          [1] GeneralNames OPTIONAL,
+///     objectDigestInfo    [2] ObjectDigestInfo OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holder<'a> {
+    pub base_certificate_id: Option<IssuerSerial<'a>>,
+    pub entity_name: Option<Vec<GeneralName<'a>>>,
+    /// The raw contents of the object digest info (not parsed)
+    pub object_digest_info: Option<&'a [u8]>,
+}
+
+impl<'a> Holder<'a> {
+    /// Test if the holder is the subject of the public key certificate `cert`
+    ///
+    /// The `baseCertificateID` matches if it contains the issuer and serial number of `cert`.
+    /// The `entityName` matches if one of its names is the subject of `cert`, or one of its
+    /// subject alternative names. All present fields must match.
+    ///
+    /// Holders identified only by an `objectDigestInfo` are not supported, and never match.
+    pub fn matches(&self, cert: &X509Certificate) -> bool {
+        if self.base_certificate_id.is_none() && self.entity_name.is_none() {
+            return false;
+        }
+        if let Some(issuer_serial) = &self.base_certificate_id {
+            if !issuer_serial.matches(cert) {
+                return false;
+            }
+        }
+        if let Some(names) = &self.entity_name {
+            let san = match cert.subject_alternative_name() {
+                Ok(Some(san)) => san.value.general_names.as_slice(),
+                _ => &[],
+            };
+            let matches = names.iter().any(|name| match name {
+                GeneralName::DirectoryName(dn) => dn.as_raw() == cert.subject().as_raw(),
+                name => san.contains(name),
+            });
+            if !matches {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for Holder<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, base_certificate_id) = opt(complete(|i| parse_implicit(i, 0)))(i)?;
+            let base_certificate_id = base_certificate_id
+                .map(|data| all_consuming(IssuerSerial::from_content)(data).map(|(_, v)| v))
+                .transpose()?;
+            let (i, entity_name) = opt(complete(|i| parse_implicit(i, 1)))(i)?;
+            let entity_name = entity_name.map(parse_generalnames_content).transpose()?;
+            let (i, object_digest_info) = opt(complete(|i| parse_implicit(i, 2)))(i)?;
+            let holder = Holder {
+                base_certificate_id,
+                entity_name,
+                object_digest_info,
+            };
+            Ok((i, holder))
+        })(i)
+    }
+}
+
+/// The issuer and serial number of a public key certificate
+///
+/// <pre>
+/// IssuerSerial  ::=  SEQUENCE {
+///     issuer         GeneralNames,
+///     serial         CertificateSerialNumber,
+///     issuerUID      UniqueIdentifier OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuerSerial<'a> {
+    pub issuer: Vec<GeneralName<'a>>,
+    pub raw_serial: &'a [u8],
+    pub issuer_uid: Option<BitString<'a>>,
+}
+
+impl<'a> IssuerSerial<'a> {
+    /// Test if this is the issuer and serial number of `cert`
+    pub fn matches(&self, cert: &X509Certificate) -> bool {
+        self.raw_serial == cert.raw_serial()
+            && self.issuer.iter().any(|name| match name {
+                GeneralName::DirectoryName(dn) => dn.as_raw() == cert.issuer().as_raw(),
+                _ => false,
+            })
+    }
+
+    // Parse the contents of an IssuerSerial (used with implicit tags)
+    fn from_content(i: &'a [u8]) -> X509Result<'a, Self> {
+        let (i, issuer) = parse_generalnames(i).or(Err(X509Error::InvalidAttributeCertificate))?;
+        let (i, raw_serial) = parse_serial(i)?;
+        let (i, issuer_uid) = opt(complete(BitString::from_der))(i)
+            .or(Err(X509Error::InvalidAttributeCertificate))?;
+        let issuer_serial = IssuerSerial {
+            issuer,
+            raw_serial,
+            issuer_uid,
+        };
+        Ok((i, issuer_serial))
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for IssuerSerial<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| Self::from_content(i))(i)
+    }
+}
+
+/// The issuer of an attribute certificate
+///
+/// <pre>
+/// AttCertIssuer ::= CHOICE {
+///     v1Form   GeneralNames,  -- MUST NOT be used in this profile
+///     v2Form   [0] V2Form     -- v2 only
+/// }
+///
+/// V2Form ::= SEQUENCE {
+///     issuerName            GeneralNames  OPTIONAL,
+///     baseCertificateID     [0] IssuerSerial  OPTIONAL,
+///     objectDigestInfo      [1] ObjectDigestInfo  OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttCertIssuer<'a> {
+    V1Form(Vec<GeneralName<'a>>),
+    V2Form {
+        issuer_name: Option<Vec<GeneralName<'a>>>,
+        base_certificate_id: Option<IssuerSerial<'a>>,
+        /// The raw contents of the object digest info (not parsed)
+        object_digest_info: Option<&'a [u8]>,
+    },
+}
+
+impl<'a> AttCertIssuer<'a> {
+    /// Return the names of the issuer
+    pub fn names(&self) -> &[GeneralName<'a>] {
+        match self {
+            AttCertIssuer::V1Form(names) => names,
+            AttCertIssuer::V2Form { issuer_name, .. } => issuer_name.as_deref().unwrap_or(&[]),
+        }
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for AttCertIssuer<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        if let Ok((rem, data)) = parse_implicit(i, 0) {
+            let (_, issuer) = all_consuming(|i| {
+                let (i, issuer_name) = opt(complete(parse_generalnames))(i)
+                    .or(Err(X509Error::InvalidAttributeCertificate))?;
+                let (i, base_certificate_id) = opt(complete(|i| parse_implicit(i, 0)))(i)?;
+                let base_certificate_id = base_certificate_id
+                    .map(|data| all_consuming(IssuerSerial::from_content)(data).map(|(_, v)| v))
+                    .transpose()?;
+                let (i, object_digest_info) = opt(complete(|i| parse_implicit(i, 1)))(i)?;
+                let issuer = AttCertIssuer::V2Form {
+                    issuer_name,
+                    base_certificate_id,
+                    object_digest_info,
+                };
+                Ok((i, issuer))
+            })(data)?;
+            return Ok((rem, issuer));
+        }
+        let (rem, names) = parse_generalnames(i).or(Err(X509Error::InvalidAttributeCertificate))?;
+        Ok((rem, AttCertIssuer::V1Form(names)))
+    }
+}
+
+/// The validity period of an attribute certificate
+///
+/// <pre>
+/// AttCertValidityPeriod  ::= SEQUENCE {
+///     notBeforeTime  GeneralizedTime,
+///     notAfterTime   GeneralizedTime }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttCertValidityPeriod {
+    pub not_before: ASN1Time,
+    pub not_after: ASN1Time,
+}
+
+impl<'a> FromDer<'a, X509Error> for AttCertValidityPeriod {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, not_before) = ASN1Time::from_der(i)?;
+            let (i, not_after) = ASN1Time::from_der(i)?;
+            let validity = AttCertValidityPeriod {
+                not_before,
+                not_after,
+            };
+            Ok((i, validity))
+        })(i)
+    }
+}
+
+/// An attribute of an attribute certificate
+///
+/// <pre>
+/// Attribute ::= SEQUENCE {
+///     type      AttributeType,
+///     values    SET OF AttributeValue }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcAttribute<'a> {
+    pub attr_type: Oid<'a>,
+    /// The raw DER encoding of the values
+    pub values: Vec<&'a [u8]>,
+}
+
+impl<'a> FromDer<'a, X509Error> for AcAttribute<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, attr_type) =
+                Oid::from_der(i).or(Err(X509Error::InvalidAttributeCertificate))?;
+            let (i, values) = parse_der_set_defined_g(|a, _| {
+                all_consuming(many0_lazy(complete(parse_raw_any)))(a)
+            })(i)?;
+            let attr = AcAttribute { attr_type, values };
+            Ok((i, attr))
+        })(i)
+    }
+}
+
+/// The value of a role attribute
+///
+/// <pre>
+/// RoleSyntax ::= SEQUENCE {
+///     roleAuthority  [0] GeneralNames OPTIONAL,
+///     roleName       [1] GeneralName }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoleSyntax<'a> {
+    pub role_authority: Option<Vec<GeneralName<'a>>>,
+    pub role_name: GeneralName<'a>,
+}
+
+impl<'a> FromDer<'a, X509Error> for RoleSyntax<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, role_authority) = opt(complete(|i| parse_implicit(i, 0)))(i)?;
+            let role_authority = role_authority.map(parse_generalnames_content).transpose()?;
+            // GeneralName is a CHOICE, so the tag is explicit
+            let (i, data) = parse_implicit(i, 1)?;
+            let (_, role_name) = all_consuming(parse_generalname)(data)
+                .or(Err(X509Error::InvalidAttributeCertificate))?;
+            let role = RoleSyntax {
+                role_authority,
+                role_name,
+            };
+            Ok((i, role))
+        })(i)
+    }
+}
+
+/// The value of a group (or other IETF) attribute
+///
+/// <pre>
+/// IetfAttrSyntax ::= SEQUENCE {
+///     policyAuthority [0] GeneralNames    OPTIONAL,
+///     values          SEQUENCE OF CHOICE {
+///                     octets    OCTET STRING,
+///                     oid       OBJECT IDENTIFIER,
+///                     string    UTF8String
+///    }
+/// }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct IetfAttrSyntax<'a> {
+    pub policy_authority: Option<Vec<GeneralName<'a>>>,
+    pub values: Vec<IetfAttrValue<'a>>,
+}
+
+/// A value of an [`IetfAttrSyntax`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum IetfAttrValue<'a> {
+    Octets(&'a [u8]),
+    Oid(Oid<'a>),
+    String(&'a str),
+}
+
+impl<'a> FromDer<'a, X509Error> for IetfAttrValue<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidAttributeCertificate))?;
+        let value = match any.tag() {
+            Tag::OctetString => IetfAttrValue::Octets(any.data),
+            Tag::Oid => IetfAttrValue::Oid(
+                Oid::try_from(any).or(Err(X509Error::InvalidAttributeCertificate))?,
+            ),
+            Tag::Utf8String => IetfAttrValue::String(
+                std::str::from_utf8(any.data).or(Err(X509Error::InvalidAttributeCertificate))?,
+            ),
+            _ => return Err(Err::Error(X509Error::InvalidAttributeCertificate)),
+        };
+        Ok((rem, value))
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for IetfAttrSyntax<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, policy_authority) = opt(complete(|i| parse_implicit(i, 0)))(i)?;
+            let policy_authority = policy_authority
+                .map(parse_generalnames_content)
+                .transpose()?;
+            let (i, values) = parse_der_sequence_defined_g(|a, _| {
+                all_consuming(many0_lazy(complete(IetfAttrValue::from_der)))(a)
+            })(i)?;
+            let attr = IetfAttrSyntax {
+                policy_authority,
+                values,
+            };
+            Ok((i, attr))
+        })(i)
+    }
+}
+
+// Parse a context-specific constructed element with tag `tag`, and return its contents
+fn parse_implicit(i: &[u8], tag: u32) -> X509Result<&[u8]> {
+    let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidAttributeCertificate))?;
+    if any.class() != Class::ContextSpecific || any.tag().0 != tag {
+        return Err(Err::Error(X509Error::InvalidAttributeCertificate));
+    }
+    Ok((rem, any.data))
+}
+
+// Parse the contents of an implicitly tagged GeneralNames
+fn parse_generalnames_content(i: &[u8]) -> Result<Vec<GeneralName>, Err<X509Error>> {
+    all_consuming(many1(complete(parse_generalname)))(i)
+        .map(|(_, names)| names)
+        .or(Err(Err::Error(X509Error::InvalidAttributeCertificate)))
+}
+
+// Return the raw DER encoding of the next element
+fn parse_raw_any(i: &[u8]) -> X509Result<&[u8]> {
+    let (rem, _) = Any::from_der(i).or(Err(X509Error::InvalidAttributeCertificate))?;
+    Ok((rem, &i[..i.offset(rem)]))
+}
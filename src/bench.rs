@@ -0,0 +1,316 @@
+//! Throughput measurement of the parsers, over corpora of objects
+//!
+//! This module measures the parsing throughput (objects per second and bytes per second) over
+//! a user-supplied corpus, for ex. a directory of certificates collected in production. It is
+//! meant to track performance regressions of the parser, and of the parsing modes
+//! (see [`ParseMode`]), across versions of this crate.
+//!
+//! Allocations cannot be counted without replacing the global allocator. To get allocation
+//! counts, install a counting allocator in the benchmark program, and register a function
+//! returning the number of allocations with [`BenchConfig::with_allocation_counter`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::bench::{run_certificates, BenchConfig, Corpus, ParseMode};
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let corpus = Corpus::load_dir("assets")?;
+//! let config = BenchConfig::new()
+//!     .with_iterations(2)
+//!     .with_mode(ParseMode::Lazy);
+//! let report = run_certificates(&corpus, &config);
+//! println!("{}", report);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::certificate::X509CertificateParser;
+use crate::pem::Pem;
+use nom::Parser;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// A set of DER-encoded inputs
+#[derive(Clone, Debug, Default)]
+pub struct Corpus {
+    inputs: Vec<(String, Vec<u8>)>,
+}
+
+impl Corpus {
+    /// Create an empty corpus
+    pub fn new() -> Self {
+        Corpus::default()
+    }
+
+    /// Load all files of a directory (not recursively), in file name order
+    ///
+    /// PEM files are decoded, and each PEM block is added as a separate input. Other files are
+    /// added as DER.
+    pub fn load_dir<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        let mut corpus = Corpus::new();
+        for path in paths {
+            let data = fs::read(&path)?;
+            corpus.add_file(&path.display().to_string(), data);
+        }
+        Ok(corpus)
+    }
+
+    /// Add the content of a file, decoding it if it is PEM
+    pub fn add_file(&mut self, name: &str, data: Vec<u8>) {
+        if !data.starts_with(b"-----BEGIN") {
+            self.add(name, data);
+            return;
+        }
+        for (index, pem) in Pem::iter_from_buffer(&data).flatten().enumerate() {
+            self.add(&format!("{}#{}", name, index), pem.contents);
+        }
+    }
+
+    /// Add a DER-encoded input
+    pub fn add(&mut self, name: &str, der: Vec<u8>) {
+        self.inputs.push((name.to_string(), der));
+    }
+
+    /// Return an iterator over the inputs, and their names
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.inputs
+            .iter()
+            .map(|(name, der)| (name.as_str(), der.as_slice()))
+    }
+
+    /// Return the number of inputs
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Return `true` if the corpus is empty
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Return the total size of the inputs, in bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.inputs.iter().map(|(_, der)| der.len() as u64).sum()
+    }
+}
+
+/// The options of the certificate parser
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Default options: extensions are parsed
+    Full,
+    /// Extensions are not parsed (see [`X509CertificateParser::with_deep_parse_extensions`])
+    Lazy,
+    /// Strict mode (see [`X509CertificateParser::with_strict`])
+    Strict,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for ParseMode {
+    fn default() -> Self {
+        ParseMode::Full
+    }
+}
+
+impl ParseMode {
+    /// Return the certificate parser for this mode
+    pub fn parser(self) -> X509CertificateParser {
+        match self {
+            ParseMode::Full => X509CertificateParser::new(),
+            ParseMode::Lazy => X509CertificateParser::new().with_deep_parse_extensions(false),
+            ParseMode::Strict => X509CertificateParser::new().with_strict(true),
+        }
+    }
+}
+
+/// Benchmark options
+#[derive(Clone, Copy, Debug)]
+pub struct BenchConfig {
+    iterations: usize,
+    mode: ParseMode,
+    allocation_counter: Option<fn() -> u64>,
+}
+
+impl BenchConfig {
+    /// Create the default options: one iteration, default parsing mode, allocations not counted
+    pub const fn new() -> Self {
+        BenchConfig {
+            iterations: 1,
+            mode: ParseMode::Full,
+            allocation_counter: None,
+        }
+    }
+
+    /// Set the number of times the corpus is parsed (at least 1)
+    pub const fn with_iterations(self, iterations: usize) -> Self {
+        BenchConfig { iterations, ..self }
+    }
+
+    /// Set the parsing mode, for the certificate benchmarks
+    pub const fn with_mode(self, mode: ParseMode) -> Self {
+        BenchConfig { mode, ..self }
+    }
+
+    /// Set the function returning the total number of allocations of the program
+    ///
+    /// The function is called before and after the benchmark, and the difference is reported.
+    pub const fn with_allocation_counter(self, counter: fn() -> u64) -> Self {
+        BenchConfig {
+            allocation_counter: Some(counter),
+            ..self
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig::new()
+    }
+}
+
+/// Result of a benchmark
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchReport {
+    /// Number of inputs in the corpus
+    pub inputs: usize,
+    /// Number of times the corpus was parsed
+    pub iterations: usize,
+    /// Names of the inputs which could not be parsed
+    pub failures: Vec<String>,
+    /// Total number of bytes parsed, for all iterations
+    pub bytes: u64,
+    /// Total parsing time
+    pub elapsed: Duration,
+    /// Number of allocations, if an allocation counter was configured
+    pub allocations: Option<u64>,
+}
+
+impl BenchReport {
+    /// Number of objects parsed per second
+    pub fn objects_per_sec(&self) -> f64 {
+        per_sec((self.inputs * self.iterations) as f64, self.elapsed)
+    }
+
+    /// Number of bytes parsed per second
+    pub fn bytes_per_sec(&self) -> f64 {
+        per_sec(self.bytes as f64, self.elapsed)
+    }
+
+    /// Average number of allocations per parsed object, if allocations were counted
+    pub fn allocations_per_object(&self) -> Option<f64> {
+        let count = self.inputs * self.iterations;
+        match self.allocations {
+            Some(allocations) if count > 0 => Some(allocations as f64 / count as f64),
+            _ => None,
+        }
+    }
+}
+
+fn per_sec(count: f64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        count / secs
+    } else {
+        0.0
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} objects x {} iterations in {:.3}s: {:.0} objects/s, {:.2} MB/s, {} failures",
+            self.inputs,
+            self.iterations,
+            self.elapsed.as_secs_f64(),
+            self.objects_per_sec(),
+            self.bytes_per_sec() / 1_000_000.0,
+            self.failures.len()
+        )?;
+        if let Some(allocations) = self.allocations_per_object() {
+            write!(f, ", {:.1} allocations/object", allocations)?;
+        }
+        Ok(())
+    }
+}
+
+/// Measure the throughput of a parsing function over the corpus
+///
+/// `parse` is called on each input, and must return `true` if parsing succeeded.
+pub fn run<F>(corpus: &Corpus, config: &BenchConfig, mut parse: F) -> BenchReport
+where
+    F: FnMut(&[u8]) -> bool,
+{
+    let iterations = config.iterations.max(1);
+    let mut failures = Vec::new();
+    let allocations_start = config.allocation_counter.map(|counter| counter());
+    let start = Instant::now();
+    for iteration in 0..iterations {
+        for (name, der) in corpus.iter() {
+            if !parse(der) && iteration == 0 {
+                failures.push(name.to_string());
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    let allocations = match (config.allocation_counter, allocations_start) {
+        (Some(counter), Some(start)) => Some(counter().saturating_sub(start)),
+        _ => None,
+    };
+    BenchReport {
+        inputs: corpus.len(),
+        iterations,
+        failures,
+        bytes: corpus.total_bytes() * iterations as u64,
+        elapsed,
+        allocations,
+    }
+}
+
+/// Measure the throughput of the certificate parser over the corpus
+///
+/// The parser options are set by the parsing mode of `config`.
+pub fn run_certificates(corpus: &Corpus, config: &BenchConfig) -> BenchReport {
+    let mut parser = config.mode.parser();
+    run(corpus, config, |der| parser.parse(der).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    #[test]
+    fn test_bench_certificates() {
+        let mut corpus = Corpus::new();
+        corpus.add("ca", include_bytes!("../assets/test-ca.der").to_vec());
+        corpus.add_file("pem", include_bytes!("../assets/certificate.pem").to_vec());
+        corpus.add("invalid", vec![0x30, 0x00]);
+        assert_eq!(corpus.len(), 3);
+        let config = BenchConfig::new()
+            .with_iterations(3)
+            .with_mode(ParseMode::Lazy)
+            .with_allocation_counter(|| COUNTER.fetch_add(10, Ordering::SeqCst));
+        let report = run_certificates(&corpus, &config);
+        assert_eq!(report.inputs, 3);
+        assert_eq!(report.failures, ["invalid"]);
+        assert_eq!(report.bytes, corpus.total_bytes() * 3);
+        assert_eq!(report.allocations, Some(10));
+        assert!(report.to_string().contains("3 objects x 3 iterations"));
+    }
+}
@@ -1,29 +1,37 @@
 //! X.509 Certificate object definitions and operations
 
-use crate::error::{X509Error, X509Result};
+use crate::error::{PEMError, X509Error, X509Result};
 use crate::extensions::*;
+use crate::ocsp::OID_PKIX_OCSP_NOCHECK;
+use crate::pem::parse_x509_pem;
+use crate::report::CertificateReport;
 use crate::time::ASN1Time;
-use crate::utils::format_serial;
+use crate::utils::{
+    constant_time_eq, der_max_depth, der_tlv, format_serial, format_serial_with, SerialFormat,
+};
 #[cfg(feature = "validate")]
 use crate::validate::*;
 use crate::x509::{
-    parse_serial, parse_signature_value, AlgorithmIdentifier, SubjectPublicKeyInfo, X509Name,
-    X509Version,
+    parse_serial, parse_signature_value, serial_to_u128, AlgorithmIdentifier, SubjectPublicKeyInfo,
+    X509Name, X509Version,
 };
 
 #[cfg(feature = "verify")]
 use crate::verify::verify_signature;
-use asn1_rs::{BitString, FromDer, OptTaggedExplicit};
+use asn1_rs::{Any, BitString, Class, FromDer, OptTaggedExplicit};
 use core::ops::Deref;
 use der_parser::ber::Tag;
 use der_parser::der::*;
 use der_parser::error::*;
+#[cfg(feature = "bigint")]
 use der_parser::num_bigint::BigUint;
 use der_parser::*;
 use nom::{Offset, Parser};
 use oid_registry::Oid;
 use oid_registry::*;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
 use time::Duration;
 
 /// An X.509 v3 Certificate.
@@ -39,6 +47,10 @@ use time::Duration;
 /// A `X509Certificate` is a zero-copy view over a buffer, so the lifetime is the same as the
 /// buffer containing the binary representation.
 ///
+/// `PartialEq` is a structural comparison of the parsed fields (TBS certificate, signature
+/// algorithm and signature value). To check if two certificates are exactly the same, use
+/// [`X509Certificate::eq_der`], which only compares the DER encodings and is much cheaper.
+///
 /// ```rust
 /// # use x509_parser::prelude::FromDer;
 /// # use x509_parser::certificate::X509Certificate;
@@ -68,9 +80,46 @@ pub struct X509Certificate<'a> {
     pub tbs_certificate: TbsCertificate<'a>,
     pub signature_algorithm: AlgorithmIdentifier<'a>,
     pub signature_value: BitString<'a>,
+    pub(crate) raw: &'a [u8],
 }
 
 impl<'a> X509Certificate<'a> {
+    /// Build a certificate from its parts, for ex. a separately parsed [`TbsCertificate`]
+    ///
+    /// The certificate has no DER encoding: [`as_raw`](Self::as_raw) returns an empty slice,
+    /// and it cannot be converted to a [`X509CertificateOwned`].
+    pub fn from_parts(
+        tbs_certificate: TbsCertificate<'a>,
+        signature_algorithm: AlgorithmIdentifier<'a>,
+        signature_value: BitString<'a>,
+    ) -> Self {
+        X509Certificate {
+            tbs_certificate,
+            signature_algorithm,
+            signature_value,
+            raw: &[],
+        }
+    }
+
+    /// Return the raw DER encoding of the certificate
+    ///
+    /// This is the encoding the certificate was parsed from. It is empty if the certificate
+    /// was built using [`from_parts`](Self::from_parts).
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// Test if both certificates have the same DER encoding
+    ///
+    /// This only compares bytes, without allocation or comparison of the parsed fields, so it
+    /// is suited for deduplication of certificates.
+    ///
+    /// Certificates built using [`from_parts`](Self::from_parts) have no encoding, and are
+    /// never equal to any certificate.
+    pub fn eq_der(&self, other: &X509Certificate) -> bool {
+        !self.raw.is_empty() && self.raw == other.raw
+    }
+
     /// Verify the cryptographic signature of this certificate
     ///
     /// `public_key` is the public key of the **signer**. For a self-signed certificate,
@@ -95,6 +144,210 @@ impl<'a> X509Certificate<'a> {
             self.tbs_certificate.raw,
         )
     }
+
+    /// Return the elements required to verify the signature of this certificate
+    ///
+    /// The returned tuple contains the signed data (the raw DER bytes of the `TBSCertificate`),
+    /// the signature value (a `BitString`, including the number of unused bits) and the
+    /// signature algorithm.
+    ///
+    /// This is useful when verification is done outside of this crate, for ex. using a HSM, a
+    /// cloud KMS or an algorithm not supported by the `verify` feature.
+    /// Algorithm parameters (for ex. RSASSA-PSS parameters) can be parsed using
+    /// [`SignatureAlgorithm::try_from`](crate::signature_algorithm::SignatureAlgorithm).
+    pub fn signature_input(&self) -> (&'a [u8], &BitString<'a>, &AlgorithmIdentifier<'a>) {
+        (
+            self.tbs_certificate.raw,
+            &self.signature_value,
+            &self.signature_algorithm,
+        )
+    }
+
+    /// Test if this certificate was issued by the given certificate
+    ///
+    /// The certificate issuer must be equal to the subject of `issuer`. If this certificate has
+    /// an Authority Key Identifier extension, it must also match `issuer`: the key identifier is
+    /// compared to the Subject Key Identifier of `issuer` (if both are present), and the issuer
+    /// and serial number are compared to the issuer and serial of `issuer` (if present).
+    ///
+    /// This function does not verify the signature, or the key usage of `issuer`.
+    pub fn is_issued_by(&self, issuer: &X509Certificate) -> bool {
+        if self.issuer().as_raw() != issuer.subject().as_raw() {
+            return false;
+        }
+        let aki = match self.authority_key_identifier() {
+            Ok(Some(aki)) => aki.value,
+            _ => return true,
+        };
+        if let Some(key_id) = &aki.key_identifier {
+            if let Ok(Some(ski)) = issuer.subject_key_identifier() {
+                if key_id != ski.value {
+                    return false;
+                }
+            }
+        }
+        if aki.authority_cert_issuer.is_some() || aki.authority_cert_serial.is_some() {
+            return aki.matches_issuer_certificate(&issuer.tbs_certificate);
+        }
+        true
+    }
+
+    /// Build a flat summary of the certificate, see [`CertificateReport`]
+    pub fn report(&self) -> CertificateReport {
+        CertificateReport::new(self)
+    }
+
+    /// Serialize a summary of the certificate to JSON, see [`CertificateReport`] for the format
+    pub fn to_json(&self) -> String {
+        self.report().to_json()
+    }
+
+    /// Build a redacted summary of the certificate, see [`CertificateReport::redacted`]
+    #[cfg(feature = "hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+    pub fn redacted_report(&self, salt: &[u8]) -> CertificateReport {
+        CertificateReport::redacted(self, salt)
+    }
+
+    /// Returns true if the certificate can act as a CA
+    ///
+    /// For v3 certificates, this is the same as [`is_ca`](TbsCertificate::is_ca)
+    /// (`basicConstraints CA:true`). Version 1 and 2 certificates cannot have extensions, so
+    /// `policy` decides if they are CA certificates. This allows accepting the v1 roots of
+    /// legacy root stores, using [`V1CaPolicy::SelfIssued`].
+    pub fn assume_v1_ca(&self, policy: V1CaPolicy) -> bool {
+        if self.version() == X509Version::V3 {
+            return self.is_ca();
+        }
+        match policy {
+            V1CaPolicy::Never => false,
+            V1CaPolicy::SelfIssued => self.subject().as_raw() == self.issuer().as_raw(),
+            V1CaPolicy::Always => true,
+        }
+    }
+
+    /// Classify the certificate, using basicConstraints, extended key usage and names
+    ///
+    /// This is a heuristic, and does not validate the certificate:
+    /// - a CA certificate (`basicConstraints CA:true`, or a v1/v2 self-issued certificate, see
+    ///   [`V1CaPolicy::SelfIssued`]) is a root if it is self-issued (same subject and issuer),
+    ///   an intermediate otherwise. If the key usage extension is present, it must contain
+    ///   `keyCertSign`: a CA which cannot sign certificates (for ex. a dedicated CRL signer) is
+    ///   classified as a leaf
+    /// - other certificates are leaves, classified using the first matching extended key
+    ///   usage (in the order of [`LeafKind`] variants)
+    pub fn certificate_kind(&self) -> CertificateKind {
+        let self_issued = self.subject().as_raw() == self.issuer().as_raw();
+        let key_cert_sign = match self.key_usage() {
+            Ok(Some(ku)) => ku.value.key_cert_sign(),
+            _ => true,
+        };
+        if key_cert_sign && self.assume_v1_ca(V1CaPolicy::SelfIssued) {
+            return if self_issued {
+                CertificateKind::Root
+            } else {
+                CertificateKind::Intermediate
+            };
+        }
+        let leaf_kind = match self.extended_key_usage() {
+            Ok(Some(eku)) => {
+                let eku = eku.value;
+                if eku.server_auth {
+                    LeafKind::TlsServer
+                } else if eku.client_auth {
+                    LeafKind::TlsClient
+                } else if eku.email_protection {
+                    LeafKind::SMime
+                } else if eku.code_signing {
+                    LeafKind::CodeSigning
+                } else if eku.ocsp_signing {
+                    LeafKind::OcspResponder
+                } else if eku.time_stamping {
+                    LeafKind::TimeStamping
+                } else {
+                    LeafKind::Unknown
+                }
+            }
+            _ => LeafKind::Unknown,
+        };
+        CertificateKind::Leaf(leaf_kind)
+    }
+
+    /// Check if the certificate expires within `duration` from now
+    ///
+    /// See [`Validity::expires_within`].
+    #[inline]
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        self.validity().expires_within(duration)
+    }
+
+    /// The number of whole days left before the certificate expires
+    ///
+    /// See [`Validity::days_remaining`].
+    #[inline]
+    pub fn days_remaining(&self) -> Option<i64> {
+        self.validity().days_remaining()
+    }
+
+    /// The total validity period of the certificate
+    ///
+    /// See [`Validity::lifetime`].
+    #[inline]
+    pub fn lifetime(&self) -> Option<Duration> {
+        self.validity().lifetime()
+    }
+}
+
+/// How version 1 and 2 certificates are handled, when deciding if a certificate is a CA
+///
+/// These certificates have no basicConstraints extension. RFC 5280 requires the extension
+/// for intermediate CAs, but some root stores still contain v1 roots.
+///
+/// See [`X509Certificate::assume_v1_ca`]. The policy is used by `verify::VerificationCache`
+/// (`verify` feature) and
+/// [`order_chain_with_v1_ca_policy`](crate::chain::order_chain_with_v1_ca_policy). The validators
+/// of the `validate` module do not decide if a certificate is a CA, and do not use it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum V1CaPolicy {
+    /// v1 and v2 certificates are never CA certificates (default)
+    Never,
+    /// Self-issued v1 and v2 certificates are CA certificates (like OpenSSL)
+    SelfIssued,
+    /// All v1 and v2 certificates are CA certificates (insecure: any end-entity v1 certificate
+    /// could issue certificates)
+    Always,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for V1CaPolicy {
+    fn default() -> Self {
+        V1CaPolicy::Never
+    }
+}
+
+/// The kind of a certificate, as returned by [`X509Certificate::certificate_kind`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CertificateKind {
+    /// Self-issued CA certificate
+    Root,
+    /// CA certificate issued by another CA
+    Intermediate,
+    /// End-entity certificate
+    Leaf(LeafKind),
+}
+
+/// The usage of an end-entity certificate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LeafKind {
+    TlsServer,
+    TlsClient,
+    SMime,
+    CodeSigning,
+    OcspResponder,
+    TimeStamping,
+    /// No extended key usage, or none of the above
+    Unknown,
 }
 
 impl<'a> Deref for X509Certificate<'a> {
@@ -148,13 +401,135 @@ impl<'a> FromDer<'a, X509Error> for X509Certificate<'a> {
     }
 }
 
+impl<'a> TryFrom<&'a [u8]> for X509Certificate<'a> {
+    type Error = X509Error;
+
+    /// Parse a DER-encoded certificate, using the default parser options
+    ///
+    /// Unlike [`parse_x509_certificate`](crate::parse_x509_certificate), the input must contain
+    /// exactly one certificate: trailing data is an error.
+    fn try_from(i: &'a [u8]) -> Result<Self, Self::Error> {
+        match X509Certificate::from_der(i)? {
+            ([], x509) => Ok(x509),
+            _ => Err(X509Error::InvalidCertificate),
+        }
+    }
+}
+
+/// A certificate owning its DER encoding
+///
+/// [`X509Certificate`] borrows the input buffer, which makes it difficult to store in long-lived
+/// structures. This type stores a copy of the encoding, and can be sent to other threads.
+/// The parsed object is obtained using [`Self::certificate`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct X509CertificateOwned {
+    raw: Vec<u8>,
+}
+
+impl X509CertificateOwned {
+    /// Parse a DER-encoded certificate, taking ownership of the buffer
+    ///
+    /// Trailing data after the certificate is removed.
+    pub fn new(mut raw: Vec<u8>) -> Result<Self, X509Error> {
+        let (rem, _) = X509Certificate::from_der(&raw)?;
+        let len = raw.len() - rem.len();
+        raw.truncate(len);
+        Ok(X509CertificateOwned { raw })
+    }
+
+    // Build the object without parsing the encoding, which must have been checked by the caller
+    #[cfg(feature = "hash")]
+    pub(crate) fn from_raw_unchecked(raw: Vec<u8>) -> Self {
+        X509CertificateOwned { raw }
+    }
+
+    /// Return the raw DER encoding of the certificate
+    pub fn as_raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Return the parsed certificate, borrowing the stored buffer
+    ///
+    /// The stored encoding is parsed on each call, so the returned object should be kept if
+    /// several fields are accessed. The encoding is checked when the object is built, so this
+    /// does not fail in practice.
+    pub fn certificate(&self) -> Result<X509Certificate<'_>, X509Error> {
+        let (_, obj) = X509Certificate::from_der(&self.raw)?;
+        Ok(obj)
+    }
+}
+
+impl<'a> X509Certificate<'a> {
+    /// Return a copy of this certificate, owning its DER encoding
+    ///
+    /// Returns [`X509Error::MissingEncoding`] if the certificate has no encoding (see
+    /// [`from_parts`](Self::from_parts)).
+    pub fn to_owned(&self) -> Result<X509CertificateOwned, X509Error> {
+        X509CertificateOwned::try_from(self)
+    }
+}
+
+impl<'a> TryFrom<&X509Certificate<'a>> for X509CertificateOwned {
+    type Error = X509Error;
+
+    fn try_from(obj: &X509Certificate<'a>) -> Result<Self, Self::Error> {
+        if obj.raw.is_empty() {
+            return Err(X509Error::MissingEncoding);
+        }
+        Ok(X509CertificateOwned {
+            raw: obj.raw.to_vec(),
+        })
+    }
+}
+
+impl TryFrom<Vec<u8>> for X509CertificateOwned {
+    type Error = X509Error;
+
+    /// Parse a DER-encoded certificate, taking ownership of the buffer
+    ///
+    /// Like `TryFrom<&[u8]>` for [`X509Certificate`], the input must contain exactly one
+    /// certificate: trailing data is an error. Use [`X509CertificateOwned::new`] to ignore it.
+    fn try_from(raw: Vec<u8>) -> Result<Self, Self::Error> {
+        X509Certificate::try_from(raw.as_slice())?;
+        Ok(X509CertificateOwned { raw })
+    }
+}
+
+impl FromStr for X509CertificateOwned {
+    type Err = PEMError;
+
+    /// Read a PEM-encoded certificate (`CERTIFICATE` tag)
+    ///
+    /// Only the *first* PEM block is decoded, and its label must be `CERTIFICATE`. The decoded
+    /// contents must contain exactly one certificate.
+    ///
+    /// ```rust
+    /// use x509_parser::certificate::X509CertificateOwned;
+    ///
+    /// let data = std::fs::read_to_string("assets/certificate.pem").unwrap();
+    /// let cert: X509CertificateOwned = data.parse().expect("invalid certificate");
+    /// assert_eq!(cert.certificate().unwrap().version().0, 2);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, pem) = parse_x509_pem(s.as_bytes()).map_err(|e| match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => PEMError::IncompletePEM,
+        })?;
+        if pem.label != "CERTIFICATE" {
+            return Err(PEMError::UnexpectedLabel(pem.label));
+        }
+        Ok(X509CertificateOwned::try_from(pem.contents)?)
+    }
+}
+
 /// X.509 Certificate parser
 ///
-/// This object is a parser builder, and allows specifying parsing options.
-/// Currently, the only option is to control deep parsing of X.509v3 extensions:
-/// a parser can decide to skip deep-parsing to be faster (the structure of extensions is still
-/// parsed, and the contents can be parsed later using the [`from_der`](FromDer::from_der)
-/// method from individual extension objects).
+/// This object is a parser builder, and allows specifying parsing options:
+/// - deep parsing of X.509v3 extensions: a parser can decide to skip deep-parsing to be faster
+///   (the structure of extensions is still parsed, and the contents can be parsed later using
+///   the [`from_der`](FromDer::from_der) method from individual extension objects).
+/// - strict mode: reject some encodings which are forbidden, but usually accepted (for ex.
+///   parameters in Ed25519 algorithm identifiers, forbidden by RFC 8410).
 ///
 /// This object uses the `nom::Parser` trait, which must be imported.
 ///
@@ -187,7 +562,8 @@ impl<'a> FromDer<'a, X509Error> for X509Certificate<'a> {
 #[derive(Clone, Copy, Debug)]
 pub struct X509CertificateParser {
     deep_parse_extensions: bool,
-    // strict: bool,
+    strict: bool,
+    clock: Option<fn() -> std::time::Duration>,
 }
 
 impl X509CertificateParser {
@@ -195,6 +571,8 @@ impl X509CertificateParser {
     pub const fn new() -> Self {
         X509CertificateParser {
             deep_parse_extensions: true,
+            strict: false,
+            clock: None,
         }
     }
 
@@ -202,26 +580,115 @@ impl X509CertificateParser {
     pub const fn with_deep_parse_extensions(self, deep_parse_extensions: bool) -> Self {
         X509CertificateParser {
             deep_parse_extensions,
+            ..self
+        }
+    }
+
+    /// Enable or disable strict mode
+    ///
+    /// In strict mode, algorithm identifiers with forbidden parameters (see
+    /// [`AlgorithmIdentifier::has_forbidden_parameters`]) are rejected.
+    #[inline]
+    pub const fn with_strict(self, strict: bool) -> Self {
+        X509CertificateParser { strict, ..self }
+    }
+
+    /// Set the clock used to measure the parsing time in [`ParseStats`]
+    ///
+    /// The function must return the time elapsed since an arbitrary, fixed origin (for ex.
+    /// using `std::time::Instant`). No clock is set by default, so the parser does not depend on
+    /// the time source of the platform.
+    #[inline]
+    pub const fn with_clock(self, clock: fn() -> std::time::Duration) -> Self {
+        X509CertificateParser {
+            clock: Some(clock),
+            ..self
+        }
+    }
+
+    /// Parse a certificate, and return statistics about the input with the certificate
+    ///
+    /// This is intended for services ingesting certificates, to monitor the parser and detect
+    /// anomalous inputs (for ex. unusually deep nesting, or trailing data).
+    pub fn parse_with_stats<'a>(
+        &mut self,
+        input: &'a [u8],
+    ) -> IResult<&'a [u8], (X509Certificate<'a>, ParseStats), X509Error> {
+        let start = self.clock.map(|clock| clock());
+        let (rem, cert) = self.parse(input)?;
+        let elapsed = match (self.clock, start) {
+            (Some(clock), Some(start)) => Some(clock().saturating_sub(start)),
+            _ => None,
+        };
+        let stats = ParseStats {
+            trailing_bytes: rem.len(),
+            elapsed,
+            ..ParseStats::from_certificate(&cert)
+        };
+        Ok((rem, (cert, stats)))
+    }
+}
+
+/// Statistics about a parsed certificate, see [`X509CertificateParser::parse_with_stats`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Size of the DER encoding of the certificate
+    pub bytes_consumed: usize,
+    /// Number of bytes after the certificate, in the input
+    pub trailing_bytes: usize,
+    /// Number of extensions
+    pub extensions: usize,
+    /// Number of entries of the subject alternative name extension (0 if the extension is
+    /// absent, or was not parsed)
+    pub san_entries: usize,
+    /// Maximum nesting depth of the DER encoding (the certificate `SEQUENCE` has depth 1)
+    pub max_depth: usize,
+    /// Parsing time, if a clock was set using [`X509CertificateParser::with_clock`]
+    pub elapsed: Option<std::time::Duration>,
+}
+
+impl ParseStats {
+    /// Compute the statistics of an already parsed certificate
+    ///
+    /// The parsing time and the trailing data are unknown.
+    pub fn from_certificate(cert: &X509Certificate) -> Self {
+        let san_entries = match cert.subject_alternative_name() {
+            Ok(Some(san)) => san.value.general_names.len(),
+            _ => 0,
+        };
+        ParseStats {
+            bytes_consumed: cert.as_raw().len(),
+            trailing_bytes: 0,
+            extensions: cert.extensions().len(),
+            san_entries,
+            max_depth: der_max_depth(cert.as_raw()),
+            elapsed: None,
         }
     }
 }
 
 impl<'a> Parser<&'a [u8], X509Certificate<'a>, X509Error> for X509CertificateParser {
     fn parse(&mut self, input: &'a [u8]) -> IResult<&'a [u8], X509Certificate<'a>, X509Error> {
-        parse_der_sequence_defined_g(|i, _| {
+        trace_field!("certificate", input);
+        let (rem, mut cert) = parse_der_sequence_defined_g(|i, _| {
             // pass options to TbsCertificate parser
-            let mut tbs_parser =
-                TbsCertificateParser::new().with_deep_parse_extensions(self.deep_parse_extensions);
+            let mut tbs_parser = TbsCertificateParser::new()
+                .with_deep_parse_extensions(self.deep_parse_extensions)
+                .with_strict(self.strict);
             let (i, tbs_certificate) = tbs_parser.parse(i)?;
+            trace_field!("signatureAlgorithm", i);
             let (i, signature_algorithm) = AlgorithmIdentifier::from_der(i)?;
+            if self.strict && signature_algorithm.has_forbidden_parameters() {
+                return Err(nom::Err::Error(X509Error::InvalidAlgorithmIdentifier));
+            }
+            trace_field!("signatureValue", i);
             let (i, signature_value) = parse_signature_value(i)?;
-            let cert = X509Certificate {
-                tbs_certificate,
-                signature_algorithm,
-                signature_value,
-            };
+            let cert =
+                X509Certificate::from_parts(tbs_certificate, signature_algorithm, signature_value);
             Ok((i, cert))
-        })(input)
+        })(input)?;
+        cert.raw = &input[..input.offset(rem)];
+        Ok((rem, cert))
     }
 }
 
@@ -263,6 +730,8 @@ impl Validate for X509Certificate<'_> {
 #[derive(Clone, Debug, PartialEq)]
 pub struct TbsCertificate<'a> {
     pub version: X509Version,
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
     pub serial: BigUint,
     pub signature: AlgorithmIdentifier<'a>,
     pub issuer: X509Name<'a>,
@@ -327,6 +796,13 @@ impl<'a> TbsCertificate<'a> {
         get_extension_unique(&self.extensions, oid)
     }
 
+    /// Returns the `Oid`s of extensions present twice or more, in order of first occurrence
+    ///
+    /// RFC5280 forbids duplicate extensions: a non-empty result can be used to reject the certificate.
+    pub fn duplicate_extensions(&self) -> Vec<&Oid<'a>> {
+        duplicate_extensions(&self.extensions)
+    }
+
     /// Searches for an extension with the given `Oid`.
     ///
     /// ## Duplicate extensions
@@ -361,6 +837,38 @@ impl<'a> TbsCertificate<'a> {
             })
     }
 
+    /// Attempt to get the certificate Subject Key Identifier extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn subject_key_identifier(
+        &self,
+    ) -> Result<Option<BasicExtension<&KeyIdentifier>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_SUBJECT_KEY_IDENTIFIER)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::SubjectKeyIdentifier(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the certificate Authority Key Identifier extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn authority_key_identifier(
+        &self,
+    ) -> Result<Option<BasicExtension<&AuthorityKeyIdentifier>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::AuthorityKeyIdentifier(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
     /// Attempt to get the certificate Basic Constraints extension
     ///
     /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
@@ -409,6 +917,22 @@ impl<'a> TbsCertificate<'a> {
             })
     }
 
+    /// Attempt to get the certificate Certificate Policies extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn certificate_policies(
+        &self,
+    ) -> Result<Option<BasicExtension<&CertificatePolicies>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_CERTIFICATE_POLICIES)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::CertificatePolicies(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
     /// Attempt to get the certificate Policy Constraints extension
     ///
     /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
@@ -471,6 +995,260 @@ impl<'a> TbsCertificate<'a> {
             })
     }
 
+    /// Attempt to get the certificate CRL Distribution Points extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn crl_distribution_points(
+        &self,
+    ) -> Result<Option<BasicExtension<&CRLDistributionPoints>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_CRL_DISTRIBUTION_POINTS)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::CRLDistributionPoints(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the certificate Private Key Usage Period extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn private_key_usage_period(
+        &self,
+    ) -> Result<Option<BasicExtension<&PrivateKeyUsagePeriod>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_PRIVATE_KEY_USAGE_PERIOD)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::PrivateKeyUsagePeriod(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the certificate Freshest CRL (Delta CRL Distribution Point) extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn freshest_crl(
+        &self,
+    ) -> Result<Option<BasicExtension<&CRLDistributionPoints>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_FRESHEST_CRL)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::FreshestCRL(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the certificate Authority Information Access extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn authority_info_access(
+        &self,
+    ) -> Result<Option<BasicExtension<&AuthorityInfoAccess>>, X509Error> {
+        self.get_extension_unique(&OID_PKIX_AUTHORITY_INFO_ACCESS)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::AuthorityInfoAccess(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the certificate Subject Information Access extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn subject_info_access(
+        &self,
+    ) -> Result<Option<BasicExtension<&SubjectInfoAccess>>, X509Error> {
+        self.get_extension_unique(&OID_PKIX_SUBJECT_INFO_ACCESS)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::SubjectInfoAccess(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the certificate Issuer Alternative Name extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn issuer_alternative_name(
+        &self,
+    ) -> Result<Option<BasicExtension<&IssuerAlternativeName>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_ISSUER_ALT_NAME)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::IssuerAlternativeName(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the Netscape certificate type extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn netscape_cert_type(&self) -> Result<Option<BasicExtension<&NSCertType>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_CERT_TYPE)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::NSCertType(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the Netscape comment extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn netscape_comment(&self) -> Result<Option<BasicExtension<&str>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_CERT_COMMENT)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::NsCertComment(value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the Netscape base URL extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn netscape_base_url(&self) -> Result<Option<BasicExtension<&str>>, X509Error> {
+        self.get_extension_unique(&OID_X509_EXT_BASE_URL)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::NsBaseUrl(value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the Microsoft NTDS CA Security extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn ntds_ca_security(&self) -> Result<Option<BasicExtension<&NtdsCaSecurity>>, X509Error> {
+        self.get_extension_unique(&OID_MS_NTDS_CA_SECURITY_EXT)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::NtdsCaSecurity(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the S/MIME Capabilities extension
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn smime_capabilities(
+        &self,
+    ) -> Result<Option<BasicExtension<&SMIMECapabilities>>, X509Error> {
+        self.get_extension_unique(&OID_PKCS9_SMIME_CAPABILITIES)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::SMIMECapabilities(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Return the human-readable notices of the certificate, for display purposes
+    ///
+    /// This aggregates the Netscape comment and the explicit texts of the user notices in the
+    /// certificate policies (often used to point to the Certification Practice Statement), in
+    /// the order of extensions. Whitespace is normalized (line breaks and repeated spaces are
+    /// replaced by a single space), and empty or duplicate notices are skipped.
+    ///
+    /// Invalid extensions and qualifiers are ignored.
+    pub fn comments(&self) -> impl Iterator<Item = String> {
+        let mut comments: Vec<String> = Vec::new();
+        let mut add = |text: &str| {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !text.is_empty() && !comments.contains(&text) {
+                comments.push(text);
+            }
+        };
+        for ext in self.extensions() {
+            match ext.parsed_extension() {
+                ParsedExtension::NsCertComment(comment) => add(comment),
+                ParsedExtension::CertificatePolicies(policies) => {
+                    for notice in policies.iter().flat_map(|p| p.user_notices()) {
+                        if let Some(text) = &notice.explicit_text {
+                            add(text);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        comments.into_iter()
+    }
+
+    /// Attempt to get the Microsoft certificate template name extension (version 1 templates)
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn enroll_cert_type(&self) -> Result<Option<BasicExtension<&str>>, X509Error> {
+        self.get_extension_unique(&OID_MS_ENROLL_CERTTYPE)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::EnrollCertType(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value.as_str())))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the Microsoft certificate template extension (version 2 templates)
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn certificate_template(
+        &self,
+    ) -> Result<Option<BasicExtension<&CertificateTemplate>>, X509Error> {
+        self.get_extension_unique(&OID_MS_CERTIFICATE_TEMPLATE)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::CertificateTemplate(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Attempt to get the Admission extension (Common PKI), describing the professions of the
+    /// subject
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn admission(&self) -> Result<Option<BasicExtension<&AdmissionSyntax>>, X509Error> {
+        self.get_extension_unique(&OID_ISISMTT_AT_ADMISSION)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::Admission(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value)))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Get the security identifier (SID) of the subject, from the Microsoft NTDS CA Security
+    /// extension
+    ///
+    /// This is used by Active Directory for strong certificate mapping.
+    pub fn subject_sid(&self) -> Result<Option<&str>, X509Error> {
+        self.ntds_ca_security()
+            .map(|ext| ext.map(|ext| ext.value.object_sid))
+    }
+
     /// Attempt to get the certificate Name Constraints extension
     ///
     /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
@@ -485,7 +1263,182 @@ impl<'a> TbsCertificate<'a> {
             })
     }
 
+    /// Attempt to get the Signed Certificate Timestamps (SCT) embedded in the certificate
+    ///
+    /// The SCT list extension (`1.3.6.1.4.1.11129.2.4.2`, RFC6962 section 3.3) contains the
+    /// promises of inclusion from Certificate Transparency logs.
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error if the extension is invalid, or is present twice or more.
+    pub fn signed_certificate_timestamps(
+        &self,
+    ) -> Result<Option<BasicExtension<&[SignedCertificateTimestamp<'a>]>>, X509Error> {
+        self.get_extension_unique(&OID_CT_LIST_SCT)?
+            .map_or(Ok(None), |ext| match ext.parsed_extension {
+                ParsedExtension::SCT(ref value) => {
+                    Ok(Some(BasicExtension::new(ext.critical, value.as_slice())))
+                }
+                _ => Err(X509Error::InvalidExtensions),
+            })
+    }
+
+    /// Returns true if this is a Certificate Transparency precertificate (the critical poison
+    /// extension is present)
+    ///
+    /// RFC 6962 requires the poison extension to be critical: a non-critical poison extension is
+    /// ignored.
+    pub fn is_precertificate(&self) -> bool {
+        self.iter_extensions()
+            .any(|ext| ext.oid == OID_CT_PRECERT_POISON && ext.critical)
+    }
+
+    /// Returns true if this is a Certificate Transparency Precertificate Signing Certificate (the
+    /// extended key usage contains the Precertificate Signing Certificate key purpose)
+    ///
+    /// See [`ct_tbs_certificate_with_signer`](Self::ct_tbs_certificate_with_signer) for the
+    /// precertificates issued by such certificates.
+    pub fn is_precert_signing_cert(&self) -> bool {
+        match self.extended_key_usage() {
+            Ok(Some(eku)) => eku.value.other.contains(&OID_CT_PRECERT_SIGNING_CERT),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the OCSP No Check extension is present
+    ///
+    /// This extension is set in delegated OCSP responder certificates, to indicate that their
+    /// revocation status must not be checked (section 4.2.2.2.1 of rfc 6960).
+    pub fn has_ocsp_no_check(&self) -> bool {
+        self.iter_extensions()
+            .any(|ext| ext.oid == OID_PKIX_OCSP_NOCHECK)
+    }
+
+    /// Returns true if the CanSignHttpExchanges extension is present
+    ///
+    /// Certificates used to sign Signed HTTP Exchanges must have this extension, unless they
+    /// were issued before its definition. Note that the extension is only accepted by user agents
+    /// in certificates with a short validity period (90 days at most).
+    pub fn can_sign_http_exchanges(&self) -> bool {
+        self.iter_extensions()
+            .any(|ext| ext.oid == OID_CAN_SIGN_HTTP_EXCHANGES)
+    }
+
+    /// Return the DER encoding of the TBS certificate, with the extensions of types `oids` removed
+    ///
+    /// The other fields and extensions are copied unchanged, in the same order. If no extension
+    /// remains, the extensions field is omitted.
+    pub fn raw_without_extensions(&self, oids: &[Oid]) -> Result<Vec<u8>, X509Error> {
+        self.rewrite_raw(None, |oid, ext, kept| {
+            if !oids.contains(oid) {
+                kept.extend_from_slice(ext);
+            }
+        })
+    }
+
+    // Copy the DER encoding of the TBS certificate, replacing the issuer if `issuer` is set, and
+    // passing each extension (OID and DER encoding) to `map_extension`, which appends the
+    // extensions to keep. If no extension remains, the extensions field is omitted.
+    fn rewrite_raw<F>(
+        &self,
+        issuer: Option<&[u8]>,
+        mut map_extension: F,
+    ) -> Result<Vec<u8>, X509Error>
+    where
+        F: FnMut(&Oid, &[u8], &mut Vec<u8>),
+    {
+        // TBSCertificate ::= SEQUENCE { [0] version, serialNumber, signature, issuer, ...,
+        //                               extensions [3] EXPLICIT Extensions OPTIONAL }
+        let (_, tbs) = Any::from_der(self.raw).or(Err(X509Error::InvalidTbsCertificate))?;
+        let mut content = Vec::with_capacity(self.raw.len());
+        let mut universal_index = 0;
+        let mut i = tbs.data;
+        while !i.is_empty() {
+            let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidTbsCertificate))?;
+            let element = &i[..i.len() - rem.len()];
+            i = rem;
+            if any.class() == Class::Universal {
+                match issuer {
+                    Some(issuer) if universal_index == 2 => content.extend_from_slice(issuer),
+                    _ => content.extend_from_slice(element),
+                }
+                universal_index += 1;
+                continue;
+            }
+            if any.class() != Class::ContextSpecific || any.tag() != Tag(3) {
+                content.extend_from_slice(element);
+                continue;
+            }
+            let mut kept = Vec::new();
+            for (oid, ext) in split_raw_extensions(any.data)? {
+                map_extension(&oid, ext, &mut kept);
+            }
+            if !kept.is_empty() {
+                content.extend(der_tlv(0xa3, &der_tlv(0x30, &kept)));
+            }
+        }
+        Ok(der_tlv(0x30, &content))
+    }
+
+    /// Return the DER encoding of the TBS certificate as signed by Certificate Transparency logs
+    ///
+    /// The poison extension (for precertificates) and the SCT list extension (for final
+    /// certificates) are removed. This gives the `tbs_certificate` of a precertificate log entry
+    /// (see [`LogEntry::precert`](crate::ct::LogEntry)), required to verify embedded SCTs, and
+    /// the same bytes for a precertificate and the corresponding final certificate.
+    ///
+    /// Note that if the precertificate was issued by a dedicated precertificate signing
+    /// certificate, the issuer and authority key identifier must also be replaced, see
+    /// [`ct_tbs_certificate_with_signer`](Self::ct_tbs_certificate_with_signer).
+    pub fn ct_tbs_certificate(&self) -> Result<Vec<u8>, X509Error> {
+        self.raw_without_extensions(&[OID_CT_PRECERT_POISON, OID_CT_LIST_SCT])
+    }
+
+    /// Return the DER encoding of the TBS certificate as signed by Certificate Transparency logs,
+    /// for a precertificate issued by the Precertificate Signing Certificate `signer`
+    ///
+    /// In addition to the changes of [`ct_tbs_certificate`](Self::ct_tbs_certificate), the
+    /// issuer is replaced by the issuer of `signer` (the CA issuing the final certificate), and
+    /// the authority key identifier by the one of `signer`, or removed if `signer` has none
+    /// (RFC6962 section 3.2). The `issuer_key_hash` of the log entry must then be computed from
+    /// the public key of the issuer of `signer`, not from the key of `signer`.
+    ///
+    /// Returns `InvalidCertificate` if `signer` is not a Precertificate Signing Certificate
+    /// (see [`is_precert_signing_cert`](Self::is_precert_signing_cert)).
+    pub fn ct_tbs_certificate_with_signer(
+        &self,
+        signer: &TbsCertificate,
+    ) -> Result<Vec<u8>, X509Error> {
+        if !signer.is_precert_signing_cert() {
+            return Err(X509Error::InvalidCertificate);
+        }
+        let (_, tbs) = Any::from_der(signer.raw).or(Err(X509Error::InvalidTbsCertificate))?;
+        let mut signer_aki = None;
+        let mut i = tbs.data;
+        while !i.is_empty() {
+            let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidTbsCertificate))?;
+            i = rem;
+            if any.class() == Class::ContextSpecific && any.tag() == Tag(3) {
+                signer_aki = split_raw_extensions(any.data)?
+                    .into_iter()
+                    .find(|(oid, _)| *oid == OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER)
+                    .map(|(_, ext)| ext);
+            }
+        }
+        self.rewrite_raw(Some(signer.issuer.as_raw()), |oid, ext, kept| {
+            if *oid == OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER {
+                if let Some(aki) = signer_aki {
+                    kept.extend_from_slice(aki);
+                }
+            } else if *oid != OID_CT_PRECERT_POISON && *oid != OID_CT_LIST_SCT {
+                kept.extend_from_slice(ext);
+            }
+        })
+    }
+
     /// Returns true if certificate has `basicConstraints CA:true`
+    ///
+    /// This is always `false` for v1 and v2 certificates, see
+    /// [`X509Certificate::assume_v1_ca`] to accept them as CA certificates.
     pub fn is_ca(&self) -> bool {
         self.basic_constraints()
             .unwrap_or(None)
@@ -498,29 +1451,45 @@ impl<'a> TbsCertificate<'a> {
         self.raw_serial
     }
 
+    /// Get the certificate serial number as `u128`
+    ///
+    /// Returns `None` if the serial number does not fit in 128 bits.
+    pub fn serial_u128(&self) -> Option<u128> {
+        serial_to_u128(self.raw_serial)
+    }
+
+    /// Compare the raw bytes of the certificate serial number with `serial`, in constant time
+    ///
+    /// Leading zero bytes are significant, `serial` should be the raw content of the
+    /// encoded INTEGER (as returned by [`raw_serial`](Self::raw_serial)).
+    pub fn raw_serial_ct_eq(&self, serial: &[u8]) -> bool {
+        constant_time_eq(self.raw_serial, serial)
+    }
+
     /// Get a formatted string of the certificate serial number, separated by ':'
     pub fn raw_serial_as_string(&self) -> String {
         format_serial(self.raw_serial)
     }
+
+    /// Get a formatted string of the certificate serial number, using the given style
+    pub fn format_serial(&self, style: SerialFormat) -> String {
+        format_serial_with(self.raw_serial, style)
+    }
 }
 
-/// Searches for an extension with the given `Oid`.
-///
-/// Note: if there are several extensions with the same `Oid`, an error `DuplicateExtensions` is returned.
-fn get_extension_unique<'a, 'b>(
-    extensions: &'a [X509Extension<'b>],
-    oid: &Oid,
-) -> Result<Option<&'a X509Extension<'b>>, X509Error> {
-    let mut res = None;
-    for ext in extensions {
-        if ext.oid == *oid {
-            if res.is_some() {
-                return Err(X509Error::DuplicateExtensions);
-            }
-            res = Some(ext);
-        }
+// Split the content of the `[3] EXPLICIT Extensions` field into the OID and DER encoding of each
+// extension
+fn split_raw_extensions(data: &[u8]) -> Result<Vec<(Oid, &[u8])>, X509Error> {
+    let (_, extensions) = Any::from_der(data).or(Err(X509Error::InvalidExtensions))?;
+    let mut v = Vec::new();
+    let mut e = extensions.data;
+    while !e.is_empty() {
+        let (rem, ext) = Any::from_der(e).or(Err(X509Error::InvalidExtensions))?;
+        let (_, oid) = Oid::from_der(ext.data).or(Err(X509Error::InvalidExtensions))?;
+        v.push((oid, &e[..e.len() - rem.len()]));
+        e = rem;
     }
-    Ok(res)
+    Ok(v)
 }
 
 impl<'a> AsRef<[u8]> for TbsCertificate<'a> {
@@ -550,36 +1519,7 @@ impl<'a> FromDer<'a, X509Error> for TbsCertificate<'a> {
     ///                           -- If present, version MUST be v3 --  }
     /// </pre>
     fn from_der(i: &'a [u8]) -> X509Result<TbsCertificate<'a>> {
-        let start_i = i;
-        parse_der_sequence_defined_g(move |i, _| {
-            let (i, version) = X509Version::from_der_tagged_0(i)?;
-            let (i, serial) = parse_serial(i)?;
-            let (i, signature) = AlgorithmIdentifier::from_der(i)?;
-            let (i, issuer) = X509Name::from_der(i)?;
-            let (i, validity) = Validity::from_der(i)?;
-            let (i, subject) = X509Name::from_der(i)?;
-            let (i, subject_pki) = SubjectPublicKeyInfo::from_der(i)?;
-            let (i, issuer_uid) = UniqueIdentifier::from_der_issuer(i)?;
-            let (i, subject_uid) = UniqueIdentifier::from_der_subject(i)?;
-            let (i, extensions) = parse_extensions(i, Tag(3))?;
-            let len = start_i.offset(i);
-            let tbs = TbsCertificate {
-                version,
-                serial: serial.1,
-                signature,
-                issuer,
-                validity,
-                subject,
-                subject_pki,
-                issuer_uid,
-                subject_uid,
-                extensions,
-
-                raw: &start_i[..len],
-                raw_serial: serial.0,
-            };
-            Ok((i, tbs))
-        })(i)
+        TbsCertificateParser::new().parse(i)
     }
 }
 
@@ -587,6 +1527,7 @@ impl<'a> FromDer<'a, X509Error> for TbsCertificate<'a> {
 #[derive(Clone, Copy, Debug)]
 pub struct TbsCertificateParser {
     deep_parse_extensions: bool,
+    strict: bool,
 }
 
 impl TbsCertificateParser {
@@ -594,6 +1535,7 @@ impl TbsCertificateParser {
     pub const fn new() -> Self {
         TbsCertificateParser {
             deep_parse_extensions: true,
+            strict: false,
         }
     }
 
@@ -601,23 +1543,49 @@ impl TbsCertificateParser {
     pub const fn with_deep_parse_extensions(self, deep_parse_extensions: bool) -> Self {
         TbsCertificateParser {
             deep_parse_extensions,
+            ..self
         }
     }
+
+    /// Enable or disable strict mode
+    ///
+    /// See [`X509CertificateParser::with_strict`].
+    #[inline]
+    pub const fn with_strict(self, strict: bool) -> Self {
+        TbsCertificateParser { strict, ..self }
+    }
 }
 
 impl<'a> Parser<&'a [u8], TbsCertificate<'a>, X509Error> for TbsCertificateParser {
     fn parse(&mut self, input: &'a [u8]) -> IResult<&'a [u8], TbsCertificate<'a>, X509Error> {
         let start_i = input;
+        trace_field!("tbsCertificate", input);
         parse_der_sequence_defined_g(move |i, _| {
+            trace_field!("tbsCertificate.version", i);
             let (i, version) = X509Version::from_der_tagged_0(i)?;
-            let (i, serial) = parse_serial(i)?;
+            trace_field!("tbsCertificate.serialNumber", i);
+            let (i, raw_serial) = parse_serial(i)?;
+            trace_field!("tbsCertificate.signature", i);
             let (i, signature) = AlgorithmIdentifier::from_der(i)?;
+            if self.strict && signature.has_forbidden_parameters() {
+                return Err(nom::Err::Error(X509Error::InvalidAlgorithmIdentifier));
+            }
+            trace_field!("tbsCertificate.issuer", i);
             let (i, issuer) = X509Name::from_der(i)?;
+            trace_field!("tbsCertificate.validity", i);
             let (i, validity) = Validity::from_der(i)?;
+            trace_field!("tbsCertificate.subject", i);
             let (i, subject) = X509Name::from_der(i)?;
+            trace_field!("tbsCertificate.subjectPublicKeyInfo", i);
             let (i, subject_pki) = SubjectPublicKeyInfo::from_der(i)?;
+            if self.strict && subject_pki.algorithm.has_forbidden_parameters() {
+                return Err(nom::Err::Error(X509Error::InvalidAlgorithmIdentifier));
+            }
+            trace_field!("tbsCertificate.issuerUniqueID", i);
             let (i, issuer_uid) = UniqueIdentifier::from_der_issuer(i)?;
+            trace_field!("tbsCertificate.subjectUniqueID", i);
             let (i, subject_uid) = UniqueIdentifier::from_der_subject(i)?;
+            trace_field!("tbsCertificate.extensions", i);
             let (i, extensions) = if self.deep_parse_extensions {
                 parse_extensions(i, Tag(3))?
             } else {
@@ -626,7 +1594,8 @@ impl<'a> Parser<&'a [u8], TbsCertificate<'a>, X509Error> for TbsCertificateParse
             let len = start_i.offset(i);
             let tbs = TbsCertificate {
                 version,
-                serial: serial.1,
+                #[cfg(feature = "bigint")]
+                serial: BigUint::from_bytes_be(raw_serial),
                 signature,
                 issuer,
                 validity,
@@ -637,7 +1606,7 @@ impl<'a> Parser<&'a [u8], TbsCertificate<'a>, X509Error> for TbsCertificateParse
                 extensions,
 
                 raw: &start_i[..len],
-                raw_serial: serial.0,
+                raw_serial,
             };
             Ok((i, tbs))
         })(input)
@@ -703,6 +1672,38 @@ impl Validity {
     pub fn is_valid(&self) -> bool {
         self.is_valid_at(ASN1Time::now())
     }
+
+    /// The total validity period of the certificate (from `notBefore` to `notAfter`)
+    ///
+    /// Return `None` if `notAfter` is not after `notBefore`.
+    pub fn lifetime(&self) -> Option<Duration> {
+        self.not_after - self.not_before
+    }
+
+    /// Check if the certificate expires within `duration` of the provided date/time
+    ///
+    /// A certificate that has already expired at `time` is considered as expiring.
+    pub fn expires_within_at(&self, duration: Duration, time: ASN1Time) -> bool {
+        match time + duration {
+            Some(limit) => self.not_after <= limit,
+            None => true,
+        }
+    }
+
+    /// Check if the certificate expires within `duration` from now
+    ///
+    /// A certificate that has already expired is considered as expiring.
+    #[inline]
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        self.expires_within_at(duration, ASN1Time::now())
+    }
+
+    /// The number of whole days left before the certificate expires
+    ///
+    /// If the certificate is not currently valid, then `None` is returned.
+    pub fn days_remaining(&self) -> Option<i64> {
+        self.time_to_expiration().map(|d| d.whole_days())
+    }
 }
 
 impl<'a> FromDer<'a, X509Error> for Validity {
@@ -778,5 +1779,8 @@ mod tests {
         assert!(r3.is_ok());
         let r4 = get_extension_unique(&extensions, &oid! {1.4});
         assert!(r4.is_err());
+
+        let dups = duplicate_extensions(&extensions);
+        assert_eq!(dups, vec![&oid! {1.2}, &oid! {1.4}]);
     }
 }
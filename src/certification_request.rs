@@ -12,7 +12,11 @@ use der_parser::der::*;
 use der_parser::oid::Oid;
 use der_parser::*;
 use nom::Offset;
+use oid_registry::{
+    OID_PKCS9_CHALLENGE_PASSWORD, OID_PKCS9_EXTENSION_REQUEST, OID_PKCS9_SMIME_CAPABILITIES,
+};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 /// Certification Signing Request (CSR)
 #[derive(Debug, PartialEq)]
@@ -20,9 +24,28 @@ pub struct X509CertificationRequest<'a> {
     pub certification_request_info: X509CertificationRequestInfo<'a>,
     pub signature_algorithm: AlgorithmIdentifier<'a>,
     pub signature_value: BitString<'a>,
+    pub(crate) raw: &'a [u8],
 }
 
 impl<'a> X509CertificationRequest<'a> {
+    /// Build a certification request from its parts, for ex. a separately parsed
+    /// [`X509CertificationRequestInfo`]
+    ///
+    /// The request has no DER encoding: [`as_raw`](Self::as_raw) returns an empty slice, and it
+    /// cannot be converted to a [`X509CertificationRequestOwned`].
+    pub fn from_parts(
+        certification_request_info: X509CertificationRequestInfo<'a>,
+        signature_algorithm: AlgorithmIdentifier<'a>,
+        signature_value: BitString<'a>,
+    ) -> Self {
+        X509CertificationRequest {
+            certification_request_info,
+            signature_algorithm,
+            signature_value,
+            raw: &[],
+        }
+    }
+
     pub fn requested_extensions(&self) -> Option<impl Iterator<Item = &ParsedExtension>> {
         self.certification_request_info
             .iter_attributes()
@@ -35,6 +58,39 @@ impl<'a> X509CertificationRequest<'a> {
             })
     }
 
+    /// Return the raw DER encoding of the certification request
+    ///
+    /// This is the encoding the request was parsed from. It is empty if the request was built
+    /// using [`from_parts`](Self::from_parts).
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// Return a copy of this certification request, owning its DER encoding
+    ///
+    /// Returns [`X509Error::MissingEncoding`] if the request has no encoding (see
+    /// [`from_parts`](Self::from_parts)).
+    pub fn to_owned(&self) -> Result<X509CertificationRequestOwned, X509Error> {
+        X509CertificationRequestOwned::try_from(self)
+    }
+
+    /// Return the elements required to verify the signature of this certification request
+    ///
+    /// The returned tuple contains the signed data (the raw DER bytes of the
+    /// `CertificationRequestInfo`), the signature value (a `BitString`, including the number of
+    /// unused bits) and the signature algorithm. The public key is the subject public key of the
+    /// request.
+    ///
+    /// This is useful when verification is done outside of this crate, for ex. using a HSM, or
+    /// without the `verify` feature.
+    pub fn signature_input(&self) -> (&'a [u8], &BitString<'a>, &AlgorithmIdentifier<'a>) {
+        (
+            self.certification_request_info.raw,
+            &self.signature_value,
+            &self.signature_algorithm,
+        )
+    }
+
     /// Verify the cryptographic signature of this certification request
     ///
     /// Uses the public key contained in the CSR, which must be the one of the entity
@@ -59,18 +115,70 @@ impl<'a> X509CertificationRequest<'a> {
 /// }
 /// </pre>
 impl<'a> FromDer<'a, X509Error> for X509CertificationRequest<'a> {
-    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
-        parse_der_sequence_defined_g(|i, _| {
+    fn from_der(input: &'a [u8]) -> X509Result<'a, Self> {
+        let (rem, mut req) = parse_der_sequence_defined_g(|i, _| {
             let (i, certification_request_info) = X509CertificationRequestInfo::from_der(i)?;
             let (i, signature_algorithm) = AlgorithmIdentifier::from_der(i)?;
             let (i, signature_value) = parse_signature_value(i)?;
-            let cert = X509CertificationRequest {
+            let cert = X509CertificationRequest::from_parts(
                 certification_request_info,
                 signature_algorithm,
                 signature_value,
-            };
+            );
             Ok((i, cert))
-        })(i)
+        })(input)?;
+        req.raw = &input[..input.offset(rem)];
+        Ok((rem, req))
+    }
+}
+
+/// A certification request owning its DER encoding
+///
+/// [`X509CertificationRequest`] borrows the input buffer, which makes it difficult to store in long-lived
+/// structures. This type stores a copy of the encoding, and can be sent to other threads.
+/// The parsed object is obtained using [`Self::request`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct X509CertificationRequestOwned {
+    raw: Vec<u8>,
+}
+
+impl X509CertificationRequestOwned {
+    /// Parse a DER-encoded certification request, taking ownership of the buffer
+    ///
+    /// Trailing data after the certification request is removed.
+    pub fn new(mut raw: Vec<u8>) -> Result<Self, X509Error> {
+        let (rem, _) = X509CertificationRequest::from_der(&raw)?;
+        let len = raw.len() - rem.len();
+        raw.truncate(len);
+        Ok(X509CertificationRequestOwned { raw })
+    }
+
+    /// Return the raw DER encoding of the certification request
+    pub fn as_raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Return the parsed certification request, borrowing the stored buffer
+    ///
+    /// The stored encoding is parsed on each call, so the returned object should be kept if
+    /// several fields are accessed. The encoding is checked when the object is built, so this
+    /// does not fail in practice.
+    pub fn request(&self) -> Result<X509CertificationRequest<'_>, X509Error> {
+        let (_, obj) = X509CertificationRequest::from_der(&self.raw)?;
+        Ok(obj)
+    }
+}
+
+impl<'a> TryFrom<&X509CertificationRequest<'a>> for X509CertificationRequestOwned {
+    type Error = X509Error;
+
+    fn try_from(obj: &X509CertificationRequest<'a>) -> Result<Self, Self::Error> {
+        if obj.raw.is_empty() {
+            return Err(X509Error::MissingEncoding);
+        }
+        Ok(X509CertificationRequestOwned {
+            raw: obj.raw.to_vec(),
+        })
     }
 }
 
@@ -97,10 +205,17 @@ pub struct X509CertificationRequestInfo<'a> {
     pub subject: X509Name<'a>,
     pub subject_pki: SubjectPublicKeyInfo<'a>,
     attributes: Vec<X509CriAttribute<'a>>,
+    /// The raw DER encoding of the `CertificationRequestInfo` (the signed data)
     pub raw: &'a [u8],
 }
 
 impl<'a> X509CertificationRequestInfo<'a> {
+    /// Return the raw DER encoding of the `CertificationRequestInfo` (the signed data)
+    #[inline]
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
     /// Get the CRL entry extensions.
     #[inline]
     pub fn attributes(&self) -> &[X509CriAttribute] {
@@ -120,6 +235,92 @@ impl<'a> X509CertificationRequestInfo<'a> {
         self.attributes.iter().find(|&ext| ext.oid == *oid)
     }
 
+    /// Searches for an attribute with the given `Oid`.
+    ///
+    /// Return `Ok(Some(attribute))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error `DuplicateAttributes` if the attribute is present twice or more.
+    pub fn get_attribute_unique(
+        &self,
+        oid: &Oid,
+    ) -> Result<Option<&X509CriAttribute<'a>>, X509Error> {
+        let mut res = None;
+        for attr in &self.attributes {
+            if attr.oid == *oid {
+                if res.is_some() {
+                    return Err(X509Error::DuplicateAttributes);
+                }
+                res = Some(attr);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Get the challenge password attribute, if present
+    ///
+    /// Return an error if the attribute is present twice or more, or is invalid.
+    pub fn challenge_password(&self) -> Result<Option<&ChallengePassword>, X509Error> {
+        self.get_attribute_unique(&OID_PKCS9_CHALLENGE_PASSWORD)?
+            .map_or(Ok(None), |attr| match attr.parsed_attribute {
+                ParsedCriAttribute::ChallengePassword(ref value) => Ok(Some(value)),
+                _ => Err(X509Error::InvalidAttributes),
+            })
+    }
+
+    /// Get the extension request attribute, if present
+    ///
+    /// Return an error if the attribute is present twice or more, or is invalid.
+    pub fn extension_request(&self) -> Result<Option<&ExtensionRequest<'a>>, X509Error> {
+        self.get_attribute_unique(&OID_PKCS9_EXTENSION_REQUEST)?
+            .map_or(Ok(None), |attr| match attr.parsed_attribute {
+                ParsedCriAttribute::ExtensionRequest(ref value) => Ok(Some(value)),
+                _ => Err(X509Error::InvalidAttributes),
+            })
+    }
+
+    /// Get the Microsoft OS version attribute, if present
+    ///
+    /// Return an error if the attribute is present twice or more, or is invalid.
+    pub fn os_version(&self) -> Result<Option<&OsVersion<'a>>, X509Error> {
+        self.get_attribute_unique(&OID_MS_OS_VERSION)?
+            .map_or(Ok(None), |attr| match attr.parsed_attribute {
+                ParsedCriAttribute::OsVersion(ref value) => Ok(Some(value)),
+                _ => Err(X509Error::InvalidAttributes),
+            })
+    }
+
+    /// Get the Microsoft request client info attribute, if present
+    ///
+    /// Return an error if the attribute is present twice or more, or is invalid.
+    pub fn request_client_info(&self) -> Result<Option<&RequestClientInfo<'a>>, X509Error> {
+        self.get_attribute_unique(&OID_MS_REQUEST_CLIENT_INFO)?
+            .map_or(Ok(None), |attr| match attr.parsed_attribute {
+                ParsedCriAttribute::RequestClientInfo(ref value) => Ok(Some(value)),
+                _ => Err(X509Error::InvalidAttributes),
+            })
+    }
+
+    /// Get the Microsoft renewal certificate attribute, if present
+    ///
+    /// Return an error if the attribute is present twice or more, or is invalid.
+    pub fn renewal_certificate(&self) -> Result<Option<&RenewalCertificate<'a>>, X509Error> {
+        self.get_attribute_unique(&OID_MS_RENEWAL_CERTIFICATE)?
+            .map_or(Ok(None), |attr| match attr.parsed_attribute {
+                ParsedCriAttribute::RenewalCertificate(ref value) => Ok(Some(value)),
+                _ => Err(X509Error::InvalidAttributes),
+            })
+    }
+
+    /// Get the S/MIME capabilities attribute, if present
+    ///
+    /// Return an error if the attribute is present twice or more, or is invalid.
+    pub fn smime_capabilities(&self) -> Result<Option<&SMIMECapabilities<'a>>, X509Error> {
+        self.get_attribute_unique(&OID_PKCS9_SMIME_CAPABILITIES)?
+            .map_or(Ok(None), |attr| match attr.parsed_attribute {
+                ParsedCriAttribute::SMIMECapabilities(ref value) => Ok(Some(value)),
+                _ => Err(X509Error::InvalidAttributes),
+            })
+    }
+
     /// Builds and returns a map of CRL entry extensions.
     ///
     /// If an extension is present twice, this will fail and return `DuplicateExtensions`.
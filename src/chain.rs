@@ -0,0 +1,240 @@
+//! Helpers to build certificate chains from unordered sets of certificates
+//!
+//! Certificates received from peers or read from bundles are often in random order, or contain
+//! unrelated certificates. The functions from this module help finding the relations between
+//! certificates.
+//!
+//! Note that these functions do not validate the chain: signatures (unless used to break ties),
+//! validity periods, key usages, constraints etc. are not checked.
+
+use crate::certificate::{V1CaPolicy, X509Certificate};
+use crate::x509::X509Name;
+
+/// Find the issuer of `cert` in `candidates`
+///
+/// A candidate is considered as an issuer if [`X509Certificate::is_issued_by`] returns `true`
+/// (names and key identifiers match). If several candidates match (for ex. a re-issued
+/// intermediate certificate, with the same name and key), and the `verify` feature is enabled,
+/// the first candidate whose key verifies the signature of `cert` is preferred.
+///
+/// If `cert` is self-issued and is present in `candidates`, it can be returned as its own issuer.
+pub fn find_issuer<'a, 'b>(
+    cert: &X509Certificate,
+    candidates: &'b [X509Certificate<'a>],
+) -> Option<&'b X509Certificate<'a>> {
+    let matches: Vec<_> = candidates
+        .iter()
+        .filter(|candidate| cert.is_issued_by(candidate))
+        .collect();
+    #[cfg(feature = "verify")]
+    {
+        if matches.len() > 1 {
+            let verified = matches
+                .iter()
+                .find(|candidate| cert.verify_signature(Some(candidate.public_key())).is_ok());
+            if let Some(candidate) = verified {
+                return Some(candidate);
+            }
+        }
+    }
+    matches.first().copied()
+}
+
+/// A set of certificates, arranged in chain order
+///
+/// See [`order_chain`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderedChain<'a, 'b> {
+    /// The certificates of the chain, from the leaf to the root (or the last issuer found)
+    pub chain: Vec<&'b X509Certificate<'a>>,
+    /// The certificates that are not part of the chain
+    pub extra: Vec<&'b X509Certificate<'a>>,
+    /// If the chain does not end with a self-issued certificate, the issuer name of the last
+    /// certificate (the certificate that is missing)
+    pub missing_issuer: Option<&'b X509Name<'a>>,
+    /// `true` if the issuer of the last certificate is already part of the chain (for ex.
+    /// cross-signed certificates issuing each other)
+    pub loop_detected: bool,
+}
+
+impl<'a, 'b> OrderedChain<'a, 'b> {
+    /// Return `true` if the chain ends with a self-issued certificate
+    ///
+    /// A chain with a loop is never complete.
+    pub fn is_complete(&self) -> bool {
+        match self.chain.last() {
+            Some(last) => {
+                !self.loop_detected
+                    && self.missing_issuer.is_none()
+                    && last.subject().as_raw() == last.issuer().as_raw()
+            }
+            None => false,
+        }
+    }
+}
+
+/// Arrange an unordered set of certificates in chain order (leaf to root)
+///
+/// If `leaf_hint` is provided, the chain starts with this certificate (which may or may not be
+/// part of `certs`). Else, the leaf is guessed: it is a certificate which did not issue any other
+/// certificate of the set, and a non-CA certificate is preferred.
+///
+/// Issuers are then added using [`find_issuer`], until a self-issued certificate is found, or
+/// the issuer is missing (in that case, `missing_issuer` is set), or the issuer is already part
+/// of the chain (in that case, `loop_detected` is set).
+/// Certificates which are not part of the chain are returned in `extra`.
+///
+/// Version 1 and 2 certificates are never considered as CA certificates when guessing the
+/// leaf, see [`order_chain_with_v1_ca_policy`].
+pub fn order_chain<'a, 'b>(
+    leaf_hint: Option<&'b X509Certificate<'a>>,
+    certs: &'b [X509Certificate<'a>],
+) -> OrderedChain<'a, 'b> {
+    order_chain_with_v1_ca_policy(leaf_hint, certs, V1CaPolicy::Never)
+}
+
+/// Arrange an unordered set of certificates in chain order (leaf to root), using `v1_ca_policy`
+/// to decide if v1 and v2 certificates are CA certificates when guessing the leaf
+///
+/// See [`order_chain`] and [`X509Certificate::assume_v1_ca`].
+pub fn order_chain_with_v1_ca_policy<'a, 'b>(
+    leaf_hint: Option<&'b X509Certificate<'a>>,
+    certs: &'b [X509Certificate<'a>],
+    v1_ca_policy: V1CaPolicy,
+) -> OrderedChain<'a, 'b> {
+    let mut used = vec![false; certs.len()];
+    let mut chain = Vec::new();
+    let mut missing_issuer = None;
+    let mut loop_detected = false;
+
+    let mut current = leaf_hint.or_else(|| guess_leaf(certs, v1_ca_policy).map(|idx| &certs[idx]));
+    while let Some(cert) = current {
+        if let Some(idx) = certs
+            .iter()
+            .position(|c| c.tbs_certificate.raw == cert.tbs_certificate.raw)
+        {
+            used[idx] = true;
+        }
+        chain.push(cert);
+        if cert.subject().as_raw() == cert.issuer().as_raw() {
+            break;
+        }
+        current = match find_issuer(cert, certs) {
+            Some(issuer) if chain.iter().any(|c| std::ptr::eq(*c, issuer)) => {
+                // issuer is already part of the chain (loop)
+                loop_detected = true;
+                None
+            }
+            Some(issuer) => Some(issuer),
+            None => {
+                missing_issuer = Some(&cert.tbs_certificate.issuer);
+                None
+            }
+        };
+    }
+
+    let extra = certs
+        .iter()
+        .zip(used.iter())
+        .filter(|(_, &used)| !used)
+        .map(|(c, _)| c)
+        .collect();
+    OrderedChain {
+        chain,
+        extra,
+        missing_issuer,
+        loop_detected,
+    }
+}
+
+// Find a certificate that did not issue any other certificate of the set, preferring non-CA
+// certificates
+fn guess_leaf(certs: &[X509Certificate], v1_ca_policy: V1CaPolicy) -> Option<usize> {
+    let candidates: Vec<_> = (0..certs.len())
+        .filter(|&idx| {
+            !certs
+                .iter()
+                .enumerate()
+                .any(|(other, c)| other != idx && c.is_issued_by(&certs[idx]))
+        })
+        .collect();
+    candidates
+        .iter()
+        .find(|&&idx| !certs[idx].assume_v1_ca(v1_ca_policy))
+        .or_else(|| candidates.first())
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asn1_rs::FromDer;
+
+    static IGCA_DER: &[u8] = include_bytes!("../assets/IGC_A.der");
+    static V1_DER: &[u8] = include_bytes!("../assets/v1.der");
+    static TEST_CA_DER: &[u8] = include_bytes!("../assets/test-ca.der");
+    static TEST_LEAF_DER: &[u8] = include_bytes!("../assets/test-leaf.der");
+    static CROSS_A_DER: &[u8] = include_bytes!("../assets/test-cross-a.der");
+    static CROSS_B_DER: &[u8] = include_bytes!("../assets/test-cross-b.der");
+
+    fn parse(der: &[u8]) -> X509Certificate {
+        X509Certificate::from_der(der).expect("parsing failed").1
+    }
+
+    #[test]
+    fn test_find_issuer() {
+        let leaf = parse(TEST_LEAF_DER);
+        let candidates = vec![parse(IGCA_DER), leaf.clone(), parse(TEST_CA_DER)];
+        let issuer = find_issuer(&leaf, &candidates).expect("issuer not found");
+        assert_eq!(issuer.subject(), candidates[2].subject());
+        // self-signed certificates are their own issuer
+        let issuer = find_issuer(&candidates[0], &candidates).expect("issuer not found");
+        assert_eq!(issuer.subject(), candidates[0].subject());
+        // no issuer
+        assert!(find_issuer(&leaf, &candidates[..2]).is_none());
+    }
+
+    #[test]
+    fn test_order_chain() {
+        let certs = vec![parse(TEST_CA_DER), parse(IGCA_DER), parse(TEST_LEAF_DER)];
+        let ordered = order_chain(None, &certs);
+        assert!(ordered.is_complete());
+        assert_eq!(ordered.chain, vec![&certs[2], &certs[0]]);
+        assert_eq!(ordered.extra, vec![&certs[1]]);
+
+        // missing root
+        let ordered = order_chain(Some(&certs[2]), &certs[1..]);
+        assert!(!ordered.is_complete());
+        assert_eq!(ordered.chain, vec![&certs[2]]);
+        assert_eq!(ordered.missing_issuer, Some(certs[0].subject()));
+        assert_eq!(ordered.extra, vec![&certs[1]]);
+
+        let ordered = order_chain(None, &[]);
+        assert!(ordered.chain.is_empty());
+        assert!(!ordered.is_complete());
+    }
+
+    #[test]
+    fn test_order_chain_loop() {
+        // two CA certificates, cross-signed: each one issued the other
+        let certs = vec![parse(CROSS_A_DER), parse(CROSS_B_DER)];
+        let ordered = order_chain(Some(&certs[0]), &certs);
+        assert_eq!(ordered.chain, vec![&certs[0], &certs[1]]);
+        assert!(ordered.extra.is_empty());
+        assert!(ordered.missing_issuer.is_none());
+        assert!(ordered.loop_detected);
+        assert!(!ordered.is_complete());
+    }
+
+    #[test]
+    fn test_order_chain_v1_ca_policy() {
+        // unrelated certificates: a v1 root, and a leaf
+        let certs = vec![parse(V1_DER), parse(TEST_LEAF_DER)];
+        let ordered = order_chain(None, &certs);
+        assert_eq!(ordered.chain, vec![&certs[0]]);
+        // the v1 root is a CA, the leaf is preferred
+        let ordered = order_chain_with_v1_ca_policy(None, &certs, V1CaPolicy::SelfIssued);
+        assert_eq!(ordered.chain, vec![&certs[1]]);
+        assert_eq!(ordered.extra, vec![&certs[0]]);
+    }
+}
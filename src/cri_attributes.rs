@@ -1,19 +1,24 @@
 use crate::{
+    certificate::X509Certificate,
     error::{X509Error, X509Result},
-    extensions::X509Extension,
+    extensions::{SMIMECapabilities, X509Extension},
+    utils::many0_lazy,
 };
 
-use asn1_rs::{Error, FromDer, Header, Oid, Sequence, Tag};
+use asn1_rs::{oid, Any, Error, FromDer, Header, Oid, Sequence, Tag};
+use der_parser::der::parse_der;
 use nom::combinator::{all_consuming, complete};
-use nom::multi::many0;
 use nom::Err;
 use oid_registry::*;
 use std::collections::HashMap;
+use std::fmt::Write;
 
 /// Attributes for Certification Request
 #[derive(Clone, Debug, PartialEq)]
 pub struct X509CriAttribute<'a> {
+    /// OID describing the attribute content
     pub oid: Oid<'a>,
+    /// Raw content of the attribute (the `SET` of values)
     pub value: &'a [u8],
     pub(crate) parsed_attribute: ParsedCriAttribute<'a>,
 }
@@ -28,8 +33,8 @@ impl<'a> FromDer<'a, X509Error> for X509CriAttribute<'a> {
                 return Err(Err::Error(Error::BerTypeError));
             };
 
-            let (i, parsed_attribute) = crate::cri_attributes::parser::parse_attribute(i, &oid)
-                .map_err(|_| Err::Error(Error::BerValueError))?;
+            let (i, parsed_attribute) =
+                parser::parse_attribute(i, &oid).map_err(|_| Err::Error(Error::BerValueError))?;
             let attribute = X509CriAttribute {
                 oid,
                 value: &value_start[..value_start.len() - i.len()],
@@ -42,11 +47,41 @@ impl<'a> FromDer<'a, X509Error> for X509CriAttribute<'a> {
 }
 
 impl<'a> X509CriAttribute<'a> {
+    /// Creates a new attribute with the provided values.
+    #[inline]
+    pub const fn new(
+        oid: Oid<'a>,
+        value: &'a [u8],
+        parsed_attribute: ParsedCriAttribute<'a>,
+    ) -> X509CriAttribute<'a> {
+        X509CriAttribute {
+            oid,
+            value,
+            parsed_attribute,
+        }
+    }
+
     /// Return the attribute type or `UnsupportedAttribute` if the attribute is unknown.
     #[inline]
     pub fn parsed_attribute(&self) -> &ParsedCriAttribute<'a> {
         &self.parsed_attribute
     }
+
+    /// Return the attribute value formatted as an ASN.1 tree
+    ///
+    /// This is meant for debugging, for ex. to inspect the structure of unsupported attributes.
+    /// An error is returned if the value is not valid DER.
+    pub fn debug_dump(&self) -> Result<String, X509Error> {
+        let mut s = String::new();
+        let mut i = self.value;
+        while !i.is_empty() {
+            let (rem, obj) = parse_der(i)?;
+            // cannot fail, writing to a String
+            let _ = writeln!(s, "{:?}", obj.as_pretty(0, 2));
+            i = rem;
+        }
+        Ok(s)
+    }
 }
 
 /// Section 3.1 of rfc 5272
@@ -64,14 +99,57 @@ impl<'a> FromDer<'a, X509Error> for ExtensionRequest<'a> {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ChallengePassword(pub String);
 
+/// OID of the Microsoft OS version attribute (`szOID_OS_VERSION`)
+pub const OID_MS_OS_VERSION: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .13 .2 .3);
+/// OID of the Microsoft request client info attribute (`szOID_REQUEST_CLIENT_INFO`)
+pub const OID_MS_REQUEST_CLIENT_INFO: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .21 .20);
+/// OID of the Microsoft renewal certificate attribute (`szOID_RENEWAL_CERTIFICATE`)
+pub const OID_MS_RENEWAL_CERTIFICATE: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .13 .1);
+
+/// Microsoft OS version attribute (for ex. `10.0.19045.2`)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OsVersion<'a>(pub &'a str);
+
+/// Microsoft request client info attribute
+///
+/// <pre>
+/// ClientInformation ::= SEQUENCE {
+///     clientId       INTEGER,
+///     MachineName    UTF8String,
+///     UserName       UTF8String,
+///     ProcessName    UTF8String }
+/// </pre>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestClientInfo<'a> {
+    pub client_id: u32,
+    pub machine_name: &'a str,
+    pub user_name: &'a str,
+    pub process_name: &'a str,
+}
+
+/// Microsoft renewal certificate attribute: the certificate being renewed
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenewalCertificate<'a>(pub Box<X509Certificate<'a>>);
+
 /// Attributes for Certification Request
 #[derive(Clone, Debug, PartialEq)]
 pub enum ParsedCriAttribute<'a> {
     ChallengePassword(ChallengePassword),
     ExtensionRequest(ExtensionRequest<'a>),
+    OsVersion(OsVersion<'a>),
+    RequestClientInfo(RequestClientInfo<'a>),
+    RenewalCertificate(RenewalCertificate<'a>),
+    SMIMECapabilities(SMIMECapabilities<'a>),
     UnsupportedAttribute,
 }
 
+impl<'a> ParsedCriAttribute<'a> {
+    /// Return `true` if the attribute is unsupported
+    pub fn unsupported(&self) -> bool {
+        matches!(self, &ParsedCriAttribute::UnsupportedAttribute)
+    }
+}
+
 pub(crate) mod parser {
     use crate::cri_attributes::*;
     use der_parser::der::{
@@ -99,10 +177,32 @@ pub(crate) mod parser {
                 OID_PKCS9_CHALLENGE_PASSWORD,
                 parse_challenge_password_attr
             );
+            add!(m, OID_MS_OS_VERSION, parse_os_version_attr);
+            add!(
+                m,
+                OID_MS_REQUEST_CLIENT_INFO,
+                parse_request_client_info_attr
+            );
+            add!(
+                m,
+                OID_MS_RENEWAL_CERTIFICATE,
+                parse_renewal_certificate_attr
+            );
+            add!(
+                m,
+                OID_PKCS9_SMIME_CAPABILITIES,
+                parse_smime_capabilities_attr
+            );
             m
         };
     }
 
+    /// Returns the OIDs of the attributes with a registered parser
+    #[cfg(test)]
+    pub(crate) fn supported_attributes() -> impl Iterator<Item = &'static Oid<'static>> {
+        ATTRIBUTE_PARSERS.keys()
+    }
+
     // look into the parser map if the extension is known, and parse it
     // otherwise, leave it as UnsupportedExtension
     pub(crate) fn parse_attribute<'a>(
@@ -167,12 +267,52 @@ pub(crate) mod parser {
             ParsedCriAttribute::ChallengePassword,
         )(i)
     }
+
+    // szOID_OS_VERSION: IA5String
+    fn parse_os_version_attr(i: &[u8]) -> X509Result<ParsedCriAttribute> {
+        let (rem, any) = Any::from_der(i).map_err(|_| X509Error::InvalidAttributes)?;
+        if any.tag() != Tag::Ia5String {
+            return Err(Err::Error(X509Error::InvalidAttributes));
+        }
+        let s = std::str::from_utf8(any.data).map_err(|_| X509Error::InvalidAttributes)?;
+        Ok((rem, ParsedCriAttribute::OsVersion(OsVersion(s))))
+    }
+
+    fn parse_request_client_info_attr(i: &[u8]) -> X509Result<ParsedCriAttribute> {
+        Sequence::from_der_and_then(i, |i| {
+            let (i, client_id) = u32::from_der(i)?;
+            let (i, machine_name) = <&str>::from_der(i)?;
+            let (i, user_name) = <&str>::from_der(i)?;
+            let (i, process_name) = <&str>::from_der(i)?;
+            let info = RequestClientInfo {
+                client_id,
+                machine_name,
+                user_name,
+                process_name,
+            };
+            Ok((i, ParsedCriAttribute::RequestClientInfo(info)))
+        })
+        .map_err(|_: Err<Error>| Err::Error(X509Error::InvalidAttributes))
+    }
+
+    fn parse_renewal_certificate_attr(i: &[u8]) -> X509Result<ParsedCriAttribute> {
+        map(X509Certificate::from_der, |cert| {
+            ParsedCriAttribute::RenewalCertificate(RenewalCertificate(Box::new(cert)))
+        })(i)
+    }
+
+    fn parse_smime_capabilities_attr(i: &[u8]) -> X509Result<ParsedCriAttribute> {
+        map(
+            SMIMECapabilities::from_der,
+            ParsedCriAttribute::SMIMECapabilities,
+        )(i)
+    }
 }
 
 pub(crate) fn parse_cri_attributes(i: &[u8]) -> X509Result<Vec<X509CriAttribute>> {
     let (i, hdr) = Header::from_der(i).map_err(|_| Err::Error(X509Error::InvalidAttributes))?;
     if hdr.is_contextspecific() && hdr.tag().0 == 0 {
-        all_consuming(many0(complete(X509CriAttribute::from_der)))(i)
+        all_consuming(many0_lazy(complete(X509CriAttribute::from_der)))(i)
     } else {
         Err(Err::Error(X509Error::InvalidAttributes))
     }
@@ -0,0 +1,500 @@
+//! Certificate transparency log entries and proofs ([RFC6962])
+//!
+//! Logs store certificates (or precertificates) in the leaves of a Merkle tree. To verify an
+//! inclusion proof returned by a log, the leaf hash must be computed from the certificate and
+//! from the timestamp and extensions of the Signed Certificate Timestamp (SCT):
+//!
+//! <pre>
+//! struct {
+//!     Version version;
+//!     MerkleLeafType leaf_type;
+//!     select (leaf_type) {
+//!         case timestamped_entry: TimestampedEntry;
+//!     }
+//! } MerkleTreeLeaf;
+//!
+//! struct {
+//!     uint64 timestamp;
+//!     LogEntryType entry_type;
+//!     select(entry_type) {
+//!         case x509_entry: ASN.1Cert;
+//!         case precert_entry: PreCert;
+//!     } signed_entry;
+//!     CtExtensions extensions;
+//! } TimestampedEntry;
+//! </pre>
+//!
+//! The leaf hash is `SHA-256(0x00 || MerkleTreeLeaf)`, and requires the `hash` feature.
+//!
+//! Inclusion proofs and consistency proofs between tree heads can then be checked using
+//! [`verify_inclusion_proof`] and [`verify_consistency_proof`], and the signature of a
+//! [`SignedTreeHead`] using the log public key (`verify` feature).
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "hash")] {
+//! use x509_parser::ct::{leaf_hash, LogEntry};
+//! use x509_parser::prelude::*;
+//!
+//! fn print_leaf_hash(der: &[u8], timestamp: u64) {
+//!     let (_, x509) = parse_x509_certificate(der).expect("invalid certificate");
+//!     let hash = leaf_hash(&LogEntry::from_certificate(&x509), timestamp, &[])
+//!         .expect("entry is too large");
+//!     println!("leaf hash: {:x?}", hash);
+//! }
+//! # }
+//! ```
+//!
+//! [RFC6962]: https://datatracker.ietf.org/doc/html/rfc6962
+
+use crate::certificate::X509Certificate;
+use crate::error::X509Error;
+use crate::extensions::DigitallySigned;
+#[cfg(feature = "hash")]
+use crate::extensions::SignedCertificateTimestamp;
+#[cfg(any(feature = "hash", feature = "verify"))]
+use crate::x509::SubjectPublicKeyInfo;
+use std::convert::TryFrom;
+
+/// An entry of a certificate transparency log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogEntry<'a> {
+    /// A certificate (`x509_entry`), as its DER encoding
+    X509(&'a [u8]),
+    /// A precertificate (`precert_entry`)
+    Precert {
+        /// SHA-256 hash of the DER-encoded `SubjectPublicKeyInfo` of the issuer
+        issuer_key_hash: [u8; 32],
+        /// The DER-encoded `TBSCertificate` of the precertificate, without the poison extension
+        tbs_certificate: &'a [u8],
+    },
+}
+
+impl<'a> LogEntry<'a> {
+    /// Build a `x509_entry` from a certificate
+    pub fn from_certificate(x509: &X509Certificate<'a>) -> Self {
+        LogEntry::X509(x509.as_raw())
+    }
+
+    /// Build a `precert_entry` from the issuer public key and the TBS certificate
+    ///
+    /// `tbs_certificate` must be the DER encoding of the precertificate `TBSCertificate`,
+    /// with the poison extension removed.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+    pub fn precert(issuer_spki: &SubjectPublicKeyInfo, tbs_certificate: &'a [u8]) -> Self {
+        use sha2::{Digest, Sha256};
+        LogEntry::Precert {
+            issuer_key_hash: Sha256::digest(issuer_spki.raw).into(),
+            tbs_certificate,
+        }
+    }
+
+    /// Return the `LogEntryType` value (0 for `x509_entry`, 1 for `precert_entry`)
+    pub fn entry_type(&self) -> u16 {
+        match self {
+            LogEntry::X509(_) => 0,
+            LogEntry::Precert { .. } => 1,
+        }
+    }
+}
+
+/// Build the TLS encoding of the `MerkleTreeLeaf` (version 1) for the given entry
+///
+/// `timestamp` and `extensions` are the values from the SCT issued by the log.
+///
+/// Entries and extensions larger than the sizes allowed by RFC6962 (2^24-1 and 2^16-1 bytes)
+/// cannot be encoded, and return `InvalidCertificate` and `InvalidExtensions` respectively.
+pub fn merkle_tree_leaf(
+    entry: &LogEntry,
+    timestamp: u64,
+    extensions: &[u8],
+) -> Result<Vec<u8>, X509Error> {
+    let mut v = Vec::new();
+    // version: v1(0), leaf_type: timestamped_entry(0)
+    v.extend_from_slice(&[0, 0]);
+    v.extend_from_slice(&timestamp.to_be_bytes());
+    v.extend_from_slice(&entry.entry_type().to_be_bytes());
+    let data = match entry {
+        LogEntry::X509(cert) => cert,
+        LogEntry::Precert {
+            issuer_key_hash,
+            tbs_certificate,
+        } => {
+            v.extend_from_slice(issuer_key_hash);
+            tbs_certificate
+        }
+    };
+    // opaque <1..2^24-1>
+    if data.len() >= 1 << 24 {
+        return Err(X509Error::InvalidCertificate);
+    }
+    v.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    v.extend_from_slice(data);
+    // opaque CtExtensions<0..2^16-1>
+    let extensions_len = u16::try_from(extensions.len()).or(Err(X509Error::InvalidExtensions))?;
+    v.extend_from_slice(&extensions_len.to_be_bytes());
+    v.extend_from_slice(extensions);
+    Ok(v)
+}
+
+/// Compute the Merkle tree leaf hash of the given entry
+///
+/// `timestamp` and `extensions` are the values from the SCT issued by the log. See
+/// [`merkle_tree_leaf`] for the errors.
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+pub fn leaf_hash(
+    entry: &LogEntry,
+    timestamp: u64,
+    extensions: &[u8],
+) -> Result<[u8; 32], X509Error> {
+    use sha2::{Digest, Sha256};
+    let leaf = merkle_tree_leaf(entry, timestamp, extensions)?;
+    let mut hasher = Sha256::new();
+    hasher.update([0]);
+    hasher.update(leaf);
+    Ok(hasher.finalize().into())
+}
+
+/// Compute the Merkle tree leaf hash of the given entry, using the timestamp and extensions of a SCT
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+pub fn leaf_hash_for_sct(
+    entry: &LogEntry,
+    sct: &SignedCertificateTimestamp,
+) -> Result<[u8; 32], X509Error> {
+    leaf_hash(entry, sct.timestamp, sct.extensions.0)
+}
+
+/// Hash of an interior node of the Merkle tree: `SHA-256(0x01 || left || right)`
+#[cfg(feature = "hash")]
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update([1]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Verify an inclusion (audit) proof, as returned by the `get-proof-by-hash` log API
+///
+/// Returns `true` if `proof` shows that the leaf with hash `leaf_hash`, at index `leaf_index`,
+/// is included in the tree of size `tree_size` with root hash `root_hash`.
+///
+/// The verification algorithm is described in [RFC9162 Section 2.1.3.2](https://datatracker.ietf.org/doc/html/rfc9162#section-2.1.3.2).
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+pub fn verify_inclusion_proof(
+    leaf_hash: &[u8; 32],
+    leaf_index: u64,
+    tree_size: u64,
+    proof: &[[u8; 32]],
+    root_hash: &[u8; 32],
+) -> bool {
+    if leaf_index >= tree_size {
+        return false;
+    }
+    let (mut fnode, mut snode) = (leaf_index, tree_size - 1);
+    let mut r = *leaf_hash;
+    for p in proof {
+        if snode == 0 {
+            return false;
+        }
+        if fnode & 1 == 1 || fnode == snode {
+            r = node_hash(p, &r);
+            while fnode & 1 == 0 && fnode != 0 {
+                fnode >>= 1;
+                snode >>= 1;
+            }
+        } else {
+            r = node_hash(&r, p);
+        }
+        fnode >>= 1;
+        snode >>= 1;
+    }
+    snode == 0 && r == *root_hash
+}
+
+/// Verify a consistency proof between two signed tree heads, as returned by the
+/// `get-sth-consistency` log API
+///
+/// Returns `true` if `proof` shows that the tree of size `first_size` with root hash `first_root`
+/// is a prefix of the tree of size `second_size` with root hash `second_root`.
+///
+/// The verification algorithm is described in [RFC9162 Section 2.1.4.2](https://datatracker.ietf.org/doc/html/rfc9162#section-2.1.4.2).
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+pub fn verify_consistency_proof(
+    first_size: u64,
+    second_size: u64,
+    first_root: &[u8; 32],
+    second_root: &[u8; 32],
+    proof: &[[u8; 32]],
+) -> bool {
+    if first_size > second_size {
+        return false;
+    }
+    if first_size == second_size {
+        return proof.is_empty() && first_root == second_root;
+    }
+    if first_size == 0 {
+        // the empty tree is a prefix of any tree
+        return proof.is_empty();
+    }
+    if proof.is_empty() {
+        return false;
+    }
+    // if the first tree is complete, its root is the first node of the path
+    let (first, path) = if first_size.is_power_of_two() {
+        (first_root, proof)
+    } else {
+        (&proof[0], &proof[1..])
+    };
+    let (mut fnode, mut snode) = (first_size - 1, second_size - 1);
+    while fnode & 1 == 1 {
+        fnode >>= 1;
+        snode >>= 1;
+    }
+    let (mut fr, mut sr) = (*first, *first);
+    for c in path {
+        if snode == 0 {
+            return false;
+        }
+        if fnode & 1 == 1 || fnode == snode {
+            fr = node_hash(c, &fr);
+            sr = node_hash(c, &sr);
+            while fnode & 1 == 0 && fnode != 0 {
+                fnode >>= 1;
+                snode >>= 1;
+            }
+        } else {
+            sr = node_hash(&sr, c);
+        }
+        fnode >>= 1;
+        snode >>= 1;
+    }
+    snode == 0 && fr == *first_root && sr == *second_root
+}
+
+/// A Signed Tree Head (STH), as returned by the `get-sth` log API
+///
+/// See [RFC6962 Section 3.5](https://datatracker.ietf.org/doc/html/rfc6962#section-3.5).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedTreeHead<'a> {
+    pub tree_size: u64,
+    pub timestamp: u64,
+    pub sha256_root_hash: [u8; 32],
+    pub signature: DigitallySigned<'a>,
+}
+
+impl<'a> SignedTreeHead<'a> {
+    /// Build the TLS encoding of the `TreeHeadSignature` structure, which is the data signed by the log
+    pub fn signed_data(&self) -> Vec<u8> {
+        let mut v = Vec::with_capacity(2 + 8 + 8 + 32);
+        // version: v1(0), signature_type: tree_hash(1)
+        v.extend_from_slice(&[0, 1]);
+        v.extend_from_slice(&self.timestamp.to_be_bytes());
+        v.extend_from_slice(&self.tree_size.to_be_bytes());
+        v.extend_from_slice(&self.sha256_root_hash);
+        v
+    }
+
+    /// Verify the signature of the tree head, using the public key of the log
+    ///
+    /// Logs sign using ECDSA (NIST P-256) or RSA (PKCS#1 v1.5), with SHA-256.
+    #[cfg(feature = "verify")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+    pub fn verify_signature(&self, log_key: &SubjectPublicKeyInfo) -> Result<(), X509Error> {
+        use crate::x509::AlgorithmIdentifier;
+        use asn1_rs::BitString;
+        use oid_registry::{OID_PKCS1_SHA256WITHRSA, OID_SIG_ECDSA_WITH_SHA256};
+        // HashAlgorithm sha256(4), SignatureAlgorithm rsa(1) or ecdsa(3)
+        let oid = match (self.signature.hash_alg_id, self.signature.sign_alg_id) {
+            (4, 1) => OID_PKCS1_SHA256WITHRSA,
+            (4, 3) => OID_SIG_ECDSA_WITH_SHA256,
+            _ => return Err(X509Error::SignatureUnsupportedAlgorithm),
+        };
+        let algorithm = AlgorithmIdentifier::new(oid, None);
+        let signature = BitString::new(0, self.signature.data);
+        crate::verify::verify_signature(log_key, &algorithm, &signature, &self.signed_data())
+    }
+}
+
+#[cfg(all(test, feature = "hash"))]
+mod tests {
+    use super::*;
+    use crate::parse_x509_certificate;
+    use data_encoding::HEXLOWER;
+    use sha2::{Digest, Sha256};
+
+    static TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+    static TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+
+    #[test]
+    fn test_ct_leaf_hash() {
+        let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+        let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+        let timestamp = 1_700_000_000_000;
+
+        let entry = LogEntry::from_certificate(&leaf);
+        let leaf_data = merkle_tree_leaf(&entry, timestamp, &[]).unwrap();
+        assert_eq!(leaf_data.len(), 2 + 8 + 2 + 3 + TEST_LEAF.len() + 2);
+        assert_eq!(
+            HEXLOWER.encode(&leaf_hash(&entry, timestamp, &[]).unwrap()),
+            "cbd64c8cf653714458f59b4e534efbfe8a5fe9a6bbc096d115ceb1a0610ffdbd"
+        );
+
+        let entry = LogEntry::precert(ca.public_key(), leaf.tbs_certificate.as_ref());
+        assert_eq!(entry.entry_type(), 1);
+        assert_eq!(
+            HEXLOWER.encode(&leaf_hash(&entry, timestamp, &[1, 2]).unwrap()),
+            "9e61eaf47550c696a99165ddd473822057049ee93863a5873146022972ae1a8a"
+        );
+
+        // lengths which do not fit in the encoding
+        let big = vec![0; 1 << 24];
+        assert_eq!(
+            merkle_tree_leaf(&LogEntry::X509(&big), timestamp, &[]),
+            Err(X509Error::InvalidCertificate)
+        );
+        assert!(merkle_tree_leaf(&LogEntry::X509(&big[1..]), timestamp, &[]).is_ok());
+        assert_eq!(
+            merkle_tree_leaf(&entry, timestamp, &big[..1 << 16]),
+            Err(X509Error::InvalidExtensions)
+        );
+    }
+
+    // Reference implementation of RFC6962 Section 2.1: root hash, audit path and consistency proof
+
+    fn largest_power_of_two_below(n: usize) -> usize {
+        let mut k = 1;
+        while k << 1 < n {
+            k <<= 1;
+        }
+        k
+    }
+
+    fn mth(leaves: &[[u8; 32]]) -> [u8; 32] {
+        match leaves.len() {
+            0 => Sha256::digest([]).into(),
+            1 => leaves[0],
+            n => {
+                let k = largest_power_of_two_below(n);
+                node_hash(&mth(&leaves[..k]), &mth(&leaves[k..]))
+            }
+        }
+    }
+
+    fn path(m: usize, leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        let n = leaves.len();
+        if n <= 1 {
+            return Vec::new();
+        }
+        let k = largest_power_of_two_below(n);
+        if m < k {
+            let mut v = path(m, &leaves[..k]);
+            v.push(mth(&leaves[k..]));
+            v
+        } else {
+            let mut v = path(m - k, &leaves[k..]);
+            v.push(mth(&leaves[..k]));
+            v
+        }
+    }
+
+    fn subproof(m: usize, leaves: &[[u8; 32]], complete: bool) -> Vec<[u8; 32]> {
+        let n = leaves.len();
+        if m == n {
+            return if complete {
+                Vec::new()
+            } else {
+                vec![mth(leaves)]
+            };
+        }
+        let k = largest_power_of_two_below(n);
+        if m <= k {
+            let mut v = subproof(m, &leaves[..k], complete);
+            v.push(mth(&leaves[k..]));
+            v
+        } else {
+            let mut v = subproof(m - k, &leaves[k..], false);
+            v.push(mth(&leaves[..k]));
+            v
+        }
+    }
+
+    #[test]
+    fn test_ct_proofs() {
+        let leaves: Vec<[u8; 32]> = (0..13u8)
+            .map(|i| leaf_hash(&LogEntry::X509(&[i]), 0, &[]).unwrap())
+            .collect();
+        for n in 1..=leaves.len() {
+            let root = mth(&leaves[..n]);
+            for m in 0..n {
+                let proof = path(m, &leaves[..n]);
+                assert!(verify_inclusion_proof(
+                    &leaves[m], m as u64, n as u64, &proof, &root
+                ));
+                // wrong leaf or truncated proof
+                if !proof.is_empty() {
+                    let truncated = &proof[..proof.len() - 1];
+                    assert!(!verify_inclusion_proof(
+                        &leaves[m], m as u64, n as u64, truncated, &root
+                    ));
+                }
+                if n > 1 {
+                    assert!(!verify_inclusion_proof(
+                        &leaves[(m + 1) % n],
+                        m as u64,
+                        n as u64,
+                        &proof,
+                        &root
+                    ));
+                }
+            }
+            for m in 1..n {
+                let first = mth(&leaves[..m]);
+                let proof = subproof(m, &leaves[..n], true);
+                assert!(verify_consistency_proof(
+                    m as u64, n as u64, &first, &root, &proof
+                ));
+                assert!(!verify_consistency_proof(
+                    m as u64, n as u64, &root, &first, &proof
+                ));
+            }
+        }
+        let root = mth(&leaves);
+        assert!(!verify_inclusion_proof(&leaves[0], 13, 13, &[], &root));
+        assert!(verify_consistency_proof(13, 13, &root, &root, &[]));
+        assert!(!verify_consistency_proof(5, 13, &root, &root, &[]));
+    }
+
+    #[cfg(feature = "verify")]
+    #[test]
+    fn test_ct_sth_signature() {
+        use crate::pem::parse_x509_pem;
+
+        static PUBKEY_PEM: &[u8] = include_bytes!("../assets/public-key.pem");
+        let (_, pem) = parse_x509_pem(PUBKEY_PEM).unwrap();
+        let log_key = pem.parse_subject_public_key_info().unwrap();
+        let signature = HEXLOWER
+            .decode(b"30460221009138309bbc60893d5e0852e8e14fb36a3e14e3033fc2047441d4de696065c845022100b9ee85219942ebd361a648d27f21d214fa13a0dffed23ecf5e3bc1bae473f80c")
+            .unwrap();
+        let mut sth = SignedTreeHead {
+            tree_size: 7,
+            timestamp: 1_700_000_000_000,
+            sha256_root_hash: Sha256::digest(b"root").into(),
+            signature: DigitallySigned {
+                hash_alg_id: 4,
+                sign_alg_id: 3,
+                data: &signature,
+            },
+        };
+        assert!(sth.verify_signature(&log_key).is_ok());
+        sth.tree_size = 8;
+        assert!(sth.verify_signature(&log_key).is_err());
+    }
+}
@@ -0,0 +1,410 @@
+//! Text representation of certificates, similar to `openssl x509 -text -noout`
+//!
+//! The output follows the layout of OpenSSL (indentation, field names, hex dumps of the serial
+//! number, public key and signature), so both can be compared using `diff` when migrating tools.
+//! Some extensions are formatted using their one-line description (see the `Display`
+//! implementation of [`ParsedExtension`]), which may differ from OpenSSL for complex values.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::display::CertificateText;
+//! use x509_parser::prelude::*;
+//!
+//! fn print_cert(der: &[u8]) {
+//!     let (_, x509) = parse_x509_certificate(der).expect("invalid certificate");
+//!     print!("{}", CertificateText(&x509));
+//! }
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::extensions::*;
+use crate::objects::{oid2description, oid2sn, oid_registry};
+use crate::ocsp::OID_PKIX_OCSP_NOCHECK;
+use crate::public_key::PublicKey;
+use crate::time::ASN1Time;
+use crate::x509::X509Name;
+use der_parser::oid::Oid;
+use oid_registry::*;
+use std::collections::HashMap;
+use std::fmt;
+use time::macros::format_description;
+
+/// Display wrapper formatting a certificate like `openssl x509 -text -noout`
+#[derive(Debug)]
+pub struct CertificateText<'a, 'b>(pub &'b X509Certificate<'a>);
+
+impl<'a, 'b> fmt::Display for CertificateText<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x509 = self.0;
+        writeln!(f, "Certificate:")?;
+        writeln!(f, "    Data:")?;
+        let version = x509.version().0;
+        writeln!(f, "        Version: {} (0x{:x})", version + 1, version)?;
+        write_serial(f, x509.raw_serial())?;
+        writeln!(
+            f,
+            "        Signature Algorithm: {}",
+            oid_name(&x509.tbs_certificate.signature.algorithm)
+        )?;
+        writeln!(f, "        Issuer: {}", name_text(x509.issuer()))?;
+        writeln!(f, "        Validity")?;
+        writeln!(
+            f,
+            "            Not Before: {}",
+            openssl_time(&x509.validity().not_before)
+        )?;
+        writeln!(
+            f,
+            "            Not After : {}",
+            openssl_time(&x509.validity().not_after)
+        )?;
+        writeln!(f, "        Subject: {}", name_text(x509.subject()))?;
+        write_public_key(f, x509)?;
+        if !x509.extensions().is_empty() {
+            writeln!(f, "        X509v3 extensions:")?;
+            for ext in x509.extensions() {
+                write_extension(f, ext)?;
+            }
+        }
+        writeln!(
+            f,
+            "    Signature Algorithm: {}",
+            oid_name(&x509.signature_algorithm.algorithm)
+        )?;
+        writeln!(f, "    Signature Value:")?;
+        write_hex_dump(f, &x509.signature_value.data, 18, 8)
+    }
+}
+
+/// Format a certificate like `openssl x509 -text -noout`
+pub fn certificate_text(x509: &X509Certificate) -> String {
+    CertificateText(x509).to_string()
+}
+
+fn oid_name(oid: &Oid) -> String {
+    oid2sn(oid, oid_registry())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+/// Format a name, using the OpenSSL abbreviation for email addresses
+fn name_text(name: &X509Name) -> String {
+    let mut abbrevs = HashMap::new();
+    abbrevs.insert(OID_PKCS9_EMAIL_ADDRESS, "emailAddress");
+    name.to_string_with_abbrevs(&abbrevs, oid_registry())
+        .unwrap_or_else(|_| name.to_string())
+}
+
+/// Format a time as `Dec 13 14:29:23 2002 GMT`
+fn openssl_time(t: &ASN1Time) -> String {
+    let format = format_description!(
+        "[month repr:short] [day padding:space] [hour]:[minute]:[second] [year] GMT"
+    );
+    t.to_datetime()
+        .to_offset(time::UtcOffset::UTC)
+        .format(format)
+        .unwrap_or_else(|e| format!("Invalid date: {}", e))
+}
+
+/// Write bytes as colon-separated lowercase hex, `per_line` bytes per line
+fn write_hex_dump(
+    f: &mut fmt::Formatter<'_>,
+    bytes: &[u8],
+    per_line: usize,
+    indent: usize,
+) -> fmt::Result {
+    let chunks: Vec<_> = bytes.chunks(per_line).collect();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let line = chunk
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(":");
+        let sep = if idx + 1 < chunks.len() { ":" } else { "" };
+        writeln!(f, "{:indent$}{}{}", "", line, sep, indent = indent)?;
+    }
+    Ok(())
+}
+
+fn write_serial(f: &mut fmt::Formatter<'_>, raw_serial: &[u8]) -> fmt::Result {
+    let start = raw_serial
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(raw_serial.len());
+    let bytes = &raw_serial[start..];
+    // OpenSSL prints positive serials fitting in an i64 as integers
+    if bytes.len() <= 8 && raw_serial.first().copied().unwrap_or(0) & 0x80 == 0 {
+        let n = bytes.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        if n <= i64::MAX as u64 {
+            return writeln!(f, "        Serial Number: {} (0x{:x})", n, n);
+        }
+    }
+    writeln!(f, "        Serial Number:")?;
+    let hex = bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":");
+    writeln!(f, "            {}", hex)
+}
+
+fn write_public_key(f: &mut fmt::Formatter<'_>, x509: &X509Certificate) -> fmt::Result {
+    let spki = x509.public_key();
+    writeln!(f, "        Subject Public Key Info:")?;
+    writeln!(
+        f,
+        "            Public Key Algorithm: {}",
+        oid_name(&spki.algorithm.algorithm)
+    )?;
+    match spki.parsed() {
+        Ok(PublicKey::RSA(rsa)) => {
+            writeln!(f, "                Public-Key: ({} bit)", rsa.key_size())?;
+            writeln!(f, "                Modulus:")?;
+            write_hex_dump(f, rsa.modulus, 15, 20)?;
+            match rsa.try_exponent() {
+                Ok(e) => writeln!(f, "                Exponent: {} (0x{:x})", e, e),
+                Err(_) => {
+                    writeln!(f, "                Exponent:")?;
+                    write_hex_dump(f, rsa.exponent, 15, 20)
+                }
+            }
+        }
+        Ok(PublicKey::EC(ec)) => {
+            writeln!(f, "                Public-Key: ({} bit)", ec.key_size())?;
+            writeln!(f, "                pub:")?;
+            write_hex_dump(f, ec.data(), 15, 20)?;
+            let curve = spki
+                .algorithm
+                .parameters
+                .as_ref()
+                .and_then(|p| p.as_oid().ok());
+            if let Some(curve) = curve {
+                writeln!(f, "                ASN1 OID: {}", oid_name(&curve))?;
+                let nist = if curve == OID_EC_P256 {
+                    Some("P-256")
+                } else if curve == OID_NIST_EC_P384 {
+                    Some("P-384")
+                } else if curve == OID_NIST_EC_P521 {
+                    Some("P-521")
+                } else {
+                    None
+                };
+                if let Some(nist) = nist {
+                    writeln!(f, "                NIST CURVE: {}", nist)?;
+                }
+            }
+            Ok(())
+        }
+        _ => {
+            writeln!(f, "                pub:")?;
+            write_hex_dump(f, &spki.subject_public_key.data, 15, 20)
+        }
+    }
+}
+
+/// Name of the extension, as printed by OpenSSL
+fn extension_name(oid: &Oid) -> String {
+    let name = if *oid == OID_PKIX_AUTHORITY_INFO_ACCESS {
+        "Authority Information Access"
+    } else if *oid == OID_PKIX_SUBJECT_INFO_ACCESS {
+        "Subject Information Access"
+    } else if *oid == OID_CT_LIST_SCT {
+        "CT Precertificate SCTs"
+    } else if *oid == OID_CT_PRECERT_POISON {
+        "CT Precertificate Poison"
+    } else if *oid == OID_PKIX_OCSP_NOCHECK {
+        "OCSP No Check"
+    } else if *oid == OID_CAN_SIGN_HTTP_EXCHANGES {
+        "CanSignHttpExchanges"
+    } else if *oid == OID_ISISMTT_AT_ADMISSION {
+        "Professional Information or basis for Admission"
+    } else if *oid == OID_X509_EXT_CERT_TYPE {
+        "Netscape Cert Type"
+    } else if *oid == OID_X509_EXT_CERT_COMMENT {
+        "Netscape Comment"
+    } else if *oid == OID_X509_EXT_BASE_URL {
+        "Netscape Base Url"
+    } else if *oid == OID_PKCS9_SMIME_CAPABILITIES {
+        "S/MIME Capabilities"
+    } else {
+        match oid2description(oid, oid_registry()) {
+            Ok(desc) if desc.starts_with("X509v3") => desc,
+            _ => return oid.to_id_string(),
+        }
+    };
+    name.to_string()
+}
+
+// OpenSSL names of the key usage flags, in the same order as the bits
+const KEY_USAGE_NAMES: &[&str] = &[
+    "Digital Signature",
+    "Non Repudiation",
+    "Key Encipherment",
+    "Data Encipherment",
+    "Key Agreement",
+    "Certificate Sign",
+    "CRL Sign",
+    "Encipher Only",
+    "Decipher Only",
+];
+
+fn upper_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Write bytes as colon-separated uppercase hex, 16 bytes per line, aligned on the value column
+fn write_sct_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    let chunks: Vec<_> = bytes.chunks(16).collect();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        if idx > 0 {
+            write!(f, "{:32}", "")?;
+        }
+        let sep = if idx + 1 < chunks.len() { ":" } else { "" };
+        writeln!(f, "{}{}", upper_hex(chunk), sep)?;
+    }
+    Ok(())
+}
+
+fn write_sct(f: &mut fmt::Formatter<'_>, sct: &SignedCertificateTimestamp) -> fmt::Result {
+    writeln!(f, "                Signed Certificate Timestamp:")?;
+    writeln!(
+        f,
+        "                    Version   : v{} (0x{:x})",
+        u32::from(sct.version.0) + 1,
+        sct.version.0
+    )?;
+    write!(f, "                    Log ID    : ")?;
+    write_sct_hex(f, sct.id.key_id)?;
+    // SCT timestamps are in milliseconds since the epoch
+    let format = format_description!(
+        "[month repr:short] [day padding:space] [hour]:[minute]:[second].[subsecond digits:3] [year] GMT"
+    );
+    let timestamp =
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(sct.timestamp) * 1_000_000)
+            .ok()
+            .and_then(|dt| dt.format(format).ok())
+            .unwrap_or_else(|| format!("{}", sct.timestamp));
+    writeln!(f, "                    Timestamp : {}", timestamp)?;
+    if sct.extensions.0.is_empty() {
+        writeln!(f, "                    Extensions: none")?;
+    } else {
+        write!(f, "                    Extensions: ")?;
+        write_sct_hex(f, sct.extensions.0)?;
+    }
+    let algorithm = match (sct.signature.hash_alg_id, sct.signature.sign_alg_id) {
+        (4, 1) => "sha256WithRSAEncryption".to_string(),
+        (4, 3) => "ecdsa-with-SHA256".to_string(),
+        (hash, sign) => format!("{}/{}", hash, sign),
+    };
+    writeln!(f, "                    Signature : {}", algorithm)?;
+    write!(f, "{:32}", "")?;
+    write_sct_hex(f, sct.signature.data)
+}
+
+fn write_extension(f: &mut fmt::Formatter<'_>, ext: &X509Extension) -> fmt::Result {
+    let critical = if ext.critical { "critical" } else { "" };
+    writeln!(f, "            {}: {}", extension_name(&ext.oid), critical)?;
+    let indent = "                ";
+    match ext.parsed_extension() {
+        ParsedExtension::SubjectKeyIdentifier(id) => writeln!(f, "{}{}", indent, upper_hex(id.0)),
+        ParsedExtension::AuthorityKeyIdentifier(aki) => {
+            if let Some(id) = &aki.key_identifier {
+                writeln!(f, "{}{}", indent, upper_hex(id.0))?;
+            }
+            if let Some(issuer) = &aki.authority_cert_issuer {
+                for name in issuer {
+                    writeln!(f, "{}{}", indent, general_name_to_string(name))?;
+                }
+            }
+            if let Some(serial) = aki.authority_cert_serial {
+                writeln!(f, "{}serial:{}", indent, upper_hex(serial))?;
+            }
+            Ok(())
+        }
+        ParsedExtension::KeyUsage(ku) => {
+            let flags: Vec<_> = KEY_USAGE_NAMES
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| (ku.flags >> idx) & 1 == 1)
+                .map(|(_, name)| *name)
+                .collect();
+            writeln!(f, "{}{}", indent, flags.join(", "))
+        }
+        ParsedExtension::CRLDistributionPoints(points) => {
+            for point in points.iter() {
+                if let Some(DistributionPointName::FullName(names)) = &point.distribution_point {
+                    writeln!(f, "{}Full Name:", indent)?;
+                    for name in names {
+                        writeln!(f, "{}  {}", indent, general_name_to_string(name))?;
+                    }
+                } else {
+                    writeln!(f, "{}{}", indent, ext.parsed_extension())?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+        ParsedExtension::AuthorityInfoAccess(aia) => {
+            for desc in &aia.accessdescs {
+                let method = if desc.access_method == OID_PKIX_ACCESS_DESCRIPTOR_OCSP {
+                    "OCSP".to_string()
+                } else if desc.access_method == OID_PKIX_ACCESS_DESCRIPTOR_CA_ISSUERS {
+                    "CA Issuers".to_string()
+                } else {
+                    oid_name(&desc.access_method)
+                };
+                let location = general_name_to_string(&desc.access_location);
+                writeln!(f, "{}{} - {}", indent, method, location)?;
+            }
+            Ok(())
+        }
+        ParsedExtension::CertificatePolicies(policies) => {
+            for policy in policies.iter() {
+                writeln!(f, "{}Policy: {}", indent, oid_name(&policy.policy_id))?;
+                for qualifier in policy.policy_qualifiers.iter().flatten() {
+                    match qualifier.parsed_qualifier() {
+                        Ok(PolicyQualifier::CPSUri(uri)) => {
+                            writeln!(f, "{}  CPS: {}", indent, uri)?;
+                        }
+                        Ok(PolicyQualifier::UserNotice(notice)) => {
+                            writeln!(f, "{}  User Notice:", indent)?;
+                            if let Some(notice_ref) = &notice.notice_ref {
+                                writeln!(
+                                    f,
+                                    "{}    Organization: {}",
+                                    indent, notice_ref.organization
+                                )?;
+                                let numbers: Vec<_> = notice_ref
+                                    .notice_numbers
+                                    .iter()
+                                    .map(|n| n.to_string())
+                                    .collect();
+                                writeln!(f, "{}    Numbers: {}", indent, numbers.join(", "))?;
+                            }
+                            if let Some(text) = &notice.explicit_text {
+                                writeln!(f, "{}    Explicit Text: {}", indent, text)?;
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            Ok(())
+        }
+        ParsedExtension::SCT(scts) => {
+            for sct in scts {
+                write_sct(f, sct)?;
+            }
+            Ok(())
+        }
+        ParsedExtension::UnsupportedExtension { .. }
+        | ParsedExtension::ParseError { .. }
+        | ParsedExtension::Unparsed => writeln!(f, "{}{}", indent, upper_hex(ext.value)),
+        parsed => writeln!(f, "{}{}", indent, parsed),
+    }
+}
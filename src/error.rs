@@ -1,8 +1,11 @@
 //! X.509 errors
 
+use crate::objects::{oid2sn, oid_registry};
 use der_parser::error::BerError;
+use der_parser::oid::Oid;
 use nom::error::{ErrorKind, ParseError};
 use nom::IResult;
+use std::fmt;
 
 /// An error that can occur while converting an OID to a Nid.
 #[derive(Debug, PartialEq, Eq)]
@@ -52,9 +55,31 @@ pub enum X509Error {
     #[error("invalid User certificate")]
     InvalidUserCertificate,
 
+    // error types from OCSP
+    #[error("invalid OCSP response")]
+    InvalidOCSPResponse,
+
+    #[error("invalid PKCS#7 structure")]
+    InvalidPKCS7,
+
+    // error types from attribute certificates
+    #[error("invalid attribute certificate")]
+    InvalidAttributeCertificate,
+
+    // error types from PKCS#12
+    #[error("invalid PKCS#12 structure")]
+    InvalidPKCS12,
+    #[error("PKCS#12 decryption error")]
+    PKCS12DecryptionError,
+    #[error("PKCS#12 unsupported encryption algorithm")]
+    PKCS12UnsupportedAlgorithm,
+
     /// Top-level certificate structure is invalid
     #[error("invalid certificate")]
     InvalidCertificate,
+    /// The object was built from its parts, and has no DER encoding
+    #[error("missing DER encoding")]
+    MissingEncoding,
 
     #[error("signature verification error")]
     SignatureVerificationError,
@@ -64,12 +89,316 @@ pub enum X509Error {
     #[error("invalid number")]
     InvalidNumber,
 
+    #[error("invalid hex encoding")]
+    InvalidHex,
+
+    #[error("invalid certificate store snapshot")]
+    InvalidStoreSnapshot,
+
+    // error types from certificate pinning
+    #[error("invalid pin")]
+    InvalidPin,
+    #[error("no certificate matches the pin set")]
+    PinMismatch,
+    #[error("pin set has no backup pin")]
+    MissingBackupPin,
+
+    // error types from hostname verification
+    #[error("invalid hostname")]
+    InvalidHostname,
+    #[error("hostname does not match the certificate")]
+    HostnameMismatch,
+
     #[error("BER error: {0}")]
     Der(#[from] BerError),
     #[error("nom error: {0:?}")]
     NomError(ErrorKind),
 }
 
+impl X509Error {
+    /// Return a detailed description of the error, for end-user messages
+    ///
+    /// Unlike the short `Display` form, the explanation states which element was expected and
+    /// what the usual causes are.
+    pub fn explanation(&self) -> String {
+        let s = match self {
+            X509Error::Generic => "An unspecified error occurred while parsing the object.",
+            X509Error::InvalidVersion => {
+                "The version field is invalid or not supported: expected v1, v2 or v3 \
+                 (encoded as 0, 1 or 2) for certificates and CRLs, v1 for CSRs, v3 for PKCS#12."
+            }
+            X509Error::InvalidSerial => {
+                "The serial number is not a valid INTEGER (it must be a positive number, of at \
+                 most 20 bytes in strict mode)."
+            }
+            X509Error::InvalidAlgorithmIdentifier => {
+                "An AlgorithmIdentifier is malformed: expected a SEQUENCE with an OID and \
+                 optional parameters."
+            }
+            X509Error::InvalidX509Name => {
+                "A distinguished name (issuer or subject) is malformed: expected a SEQUENCE of \
+                 SET of (attribute type, value) pairs."
+            }
+            X509Error::InvalidDate => {
+                "A date is malformed: expected a UTCTime (YYMMDDHHMMSSZ) or a GeneralizedTime \
+                 (YYYYMMDDHHMMSSZ) in UTC."
+            }
+            X509Error::InvalidSPKI => {
+                "The SubjectPublicKeyInfo is malformed: expected an algorithm identifier \
+                 followed by the public key as a BIT STRING."
+            }
+            X509Error::InvalidSubjectUID => {
+                "The subjectUniqueID field ([2] IMPLICIT BIT STRING) is malformed."
+            }
+            X509Error::InvalidIssuerUID => {
+                "The issuerUniqueID field ([1] IMPLICIT BIT STRING) is malformed."
+            }
+            X509Error::InvalidExtensions => {
+                "An extension is malformed: either the extension list is not a SEQUENCE of \
+                 (OID, critical, OCTET STRING), or the content of a known extension does not \
+                 match its definition."
+            }
+            X509Error::InvalidAttributes => {
+                "An attribute (in a name or a certification request) is malformed, or its value \
+                 has an unexpected type."
+            }
+            X509Error::DuplicateExtensions => {
+                "An extension is present more than once, which is forbidden by RFC 5280 \
+                 (section 4.2)."
+            }
+            X509Error::DuplicateAttributes => {
+                "An attribute is present more than once in a certification request."
+            }
+            X509Error::InvalidSignatureValue => {
+                "The signature value is malformed: expected a BIT STRING, or a signature \
+                 structure matching the signature algorithm."
+            }
+            X509Error::InvalidTbsCertificate => {
+                "The TBSCertificate (the signed part of the certificate) is malformed or \
+                 truncated."
+            }
+            X509Error::InvalidUserCertificate => {
+                "A revoked certificate entry of the CRL is malformed: expected a SEQUENCE with \
+                 the serial number, the revocation date and optional extensions."
+            }
+            X509Error::InvalidOCSPResponse => {
+                "The OCSP response is malformed, or does not follow the structure of RFC 6960."
+            }
+            X509Error::InvalidPKCS7 => {
+                "The PKCS#7 / CMS structure is malformed, or is not a SignedData."
+            }
+            X509Error::InvalidAttributeCertificate => {
+                "The attribute certificate is malformed, or does not follow the structure of \
+                 RFC 5755."
+            }
+            X509Error::InvalidPKCS12 => {
+                "The PKCS#12 structure is malformed, or uses an unsupported mode (only the \
+                 password integrity mode is supported)."
+            }
+            X509Error::PKCS12DecryptionError => {
+                "The PKCS#12 contents could not be decrypted: the password is probably wrong, \
+                 or the file is corrupted."
+            }
+            X509Error::PKCS12UnsupportedAlgorithm => {
+                "The PKCS#12 contents are encrypted with an unsupported algorithm: only PBES2 \
+                 with PBKDF2 and AES-CBC is supported (legacy RC2, RC4 and 3DES are not)."
+            }
+            X509Error::InvalidCertificate => {
+                "The certificate is malformed: expected a SEQUENCE with the TBSCertificate, \
+                 the signature algorithm and the signature value. The input may not be DER \
+                 (for ex. PEM data, which must be decoded first)."
+            }
+            X509Error::MissingEncoding => {
+                "The object was built from its parts (for ex. a separately parsed TBSCertificate) \
+                 and has no DER encoding, which is required to store or compare it. Parse it \
+                 from its DER encoding instead."
+            }
+            X509Error::SignatureVerificationError => {
+                "The signature does not match: the certificate was not signed by the provided \
+                 issuer key, or it was modified after signing."
+            }
+            X509Error::SignatureUnsupportedAlgorithm => {
+                "The signature algorithm or the public key type is not supported for \
+                 verification."
+            }
+            X509Error::InvalidNumber => "A number is invalid or too large for the expected type.",
+            X509Error::InvalidHex => {
+                "The input is not valid hex: expected pairs of hex digits, optionally separated \
+                 by colons or whitespace."
+            }
+            X509Error::InvalidStoreSnapshot => {
+                "The certificate store snapshot is truncated or corrupted, was written by an \
+                 unsupported version, or a certificate does not match its fingerprint."
+            }
+            X509Error::InvalidPin => {
+                "The pin is invalid: expected the base64 encoding of the SHA-256 hash of a \
+                 DER-encoded SubjectPublicKeyInfo (32 bytes)."
+            }
+            X509Error::PinMismatch => {
+                "None of the public keys of the certificate chain matches the pin set: the \
+                 server may use an unexpected key, or be impersonated."
+            }
+            X509Error::MissingBackupPin => {
+                "All pins of the pin set match keys of the certificate chain: a backup pin, for \
+                 a key which is not currently used, is required to recover from a key loss."
+            }
+            X509Error::InvalidHostname => {
+                "The hostname is invalid: expected an IP address, or a DNS name with non-empty \
+                 labels. Internationalized names must be converted to A-labels (xn--)."
+            }
+            X509Error::HostnameMismatch => {
+                "No name of the certificate matches the hostname: the certificate may have been \
+                 issued for another server."
+            }
+            X509Error::Der(e) => {
+                return format!(
+                    "The DER encoding is invalid ({}). The input may be truncated, or use a \
+                     non-DER encoding.",
+                    e
+                )
+            }
+            X509Error::NomError(e) => {
+                return format!(
+                    "The input could not be parsed (parser error {:?}). The input may be \
+                     truncated or contain unexpected data.",
+                    e
+                )
+            }
+        };
+        s.to_string()
+    }
+}
+
+/// An [`X509Error`], with the context in which it occurred
+///
+/// The context locates the error (the index of the certificate in a chain, and the extension
+/// involved) and, if relevant, states the expected and found values. [`explanation`](Self::explanation)
+/// combines it with [`X509Error::explanation`], for actionable end-user messages.
+///
+/// ```rust
+/// use x509_parser::error::{X509Error, X509ErrorContext};
+/// use x509_parser::oid_registry::OID_X509_EXT_BASIC_CONSTRAINTS;
+///
+/// let e = X509ErrorContext::new(X509Error::InvalidExtensions)
+///     .with_chain_index(1)
+///     .with_extension(OID_X509_EXT_BASIC_CONSTRAINTS)
+///     .with_expected_found("CA:true", "CA:false");
+/// assert!(e.explanation().starts_with("Certificate #1 of the chain, extension basicConstraints"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct X509ErrorContext {
+    /// The error
+    pub error: X509Error,
+    /// The index of the certificate in the chain (0 is the leaf)
+    pub chain_index: Option<usize>,
+    /// The OID of the extension involved
+    pub extension: Option<Oid<'static>>,
+    /// The expected value
+    pub expected: Option<String>,
+    /// The value that was found
+    pub found: Option<String>,
+}
+
+impl X509ErrorContext {
+    /// Create a context for `error`, without any information
+    pub const fn new(error: X509Error) -> Self {
+        X509ErrorContext {
+            error,
+            chain_index: None,
+            extension: None,
+            expected: None,
+            found: None,
+        }
+    }
+
+    /// Set the index of the certificate in the chain
+    pub fn with_chain_index(self, chain_index: usize) -> Self {
+        X509ErrorContext {
+            chain_index: Some(chain_index),
+            ..self
+        }
+    }
+
+    /// Set the OID of the extension involved
+    pub fn with_extension(self, oid: Oid<'static>) -> Self {
+        X509ErrorContext {
+            extension: Some(oid),
+            ..self
+        }
+    }
+
+    /// Set the expected and found values
+    pub fn with_expected_found<E: ToString, F: ToString>(self, expected: E, found: F) -> Self {
+        X509ErrorContext {
+            expected: Some(expected.to_string()),
+            found: Some(found.to_string()),
+            ..self
+        }
+    }
+
+    /// Return a detailed description of the error and of its context, for end-user messages
+    pub fn explanation(&self) -> String {
+        let mut s = String::new();
+        if let Some(idx) = self.chain_index {
+            s += &format!("Certificate #{} of the chain", idx);
+        }
+        if let Some(oid) = &self.extension {
+            s += if s.is_empty() {
+                "Extension "
+            } else {
+                ", extension "
+            };
+            match oid2sn(oid, oid_registry()) {
+                Ok(sn) => s += &format!("{} ({})", sn, oid.to_id_string()),
+                Err(_) => s += &oid.to_id_string(),
+            }
+        }
+        if !s.is_empty() {
+            s += ": ";
+        }
+        s += &self.error.explanation();
+        match (&self.expected, &self.found) {
+            (Some(expected), Some(found)) => {
+                s += &format!(" Expected: {}, found: {}.", expected, found)
+            }
+            (Some(expected), None) => s += &format!(" Expected: {}.", expected),
+            (None, Some(found)) => s += &format!(" Found: {}.", found),
+            (None, None) => (),
+        }
+        s
+    }
+}
+
+impl fmt::Display for X509ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(idx) = self.chain_index {
+            write!(f, "certificate #{}: ", idx)?;
+        }
+        if let Some(oid) = &self.extension {
+            write!(f, "extension {}: ", oid.to_id_string())?;
+        }
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for X509ErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<X509Error> for X509ErrorContext {
+    fn from(error: X509Error) -> Self {
+        X509ErrorContext::new(error)
+    }
+}
+
+impl From<X509ErrorContext> for X509Error {
+    fn from(e: X509ErrorContext) -> Self {
+        e.error
+    }
+}
+
 impl From<nom::Err<BerError>> for X509Error {
     fn from(e: nom::Err<BerError>) -> Self {
         Self::Der(BerError::from(e))
@@ -117,7 +446,116 @@ pub enum PEMError {
     InvalidHeader,
     #[error("missing header")]
     MissingHeader,
+    #[error("unexpected label: {0}")]
+    UnexpectedLabel(String),
+
+    #[error("X.509 error: {0}")]
+    X509(#[from] X509Error),
 
     #[error("IO error: {0}")]
     IOError(#[from] std::io::Error),
 }
+
+#[cfg(feature = "io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io")))]
+impl X509Error {
+    /// Return the `std::io::ErrorKind` corresponding to this error
+    ///
+    /// Truncated input is mapped to `UnexpectedEof`, unsupported algorithms to `Unsupported`,
+    /// and all other parsing and validation errors to `InvalidData`.
+    pub fn io_error_kind(&self) -> std::io::ErrorKind {
+        use std::io::ErrorKind;
+        match self {
+            X509Error::Der(BerError::Incomplete(_)) => ErrorKind::UnexpectedEof,
+            X509Error::SignatureUnsupportedAlgorithm => ErrorKind::Unsupported,
+            X509Error::Generic => ErrorKind::Other,
+            _ => ErrorKind::InvalidData,
+        }
+    }
+}
+
+#[cfg(feature = "io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io")))]
+impl From<X509Error> for std::io::Error {
+    fn from(e: X509Error) -> std::io::Error {
+        std::io::Error::new(e.io_error_kind(), e)
+    }
+}
+
+#[cfg(feature = "io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io")))]
+impl PEMError {
+    /// Return the `std::io::ErrorKind` corresponding to this error
+    pub fn io_error_kind(&self) -> std::io::ErrorKind {
+        use std::io::ErrorKind;
+        match self {
+            PEMError::IOError(e) => e.kind(),
+            PEMError::IncompletePEM => ErrorKind::UnexpectedEof,
+            PEMError::X509(e) => e.io_error_kind(),
+            _ => ErrorKind::InvalidData,
+        }
+    }
+}
+
+#[cfg(feature = "io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io")))]
+impl From<PEMError> for std::io::Error {
+    fn from(e: PEMError) -> std::io::Error {
+        match e {
+            PEMError::IOError(e) => e,
+            e => std::io::Error::new(e.io_error_kind(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explanation() {
+        let e = X509Error::DuplicateExtensions;
+        assert!(e.explanation().contains("RFC 5280"));
+        let e = X509Error::Der(BerError::InvalidTag);
+        assert!(e.explanation().starts_with("The DER encoding is invalid"));
+    }
+
+    #[test]
+    fn test_explanation_context() {
+        let e = X509ErrorContext::new(X509Error::InvalidExtensions);
+        assert_eq!(e.explanation(), X509Error::InvalidExtensions.explanation());
+        let e = e
+            .with_chain_index(2)
+            .with_extension(oid_registry::OID_X509_EXT_KEY_USAGE)
+            .with_expected_found("keyCertSign", "digitalSignature");
+        let s = e.explanation();
+        assert!(s.starts_with("Certificate #2 of the chain, extension keyUsage (2.5.29.15): "));
+        assert!(s.ends_with(" Expected: keyCertSign, found: digitalSignature."));
+        assert_eq!(
+            e.to_string(),
+            format!(
+                "certificate #2: extension 2.5.29.15: {}",
+                X509Error::InvalidExtensions
+            )
+        );
+        let e = X509ErrorContext::new(X509Error::InvalidSerial)
+            .with_extension(Oid::from(&[1, 2, 3]).unwrap());
+        assert!(e.explanation().starts_with("Extension 1.2.3: "));
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn test_io_error_conversion() {
+        use std::io::ErrorKind;
+        let e = std::io::Error::from(X509Error::InvalidSerial);
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "invalid serial");
+        let e = std::io::Error::from(X509Error::Der(BerError::Incomplete(nom::Needed::new(2))));
+        assert_eq!(e.kind(), ErrorKind::UnexpectedEof);
+        let e = std::io::Error::from(PEMError::MissingHeader);
+        assert_eq!(e.kind(), ErrorKind::InvalidData);
+        let inner = std::io::Error::from(ErrorKind::PermissionDenied);
+        let e = std::io::Error::from(PEMError::IOError(inner));
+        assert_eq!(e.kind(), ErrorKind::PermissionDenied);
+    }
+}
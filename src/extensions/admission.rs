@@ -0,0 +1,249 @@
+//! Admission extension (Common PKI / ISIS-MTT), for professional certificates
+
+use super::{general_name_to_string, parse_generalname, GeneralName};
+use crate::error::{X509Error, X509Result};
+use crate::x509::decode_directory_string;
+use asn1_rs::{oid, Any, Class, Error, FromDer, Header, Oid, OptTaggedParser, Sequence, Tag};
+use nom::combinator::{all_consuming, complete, opt};
+use nom::multi::many0;
+use nom::{Err, IResult};
+use std::fmt;
+
+/// OID of the Admission extension (`id-isismtt-at-admission`)
+pub const OID_ISISMTT_AT_ADMISSION: Oid<'static> = oid!(1.3.36 .8 .3 .3);
+
+/// Admission extension, describing the professions of the subject and the authorities which
+/// admitted them (for ex. in German healthcare or notary certificates)
+///
+/// <pre>
+/// AdmissionSyntax ::= SEQUENCE {
+///     admissionAuthority      GeneralName OPTIONAL,
+///     contentsOfAdmissions    SEQUENCE OF Admissions }
+///
+/// Admissions ::= SEQUENCE {
+///     admissionAuthority      [0] EXPLICIT GeneralName OPTIONAL,
+///     namingAuthority         [1] EXPLICIT NamingAuthority OPTIONAL,
+///     professionInfos         SEQUENCE OF ProfessionInfo }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdmissionSyntax<'a> {
+    /// Default admission authority, for all admissions
+    pub admission_authority: Option<GeneralName<'a>>,
+    pub contents_of_admissions: Vec<Admissions<'a>>,
+}
+
+impl<'a> AdmissionSyntax<'a> {
+    /// Return an iterator over the profession information of all admissions
+    pub fn profession_infos(&self) -> impl Iterator<Item = &ProfessionInfo<'a>> {
+        self.contents_of_admissions
+            .iter()
+            .flat_map(|admissions| admissions.profession_infos.iter())
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for AdmissionSyntax<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parse_admission_syntax(i).map_err(Err::convert)
+    }
+}
+
+/// Human-readable one-line representation of the extension: the professions, with their
+/// registration numbers
+impl<'a> fmt::Display for AdmissionSyntax<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(authority) = &self.admission_authority {
+            write!(f, "Authority: {}; ", general_name_to_string(authority))?;
+        }
+        let professions: Vec<_> = self
+            .profession_infos()
+            .map(|info| {
+                let mut s = info.profession_items.join(", ");
+                if let Some(number) = info.registration_number {
+                    s.push_str(&format!(" ({})", number));
+                }
+                s
+            })
+            .collect();
+        write!(f, "Professions: {}", professions.join("; "))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Admissions<'a> {
+    pub admission_authority: Option<GeneralName<'a>>,
+    pub naming_authority: Option<NamingAuthority<'a>>,
+    pub profession_infos: Vec<ProfessionInfo<'a>>,
+}
+
+/// The authority defining the profession names and OIDs
+///
+/// <pre>
+/// NamingAuthority ::= SEQUENCE {
+///     namingAuthorityId       OBJECT IDENTIFIER OPTIONAL,
+///     namingAuthorityUrl      IA5String OPTIONAL,
+///     namingAuthorityText     DirectoryString(SIZE(1..128)) OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamingAuthority<'a> {
+    pub id: Option<Oid<'a>>,
+    pub url: Option<&'a str>,
+    pub text: Option<String>,
+}
+
+/// A profession of the subject
+///
+/// <pre>
+/// ProfessionInfo ::= SEQUENCE {
+///     namingAuthority         [0] EXPLICIT NamingAuthority OPTIONAL,
+///     professionItems         SEQUENCE OF DirectoryString (SIZE(1..128)),
+///     professionOIDs          SEQUENCE OF OBJECT IDENTIFIER OPTIONAL,
+///     registrationNumber      PrintableString(SIZE(1..128)) OPTIONAL,
+///     addProfessionInfo       OCTET STRING OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfessionInfo<'a> {
+    pub naming_authority: Option<NamingAuthority<'a>>,
+    /// Names of the profession
+    pub profession_items: Vec<String>,
+    /// OIDs of the profession (empty if absent)
+    pub profession_oids: Vec<Oid<'a>>,
+    pub registration_number: Option<&'a str>,
+    pub add_profession_info: Option<&'a [u8]>,
+}
+
+pub(crate) fn parse_admission_syntax(i: &[u8]) -> IResult<&[u8], AdmissionSyntax, Error> {
+    Sequence::from_der_and_then(i, |i| {
+        // GeneralName is a CHOICE of context-specific tags, unlike the following SEQUENCE
+        let (i, admission_authority) = match i.first() {
+            Some(b) if b & 0xc0 == 0x80 => {
+                let (i, name) = parse_generalname(i)?;
+                (i, Some(name))
+            }
+            _ => (i, None),
+        };
+        let (i, contents_of_admissions) = Sequence::from_der_and_then(i, |i| {
+            all_consuming(many0(complete(parse_admissions)))(i)
+        })?;
+        let syntax = AdmissionSyntax {
+            admission_authority,
+            contents_of_admissions,
+        };
+        Ok((i, syntax))
+    })
+}
+
+fn parse_admissions(i: &[u8]) -> IResult<&[u8], Admissions, Error> {
+    Sequence::from_der_and_then(i, |i| {
+        let (i, admission_authority) = OptTaggedParser::new(Class::ContextSpecific, Tag(0))
+            .parse_der(i, |_, inner| parse_generalname(inner))?;
+        let (i, naming_authority) = OptTaggedParser::new(Class::ContextSpecific, Tag(1))
+            .parse_der(i, |_, inner| parse_naming_authority(inner))?;
+        let (i, profession_infos) = Sequence::from_der_and_then(i, |i| {
+            all_consuming(many0(complete(parse_profession_info)))(i)
+        })?;
+        let admissions = Admissions {
+            admission_authority,
+            naming_authority,
+            profession_infos,
+        };
+        Ok((i, admissions))
+    })
+}
+
+fn parse_naming_authority(i: &[u8]) -> IResult<&[u8], NamingAuthority, Error> {
+    Sequence::from_der_and_then(i, |i| {
+        let (i, id) = if next_is(i, Tag::Oid) {
+            let (i, oid) = Oid::from_der(i)?;
+            (i, Some(oid))
+        } else {
+            (i, None)
+        };
+        let (i, url) = opt_string(i, Tag::Ia5String)?;
+        let (i, text) = opt(complete(parse_directory_string))(i)?;
+        Ok((i, NamingAuthority { id, url, text }))
+    })
+}
+
+fn parse_profession_info(i: &[u8]) -> IResult<&[u8], ProfessionInfo, Error> {
+    Sequence::from_der_and_then(i, |i| {
+        let (i, naming_authority) = OptTaggedParser::new(Class::ContextSpecific, Tag(0))
+            .parse_der(i, |_, inner| parse_naming_authority(inner))?;
+        let (i, profession_items) = Sequence::from_der_and_then(i, |i| {
+            all_consuming(many0(complete(parse_directory_string)))(i)
+        })?;
+        let (i, profession_oids) = if next_is(i, Tag::Sequence) {
+            Sequence::from_der_and_then(i, |i| all_consuming(many0(complete(parse_oid)))(i))?
+        } else {
+            (i, Vec::new())
+        };
+        let (i, registration_number) = opt_string(i, Tag::PrintableString)?;
+        let (i, add_profession_info) = if next_is(i, Tag::OctetString) {
+            let (i, data) = <&[u8]>::from_der(i)?;
+            (i, Some(data))
+        } else {
+            (i, None)
+        };
+        let info = ProfessionInfo {
+            naming_authority,
+            profession_items,
+            profession_oids,
+            registration_number,
+            add_profession_info,
+        };
+        Ok((i, info))
+    })
+}
+
+// Test if the next element has the given universal tag
+//
+// Some parsers (for ex. `Oid::from_der`) do not check the tag, so optional fields are detected
+// using the header.
+fn next_is(i: &[u8], tag: Tag) -> bool {
+    matches!(
+        Header::from_der(i),
+        Ok((_, header)) if header.class() == Class::Universal && header.tag() == tag
+    )
+}
+
+fn parse_oid(i: &[u8]) -> IResult<&[u8], Oid, Error> {
+    if !next_is(i, Tag::Oid) {
+        return Err(Err::Error(Error::InvalidTag));
+    }
+    Oid::from_der(i)
+}
+
+// Parse an optional string with the given (universal) tag, and a UTF-8 compatible charset
+fn opt_string(i: &[u8], tag: Tag) -> IResult<&[u8], Option<&str>, Error> {
+    match Any::from_der(i) {
+        Ok((rem, any)) if any.class() == Class::Universal && any.tag() == tag => {
+            let s =
+                std::str::from_utf8(any.data).or(Err(Err::Error(Error::StringInvalidCharset)))?;
+            Ok((rem, Some(s)))
+        }
+        _ => Ok((i, None)),
+    }
+}
+
+// DirectoryString ::= CHOICE {
+//     teletexString       TeletexString,
+//     printableString     PrintableString,
+//     universalString     UniversalString,
+//     utf8String          UTF8String,
+//     bmpString           BMPString }
+fn parse_directory_string(i: &[u8]) -> IResult<&[u8], String, Error> {
+    let (rem, any) = Any::from_der(i)?;
+    if any.class() != Class::Universal {
+        return Err(Err::Error(Error::InvalidTag));
+    }
+    let s = match any.tag() {
+        Tag::PrintableString
+        | Tag::Utf8String
+        | Tag::TeletexString
+        | Tag::BmpString
+        | Tag::UniversalString => {
+            decode_directory_string(&any).ok_or(Err::Error(Error::StringInvalidCharset))?
+        }
+        _ => return Err(Err::Error(Error::InvalidTag)),
+    };
+    Ok((rem, s))
+}
@@ -1,12 +1,20 @@
+use super::OID_MS_USER_PRINCIPAL_NAME;
 use crate::error::{X509Error, X509Result};
 use crate::prelude::format_serial;
 use crate::x509::X509Name;
-use asn1_rs::{Any, CheckDerConstraints, Class, Error, FromDer, Oid, Sequence};
+use asn1_rs::{oid, Any, CheckDerConstraints, Class, Error, FromDer, Oid, Sequence, Tag};
 use core::convert::TryFrom;
-use nom::combinator::all_consuming;
+use core::ops::Deref;
+use der_parser::der::parse_der_sequence_defined_g;
+use nom::combinator::{all_consuming, complete, cut};
+use nom::multi::many0;
 use nom::{Err, IResult};
 use std::fmt;
 
+/// id-on-SmtpUTF8Mailbox, the otherName form of internationalized email addresses
+/// ([RFC8398](https://datatracker.ietf.org/doc/html/rfc8398))
+pub const OID_ON_SMTP_UTF8_MAILBOX: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .8 .9);
+
 #[derive(Clone, Debug, PartialEq)]
 /// Represents a GeneralName as defined in RFC5280. There
 /// is no support X.400 addresses and EDIPartyName.
@@ -30,6 +38,24 @@ pub enum GeneralName<'a> {
     /// An ip address, provided as encoded.
     IPAddress(&'a [u8]),
     RegisteredID(Oid<'a>),
+    /// An internationalized email address (otherName `id-on-SmtpUTF8Mailbox`, RFC8398)
+    SmtpUTF8Mailbox(&'a str),
+    /// A Microsoft User Principal Name (otherName `szOID_NT_PRINCIPAL_NAME`), for ex. the account
+    /// of a smart card logon certificate (`user@example.com`)
+    UserPrincipalName(&'a str),
+}
+
+impl<'a> GeneralName<'a> {
+    /// Return the email address, if this name is an `RFC822Name` or a `SmtpUTF8Mailbox`
+    ///
+    /// RFC8398 requires the `SmtpUTF8Mailbox` form for addresses with a non-ASCII local part.
+    /// Both forms must be considered when matching the address of the sender of a message.
+    pub fn email_address(&self) -> Option<&'a str> {
+        match self {
+            GeneralName::RFC822Name(s) | GeneralName::SmtpUTF8Mailbox(s) => Some(s),
+            _ => None,
+        }
+    }
 }
 
 impl<'a> TryFrom<Any<'a>> for GeneralName<'a> {
@@ -40,13 +66,19 @@ impl<'a> TryFrom<Any<'a>> for GeneralName<'a> {
         fn ia5str(any: Any) -> Result<&str, Err<Error>> {
             // Relax constraints from RFC here: we are expecting an IA5String, but many certificates
             // are using unicode characters
-            std::str::from_utf8(any.data).map_err(|_| nom::Err::Failure(Error::BerValueError))
+            std::str::from_utf8(any.data).map_err(|_| Err::Failure(Error::BerValueError))
         }
         let name = match any.tag().0 {
             0 => {
                 // otherName SEQUENCE { OID, [0] explicit any defined by oid }
                 let (rest, oid) = Oid::from_der(any.data)?;
-                GeneralName::OtherName(oid, rest)
+                match utf8_other_name(rest) {
+                    Some(s) if oid == OID_ON_SMTP_UTF8_MAILBOX => GeneralName::SmtpUTF8Mailbox(s),
+                    Some(s) if oid == OID_MS_USER_PRINCIPAL_NAME => {
+                        GeneralName::UserPrincipalName(s)
+                    }
+                    _ => GeneralName::OtherName(oid, rest),
+                }
             }
             1 => GeneralName::RFC822Name(ia5str(any)?),
             2 => GeneralName::DNSName(ia5str(any)?),
@@ -104,12 +136,122 @@ impl<'a> fmt::Display for GeneralName<'a> {
             GeneralName::URI(s) => write!(f, "URI({})", s),
             GeneralName::IPAddress(b) => write!(f, "IPAddress({})", format_serial(b)),
             GeneralName::RegisteredID(oid) => write!(f, "RegisteredID({})", oid),
+            GeneralName::SmtpUTF8Mailbox(s) => write!(f, "SmtpUTF8Mailbox({})", s),
+            GeneralName::UserPrincipalName(s) => write!(f, "UserPrincipalName({})", s),
         }
     }
 }
 
+/// A list of `GeneralName`, as defined in RFC5280
+///
+/// This type is used by several extensions (for ex. SubjectAlternativeName or
+/// IssuerAlternativeName), and can be reused by other parsers.
+///
+/// <pre>
+/// GeneralNames ::= SEQUENCE SIZE (1..MAX) OF GeneralName
+/// </pre>
+///
+/// Note: empty sequences are accepted.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneralNames<'a> {
+    pub general_names: Vec<GeneralName<'a>>,
+}
+
+impl<'a> GeneralNames<'a> {
+    /// Return an iterator over the names
+    pub fn iter(&self) -> impl Iterator<Item = &GeneralName<'a>> {
+        self.general_names.iter()
+    }
+}
+
+impl<'a> Deref for GeneralNames<'a> {
+    type Target = [GeneralName<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.general_names
+    }
+}
+
+impl<'a> From<GeneralNames<'a>> for Vec<GeneralName<'a>> {
+    fn from(names: GeneralNames<'a>) -> Self {
+        names.general_names
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for GeneralNames<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parse_generalnames(i)
+            .map(|(rem, general_names)| (rem, GeneralNames { general_names }))
+            .map_err(Err::convert)
+    }
+}
+
+/// Parse a `SEQUENCE OF GeneralName`
+pub(crate) fn parse_generalnames(i: &[u8]) -> IResult<&[u8], Vec<GeneralName>, Error> {
+    parse_der_sequence_defined_g(|input, _| {
+        all_consuming(many0(complete(cut(parse_generalname))))(input)
+    })(i)
+}
+
+// Decode the value of an otherName of type `[0] EXPLICIT UTF8String` (for ex. `SmtpUTF8Mailbox`)
+//
+// Invalid values are kept as `OtherName`, like other unsupported names.
+fn utf8_other_name(value: &[u8]) -> Option<&str> {
+    let (_, tagged) = all_consuming(Any::from_der)(value).ok()?;
+    if tagged.class() != Class::ContextSpecific || tagged.tag() != Tag(0) {
+        return None;
+    }
+    let (_, s) = all_consuming(Any::from_der)(tagged.data).ok()?;
+    if s.class() != Class::Universal || s.tag() != Tag::Utf8String {
+        return None;
+    }
+    std::str::from_utf8(s.data).ok()
+}
+
 pub(crate) fn parse_generalname(i: &[u8]) -> IResult<&[u8], GeneralName, Error> {
     let (rest, any) = Any::from_der(i)?;
     let gn = GeneralName::try_from(any)?;
     Ok((rest, gn))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generalnames() {
+        // SEQUENCE { [2] "a.com", [6] "http://x" }
+        let data = &[
+            0x30, 0x11, 0x82, 0x05, 0x61, 0x2e, 0x63, 0x6f, 0x6d, 0x86, 0x08, 0x68, 0x74, 0x74,
+            0x70, 0x3a, 0x2f, 0x2f, 0x78,
+        ];
+        let (rem, names) = GeneralNames::from_der(data).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0], GeneralName::DNSName("a.com"));
+        assert_eq!(names[1], GeneralName::URI("http://x"));
+        // not a SEQUENCE
+        assert!(GeneralNames::from_der(&data[2..]).is_err());
+    }
+
+    #[test]
+    fn test_smtp_utf8_mailbox() {
+        // [0] { OID id-on-SmtpUTF8Mailbox, [0] { UTF8String "a@b" } }
+        let data = &[
+            0xa0, 0x11, 0x06, 0x08, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x08, 0x09, 0xa0, 0x05,
+            0x0c, 0x03, 0x61, 0x40, 0x62,
+        ];
+        let (_, name) = GeneralName::from_der(data).expect("parsing failed");
+        assert_eq!(name, GeneralName::SmtpUTF8Mailbox("a@b"));
+        assert_eq!(name.email_address(), Some("a@b"));
+        // an IA5String value is invalid, and kept as otherName
+        let mut data = data.to_vec();
+        data[14] = 0x16;
+        let (_, name) = GeneralName::from_der(&data).expect("parsing failed");
+        assert_eq!(
+            name,
+            GeneralName::OtherName(OID_ON_SMTP_UTF8_MAILBOX, &data[12..])
+        );
+        assert_eq!(name.email_address(), None);
+    }
+}
@@ -0,0 +1,74 @@
+//! Hold instruction codes (CRL entry extension)
+
+use asn1_rs::{oid, Oid};
+use std::fmt;
+
+/// OID of the `id-holdinstruction-none` hold instruction
+pub const OID_HOLD_INSTRUCTION_NONE: Oid<'static> = oid!(1.2.840 .10040 .2 .1);
+/// OID of the `id-holdinstruction-callissuer` hold instruction
+pub const OID_HOLD_INSTRUCTION_CALL_ISSUER: Oid<'static> = oid!(1.2.840 .10040 .2 .2);
+/// OID of the `id-holdinstruction-reject` hold instruction
+pub const OID_HOLD_INSTRUCTION_REJECT: Oid<'static> = oid!(1.2.840 .10040 .2 .3);
+
+// RFC 2459 and RFC 3280 defined the `holdInstruction` arc as
+// `{ joint-iso-itu-t(2) member-body(2) us(840) x9cm(10040) 2 }`, which some legacy CAs
+// still emit. These values are accepted as aliases of the `iso(1)` ones.
+const OID_LEGACY_HOLD_INSTRUCTION_NONE: Oid<'static> = oid!(2.2.840 .10040 .2 .1);
+const OID_LEGACY_HOLD_INSTRUCTION_CALL_ISSUER: Oid<'static> = oid!(2.2.840 .10040 .2 .2);
+const OID_LEGACY_HOLD_INSTRUCTION_REJECT: Oid<'static> = oid!(2.2.840 .10040 .2 .3);
+
+/// The action to take when a certificate placed on hold is encountered
+///
+/// Section 5.3.2 of rfc 3280 (the extension was removed from rfc 5280, but is still used
+/// with the `certificateHold` reason code).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HoldInstruction<'a> {
+    /// No hold instruction (semantically equivalent to the extension being absent)
+    None,
+    /// Call the certificate issuer, or reject the certificate
+    CallIssuer,
+    /// Reject the certificate
+    Reject,
+    /// Unknown hold instruction
+    Other(Oid<'a>),
+}
+
+impl<'a> HoldInstruction<'a> {
+    /// Build a `HoldInstruction` from its `Oid`
+    ///
+    /// The legacy `2.2.840.10040.2` arc is recognized as an alias of `1.2.840.10040.2`.
+    pub fn from_oid(oid: Oid<'a>) -> Self {
+        if oid == OID_HOLD_INSTRUCTION_NONE || oid == OID_LEGACY_HOLD_INSTRUCTION_NONE {
+            HoldInstruction::None
+        } else if oid == OID_HOLD_INSTRUCTION_CALL_ISSUER
+            || oid == OID_LEGACY_HOLD_INSTRUCTION_CALL_ISSUER
+        {
+            HoldInstruction::CallIssuer
+        } else if oid == OID_HOLD_INSTRUCTION_REJECT || oid == OID_LEGACY_HOLD_INSTRUCTION_REJECT {
+            HoldInstruction::Reject
+        } else {
+            HoldInstruction::Other(oid)
+        }
+    }
+
+    /// Return the `Oid` of the hold instruction (using the `1.2.840.10040.2` arc for known values)
+    pub fn oid(&self) -> Oid<'a> {
+        match self {
+            HoldInstruction::None => OID_HOLD_INSTRUCTION_NONE,
+            HoldInstruction::CallIssuer => OID_HOLD_INSTRUCTION_CALL_ISSUER,
+            HoldInstruction::Reject => OID_HOLD_INSTRUCTION_REJECT,
+            HoldInstruction::Other(oid) => oid.clone(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for HoldInstruction<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HoldInstruction::None => f.write_str("None"),
+            HoldInstruction::CallIssuer => f.write_str("Call Issuer"),
+            HoldInstruction::Reject => f.write_str("Reject"),
+            HoldInstruction::Other(oid) => f.write_str(&oid.to_id_string()),
+        }
+    }
+}
@@ -42,7 +42,7 @@ impl KeyUsage {
 }
 
 // This list must have the same order as KeyUsage flags declaration (4.2.1.3)
-const KEY_USAGE_FLAGS: &[&str] = &[
+pub(crate) const KEY_USAGE_FLAGS: &[&str] = &[
     "Digital Signature",
     "Non Repudiation",
     "Key Encipherment",
@@ -0,0 +1,116 @@
+//! Microsoft-specific extensions
+
+use super::{parse_generalnames, GeneralName};
+use crate::error::{X509Error, X509Result};
+use asn1_rs::{oid, BmpString, Error, FromDer, Oid, Sequence, TaggedExplicit};
+use nom::combinator::{complete, opt};
+use nom::{Err, IResult};
+use std::fmt;
+
+/// OID of the NTDS CA Security extension (`szOID_NTDS_CA_SECURITY_EXT`)
+pub const OID_MS_NTDS_CA_SECURITY_EXT: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .25 .2);
+/// OID of the `objectSid` otherName, in the NTDS CA Security extension
+/// (`szOID_NTDS_OBJECTSID`)
+pub const OID_MS_NTDS_OBJECTSID: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .25 .2 .1);
+/// OID of the User Principal Name otherName, used for smart card logon (`szOID_NT_PRINCIPAL_NAME`)
+pub const OID_MS_USER_PRINCIPAL_NAME: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .20 .2 .3);
+/// OID of the certificate template name extension (`szOID_ENROLL_CERTTYPE_EXTENSION`)
+pub const OID_MS_ENROLL_CERTTYPE: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .20 .2);
+/// OID of the certificate template extension (`szOID_CERTIFICATE_TEMPLATE`)
+pub const OID_MS_CERTIFICATE_TEMPLATE: Oid<'static> = oid!(1.3.6 .1 .4 .1 .311 .21 .7);
+
+/// NTDS CA Security extension, used by Active Directory for strong certificate mapping
+///
+/// The extension contains the security identifier (SID) of the account the certificate was
+/// issued to, encoded as an `otherName`:
+///
+/// <pre>
+/// NtdsCaSecurityExt ::= SEQUENCE OF GeneralName
+/// -- otherName with type-id szOID_NTDS_OBJECTSID and value [0] EXPLICIT OCTET STRING
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct NtdsCaSecurity<'a> {
+    /// The SID of the account, in string form (for ex. `S-1-5-21-...`)
+    pub object_sid: &'a str,
+}
+
+impl<'a> FromDer<'a, X509Error> for NtdsCaSecurity<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parse_ntdscasecurity(i).map_err(Err::convert)
+    }
+}
+
+pub(crate) fn parse_ntdscasecurity(i: &[u8]) -> IResult<&[u8], NtdsCaSecurity, Error> {
+    let (rem, names) = parse_generalnames(i)?;
+    let object_sid = names
+        .iter()
+        .find_map(|name| match name {
+            GeneralName::OtherName(oid, value) if *oid == OID_MS_NTDS_OBJECTSID => {
+                let (_, sid) = TaggedExplicit::<&[u8], Error, 0>::from_der(value).ok()?;
+                std::str::from_utf8(sid.into_inner()).ok()
+            }
+            _ => None,
+        })
+        .ok_or(Err::Error(Error::BerValueError))?;
+    Ok((rem, NtdsCaSecurity { object_sid }))
+}
+
+/// Certificate template extension (version 2 templates), identifying the AD CS template used to
+/// issue the certificate
+///
+/// <pre>
+/// CertificateTemplate ::= SEQUENCE {
+///     templateID              EncodedObjectID,
+///     templateMajorVersion    TemplateVersion OPTIONAL,
+///     templateMinorVersion    TemplateVersion OPTIONAL }
+///
+/// TemplateVersion ::= INTEGER (0..4294967295)
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateTemplate<'a> {
+    pub template_id: Oid<'a>,
+    pub major_version: Option<u32>,
+    pub minor_version: Option<u32>,
+}
+
+impl<'a> FromDer<'a, X509Error> for CertificateTemplate<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parse_certificate_template(i).map_err(Err::convert)
+    }
+}
+
+/// Human-readable representation of the template, similar to the Windows certificate viewer
+impl<'a> fmt::Display for CertificateTemplate<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Template={}", self.template_id)?;
+        if let Some(major) = self.major_version {
+            write!(f, ", Major Version Number={}", major)?;
+        }
+        if let Some(minor) = self.minor_version {
+            write!(f, ", Minor Version Number={}", minor)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_certificate_template(i: &[u8]) -> IResult<&[u8], CertificateTemplate, Error> {
+    Sequence::from_der_and_then(i, |i| {
+        let (i, template_id) = Oid::from_der(i)?;
+        let (i, major_version) = opt(complete(u32::from_der))(i)?;
+        let (i, minor_version) = opt(complete(u32::from_der))(i)?;
+        let template = CertificateTemplate {
+            template_id,
+            major_version,
+            minor_version,
+        };
+        Ok((i, template))
+    })
+}
+
+// Certificate template name (version 1 templates), for ex. `WebServer`
+//
+// CertificateTemplateName ::= BMPString
+pub(crate) fn parse_enroll_certtype(i: &[u8]) -> IResult<&[u8], String, Error> {
+    let (rem, name) = BmpString::from_der(i)?;
+    Ok((rem, name.string()))
+}
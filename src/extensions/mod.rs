@@ -1,34 +1,50 @@
 //! X.509 Extensions objects and types
 
+use crate::certificate::TbsCertificate;
 use crate::error::{X509Error, X509Result};
+use crate::ocsp::OID_PKIX_OCSP_NOCHECK;
 use crate::time::ASN1Time;
-use crate::utils::format_serial;
-use crate::x509::{ReasonCode, RelativeDistinguishedName};
+use crate::utils::{format_serial, many0_lazy};
+use crate::x509::{serial_to_u128, ReasonCode, RelativeDistinguishedName};
 
-use asn1_rs::FromDer;
+use asn1_rs::{oid, FromDer};
 use der_parser::ber::parse_ber_bool;
 use der_parser::der::*;
 use der_parser::error::{BerError, BerResult};
+#[cfg(feature = "bigint")]
 use der_parser::num_bigint::BigUint;
 use der_parser::oid::Oid;
-use nom::combinator::{all_consuming, complete, cut, map, map_res, opt};
+use nom::combinator::{all_consuming, complete, map, map_res, opt};
 use nom::multi::{many0, many1};
 use nom::{Err, IResult, Parser};
 use oid_registry::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::fmt::{self, LowerHex};
+use std::fmt::{self, LowerHex, Write};
 
+mod admission;
 mod generalname;
+mod holdinstruction;
 mod keyusage;
+mod microsoft;
 mod nameconstraints;
 mod policymappings;
 mod sct;
+mod smime;
 
+pub use admission::*;
 pub use generalname::*;
+pub use holdinstruction::*;
 pub use keyusage::*;
+pub use microsoft::*;
 pub use nameconstraints::*;
 pub use policymappings::*;
 pub use sct::*;
+pub use smime::*;
+
+/// OID of the CanSignHttpExchanges extension, allowing a certificate to sign HTTP exchanges
+/// (Signed HTTP Exchanges, `draft-yasskin-http-origin-signed-responses`)
+pub const OID_CAN_SIGN_HTTP_EXCHANGES: Oid<'static> = oid!(1.3.6 .1 .4 .1 .11129 .2 .1 .22);
 
 /// X.509 version 3 extension
 ///
@@ -115,6 +131,74 @@ impl<'a> X509Extension<'a> {
     pub fn parsed_extension(&self) -> &ParsedExtension<'a> {
         &self.parsed_extension
     }
+
+    /// Return the extension value formatted as an ASN.1 tree
+    ///
+    /// This is meant for debugging, for ex. to inspect the structure of unsupported extensions.
+    /// An error is returned if the value is not valid DER.
+    pub fn debug_dump(&self) -> Result<String, X509Error> {
+        let mut s = String::new();
+        let mut i = self.value;
+        while !i.is_empty() {
+            let (rem, obj) = parse_der(i)?;
+            // cannot fail, writing to a String
+            let _ = writeln!(s, "{:?}", obj.as_pretty(0, 2));
+            i = rem;
+        }
+        Ok(s)
+    }
+}
+
+/// An X.509 extension, with its raw value stored as `Cow`
+///
+/// This allows keeping some extensions of a certificate (for ex. in a long-lived cache) without
+/// keeping the certificate buffer: see [`Self::into_owned`].
+/// Use [`Self::to_extension`] to parse the value again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CowX509Extension<'a> {
+    /// OID describing the extension content
+    pub oid: Oid<'a>,
+    /// Boolean value describing the 'critical' attribute of the extension
+    pub critical: bool,
+    /// Raw content of the extension
+    pub value: Cow<'a, [u8]>,
+}
+
+impl<'a> CowX509Extension<'a> {
+    /// Convert to an object owning its data, detached from the input buffer
+    pub fn into_owned(self) -> CowX509Extension<'static> {
+        CowX509Extension {
+            oid: self.oid.to_owned(),
+            critical: self.critical,
+            value: Cow::Owned(self.value.into_owned()),
+        }
+    }
+
+    /// Parse the extension value, and return the parsed extension
+    pub fn to_extension(&self) -> X509Extension<'_> {
+        let value: &[u8] = &self.value;
+        // parse_extension never fails, parsing errors are stored in `ParseError`
+        let parsed_extension = match parser::parse_extension(value, value, &self.oid) {
+            Ok((_, ext)) => ext,
+            Err(error) => ParsedExtension::ParseError { error },
+        };
+        X509Extension {
+            oid: self.oid.clone(),
+            critical: self.critical,
+            value,
+            parsed_extension,
+        }
+    }
+}
+
+impl<'a> From<&X509Extension<'a>> for CowX509Extension<'a> {
+    fn from(ext: &X509Extension<'a>) -> Self {
+        CowX509Extension {
+            oid: ext.oid.clone(),
+            critical: ext.critical,
+            value: Cow::Borrowed(ext.value),
+        }
+    }
 }
 
 /// <pre>
@@ -153,6 +237,7 @@ impl X509ExtensionParser {
 
 impl<'a> Parser<&'a [u8], X509Extension<'a>, X509Error> for X509ExtensionParser {
     fn parse(&mut self, input: &'a [u8]) -> IResult<&'a [u8], X509Extension<'a>, X509Error> {
+        trace_field!("extension", input);
         parse_der_sequence_defined_g(|i, _| {
             let (i, oid) = Oid::from_der(i)?;
             let (i, critical) = der_read_critical(i)?;
@@ -209,20 +294,51 @@ pub enum ParsedExtension<'a> {
     CRLDistributionPoints(CRLDistributionPoints<'a>),
     /// Section 4.2.1.14 of rfc 5280
     InhibitAnyPolicy(InhibitAnyPolicy),
+    /// Section 4.2.1.4 of rfc 3280
+    PrivateKeyUsagePeriod(PrivateKeyUsagePeriod),
+    /// Section 4.2.1.15 of rfc 5280 (Delta CRL Distribution Point)
+    FreshestCRL(CRLDistributionPoints<'a>),
     /// Section 4.2.2.1 of rfc 5280
     AuthorityInfoAccess(AuthorityInfoAccess<'a>),
+    /// Section 4.2.2.2 of rfc 5280
+    SubjectInfoAccess(SubjectInfoAccess<'a>),
     /// Netscape certificate type (subject is SSL client, an SSL server, or a CA)
     NSCertType(NSCertType),
     /// Netscape certificate comment
     NsCertComment(&'a str),
+    /// Netscape base URL, prepended to the other (relative) Netscape URLs
+    NsBaseUrl(&'a str),
+    /// Section 5.2.5 of rfc 5280
+    IssuingDistributionPoint(IssuingDistributionPoint<'a>),
     /// Section 5.3.1 of rfc 5280
-    CRLNumber(BigUint),
+    CRLNumber(CRLNumber<'a>),
     /// Section 5.3.1 of rfc 5280
     ReasonCode(ReasonCode),
     /// Section 5.3.3 of rfc 5280
     InvalidityDate(ASN1Time),
+    /// Section 5.3.3 of rfc 5280 (Certificate Issuer, for indirect CRLs)
+    CertificateIssuer(GeneralNames<'a>),
+    /// Section 5.3.2 of rfc 3280 (Hold Instruction Code)
+    HoldInstructionCode(HoldInstruction<'a>),
     /// rfc 6962
     SCT(Vec<SignedCertificateTimestamp<'a>>),
+    /// Precertificate poison (rfc 6962)
+    CtPrecertPoison,
+    /// OCSP No Check (section 4.2.2.2.1 of rfc 6960): the revocation status of this delegated
+    /// OCSP responder certificate is not checked
+    OcspNoCheck,
+    /// CanSignHttpExchanges (Signed HTTP Exchanges): the certificate can sign HTTP exchanges
+    CanSignHttpExchanges,
+    /// Microsoft NTDS CA Security extension (Active Directory object SID)
+    NtdsCaSecurity(NtdsCaSecurity<'a>),
+    /// Microsoft certificate template name (version 1 templates)
+    EnrollCertType(String),
+    /// Microsoft certificate template (version 2 templates)
+    CertificateTemplate(CertificateTemplate<'a>),
+    /// S/MIME capabilities (rfc 8551)
+    SMIMECapabilities(SMIMECapabilities<'a>),
+    /// Admission (Common PKI), the professions of the subject
+    Admission(AdmissionSyntax<'a>),
     /// Unparsed extension (was not requested in parsing options)
     Unparsed,
 }
@@ -242,6 +358,233 @@ impl<'a> ParsedExtension<'a> {
     }
 }
 
+/// Human-readable one-line representation of the extension, similar to OpenSSL output
+impl<'a> fmt::Display for ParsedExtension<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsedExtension::UnsupportedExtension { oid } => {
+                write!(f, "<unsupported extension {}>", oid)
+            }
+            ParsedExtension::ParseError { error } => write!(f, "<parse error: {}>", error),
+            ParsedExtension::AuthorityKeyIdentifier(aki) => {
+                let mut items = Vec::new();
+                if let Some(id) = &aki.key_identifier {
+                    items.push(format!("keyid:{:x}", id));
+                }
+                if let Some(issuer) = &aki.authority_cert_issuer {
+                    items.push(general_names_to_string(issuer));
+                }
+                if let Some(serial) = aki.authority_cert_serial {
+                    items.push(format!("serial:{}", format_serial(serial)));
+                }
+                f.write_str(&items.join(", "))
+            }
+            ParsedExtension::SubjectKeyIdentifier(id) => write!(f, "{:x}", id),
+            ParsedExtension::KeyUsage(ku) => write!(f, "{}", ku),
+            ParsedExtension::CertificatePolicies(policies) => {
+                let s = policies
+                    .iter()
+                    .map(|p| format!("Policy: {}", p.policy_id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                f.write_str(&s)
+            }
+            ParsedExtension::PolicyMappings(pm) => {
+                let s = pm
+                    .mappings
+                    .iter()
+                    .map(|m| format!("{}:{}", m.issuer_domain_policy, m.subject_domain_policy))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                f.write_str(&s)
+            }
+            ParsedExtension::SubjectAlternativeName(san) => {
+                f.write_str(&general_names_to_string(&san.general_names))
+            }
+            ParsedExtension::IssuerAlternativeName(ian) => {
+                f.write_str(&general_names_to_string(&ian.general_names))
+            }
+            ParsedExtension::BasicConstraints(bc) => {
+                write!(f, "CA:{}", if bc.ca { "TRUE" } else { "FALSE" })?;
+                if let Some(len) = bc.path_len_constraint {
+                    write!(f, ", pathlen:{}", len)?;
+                }
+                Ok(())
+            }
+            ParsedExtension::NameConstraints(nc) => {
+                let mut items = Vec::new();
+                let subtrees = [
+                    ("Permitted", &nc.permitted_subtrees),
+                    ("Excluded", &nc.excluded_subtrees),
+                ];
+                for (label, subtrees) in subtrees.iter() {
+                    if let Some(subtrees) = subtrees {
+                        let names = subtrees.iter().map(|s| s.base.clone()).collect::<Vec<_>>();
+                        items.push(format!("{}: {}", label, general_names_to_string(&names)));
+                    }
+                }
+                f.write_str(&items.join("; "))
+            }
+            ParsedExtension::PolicyConstraints(pc) => {
+                let mut items = Vec::new();
+                if let Some(n) = pc.require_explicit_policy {
+                    items.push(format!("Require Explicit Policy:{}", n));
+                }
+                if let Some(n) = pc.inhibit_policy_mapping {
+                    items.push(format!("Inhibit Policy Mapping:{}", n));
+                }
+                f.write_str(&items.join(", "))
+            }
+            ParsedExtension::ExtendedKeyUsage(eku) => {
+                let mut items = Vec::new();
+                let flags = [
+                    (eku.any, "Any Extended Key Usage"),
+                    (eku.server_auth, "TLS Web Server Authentication"),
+                    (eku.client_auth, "TLS Web Client Authentication"),
+                    (eku.code_signing, "Code Signing"),
+                    (eku.email_protection, "E-mail Protection"),
+                    (eku.time_stamping, "Time Stamping"),
+                    (eku.ocsp_signing, "OCSP Signing"),
+                ];
+                for (set, name) in flags.iter() {
+                    if *set {
+                        items.push(name.to_string());
+                    }
+                }
+                items.extend(eku.other.iter().map(|oid| oid.to_id_string()));
+                f.write_str(&items.join(", "))
+            }
+            ParsedExtension::CRLDistributionPoints(points) => write!(f, "{}", points),
+            ParsedExtension::FreshestCRL(points) => write!(f, "{}", points),
+            ParsedExtension::IssuingDistributionPoint(idp) => write!(f, "{}", idp),
+            ParsedExtension::CertificateIssuer(names) => {
+                f.write_str(&general_names_to_string(names))
+            }
+            ParsedExtension::InhibitAnyPolicy(iap) => write!(f, "{}", iap.skip_certs),
+            ParsedExtension::AuthorityInfoAccess(aia) => {
+                f.write_str(&access_descriptions_to_string(&aia.accessdescs))
+            }
+            ParsedExtension::SubjectInfoAccess(sia) => {
+                f.write_str(&access_descriptions_to_string(&sia.accessdescs))
+            }
+            ParsedExtension::NSCertType(t) => write!(f, "{}", t),
+            ParsedExtension::NsCertComment(s) => f.write_str(s),
+            ParsedExtension::NsBaseUrl(s) => f.write_str(s),
+            ParsedExtension::CRLNumber(n) => write!(f, "{}", n),
+            ParsedExtension::ReasonCode(code) => write!(f, "{}", code),
+            ParsedExtension::InvalidityDate(date) => write!(f, "{}", date),
+            ParsedExtension::HoldInstructionCode(hold) => write!(f, "{}", hold),
+            ParsedExtension::PrivateKeyUsagePeriod(period) => {
+                let mut items = Vec::new();
+                if let Some(not_before) = period.not_before {
+                    items.push(format!("Not Before: {}", not_before));
+                }
+                if let Some(not_after) = period.not_after {
+                    items.push(format!("Not After: {}", not_after));
+                }
+                f.write_str(&items.join(", "))
+            }
+            ParsedExtension::SCT(scts) => {
+                let s = scts
+                    .iter()
+                    .map(|sct| format!("SCT(log: {})", format_serial(sct.id.key_id)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                f.write_str(&s)
+            }
+            ParsedExtension::CtPrecertPoison => f.write_str("NULL"),
+            ParsedExtension::OcspNoCheck => f.write_str("NULL"),
+            ParsedExtension::CanSignHttpExchanges => f.write_str("NULL"),
+            ParsedExtension::NtdsCaSecurity(ext) => write!(f, "objectSid:{}", ext.object_sid),
+            ParsedExtension::EnrollCertType(name) => f.write_str(name),
+            ParsedExtension::CertificateTemplate(template) => write!(f, "{}", template),
+            ParsedExtension::Admission(admission) => write!(f, "{}", admission),
+            ParsedExtension::SMIMECapabilities(caps) => {
+                let s = caps
+                    .iter()
+                    .map(|c| match c.name() {
+                        Some(name) => name.to_string(),
+                        None => c.capability_id.to_id_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                f.write_str(&s)
+            }
+            ParsedExtension::Unparsed => f.write_str("<unparsed>"),
+        }
+    }
+}
+
+/// Format a `GeneralName` using the OpenSSL notation (for ex. `DNS:example.com`)
+pub(crate) fn general_name_to_string(gn: &GeneralName) -> String {
+    match gn {
+        GeneralName::OtherName(oid, _) => format!("othername:{}:<unsupported>", oid),
+        GeneralName::RFC822Name(s) => format!("email:{}", s),
+        GeneralName::SmtpUTF8Mailbox(s) => format!("othername:SmtpUTF8Mailbox:{}", s),
+        GeneralName::UserPrincipalName(s) => format!("othername:UPN:{}", s),
+        GeneralName::DNSName(s) => format!("DNS:{}", s),
+        GeneralName::X400Address(_) => "X400Name:<unsupported>".to_string(),
+        GeneralName::DirectoryName(dn) => format!("DirName:{}", dn),
+        GeneralName::EDIPartyName(_) => "EdiPartyName:<unsupported>".to_string(),
+        GeneralName::URI(s) => format!("URI:{}", s),
+        GeneralName::IPAddress(b) => match b.len() {
+            4 => format!("IP Address:{}.{}.{}.{}", b[0], b[1], b[2], b[3]),
+            16 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(b);
+                format!("IP Address:{}", std::net::Ipv6Addr::from(octets))
+            }
+            // address and mask, in name constraints
+            8 | 32 => match IpSubtree::from_bytes(b) {
+                Ok(subtree) => format!("IP Address:{}", subtree),
+                Err(_) => format!("IP Address:<invalid {}>", format_serial(b)),
+            },
+            _ => format!("IP Address:<invalid {}>", format_serial(b)),
+        },
+        GeneralName::RegisteredID(oid) => format!("Registered ID:{}", oid),
+    }
+}
+
+fn general_names_to_string(names: &[GeneralName]) -> String {
+    names
+        .iter()
+        .map(general_name_to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn access_descriptions_to_string(accessdescs: &[AccessDescription]) -> String {
+    accessdescs
+        .iter()
+        .map(|ad| {
+            let method = match &ad.access_method {
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_OCSP => "OCSP".to_string(),
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_CA_ISSUERS => "CA Issuers".to_string(),
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_TIMESTAMPING => {
+                    "Time Stamping".to_string()
+                }
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_CA_REPOSITORY => {
+                    "CA Repository".to_string()
+                }
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_RPKI_MANIFEST => {
+                    "RPKI Manifest".to_string()
+                }
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_SIGNED_OBJECT => {
+                    "Signed Object".to_string()
+                }
+                oid if *oid == OID_PKIX_ACCESS_DESCRIPTOR_RPKI_NOTIFY => "RPKI Notify".to_string(),
+                oid => oid.to_id_string(),
+            };
+            format!(
+                "{} - {}",
+                method,
+                general_name_to_string(&ad.access_location)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct AuthorityKeyIdentifier<'a> {
     pub key_identifier: Option<KeyIdentifier<'a>>,
@@ -249,6 +592,48 @@ pub struct AuthorityKeyIdentifier<'a> {
     pub authority_cert_serial: Option<&'a [u8]>,
 }
 
+impl<'a> AuthorityKeyIdentifier<'a> {
+    /// Return the names of the issuer of the authority certificate, if present
+    #[inline]
+    pub fn authority_cert_issuer(&self) -> Option<&[GeneralName<'a>]> {
+        self.authority_cert_issuer.as_deref()
+    }
+
+    /// Return the serial number of the authority certificate, if present
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+    pub fn authority_cert_serial_number(&self) -> Option<BigUint> {
+        self.authority_cert_serial.map(BigUint::from_bytes_be)
+    }
+
+    /// Test if `authorityCertIssuer` and `authorityCertSerialNumber` designate the given
+    /// certificate.
+    ///
+    /// This can be used to link a certificate to its issuer when key identifiers are not
+    /// present. Returns `false` if any of the two fields is absent. If the key identifier is
+    /// also present, it must match the subject key identifier of `issuer` (if any).
+    pub fn matches_issuer_certificate(&self, issuer: &TbsCertificate) -> bool {
+        let (names, serial) = match (&self.authority_cert_issuer, self.authority_cert_serial) {
+            (Some(names), Some(serial)) => (names, serial),
+            _ => return false,
+        };
+        if serial != issuer.raw_serial() {
+            return false;
+        }
+        if let (Some(keyid), Ok(Some(ski))) =
+            (&self.key_identifier, issuer.subject_key_identifier())
+        {
+            if keyid != ski.value {
+                return false;
+            }
+        }
+        names.iter().any(|name| match name {
+            GeneralName::DirectoryName(dn) => dn.as_raw() == issuer.issuer().as_raw(),
+            _ => false,
+        })
+    }
+}
+
 impl<'a> FromDer<'a, X509Error> for AuthorityKeyIdentifier<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
         parser::parse_authoritykeyidentifier(i).map_err(Err::convert)
@@ -263,18 +648,123 @@ pub type CertificatePolicies<'a> = Vec<PolicyInformation<'a>>;
 //     }
 // }
 
+/// id-qt-cps, the CPS pointer policy qualifier
+pub const OID_POLICY_QUALIFIER_CPS: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .2 .1);
+/// id-qt-unotice, the user notice policy qualifier
+pub const OID_POLICY_QUALIFIER_UNOTICE: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .2 .2);
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PolicyInformation<'a> {
     pub policy_id: Oid<'a>,
     pub policy_qualifiers: Option<Vec<PolicyQualifierInfo<'a>>>,
 }
 
+impl<'a> PolicyInformation<'a> {
+    /// Return the URIs of the CPS pointer qualifiers
+    ///
+    /// Invalid qualifiers are ignored.
+    pub fn cps_uris(&self) -> Vec<&'a str> {
+        self.parsed_qualifiers()
+            .filter_map(|qualifier| match qualifier {
+                PolicyQualifier::CPSUri(uri) => Some(uri),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return the user notice qualifiers
+    ///
+    /// Invalid qualifiers are ignored.
+    pub fn user_notices(&self) -> Vec<UserNotice> {
+        self.parsed_qualifiers()
+            .filter_map(|qualifier| match qualifier {
+                PolicyQualifier::UserNotice(notice) => Some(notice),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn parsed_qualifiers(&self) -> impl Iterator<Item = PolicyQualifier<'a>> + '_ {
+        self.policy_qualifiers
+            .iter()
+            .flatten()
+            .filter_map(|info| info.parsed_qualifier().ok())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PolicyQualifierInfo<'a> {
     pub policy_qualifier_id: Oid<'a>,
     pub qualifier: &'a [u8],
 }
 
+impl<'a> PolicyQualifierInfo<'a> {
+    /// Decode the qualifier, depending on its type
+    ///
+    /// Qualifiers other than CPS pointers and user notices are returned as
+    /// `PolicyQualifier::Unknown`.
+    /// Returns `InvalidExtensions` if the qualifier does not match its type.
+    pub fn parsed_qualifier(&self) -> Result<PolicyQualifier<'a>, X509Error> {
+        if self.policy_qualifier_id == OID_POLICY_QUALIFIER_CPS {
+            let (_, uri) = all_consuming(parser::parse_cps_uri)(self.qualifier)
+                .or(Err(X509Error::InvalidExtensions))?;
+            Ok(PolicyQualifier::CPSUri(uri))
+        } else if self.policy_qualifier_id == OID_POLICY_QUALIFIER_UNOTICE {
+            let (_, notice) = all_consuming(parser::parse_user_notice)(self.qualifier)
+                .or(Err(X509Error::InvalidExtensions))?;
+            Ok(PolicyQualifier::UserNotice(notice))
+        } else {
+            Ok(PolicyQualifier::Unknown(self.qualifier))
+        }
+    }
+}
+
+/// A decoded policy qualifier
+///
+/// <pre>
+/// Qualifier ::= CHOICE {
+///      cPSuri           CPSuri,
+///      userNotice       UserNotice }
+///
+/// CPSuri ::= IA5String
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyQualifier<'a> {
+    CPSUri(&'a str),
+    UserNotice(UserNotice),
+    /// Qualifier of another type (raw DER encoding)
+    Unknown(&'a [u8]),
+}
+
+/// A user notice policy qualifier, to be displayed to relying parties
+///
+/// <pre>
+/// UserNotice ::= SEQUENCE {
+///      noticeRef        NoticeReference OPTIONAL,
+///      explicitText     DisplayText OPTIONAL }
+///
+/// NoticeReference ::= SEQUENCE {
+///      organization     DisplayText,
+///      noticeNumbers    SEQUENCE OF INTEGER }
+///
+/// DisplayText ::= CHOICE {
+///      ia5String        IA5String      (SIZE (1..200)),
+///      visibleString    VisibleString  (SIZE (1..200)),
+///      bmpString        BMPString      (SIZE (1..200)),
+///      utf8String       UTF8String     (SIZE (1..200)) }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserNotice {
+    pub notice_ref: Option<NoticeReference>,
+    pub explicit_text: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoticeReference {
+    pub organization: String,
+    pub notice_numbers: Vec<u32>,
+}
+
 /// Identifies whether the subject of the certificate is a CA, and the max validation depth.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BasicConstraints {
@@ -342,7 +832,7 @@ impl NSCertType {
 }
 
 const NS_CERT_TYPE_FLAGS: &[&str] = &[
-    "SSL CLient",
+    "SSL Client",
     "SSL Server",
     "S/MIME",
     "Object Signing",
@@ -389,42 +879,53 @@ impl<'a> AuthorityInfoAccess<'a> {
     ///
     /// If several names match the same `Oid`, they are merged in the same entry.
     pub fn as_hashmap(&self) -> HashMap<Oid<'a>, Vec<&GeneralName<'a>>> {
-        // create the hashmap and merge entries with same OID
-        let mut m: HashMap<Oid, Vec<&GeneralName>> = HashMap::new();
-        for desc in &self.accessdescs {
-            let AccessDescription {
-                access_method: oid,
-                access_location: gn,
-            } = desc;
-            if let Some(general_names) = m.get_mut(oid) {
-                general_names.push(gn);
-            } else {
-                m.insert(oid.clone(), vec![gn]);
-            }
-        }
-        m
+        access_descriptions_as_hashmap(&self.accessdescs)
     }
 
     /// Returns a `HashMap` mapping `Oid` to the list of `GeneralNames` (consuming the input)
     ///
     /// If several names match the same `Oid`, they are merged in the same entry.
     pub fn into_hashmap(self) -> HashMap<Oid<'a>, Vec<GeneralName<'a>>> {
-        let mut aia_list = self.accessdescs;
-        // create the hashmap and merge entries with same OID
-        let mut m: HashMap<Oid, Vec<GeneralName>> = HashMap::new();
-        for desc in aia_list.drain(..) {
-            let AccessDescription {
-                access_method: oid,
-                access_location: gn,
-            } = desc;
-            if let Some(general_names) = m.get_mut(&oid) {
-                general_names.push(gn);
-            } else {
-                m.insert(oid, vec![gn]);
-            }
+        access_descriptions_into_hashmap(self.accessdescs)
+    }
+}
+
+fn access_descriptions_as_hashmap<'a, 'b>(
+    accessdescs: &'b [AccessDescription<'a>],
+) -> HashMap<Oid<'a>, Vec<&'b GeneralName<'a>>> {
+    // create the hashmap and merge entries with same OID
+    let mut m: HashMap<Oid, Vec<&GeneralName>> = HashMap::new();
+    for desc in accessdescs {
+        let AccessDescription {
+            access_method: oid,
+            access_location: gn,
+        } = desc;
+        if let Some(general_names) = m.get_mut(oid) {
+            general_names.push(gn);
+        } else {
+            m.insert(oid.clone(), vec![gn]);
+        }
+    }
+    m
+}
+
+fn access_descriptions_into_hashmap(
+    mut accessdescs: Vec<AccessDescription>,
+) -> HashMap<Oid, Vec<GeneralName>> {
+    // create the hashmap and merge entries with same OID
+    let mut m: HashMap<Oid, Vec<GeneralName>> = HashMap::new();
+    for desc in accessdescs.drain(..) {
+        let AccessDescription {
+            access_method: oid,
+            access_location: gn,
+        } = desc;
+        if let Some(general_names) = m.get_mut(&oid) {
+            general_names.push(gn);
+        } else {
+            m.insert(oid, vec![gn]);
         }
-        m
     }
+    m
 }
 
 impl<'a> FromDer<'a, X509Error> for AuthorityInfoAccess<'a> {
@@ -433,6 +934,48 @@ impl<'a> FromDer<'a, X509Error> for AuthorityInfoAccess<'a> {
     }
 }
 
+/// id-pe-subjectInfoAccess, the Subject Information Access extension
+pub const OID_PKIX_SUBJECT_INFO_ACCESS: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .1 .11);
+
+/// Subject Information Access extension (section 4.2.2.2 of rfc 5280)
+///
+/// This extension indicates how to access information and services for the subject of the
+/// certificate, for ex. the repository of a CA (`id-ad-caRepository`), a time stamping service
+/// (`id-ad-timeStamping`), or RPKI manifests and signed objects ([RFC6487]).
+///
+/// [RFC6487]: https://datatracker.ietf.org/doc/html/rfc6487#section-4.8.8
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubjectInfoAccess<'a> {
+    pub accessdescs: Vec<AccessDescription<'a>>,
+}
+
+impl<'a> SubjectInfoAccess<'a> {
+    /// Returns an iterator over the Access Descriptors
+    pub fn iter(&self) -> impl Iterator<Item = &AccessDescription<'a>> {
+        self.accessdescs.iter()
+    }
+
+    /// Returns a `HashMap` mapping `Oid` to the list of references to `GeneralNames`
+    ///
+    /// If several names match the same `Oid`, they are merged in the same entry.
+    pub fn as_hashmap(&self) -> HashMap<Oid<'a>, Vec<&GeneralName<'a>>> {
+        access_descriptions_as_hashmap(&self.accessdescs)
+    }
+
+    /// Returns a `HashMap` mapping `Oid` to the list of `GeneralNames` (consuming the input)
+    ///
+    /// If several names match the same `Oid`, they are merged in the same entry.
+    pub fn into_hashmap(self) -> HashMap<Oid<'a>, Vec<GeneralName<'a>>> {
+        access_descriptions_into_hashmap(self.accessdescs)
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for SubjectInfoAccess<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parser::parse_subjectinfoaccess(i).map_err(Err::convert)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct AccessDescription<'a> {
     pub access_method: Oid<'a>,
@@ -459,6 +1002,77 @@ impl<'a> FromDer<'a, X509Error> for InhibitAnyPolicy {
     }
 }
 
+/// The CRL Number extension (section 5.2.3 of rfc 5280)
+///
+/// CRL numbers are non-negative integers of up to 20 octets, so they are stored as big-endian
+/// bytes. Use [`as_u128`](Self::as_u128) to get the value, or `as_biguint` (with the `bigint`
+/// feature) for numbers which do not fit in 128 bits.
+///
+/// The value is displayed in decimal, or in hexadecimal (prefixed by `0x`) if it does not fit in
+/// 128 bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CRLNumber<'a>(&'a [u8]);
+
+impl<'a> CRLNumber<'a> {
+    /// Return the big-endian bytes of the number, without leading zeros (empty for zero)
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Return the number as `u128`
+    ///
+    /// Returns `None` if the number does not fit in 128 bits.
+    pub fn as_u128(&self) -> Option<u128> {
+        serial_to_u128(self.0)
+    }
+
+    /// Return the number as a `BigUint`
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+    pub fn as_biguint(&self) -> BigUint {
+        BigUint::from_bytes_be(self.0)
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for CRLNumber<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parser::parse_crlnumber(i).map_err(Err::convert)
+    }
+}
+
+impl fmt::Display for CRLNumber<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_u128() {
+            Some(n) => write!(f, "{}", n),
+            None => {
+                f.write_str("0x")?;
+                self.0.iter().try_for_each(|b| write!(f, "{:02X}", b))
+            }
+        }
+    }
+}
+
+/// Private Key Usage Period extension (section 4.2.1.4 of rfc 3280)
+///
+/// <pre>
+/// PrivateKeyUsagePeriod ::= SEQUENCE {
+///     notBefore       [0]     GeneralizedTime OPTIONAL,
+///     notAfter        [1]     GeneralizedTime OPTIONAL }
+/// </pre>
+///
+/// This extension was removed from rfc 5280, but is still found in some CA certificates.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrivateKeyUsagePeriod {
+    pub not_before: Option<ASN1Time>,
+    pub not_after: Option<ASN1Time>,
+}
+
+impl<'a> FromDer<'a, X509Error> for PrivateKeyUsagePeriod {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parser::parse_privatekeyusageperiod(i).map_err(Err::convert)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PolicyConstraints {
     pub require_explicit_policy: Option<u32>,
@@ -478,11 +1092,8 @@ pub struct SubjectAlternativeName<'a> {
 
 impl<'a> FromDer<'a, X509Error> for SubjectAlternativeName<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
-        parse_der_sequence_defined_g(|input, _| {
-            let (i, general_names) =
-                all_consuming(many0(complete(cut(GeneralName::from_der))))(input)?;
-            Ok((i, SubjectAlternativeName { general_names }))
-        })(i)
+        let (i, general_names) = parse_generalnames(i).map_err(Err::convert)?;
+        Ok((i, SubjectAlternativeName { general_names }))
     }
 }
 
@@ -493,11 +1104,8 @@ pub struct IssuerAlternativeName<'a> {
 
 impl<'a> FromDer<'a, X509Error> for IssuerAlternativeName<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
-        parse_der_sequence_defined_g(|input, _| {
-            let (i, general_names) =
-                all_consuming(many0(complete(cut(GeneralName::from_der))))(input)?;
-            Ok((i, IssuerAlternativeName { general_names }))
-        })(i)
+        let (i, general_names) = parse_generalnames(i).map_err(Err::convert)?;
+        Ok((i, IssuerAlternativeName { general_names }))
     }
 }
 
@@ -514,6 +1122,43 @@ impl<'a> std::ops::Deref for CRLDistributionPoints<'a> {
     }
 }
 
+impl<'a> CRLDistributionPoints<'a> {
+    /// Return the HTTP(S) and LDAP URLs of all distribution points, in order
+    ///
+    /// Only the URIs of full names are returned. Other URI schemes are ignored.
+    pub fn urls(&self) -> Vec<&'a str> {
+        self.points
+            .iter()
+            .flat_map(|point| point.uris())
+            .filter(|uri| {
+                let uri = uri.to_ascii_lowercase();
+                ["http://", "https://", "ldap://", "ldaps://"]
+                    .iter()
+                    .any(|scheme| uri.starts_with(scheme))
+            })
+            .collect()
+    }
+}
+
+/// Human-readable one-line representation of the distribution points, similar to OpenSSL output
+impl<'a> fmt::Display for CRLDistributionPoints<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut items = Vec::new();
+        for point in self.iter() {
+            if let Some(name) = &point.distribution_point {
+                items.push(name.to_string());
+            }
+            if let Some(reasons) = &point.reasons {
+                items.push(format!("Reasons: {}", reasons));
+            }
+            if let Some(issuer) = &point.crl_issuer {
+                items.push(format!("CRL Issuer: {}", general_names_to_string(issuer)));
+            }
+        }
+        f.write_str(&items.join(", "))
+    }
+}
+
 impl<'a> FromDer<'a, X509Error> for CRLDistributionPoints<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
         parser::parse_crldistributionpoints(i).map_err(Err::convert)
@@ -527,12 +1172,105 @@ pub struct CRLDistributionPoint<'a> {
     pub crl_issuer: Option<Vec<GeneralName<'a>>>,
 }
 
+impl<'a> CRLDistributionPoint<'a> {
+    /// Return the URIs of the full name of this distribution point
+    ///
+    /// Returns an empty list if the distribution point name is absent, or is relative to the
+    /// CRL issuer.
+    pub fn uris(&self) -> Vec<&'a str> {
+        match &self.distribution_point {
+            Some(DistributionPointName::FullName(names)) => names
+                .iter()
+                .filter_map(|name| match name {
+                    GeneralName::URI(uri) => Some(*uri),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DistributionPointName<'a> {
     FullName(Vec<GeneralName<'a>>),
     NameRelativeToCRLIssuer(RelativeDistinguishedName<'a>),
 }
 
+impl<'a> fmt::Display for DistributionPointName<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistributionPointName::FullName(names) => f.write_str(&general_names_to_string(names)),
+            DistributionPointName::NameRelativeToCRLIssuer(rdn) => {
+                let s = rdn
+                    .iter()
+                    .map(|attr| format!("{}={}", attr.attr_type(), attr.as_str_lossy()))
+                    .collect::<Vec<_>>()
+                    .join("+");
+                write!(f, "Relative Name: {}", s)
+            }
+        }
+    }
+}
+
+/// The issuing distribution point CRL extension
+///
+/// This extension identifies the scope of a CRL: the distribution point it was published at,
+/// and the subset of certificates and revocation reasons it covers (for partitioned CRLs).
+/// An indirect CRL may contain certificates issued by other authorities than the CRL issuer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuingDistributionPoint<'a> {
+    pub distribution_point: Option<DistributionPointName<'a>>,
+    pub only_contains_user_certs: bool,
+    pub only_contains_ca_certs: bool,
+    pub only_some_reasons: Option<ReasonFlags>,
+    pub indirect_crl: bool,
+    pub only_contains_attribute_certs: bool,
+}
+
+impl<'a> IssuingDistributionPoint<'a> {
+    /// Return `true` if the CRL only covers a subset of certificates or revocation reasons
+    pub fn is_partitioned(&self) -> bool {
+        self.only_contains_user_certs
+            || self.only_contains_ca_certs
+            || self.only_contains_attribute_certs
+            || self.only_some_reasons.is_some()
+    }
+}
+
+/// Human-readable one-line representation of the issuing distribution point, similar to OpenSSL
+/// output
+impl<'a> fmt::Display for IssuingDistributionPoint<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut items = Vec::new();
+        if let Some(name) = &self.distribution_point {
+            items.push(name.to_string());
+        }
+        if self.only_contains_user_certs {
+            items.push("Only User Certificates".to_string());
+        }
+        if self.only_contains_ca_certs {
+            items.push("Only CA Certificates".to_string());
+        }
+        if let Some(reasons) = &self.only_some_reasons {
+            items.push(format!("Only Some Reasons: {}", reasons));
+        }
+        if self.indirect_crl {
+            items.push("Indirect CRL".to_string());
+        }
+        if self.only_contains_attribute_certs {
+            items.push("Only Attribute Certificates".to_string());
+        }
+        f.write_str(&items.join(", "))
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for IssuingDistributionPoint<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parser::parse_issuingdistributionpoint(i).map_err(Err::convert)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ReasonFlags {
     pub flags: u16,
@@ -595,12 +1333,13 @@ impl fmt::Display for ReasonFlags {
 
 pub(crate) mod parser {
     use crate::extensions::*;
-    use crate::time::ASN1Time;
-    use asn1_rs::{GeneralizedTime, ParseResult};
+    use crate::time::{ASN1Time, ASN1TimeEncoding};
+    use crate::x509::decode_directory_string;
+    use asn1_rs::{Any, Class, GeneralizedTime, ParseResult, Tag};
     use der_parser::error::BerError;
     use der_parser::{oid::Oid, *};
     use lazy_static::lazy_static;
-    use nom::combinator::{cut, map};
+    use nom::combinator::map;
     use nom::{Err, IResult};
 
     type ExtParser = fn(&[u8]) -> IResult<&[u8], ParsedExtension, BerError>;
@@ -657,6 +1396,12 @@ pub(crate) mod parser {
                 OID_X509_EXT_CRL_DISTRIBUTION_POINTS,
                 parse_crldistributionpoints_ext
             );
+            add!(m, OID_X509_EXT_FRESHEST_CRL, parse_freshestcrl_ext);
+            add!(
+                m,
+                OID_X509_EXT_PRIVATE_KEY_USAGE_PERIOD,
+                parse_privatekeyusageperiod_ext
+            );
             add!(
                 m,
                 OID_X509_EXT_INHIBITANT_ANY_POLICY,
@@ -667,21 +1412,60 @@ pub(crate) mod parser {
                 OID_PKIX_AUTHORITY_INFO_ACCESS,
                 parse_authorityinfoaccess_ext
             );
+            add!(m, OID_PKIX_SUBJECT_INFO_ACCESS, parse_subjectinfoaccess_ext);
             add!(
                 m,
                 OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER,
                 parse_authoritykeyidentifier_ext
             );
             add!(m, OID_CT_LIST_SCT, parse_sct_ext);
+            add!(m, OID_CT_PRECERT_POISON, parse_ct_poison_ext);
+            add!(m, OID_PKIX_OCSP_NOCHECK, parse_ocsp_nocheck_ext);
+            add!(
+                m,
+                OID_CAN_SIGN_HTTP_EXCHANGES,
+                parse_can_sign_http_exchanges_ext
+            );
             add!(m, OID_X509_EXT_CERT_TYPE, parse_nscerttype_ext);
             add!(m, OID_X509_EXT_CERT_COMMENT, parse_nscomment_ext);
+            add!(m, OID_X509_EXT_BASE_URL, parse_nsbaseurl_ext);
             add!(m, OID_X509_EXT_CRL_NUMBER, parse_crl_number);
+            add!(
+                m,
+                OID_X509_EXT_ISSUER_DISTRIBUTION_POINT,
+                parse_issuingdistributionpoint_ext
+            );
             add!(m, OID_X509_EXT_REASON_CODE, parse_reason_code);
             add!(m, OID_X509_EXT_INVALIDITY_DATE, parse_invalidity_date);
+            add!(m, OID_X509_EXT_ISSUER, parse_certificate_issuer);
+            add!(
+                m,
+                OID_X509_EXT_HOLD_INSTRUCTION_CODE,
+                parse_hold_instruction_code
+            );
+            add!(m, OID_MS_NTDS_CA_SECURITY_EXT, parse_ntdscasecurity_ext);
+            add!(m, OID_MS_ENROLL_CERTTYPE, parse_enroll_certtype_ext);
+            add!(
+                m,
+                OID_MS_CERTIFICATE_TEMPLATE,
+                parse_certificate_template_ext
+            );
+            add!(
+                m,
+                OID_PKCS9_SMIME_CAPABILITIES,
+                parse_smime_capabilities_ext
+            );
+            add!(m, OID_ISISMTT_AT_ADMISSION, parse_admission_ext);
             m
         };
     }
 
+    /// Returns the OIDs of the extensions with a registered parser
+    #[cfg(test)]
+    pub(crate) fn supported_extensions() -> impl Iterator<Item = &'static Oid<'static>> {
+        EXTENSION_PARSERS.keys()
+    }
+
     // look into the parser map if the extension is known, and parse it
     // otherwise, leave it as UnsupportedExtension
     fn parse_extension0<'a>(
@@ -771,25 +1555,21 @@ pub(crate) mod parser {
     pub(super) fn parse_subjectalternativename_ext(
         i: &[u8],
     ) -> IResult<&[u8], ParsedExtension, BerError> {
-        parse_der_sequence_defined_g(|input, _| {
-            let (i, general_names) = all_consuming(many0(complete(cut(parse_generalname))))(input)?;
-            Ok((
-                i,
-                ParsedExtension::SubjectAlternativeName(SubjectAlternativeName { general_names }),
-            ))
-        })(i)
+        let (i, general_names) = parse_generalnames(i)?;
+        Ok((
+            i,
+            ParsedExtension::SubjectAlternativeName(SubjectAlternativeName { general_names }),
+        ))
     }
 
     pub(super) fn parse_issueralternativename_ext(
         i: &[u8],
     ) -> IResult<&[u8], ParsedExtension, BerError> {
-        parse_der_sequence_defined_g(|input, _| {
-            let (i, general_names) = all_consuming(many0(complete(cut(parse_generalname))))(input)?;
-            Ok((
-                i,
-                ParsedExtension::IssuerAlternativeName(IssuerAlternativeName { general_names }),
-            ))
-        })(i)
+        let (i, general_names) = parse_generalnames(i)?;
+        Ok((
+            i,
+            ParsedExtension::IssuerAlternativeName(IssuerAlternativeName { general_names }),
+        ))
     }
 
     pub(super) fn parse_policyconstraints(i: &[u8]) -> IResult<&[u8], PolicyConstraints, BerError> {
@@ -859,8 +1639,8 @@ pub(crate) mod parser {
     // certificateHold         (6),
     // privilegeWithdrawn      (7),
     // aACompromise            (8) }
-    fn parse_tagged1_reasons(i: &[u8]) -> BerResult<ReasonFlags> {
-        let (rem, obj) = parse_der_tagged_implicit(1, parse_der_content(Tag::BitString))(i)?;
+    fn parse_tagged_reasons(i: &[u8], tag: u32) -> BerResult<ReasonFlags> {
+        let (rem, obj) = parse_der_tagged_implicit(tag, parse_der_content(Tag::BitString))(i)?;
         if let DerObjectContent::BitString(_, b) = obj.content {
             let flags = b
                 .data
@@ -889,7 +1669,7 @@ pub(crate) mod parser {
                 opt(complete(parse_der_tagged_explicit_g(0, |b, _| {
                     parse_distributionpointname(b)
                 })))(content)?;
-            let (rem, reasons) = opt(complete(parse_tagged1_reasons))(rem)?;
+            let (rem, reasons) = opt(complete(|i| parse_tagged_reasons(i, 1)))(rem)?;
             let (rem, crl_issuer) = opt(complete(parse_der_tagged_implicit_g(2, |i, _, _| {
                 parse_crlissuer_content(i)
             })))(rem)?;
@@ -916,15 +1696,64 @@ pub(crate) mod parser {
         )(i)
     }
 
+    // FreshestCRL ::= CRLDistributionPoints
+    fn parse_freshestcrl_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_crldistributionpoints, ParsedExtension::FreshestCRL)(i)
+    }
+
+    fn parse_tagged_bool(i: &[u8], tag: u32) -> BerResult<bool> {
+        let (rem, obj) = parse_der_tagged_implicit(tag, parse_der_content(Tag::Boolean))(i)?;
+        let b = obj.as_bool().map_err(Err::Error)?;
+        Ok((rem, b))
+    }
+
+    // IssuingDistributionPoint ::= SEQUENCE {
+    //     distributionPoint          [0] DistributionPointName OPTIONAL,
+    //     onlyContainsUserCerts      [1] BOOLEAN DEFAULT FALSE,
+    //     onlyContainsCACerts        [2] BOOLEAN DEFAULT FALSE,
+    //     onlySomeReasons            [3] ReasonFlags OPTIONAL,
+    //     indirectCRL                [4] BOOLEAN DEFAULT FALSE,
+    //     onlyContainsAttributeCerts [5] BOOLEAN DEFAULT FALSE }
+    pub(super) fn parse_issuingdistributionpoint(
+        i: &[u8],
+    ) -> IResult<&[u8], IssuingDistributionPoint, BerError> {
+        parse_der_sequence_defined_g(|content, _| {
+            let (rem, distribution_point) =
+                opt(complete(parse_der_tagged_explicit_g(0, |b, _| {
+                    parse_distributionpointname(b)
+                })))(content)?;
+            let (rem, only_contains_user_certs) = opt(complete(|i| parse_tagged_bool(i, 1)))(rem)?;
+            let (rem, only_contains_ca_certs) = opt(complete(|i| parse_tagged_bool(i, 2)))(rem)?;
+            let (rem, only_some_reasons) = opt(complete(|i| parse_tagged_reasons(i, 3)))(rem)?;
+            let (rem, indirect_crl) = opt(complete(|i| parse_tagged_bool(i, 4)))(rem)?;
+            let (rem, only_contains_attribute_certs) =
+                opt(complete(|i| parse_tagged_bool(i, 5)))(rem)?;
+            let idp = IssuingDistributionPoint {
+                distribution_point,
+                only_contains_user_certs: only_contains_user_certs.unwrap_or(false),
+                only_contains_ca_certs: only_contains_ca_certs.unwrap_or(false),
+                only_some_reasons,
+                indirect_crl: indirect_crl.unwrap_or(false),
+                only_contains_attribute_certs: only_contains_attribute_certs.unwrap_or(false),
+            };
+            Ok((rem, idp))
+        })(i)
+    }
+
+    fn parse_issuingdistributionpoint_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(
+            parse_issuingdistributionpoint,
+            ParsedExtension::IssuingDistributionPoint,
+        )(i)
+    }
+
     // AuthorityInfoAccessSyntax  ::=
     //         SEQUENCE SIZE (1..MAX) OF AccessDescription
     //
     // AccessDescription  ::=  SEQUENCE {
     //         accessMethod          OBJECT IDENTIFIER,
     //         accessLocation        GeneralName  }
-    pub(super) fn parse_authorityinfoaccess(
-        i: &[u8],
-    ) -> IResult<&[u8], AuthorityInfoAccess, BerError> {
+    fn parse_accessdescriptions(i: &[u8]) -> IResult<&[u8], Vec<AccessDescription>, BerError> {
         fn parse_aia(i: &[u8]) -> IResult<&[u8], AccessDescription, BerError> {
             parse_der_sequence_defined_g(|content, _| {
                 // Read first element, an oid.
@@ -934,10 +1763,27 @@ pub(crate) mod parser {
                 Ok((rest, AccessDescription::new(oid, gn)))
             })(i)
         }
-        let (ret, accessdescs) = parse_der_sequence_of_v(parse_aia)(i)?;
+        parse_der_sequence_of_v(parse_aia)(i)
+    }
+
+    pub(super) fn parse_authorityinfoaccess(
+        i: &[u8],
+    ) -> IResult<&[u8], AuthorityInfoAccess, BerError> {
+        let (ret, accessdescs) = parse_accessdescriptions(i)?;
         Ok((ret, AuthorityInfoAccess { accessdescs }))
     }
 
+    // SubjectInfoAccessSyntax  ::=
+    //         SEQUENCE SIZE (1..MAX) OF AccessDescription
+    pub(super) fn parse_subjectinfoaccess(i: &[u8]) -> IResult<&[u8], SubjectInfoAccess, BerError> {
+        let (ret, accessdescs) = parse_accessdescriptions(i)?;
+        Ok((ret, SubjectInfoAccess { accessdescs }))
+    }
+
+    fn parse_subjectinfoaccess_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_subjectinfoaccess, ParsedExtension::SubjectInfoAccess)(i)
+    }
+
     fn parse_authorityinfoaccess_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
         map(
             parse_authorityinfoaccess,
@@ -1004,11 +1850,12 @@ pub(crate) mod parser {
             .content
             .as_bitstring()
             .or(Err(Err::Error(BerError::BerTypeError)))?;
-        // bitstring should be 1 byte long
-        if bitstring.data.len() != 1 {
-            return Err(Err::Error(BerError::BerValueError));
-        }
-        let flags = bitstring.data[0].reverse_bits();
+        // bitstring should be 1 byte long (or empty, if no flag is set)
+        let flags = match bitstring.data {
+            [] => 0,
+            [b] => b.reverse_bits(),
+            _ => return Err(Err::Error(BerError::BerValueError)),
+        };
         Ok((rest, NSCertType(flags)))
     }
 
@@ -1034,6 +1881,13 @@ pub(crate) mod parser {
         }
     }
 
+    // nsBaseUrl ::= IA5String
+    fn parse_nsbaseurl_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        let (i, obj) = parse_der_ia5string(i)?;
+        let s = obj.as_str()?;
+        Ok((i, ParsedExtension::NsBaseUrl(s)))
+    }
+
     // CertificatePolicies ::= SEQUENCE SIZE (1..MAX) OF PolicyInformation
     //
     // PolicyInformation ::= SEQUENCE {
@@ -1051,6 +1905,71 @@ pub(crate) mod parser {
     // -- augment the following definition for PolicyQualifierId
     //
     // PolicyQualifierId ::= OBJECT IDENTIFIER ( id-qt-cps | id-qt-unotice )
+    // CPSuri ::= IA5String
+    pub(super) fn parse_cps_uri(i: &[u8]) -> BerResult<&str> {
+        let (rem, obj) = parse_der_ia5string(i)?;
+        let uri = obj.as_str()?;
+        Ok((rem, uri))
+    }
+
+    // DisplayText ::= CHOICE { ia5String, visibleString, bmpString, utf8String }
+    fn parse_display_text(i: &[u8]) -> BerResult<String> {
+        let (rem, any) = Any::from_der(i)?;
+        // TeletexString is not allowed by RFC 5280, but is accepted for compatibility
+        let s = match any.tag() {
+            Tag::Ia5String
+            | Tag::VisibleString
+            | Tag::Utf8String
+            | Tag::BmpString
+            | Tag::TeletexString => {
+                decode_directory_string(&any).ok_or(BerError::StringInvalidCharset)?
+            }
+            _ => return Err(Err::Error(BerError::InvalidTag)),
+        };
+        Ok((rem, s))
+    }
+
+    // UserNotice ::= SEQUENCE {
+    //      noticeRef        NoticeReference OPTIONAL,
+    //      explicitText     DisplayText OPTIONAL }
+    //
+    // NoticeReference ::= SEQUENCE {
+    //      organization     DisplayText,
+    //      noticeNumbers    SEQUENCE OF INTEGER }
+    pub(super) fn parse_user_notice(i: &[u8]) -> BerResult<UserNotice> {
+        fn parse_notice_reference(i: &[u8]) -> BerResult<NoticeReference> {
+            parse_der_sequence_defined_g(|content, _| {
+                let (rem, organization) = parse_display_text(content)?;
+                let (rem, notice_numbers) = parse_der_sequence_defined_g(|content, _| {
+                    many0(complete(parse_der_u32))(content)
+                })(rem)?;
+                let notice_ref = NoticeReference {
+                    organization,
+                    notice_numbers,
+                };
+                Ok((rem, notice_ref))
+            })(i)
+        }
+        parse_der_sequence_defined_g(|content, _| {
+            // noticeRef is the only SEQUENCE, so errors in DisplayText are not ignored
+            let (rem, notice_ref) = if content.first() == Some(&0x30) {
+                map(parse_notice_reference, Some)(content)?
+            } else {
+                (content, None)
+            };
+            let (rem, explicit_text) = if rem.is_empty() {
+                (rem, None)
+            } else {
+                map(parse_display_text, Some)(rem)?
+            };
+            let notice = UserNotice {
+                notice_ref,
+                explicit_text,
+            };
+            Ok((rem, notice))
+        })(i)
+    }
+
     pub(super) fn parse_certificatepolicies(
         i: &[u8],
     ) -> IResult<&[u8], Vec<PolicyInformation>, BerError> {
@@ -1088,6 +2007,43 @@ pub(crate) mod parser {
         )(i)
     }
 
+    // PrivateKeyUsagePeriod ::= SEQUENCE {
+    //      notBefore       [0]     GeneralizedTime OPTIONAL,
+    //      notAfter        [1]     GeneralizedTime OPTIONAL }
+    pub(super) fn parse_privatekeyusageperiod(
+        i: &[u8],
+    ) -> IResult<&[u8], PrivateKeyUsagePeriod, BerError> {
+        // [tag] IMPLICIT GeneralizedTime OPTIONAL
+        fn parse_implicit_time(i: &[u8], tag: u32) -> ParseResult<Option<ASN1Time>> {
+            match Any::from_der(i) {
+                Ok((rem, any))
+                    if any.class() == Class::ContextSpecific && any.tag() == Tag(tag) =>
+                {
+                    let dt = GeneralizedTime::from_bytes(any.data)?.utc_datetime()?;
+                    let time = ASN1Time::new(dt).with_encoding(ASN1TimeEncoding::GeneralizedTime);
+                    Ok((rem, Some(time)))
+                }
+                _ => Ok((i, None)),
+            }
+        }
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, not_before) = parse_implicit_time(i, 0)?;
+            let (i, not_after) = parse_implicit_time(i, 1)?;
+            let period = PrivateKeyUsagePeriod {
+                not_before,
+                not_after,
+            };
+            Ok((i, period))
+        })(i)
+    }
+
+    fn parse_privatekeyusageperiod_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(
+            parse_privatekeyusageperiod,
+            ParsedExtension::PrivateKeyUsagePeriod,
+        )(i)
+    }
+
     // CRLReason ::= ENUMERATED { ...
     fn parse_reason_code(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
         let (rest, obj) = parse_der_enum(i)?;
@@ -1106,14 +2062,64 @@ pub(crate) mod parser {
     fn parse_invalidity_date(i: &[u8]) -> ParseResult<ParsedExtension> {
         let (rest, t) = GeneralizedTime::from_der(i)?;
         let dt = t.utc_datetime()?;
-        Ok((rest, ParsedExtension::InvalidityDate(ASN1Time::new(dt))))
+        let date = ASN1Time::new(dt).with_encoding(ASN1TimeEncoding::GeneralizedTime);
+        Ok((rest, ParsedExtension::InvalidityDate(date)))
+    }
+
+    // holdInstructionCode ::= OBJECT IDENTIFIER
+    fn parse_hold_instruction_code(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map_res(parse_der_oid, |obj| {
+            let oid = obj.as_oid()?.clone();
+            Ok::<_, BerError>(ParsedExtension::HoldInstructionCode(
+                HoldInstruction::from_oid(oid),
+            ))
+        })(i)
+    }
+
+    // certificateIssuer ::= GeneralNames
+    fn parse_certificate_issuer(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_generalnames, |general_names| {
+            ParsedExtension::CertificateIssuer(GeneralNames { general_names })
+        })(i)
     }
 
     // CRLNumber ::= INTEGER (0..MAX)
     // Note from RFC 3280: "CRL verifiers MUST be able to handle CRLNumber values up to 20 octets."
+    pub(super) fn parse_crlnumber(i: &[u8]) -> IResult<&[u8], CRLNumber, BerError> {
+        let (rest, obj) = parse_der_integer(i)?;
+        let bytes = obj.as_slice().map_err(Err::Error)?;
+        if matches!(bytes.first(), Some(b) if b & 0x80 != 0) {
+            return Err(Err::Error(BerError::IntegerNegative));
+        }
+        let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+        Ok((rest, CRLNumber(&bytes[start..])))
+    }
+
     fn parse_crl_number(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
-        let (rest, num) = map_res(parse_der_integer, |obj| obj.as_biguint())(i)?;
-        Ok((rest, ParsedExtension::CRLNumber(num)))
+        map(parse_crlnumber, ParsedExtension::CRLNumber)(i)
+    }
+
+    fn parse_ntdscasecurity_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_ntdscasecurity, ParsedExtension::NtdsCaSecurity)(i)
+    }
+
+    fn parse_enroll_certtype_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_enroll_certtype, ParsedExtension::EnrollCertType)(i)
+    }
+
+    fn parse_certificate_template_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(
+            parse_certificate_template,
+            ParsedExtension::CertificateTemplate,
+        )(i)
+    }
+
+    fn parse_smime_capabilities_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_smime_capabilities, ParsedExtension::SMIMECapabilities)(i)
+    }
+
+    fn parse_admission_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_admission_syntax, ParsedExtension::Admission)(i)
     }
 
     fn parse_sct_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
@@ -1122,13 +2128,83 @@ pub(crate) mod parser {
             ParsedExtension::SCT,
         )(i)
     }
+
+    fn parse_ct_poison_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_der_null, |_| ParsedExtension::CtPrecertPoison)(i)
+    }
+
+    // The value must be NULL, but some CAs use an empty value: accept both, like OpenSSL
+    fn parse_ocsp_nocheck_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        if i.is_empty() {
+            return Ok((i, ParsedExtension::OcspNoCheck));
+        }
+        map(parse_der_null, |_| ParsedExtension::OcspNoCheck)(i)
+    }
+
+    fn parse_can_sign_http_exchanges_ext(i: &[u8]) -> IResult<&[u8], ParsedExtension, BerError> {
+        map(parse_der_null, |_| ParsedExtension::CanSignHttpExchanges)(i)
+    }
+}
+
+/// Searches for an extension with the given `Oid`.
+///
+/// Note: if there are several extensions with the same `Oid`, an error `DuplicateExtensions` is returned.
+pub(crate) fn get_extension_unique<'a, 'b>(
+    extensions: &'a [X509Extension<'b>],
+    oid: &Oid,
+) -> Result<Option<&'a X509Extension<'b>>, X509Error> {
+    let mut res = None;
+    for ext in extensions {
+        if ext.oid == *oid {
+            if res.is_some() {
+                return Err(X509Error::DuplicateExtensions);
+            }
+            res = Some(ext);
+        }
+    }
+    Ok(res)
+}
+
+/// Returns the `Oid`s present twice or more, in order of first occurrence
+pub(crate) fn duplicate_extensions<'a, 'b>(
+    extensions: &'a [X509Extension<'b>],
+) -> Vec<&'a Oid<'b>> {
+    let mut dups: Vec<&Oid> = Vec::new();
+    for (idx, ext) in extensions.iter().enumerate() {
+        if !dups.contains(&&ext.oid) && extensions[idx + 1..].iter().any(|e| e.oid == ext.oid) {
+            dups.push(&ext.oid);
+        }
+    }
+    dups
 }
 
+/// Parse a DER-encoded `Extensions` block
+///
+/// <pre>
 /// Extensions  ::=  SEQUENCE SIZE (1..MAX) OF Extension
-pub(crate) fn parse_extension_sequence(i: &[u8]) -> X509Result<Vec<X509Extension>> {
-    parse_der_sequence_defined_g(|a, _| all_consuming(many0(complete(X509Extension::from_der)))(a))(
-        i,
-    )
+/// </pre>
+///
+/// This is the structure found in certificates and CRLs, but also in other protocols
+/// (for ex. OCSP, CMP or EST). Extensions are parsed using the same parsers as for
+/// certificates, and are returned in the order of the encoding.
+///
+/// For compatibility with existing encoders, an empty sequence is accepted.
+///
+/// # Examples
+/// ```
+/// use x509_parser::extensions::parse_extension_sequence;
+///
+/// # let data = &[0x30, 0x11, 0x30, 0x0f, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01, 0xff,
+/// #   0x04, 0x05, 0x30, 0x03, 0x01, 0x01, 0xff];
+/// let (_, extensions) = parse_extension_sequence(data).expect("parsing extensions failed");
+/// for ext in &extensions {
+///     println!("{}: {:?}", ext.oid, ext.parsed_extension());
+/// }
+/// ```
+pub fn parse_extension_sequence(i: &[u8]) -> X509Result<Vec<X509Extension>> {
+    parse_der_sequence_defined_g(|a, _| {
+        all_consuming(many0_lazy(complete(X509Extension::from_der)))(a)
+    })(i)
 }
 
 pub(crate) fn parse_extensions(i: &[u8], explicit_tag: Tag) -> X509Result<Vec<X509Extension>> {
@@ -1151,7 +2227,7 @@ pub(crate) fn parse_extensions(i: &[u8], explicit_tag: Tag) -> X509Result<Vec<X5
 pub(crate) fn parse_extension_envelope_sequence(i: &[u8]) -> X509Result<Vec<X509Extension>> {
     let parser = X509ExtensionParser::new().with_deep_parse_extensions(false);
 
-    parse_der_sequence_defined_g(move |a, _| all_consuming(many0(complete(parser)))(a))(i)
+    parse_der_sequence_defined_g(move |a, _| all_consuming(many0_lazy(complete(parser)))(a))(i)
 }
 
 pub(crate) fn parse_extensions_envelope(
@@ -1187,6 +2263,7 @@ fn der_read_critical(i: &[u8]) -> BerResult<bool> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use der_parser::oid;
 
     #[test]
     fn test_keyusage_flags() {
@@ -1474,10 +2551,243 @@ mod tests {
                         assert_eq!(uri, "http://example.com/myca2.crl")
                     }
                 }
+                assert_eq!(
+                    crl.urls(),
+                    [
+                        "http://example.com/myca.crl",
+                        "http://example.com/myca2.crl"
+                    ]
+                );
             }
         }
     }
 
+    #[test]
+    fn test_aki_issuer_and_serial() {
+        let data = &[
+            0x30, 0x1b, 0x80, 0x02, 0x01, 0x02, 0xa1, 0x11, 0xa4, 0x0f, 0x30, 0x0d, 0x31, 0x0b,
+            0x30, 0x09, 0x06, 0x03, 0x55, 0x04, 0x03, 0x0c, 0x02, 0x43, 0x41, 0x82, 0x02, 0x01,
+            0x00,
+        ];
+        let (rem, aki) = AuthorityKeyIdentifier::from_der(data).expect("parsing AKI failed");
+        assert!(rem.is_empty());
+        assert_eq!(aki.key_identifier, Some(KeyIdentifier(&[1, 2])));
+        let issuer = aki
+            .authority_cert_issuer()
+            .expect("no authority cert issuer");
+        assert_eq!(issuer.len(), 1);
+        assert!(matches!(&issuer[0], GeneralName::DirectoryName(dn) if dn.to_string() == "CN=CA"));
+        #[cfg(feature = "bigint")]
+        assert_eq!(aki.authority_cert_serial_number(), Some(256u32.into()));
+        assert_eq!(aki.authority_cert_serial, Some(&[0x01, 0x00][..]));
+    }
+
+    #[test]
+    fn test_aki_matches_issuer_certificate() {
+        let (_, ca) = crate::parse_x509_certificate(include_bytes!("../../assets/test-ca.der"))
+            .expect("could not parse certificate");
+        let ski = ca.subject_key_identifier().unwrap().unwrap().value.clone();
+        let aki = AuthorityKeyIdentifier {
+            key_identifier: Some(ski),
+            authority_cert_issuer: Some(vec![GeneralName::DirectoryName(ca.issuer().clone())]),
+            authority_cert_serial: Some(ca.raw_serial()),
+        };
+        assert!(aki.matches_issuer_certificate(&ca));
+        // without key identifier
+        let no_keyid = AuthorityKeyIdentifier {
+            key_identifier: None,
+            ..aki.clone()
+        };
+        assert!(no_keyid.matches_issuer_certificate(&ca));
+        // key identifier mismatch
+        let other_keyid = AuthorityKeyIdentifier {
+            key_identifier: Some(KeyIdentifier(&[1, 2, 3])),
+            ..aki.clone()
+        };
+        assert!(!other_keyid.matches_issuer_certificate(&ca));
+        // serial mismatch
+        let other_serial = AuthorityKeyIdentifier {
+            authority_cert_serial: Some(&[0x01, 0x00]),
+            ..aki.clone()
+        };
+        assert!(!other_serial.matches_issuer_certificate(&ca));
+        // issuer mismatch
+        let (_, leaf) = crate::parse_x509_certificate(include_bytes!("../../assets/test-leaf.der"))
+            .expect("could not parse certificate");
+        let other_issuer = AuthorityKeyIdentifier {
+            authority_cert_issuer: Some(vec![GeneralName::DirectoryName(leaf.subject().clone())]),
+            ..aki.clone()
+        };
+        assert!(!other_issuer.matches_issuer_certificate(&ca));
+        let other_issuer = AuthorityKeyIdentifier {
+            authority_cert_issuer: Some(vec![GeneralName::URI("http://ca.example.com")]),
+            ..aki.clone()
+        };
+        assert!(!other_issuer.matches_issuer_certificate(&ca));
+        // missing fields
+        let no_serial = AuthorityKeyIdentifier {
+            authority_cert_serial: None,
+            ..aki
+        };
+        assert!(!no_serial.matches_issuer_certificate(&ca));
+    }
+
+    #[test]
+    fn test_issuing_distribution_point_indirect() {
+        // SEQUENCE { [2] TRUE, [4] TRUE }
+        let data = &[0x30, 0x06, 0x82, 0x01, 0xff, 0x84, 0x01, 0xff];
+        let (rem, idp) = IssuingDistributionPoint::from_der(data).expect("parsing IDP failed");
+        assert!(rem.is_empty());
+        assert!(idp.distribution_point.is_none());
+        assert!(!idp.only_contains_user_certs);
+        assert!(idp.only_contains_ca_certs);
+        assert!(idp.indirect_crl);
+        assert!(idp.only_some_reasons.is_none());
+        assert_eq!(idp.to_string(), "Only CA Certificates, Indirect CRL");
+        // empty sequence: all defaults
+        let (_, idp) = IssuingDistributionPoint::from_der(&[0x30, 0x00]).unwrap();
+        assert!(!idp.is_partitioned() && !idp.indirect_crl);
+    }
+
+    #[test]
+    fn test_user_notice_bmp_explicit_text() {
+        // SEQUENCE { BMPString "Hi" }
+        let qualifier = PolicyQualifierInfo {
+            policy_qualifier_id: OID_POLICY_QUALIFIER_UNOTICE,
+            qualifier: &[0x30, 0x06, 0x1e, 0x04, 0x00, 0x48, 0x00, 0x69],
+        };
+        let notice = UserNotice {
+            notice_ref: None,
+            explicit_text: Some("Hi".to_string()),
+        };
+        assert_eq!(
+            qualifier.parsed_qualifier(),
+            Ok(PolicyQualifier::UserNotice(notice))
+        );
+        // odd length is not valid UTF-16
+        let qualifier = PolicyQualifierInfo {
+            qualifier: &[0x30, 0x05, 0x1e, 0x03, 0x00, 0x48, 0x00],
+            ..qualifier
+        };
+        assert_eq!(
+            qualifier.parsed_qualifier(),
+            Err(X509Error::InvalidExtensions)
+        );
+        // TeletexString is decoded as Latin-1, as in names
+        let qualifier = PolicyQualifierInfo {
+            qualifier: &[0x30, 0x05, 0x14, 0x03, 0x48, 0x69, 0xe9],
+            ..qualifier
+        };
+        let notice = UserNotice {
+            notice_ref: None,
+            explicit_text: Some("Hi\u{e9}".to_string()),
+        };
+        assert_eq!(
+            qualifier.parsed_qualifier(),
+            Ok(PolicyQualifier::UserNotice(notice))
+        );
+    }
+
+    #[test]
+    fn test_ocsp_nocheck_empty_value() {
+        // SEQUENCE { OID id-pkix-ocsp-nocheck, OCTET STRING {} }
+        let data = &[
+            0x30, 0x0d, 0x06, 0x09, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x05, 0x04,
+            0x00,
+        ];
+        let (_, ext) = X509Extension::from_der(data).expect("parsing extension failed");
+        assert_eq!(ext.parsed_extension(), &ParsedExtension::OcspNoCheck);
+    }
+
+    #[test]
+    fn test_extension_debug_dump() {
+        // SEQUENCE { OID 1.2.3, UTF8String "ab" }, NULL
+        let value = &[
+            0x30, 0x08, 0x06, 0x02, 0x2a, 0x03, 0x0c, 0x02, 0x61, 0x62, 0x05, 0x00,
+        ];
+        let ext = X509Extension::new(
+            oid!(1.2.3 .4),
+            false,
+            value,
+            ParsedExtension::UnsupportedExtension {
+                oid: oid!(1.2.3 .4),
+            },
+        );
+        let dump = ext.debug_dump().expect("could not dump extension");
+        assert!(dump.starts_with("Sequence["));
+        assert!(dump.contains("OID(1.2.3)"));
+        assert!(dump.contains("UTF8String(\"ab\")"));
+        assert!(dump.ends_with("Null\n"));
+        let ext = X509Extension::new(
+            oid!(1.2.3 .4),
+            false,
+            &[0x30, 0x05],
+            ParsedExtension::Unparsed,
+        );
+        assert!(ext.debug_dump().is_err());
+    }
+
+    #[test]
+    fn test_empty_extensions_no_alloc() {
+        // [3] { SEQUENCE {} }
+        let (rem, exts) = parse_extensions(&[0xa3, 0x02, 0x30, 0x00], Tag(3)).unwrap();
+        assert!(rem.is_empty());
+        assert!(exts.is_empty());
+        assert_eq!(exts.capacity(), 0);
+        let (_, exts) = parse_extensions(&[], Tag(3)).unwrap();
+        assert_eq!(exts.capacity(), 0);
+    }
+
+    #[test]
+    fn test_extension_sequence() {
+        // SEQUENCE { basicConstraints (critical, CA), keyUsage }
+        let data = &[
+            0x30, 0x1e, 0x30, 0x0f, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01, 0xff, 0x04, 0x05,
+            0x30, 0x03, 0x01, 0x01, 0xff, 0x30, 0x0b, 0x06, 0x03, 0x55, 0x1d, 0x0f, 0x04, 0x04,
+            0x03, 0x02, 0x01, 0x06,
+        ];
+        let (rem, exts) = parse_extension_sequence(data).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(exts.len(), 2);
+        assert_eq!(exts[0].oid, OID_X509_EXT_BASIC_CONSTRAINTS);
+        assert!(exts[0].critical);
+        assert!(matches!(
+            exts[0].parsed_extension(),
+            ParsedExtension::BasicConstraints(BasicConstraints { ca: true, .. })
+        ));
+        assert_eq!(exts[1].oid, OID_X509_EXT_KEY_USAGE);
+        assert!(matches!(
+            exts[1].parsed_extension(),
+            ParsedExtension::KeyUsage(_)
+        ));
+        // trailing garbage inside the sequence
+        assert!(parse_extension_sequence(&[0x30, 0x02, 0x05, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_crl_number() {
+        let (_, num) = CRLNumber::from_der(&[0x02, 0x01, 0x00]).unwrap();
+        assert_eq!(num.as_bytes(), &[] as &[u8]);
+        assert_eq!(num.as_u128(), Some(0));
+        assert_eq!(num.to_string(), "0");
+        let (_, num) = CRLNumber::from_der(&[0x02, 0x02, 0x00, 0xff]).unwrap();
+        assert_eq!(num.as_bytes(), &[0xff]);
+        assert_eq!(num.to_string(), "255");
+        // 2^128, larger than u128
+        let mut der = vec![0x02, 0x11, 0x01];
+        der.extend_from_slice(&[0; 16]);
+        let (_, num) = CRLNumber::from_der(&der).unwrap();
+        assert_eq!(num.as_u128(), None);
+        assert_eq!(num.to_string(), "0x0100000000000000000000000000000000");
+        #[cfg(feature = "bigint")]
+        assert_eq!(
+            num.as_biguint().to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        // negative
+        assert!(CRLNumber::from_der(&[0x02, 0x01, 0xff]).is_err());
+    }
+
     // Test cases for:
     // - parsing SubjectAlternativeName
     // - parsing NameConstraints
@@ -1,12 +1,15 @@
 use super::GeneralName;
 use crate::error::{X509Error, X509Result};
 use crate::extensions::parse_generalname;
+use crate::x509::X509Name;
 use asn1_rs::FromDer;
 use der_parser::der::*;
 use der_parser::error::BerError;
 use nom::combinator::{all_consuming, complete, map, opt};
 use nom::multi::many1;
 use nom::{Err, IResult};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct NameConstraints<'a> {
@@ -14,6 +17,35 @@ pub struct NameConstraints<'a> {
     pub excluded_subtrees: Option<Vec<GeneralSubtree<'a>>>,
 }
 
+impl<'a> NameConstraints<'a> {
+    /// Test if the directory name `name` is allowed by the `directoryName` constraints
+    ///
+    /// The name is rejected if it is inside an excluded subtree, or if there are permitted
+    /// `directoryName` subtrees and the name is not inside any of them (RFC5280 section
+    /// 4.2.1.10). Subtrees of other name forms are ignored.
+    ///
+    /// Note that constraints apply to the subject of a certificate only if it is not empty.
+    pub fn permits_directory_name(&self, name: &X509Name) -> bool {
+        let contains = |subtrees: &Option<Vec<GeneralSubtree>>| {
+            subtrees.as_ref().map(|subtrees| {
+                subtrees
+                    .iter()
+                    .filter_map(|subtree| subtree.contains_directory_name(name))
+                    .collect::<Vec<_>>()
+            })
+        };
+        if let Some(excluded) = contains(&self.excluded_subtrees) {
+            if excluded.iter().any(|&b| b) {
+                return false;
+            }
+        }
+        match contains(&self.permitted_subtrees) {
+            Some(permitted) if !permitted.is_empty() => permitted.iter().any(|&b| b),
+            _ => true,
+        }
+    }
+}
+
 impl<'a> FromDer<'a, X509Error> for NameConstraints<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
         parse_nameconstraints(i).map_err(Err::convert)
@@ -29,6 +61,116 @@ pub struct GeneralSubtree<'a> {
     // maximum: Option<u32>,
 }
 
+impl<'a> GeneralSubtree<'a> {
+    /// Test if the directory name `name` is inside this subtree
+    ///
+    /// Returns `None` if the base is not a `directoryName`. The name is inside the subtree if it
+    /// starts with the RDNs of the base (see [`X509Name::starts_with`]).
+    pub fn contains_directory_name(&self, name: &X509Name) -> Option<bool> {
+        match &self.base {
+            GeneralName::DirectoryName(base) => Some(name.starts_with(base)),
+            _ => None,
+        }
+    }
+
+    /// Return the IP address range, if the base is an `iPAddress` name
+    ///
+    /// Returns an error if the address or the mask is invalid.
+    pub fn ip_subtree(&self) -> Result<Option<IpSubtree>, X509Error> {
+        match self.base {
+            GeneralName::IPAddress(b) => IpSubtree::from_bytes(b).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// An IP address range, from the `iPAddress` form of a name constraint
+///
+/// In name constraints, the `iPAddress` name contains an address followed by a mask (8 bytes for
+/// IPv4, 32 bytes for IPv6, see RFC5280 section 4.2.1.10). The mask is represented as a prefix
+/// length, and must be contiguous.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IpSubtree {
+    /// The base address, as encoded (bits outside the mask are not cleared)
+    pub address: IpAddr,
+    /// The number of leading bits of the mask
+    pub prefix_len: u8,
+}
+
+impl IpSubtree {
+    /// Build an IP address range from the encoded address and mask
+    pub fn from_bytes(b: &[u8]) -> Result<Self, X509Error> {
+        let (address, mask) = match b.len() {
+            8 => {
+                let mut addr = [0u8; 4];
+                addr.copy_from_slice(&b[..4]);
+                (IpAddr::V4(Ipv4Addr::from(addr)), &b[4..])
+            }
+            32 => {
+                let mut addr = [0u8; 16];
+                addr.copy_from_slice(&b[..16]);
+                (IpAddr::V6(Ipv6Addr::from(addr)), &b[16..])
+            }
+            _ => return Err(X509Error::InvalidExtensions),
+        };
+        let prefix_len = mask.iter().map(|b| b.leading_ones()).sum::<u32>();
+        // the mask must be all ones, followed by all zeroes
+        if mask_bytes(prefix_len, mask.len()) != mask {
+            return Err(X509Error::InvalidExtensions);
+        }
+        Ok(IpSubtree {
+            address,
+            prefix_len: prefix_len as u8,
+        })
+    }
+
+    /// Test if `addr` is inside the range
+    ///
+    /// Addresses from a different family never match.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        match (self.address, addr) {
+            (IpAddr::V4(base), IpAddr::V4(addr)) => self.matches(&base.octets(), &addr.octets()),
+            (IpAddr::V6(base), IpAddr::V6(addr)) => self.matches(&base.octets(), &addr.octets()),
+            _ => false,
+        }
+    }
+
+    /// Test if the encoded address `b` (for ex. from a `GeneralName::IPAddress` in the subject
+    /// alternative name) is inside the range
+    ///
+    /// Returns `false` if `b` is not a 4 or 16 bytes address.
+    pub fn contains_bytes(&self, b: &[u8]) -> bool {
+        match self.address {
+            IpAddr::V4(base) => b.len() == 4 && self.matches(&base.octets(), b),
+            IpAddr::V6(base) => b.len() == 16 && self.matches(&base.octets(), b),
+        }
+    }
+
+    fn matches(&self, base: &[u8], addr: &[u8]) -> bool {
+        let mask = mask_bytes(u32::from(self.prefix_len), base.len());
+        base.iter()
+            .zip(addr)
+            .zip(mask.iter())
+            .all(|((b, a), m)| b & m == a & m)
+    }
+}
+
+impl fmt::Display for IpSubtree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+// Build a mask of `len` bytes, with the `prefix_len` leading bits set
+fn mask_bytes(prefix_len: u32, len: usize) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| {
+            let bits = prefix_len.saturating_sub(8 * i).min(8);
+            (0xff_u16 << (8 - bits)) as u8
+        })
+        .collect()
+}
+
 pub(crate) fn parse_nameconstraints(i: &[u8]) -> IResult<&[u8], NameConstraints, BerError> {
     fn parse_subtree(i: &[u8]) -> IResult<&[u8], GeneralSubtree, BerError> {
         parse_der_sequence_defined_g(|input, _| {
@@ -57,3 +199,204 @@ pub(crate) fn parse_nameconstraints(i: &[u8]) -> IResult<&[u8], NameConstraints,
 
     Ok((ret, named_constraints))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::x509::{AttributeTypeAndValue, RelativeDistinguishedName};
+    use asn1_rs::{Any, Tag};
+    use oid_registry::{
+        OID_X509_COMMON_NAME, OID_X509_COUNTRY_NAME, OID_X509_ORGANIZATIONAL_UNIT,
+        OID_X509_ORGANIZATION_NAME,
+    };
+
+    // build a name from RDNs, each RDN being a list of (attribute, tag, value)
+    fn name<'a>(rdns: &[&[(&asn1_rs::Oid<'static>, Tag, &'a str)]]) -> X509Name<'a> {
+        rdns.iter()
+            .map(|rdn| {
+                rdn.iter()
+                    .map(|(oid, tag, value)| {
+                        let any = Any::from_tag_and_data(*tag, value.as_bytes());
+                        AttributeTypeAndValue::new((*oid).clone(), any)
+                    })
+                    .collect::<RelativeDistinguishedName>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_directory_name_subtree() {
+        let c = &OID_X509_COUNTRY_NAME;
+        let o = &OID_X509_ORGANIZATION_NAME;
+        let cn = &OID_X509_COMMON_NAME;
+        let base = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(o, Tag::Utf8String, "Example")],
+        ]);
+        let subtree = GeneralSubtree {
+            base: GeneralName::DirectoryName(base),
+        };
+        // same RDNs, with different string types, case and whitespace
+        let subject = name(&[
+            &[(c, Tag::PrintableString, "fr")],
+            &[(o, Tag::PrintableString, "  EXAMPLE ")],
+            &[(cn, Tag::Utf8String, "leaf")],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(true));
+        // the base itself is inside the subtree
+        let subject = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(o, Tag::Utf8String, "Example")],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(true));
+        // matching must start at the first RDN
+        let subject = name(&[
+            &[(o, Tag::Utf8String, "Example")],
+            &[(cn, Tag::Utf8String, "leaf")],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(false));
+        // shorter than the base
+        let subject = name(&[&[(c, Tag::PrintableString, "FR")]]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(false));
+        // other name form
+        let subtree = GeneralSubtree {
+            base: GeneralName::DNSName("example.com"),
+        };
+        assert_eq!(subtree.contains_directory_name(&subject), None);
+    }
+
+    #[test]
+    fn test_directory_name_multi_valued_rdn() {
+        let c = &OID_X509_COUNTRY_NAME;
+        let o = &OID_X509_ORGANIZATION_NAME;
+        let ou = &OID_X509_ORGANIZATIONAL_UNIT;
+        let base = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(o, Tag::Utf8String, "Example"), (ou, Tag::Utf8String, "IT")],
+        ]);
+        let subtree = GeneralSubtree {
+            base: GeneralName::DirectoryName(base),
+        };
+        // order of attributes in a RDN is not significant
+        let subject = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(ou, Tag::Utf8String, "it"), (o, Tag::Utf8String, "Example")],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(true));
+        // the RDN must contain all the attributes, and only them
+        let subject = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(o, Tag::Utf8String, "Example")],
+            &[(ou, Tag::Utf8String, "IT")],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(false));
+        let subject = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[
+                (o, Tag::Utf8String, "Example"),
+                (ou, Tag::Utf8String, "IT"),
+                (ou, Tag::Utf8String, "Ops"),
+            ],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(false));
+        // same attribute types with different values
+        let subject = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(o, Tag::Utf8String, "Example"), (ou, Tag::Utf8String, "HR")],
+        ]);
+        assert_eq!(subtree.contains_directory_name(&subject), Some(false));
+    }
+
+    #[test]
+    fn test_permits_directory_name() {
+        let c = &OID_X509_COUNTRY_NAME;
+        let o = &OID_X509_ORGANIZATION_NAME;
+        let cn = &OID_X509_COMMON_NAME;
+        let subtree = |n: X509Name<'static>| GeneralSubtree {
+            base: GeneralName::DirectoryName(n),
+        };
+        let constraints = NameConstraints {
+            permitted_subtrees: Some(vec![
+                subtree(name(&[&[(c, Tag::PrintableString, "FR")]])),
+                GeneralSubtree {
+                    base: GeneralName::DNSName("example.com"),
+                },
+            ]),
+            excluded_subtrees: Some(vec![subtree(name(&[
+                &[(c, Tag::PrintableString, "FR")],
+                &[(o, Tag::Utf8String, "Excluded")],
+            ]))]),
+        };
+        let allowed = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(cn, Tag::Utf8String, "a")],
+        ]);
+        assert!(constraints.permits_directory_name(&allowed));
+        let excluded = name(&[
+            &[(c, Tag::PrintableString, "FR")],
+            &[(o, Tag::Utf8String, "excluded")],
+            &[(cn, Tag::Utf8String, "a")],
+        ]);
+        assert!(!constraints.permits_directory_name(&excluded));
+        let outside = name(&[&[(c, Tag::PrintableString, "DE")]]);
+        assert!(!constraints.permits_directory_name(&outside));
+        // no directoryName constraints
+        let constraints = NameConstraints {
+            permitted_subtrees: Some(vec![GeneralSubtree {
+                base: GeneralName::DNSName("example.com"),
+            }]),
+            excluded_subtrees: None,
+        };
+        assert!(constraints.permits_directory_name(&outside));
+        // an empty base permits all names
+        let constraints = NameConstraints {
+            permitted_subtrees: Some(vec![subtree(name(&[]))]),
+            excluded_subtrees: None,
+        };
+        assert!(constraints.permits_directory_name(&outside));
+    }
+
+    #[test]
+    fn test_ip_subtree_v4() {
+        let subtree = IpSubtree::from_bytes(&[192, 168, 0, 0, 255, 255, 0, 0]).unwrap();
+        assert_eq!(subtree.prefix_len, 16);
+        assert_eq!(subtree.to_string(), "192.168.0.0/16");
+        assert!(subtree.contains(&"192.168.10.1".parse().unwrap()));
+        assert!(!subtree.contains(&"192.169.0.1".parse().unwrap()));
+        assert!(!subtree.contains(&"::1".parse().unwrap()));
+        assert!(subtree.contains_bytes(&[192, 168, 255, 255]));
+        assert!(!subtree.contains_bytes(&[10, 0, 0, 1]));
+        // single address and whole address space
+        let subtree = IpSubtree::from_bytes(&[10, 0, 0, 1, 255, 255, 255, 255]).unwrap();
+        assert_eq!(subtree.prefix_len, 32);
+        assert!(subtree.contains_bytes(&[10, 0, 0, 1]));
+        assert!(!subtree.contains_bytes(&[10, 0, 0, 2]));
+        let subtree = IpSubtree::from_bytes(&[0; 8]).unwrap();
+        assert_eq!(subtree.prefix_len, 0);
+        assert!(subtree.contains_bytes(&[10, 0, 0, 2]));
+    }
+
+    #[test]
+    fn test_ip_subtree_v6() {
+        let mut b = [0u8; 32];
+        b[..4].copy_from_slice(&[0x20, 0x01, 0x0d, 0xb8]);
+        b[16..20].copy_from_slice(&[0xff, 0xff, 0xff, 0xfe]);
+        let subtree = IpSubtree::from_bytes(&b).unwrap();
+        assert_eq!(subtree.to_string(), "2001:db8::/31");
+        assert!(subtree.contains(&"2001:db9::1".parse().unwrap()));
+        assert!(!subtree.contains(&"2001:dba::1".parse().unwrap()));
+        assert!(!subtree.contains(&"32.1.13.184".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_ip_subtree_invalid() {
+        // non-contiguous mask
+        let res = IpSubtree::from_bytes(&[10, 0, 0, 0, 255, 0, 255, 0]);
+        assert_eq!(res, Err(X509Error::InvalidExtensions));
+        let res = IpSubtree::from_bytes(&[10, 0, 0, 0, 0xf0, 0x0f, 0, 0]);
+        assert_eq!(res, Err(X509Error::InvalidExtensions));
+        // address without mask
+        let res = IpSubtree::from_bytes(&[10, 0, 0, 1]);
+        assert_eq!(res, Err(X509Error::InvalidExtensions));
+    }
+}
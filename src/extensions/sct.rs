@@ -4,7 +4,7 @@
 
 use std::convert::TryInto;
 
-use asn1_rs::FromDer;
+use asn1_rs::{oid, FromDer, Oid};
 use der_parser::error::BerError;
 use nom::bytes::streaming::take;
 use nom::combinator::{complete, map_parser};
@@ -12,6 +12,21 @@ use nom::multi::{length_data, many1};
 use nom::number::streaming::{be_u16, be_u64, be_u8};
 use nom::IResult;
 
+/// Precertificate poison extension
+///
+/// This critical extension marks a precertificate, submitted to Certificate Transparency logs
+/// before issuing the final certificate
+/// ([RFC6962 Section 3.1](https://datatracker.ietf.org/doc/html/rfc6962#section-3.1)). Its value
+/// is an ASN.1 `NULL`.
+pub const OID_CT_PRECERT_POISON: Oid<'static> = oid!(1.3.6 .1 .4 .1 .11129 .2 .4 .3);
+
+/// Precertificate Signing Certificate extended key usage
+///
+/// This key purpose marks a CA certificate dedicated to signing precertificates on behalf of
+/// the CA which issues the final certificates
+/// ([RFC6962 Section 3.1](https://datatracker.ietf.org/doc/html/rfc6962#section-3.1)).
+pub const OID_CT_PRECERT_SIGNING_CERT: Oid<'static> = oid!(1.3.6 .1 .4 .1 .11129 .2 .4 .4);
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SignedCertificateTimestamp<'a> {
     pub version: CtVersion,
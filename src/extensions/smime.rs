@@ -0,0 +1,74 @@
+//! S/MIME capabilities
+
+use crate::error::{X509Error, X509Result};
+use crate::objects::{oid2sn, oid_registry};
+use asn1_rs::{Any, Error, FromDer, Oid, Sequence};
+use nom::combinator::{complete, opt};
+use nom::multi::many0;
+use nom::{Err, IResult};
+
+/// S/MIME capabilities, the algorithms supported by the sender, in order of preference
+///
+/// This is used by mail clients to choose the encryption algorithm of messages sent to the
+/// subject. It can appear as a certificate extension, or as a certification request attribute.
+///
+/// <pre>
+/// SMIMECapabilities ::= SEQUENCE OF SMIMECapability
+///
+/// SMIMECapability ::= SEQUENCE {
+///     capabilityID OBJECT IDENTIFIER,
+///     parameters ANY DEFINED BY capabilityID OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SMIMECapabilities<'a> {
+    pub capabilities: Vec<SMIMECapability<'a>>,
+}
+
+impl<'a> SMIMECapabilities<'a> {
+    /// Return an iterator over the capabilities
+    pub fn iter(&self) -> impl Iterator<Item = &SMIMECapability<'a>> {
+        self.capabilities.iter()
+    }
+
+    /// Test if the capability with the given `Oid` is present
+    pub fn contains(&self, oid: &Oid) -> bool {
+        self.capabilities.iter().any(|c| c.capability_id == *oid)
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for SMIMECapabilities<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<'a, Self> {
+        parse_smime_capabilities(i).map_err(Err::convert)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SMIMECapability<'a> {
+    pub capability_id: Oid<'a>,
+    /// Parameters of the algorithm (for ex. the key length of RC2)
+    pub parameters: Option<Any<'a>>,
+}
+
+impl<'a> SMIMECapability<'a> {
+    /// Return the short name of the algorithm (for ex. `aes-256-cbc`), if known by the registry
+    pub fn name(&self) -> Option<&str> {
+        oid2sn(&self.capability_id, oid_registry()).ok()
+    }
+}
+
+pub(crate) fn parse_smime_capabilities(i: &[u8]) -> IResult<&[u8], SMIMECapabilities, Error> {
+    let (rem, capabilities) = Sequence::from_der_and_then(i, |i| {
+        many0(complete(|i| {
+            Sequence::from_der_and_then(i, |i| {
+                let (i, capability_id) = Oid::from_der(i)?;
+                let (i, parameters) = opt(complete(Any::from_der))(i)?;
+                let capability = SMIMECapability {
+                    capability_id,
+                    parameters,
+                };
+                Ok((i, capability))
+            })
+        }))(i)
+    })?;
+    Ok((rem, SMIMECapabilities { capabilities }))
+}
@@ -0,0 +1,336 @@
+//! A small, stable high-level interface
+//!
+//! The types of this module own their DER encoding, and only expose owned values (strings,
+//! times) and the main fields of the objects. They are meant as a stable entry point for
+//! applications, which do not need to depend on the lower-level (zero-copy) types of this crate.
+//! The lower-level objects are still available, using the `parsed` methods.
+//!
+//! Inputs are read from DER or PEM. Only the first object is read: trailing data (or the
+//! following PEM blocks) are ignored.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::facade::{Certificate, Crl};
+//!
+//! # fn main() -> Result<(), x509_parser::error::PEMError> {
+//! let cert = Certificate::parse_pem(std::fs::read("assets/certificate.pem")?)?;
+//! println!("subject: {}", cert.subject());
+//! println!("valid until: {}", cert.not_after());
+//!
+//! let crl = Crl::parse_der(std::fs::read("assets/example.crl")?)?;
+//! assert!(!crl.is_revoked(&cert));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::certificate::{X509Certificate, X509CertificateOwned};
+use crate::certification_request::{X509CertificationRequest, X509CertificationRequestOwned};
+use crate::error::{PEMError, X509Error};
+use crate::extensions::GeneralName;
+use crate::pem::parse_x509_pem;
+use crate::revocation_list::{CertificateRevocationList, CertificateRevocationListOwned};
+use crate::time::ASN1Time;
+use std::convert::TryFrom;
+
+/// An X.509 certificate
+///
+/// The certificate is parsed once, when the object is built: the accessors return values
+/// computed at that time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Certificate {
+    owned: X509CertificateOwned,
+    subject: String,
+    issuer: String,
+    serial: String,
+    not_before: ASN1Time,
+    not_after: ASN1Time,
+    is_ca: bool,
+    dns_names: Vec<String>,
+}
+
+impl Certificate {
+    /// Parse a DER-encoded certificate
+    pub fn parse_der<B: AsRef<[u8]>>(der: B) -> Result<Self, X509Error> {
+        Self::try_from(X509CertificateOwned::new(der.as_ref().to_vec())?)
+    }
+
+    /// Parse a PEM-encoded certificate (`CERTIFICATE` label)
+    pub fn parse_pem<B: AsRef<[u8]>>(pem: B) -> Result<Self, PEMError> {
+        let der = read_pem(pem.as_ref(), &["CERTIFICATE"])?;
+        Ok(Self::parse_der(der)?)
+    }
+
+    /// Return the DER encoding of the certificate
+    pub fn as_der(&self) -> &[u8] {
+        self.owned.as_raw()
+    }
+
+    /// Return the parsed certificate, to access all fields
+    ///
+    /// The certificate is parsed again on each call.
+    pub fn parsed(&self) -> X509Certificate<'_> {
+        // the encoding was checked when building the object
+        self.owned
+            .certificate()
+            .expect("stored certificate is valid")
+    }
+
+    /// Return the subject name, as a human-readable string (for ex. `C=FR, CN=example.com`)
+    pub fn subject(&self) -> String {
+        self.subject.clone()
+    }
+
+    /// Return the issuer name, as a human-readable string (for ex. `C=FR, CN=example.com`)
+    pub fn issuer(&self) -> String {
+        self.issuer.clone()
+    }
+
+    /// Return the serial number, as colon-separated hex bytes
+    pub fn serial(&self) -> String {
+        self.serial.clone()
+    }
+
+    /// Return the start of the validity period
+    pub fn not_before(&self) -> ASN1Time {
+        self.not_before
+    }
+
+    /// Return the end of the validity period
+    pub fn not_after(&self) -> ASN1Time {
+        self.not_after
+    }
+
+    /// Returns true if the certificate is a CA certificate (`basicConstraints CA:true`)
+    pub fn is_ca(&self) -> bool {
+        self.is_ca
+    }
+
+    /// Return the DNS names of the subject alternative name extension
+    pub fn dns_names(&self) -> Vec<String> {
+        self.dns_names.clone()
+    }
+}
+
+impl TryFrom<X509CertificateOwned> for Certificate {
+    type Error = X509Error;
+
+    fn try_from(owned: X509CertificateOwned) -> Result<Self, X509Error> {
+        let cert = owned.certificate()?;
+        let dns_names = match cert.subject_alternative_name() {
+            Ok(Some(san)) => san
+                .value
+                .general_names
+                .iter()
+                .filter_map(|name| match name {
+                    GeneralName::DNSName(s) => Some(s.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let validity = cert.validity();
+        let (subject, issuer, serial) = (
+            cert.subject().to_string(),
+            cert.issuer().to_string(),
+            cert.raw_serial_as_string(),
+        );
+        let (not_before, not_after, is_ca) =
+            (validity.not_before, validity.not_after, cert.is_ca());
+        Ok(Certificate {
+            owned,
+            subject,
+            issuer,
+            serial,
+            not_before,
+            not_after,
+            is_ca,
+            dns_names,
+        })
+    }
+}
+
+impl From<Certificate> for X509CertificateOwned {
+    fn from(cert: Certificate) -> Self {
+        cert.owned
+    }
+}
+
+/// A certificate revocation list
+///
+/// The CRL is parsed once, when the object is built: the accessors return values computed at
+/// that time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Crl {
+    owned: CertificateRevocationListOwned,
+    issuer: String,
+    this_update: ASN1Time,
+    next_update: Option<ASN1Time>,
+    revoked_serials: Vec<String>,
+}
+
+impl Crl {
+    /// Parse a DER-encoded CRL
+    pub fn parse_der<B: AsRef<[u8]>>(der: B) -> Result<Self, X509Error> {
+        Self::try_from(CertificateRevocationListOwned::new(der.as_ref().to_vec())?)
+    }
+
+    /// Parse a PEM-encoded CRL (`X509 CRL` label)
+    pub fn parse_pem<B: AsRef<[u8]>>(pem: B) -> Result<Self, PEMError> {
+        let der = read_pem(pem.as_ref(), &["X509 CRL"])?;
+        Ok(Self::parse_der(der)?)
+    }
+
+    /// Return the DER encoding of the CRL
+    pub fn as_der(&self) -> &[u8] {
+        self.owned.as_raw()
+    }
+
+    /// Return the parsed CRL, to access all fields
+    ///
+    /// The CRL is parsed again on each call.
+    pub fn parsed(&self) -> CertificateRevocationList<'_> {
+        // the encoding was checked when building the object
+        self.owned.crl().expect("stored CRL is valid")
+    }
+
+    /// Return the issuer name, as a human-readable string (for ex. `C=FR, CN=example.com`)
+    pub fn issuer(&self) -> String {
+        self.issuer.clone()
+    }
+
+    /// Return the issue date of the CRL
+    pub fn this_update(&self) -> ASN1Time {
+        self.this_update
+    }
+
+    /// Return the date of the next CRL, if present
+    pub fn next_update(&self) -> Option<ASN1Time> {
+        self.next_update
+    }
+
+    /// Return the serial numbers of the revoked certificates, as colon-separated hex bytes
+    pub fn revoked_serials(&self) -> Vec<String> {
+        self.revoked_serials.clone()
+    }
+
+    /// Returns true if the certificate is listed in this CRL
+    ///
+    /// Both the serial number and the issuer of the certificate are compared (see
+    /// [`CertificateRevocationList::find_revoked_for_issuer`]), so both objects are parsed
+    /// again. The signature of the CRL is not verified.
+    pub fn is_revoked(&self, cert: &Certificate) -> bool {
+        let cert = cert.parsed();
+        self.parsed()
+            .find_revoked_for_issuer(cert.raw_serial(), cert.issuer())
+            .is_some()
+    }
+}
+
+impl TryFrom<CertificateRevocationListOwned> for Crl {
+    type Error = X509Error;
+
+    fn try_from(owned: CertificateRevocationListOwned) -> Result<Self, X509Error> {
+        let crl = owned.crl()?;
+        let (issuer, this_update, next_update) = (
+            crl.issuer().to_string(),
+            crl.last_update(),
+            crl.next_update(),
+        );
+        let revoked_serials = crl
+            .iter_revoked_certificates()
+            .map(|revoked| revoked.raw_serial_as_string())
+            .collect();
+        Ok(Crl {
+            owned,
+            issuer,
+            this_update,
+            next_update,
+            revoked_serials,
+        })
+    }
+}
+
+impl From<Crl> for CertificateRevocationListOwned {
+    fn from(crl: Crl) -> Self {
+        crl.owned
+    }
+}
+
+/// A certification request (PKCS#10)
+///
+/// The request is parsed once, when the object is built: the accessors return values computed
+/// at that time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Csr {
+    owned: X509CertificationRequestOwned,
+    subject: String,
+}
+
+impl Csr {
+    /// Parse a DER-encoded certification request
+    pub fn parse_der<B: AsRef<[u8]>>(der: B) -> Result<Self, X509Error> {
+        Self::try_from(X509CertificationRequestOwned::new(der.as_ref().to_vec())?)
+    }
+
+    /// Parse a PEM-encoded certification request (`CERTIFICATE REQUEST` or
+    /// `NEW CERTIFICATE REQUEST` label)
+    pub fn parse_pem<B: AsRef<[u8]>>(pem: B) -> Result<Self, PEMError> {
+        let der = read_pem(
+            pem.as_ref(),
+            &["CERTIFICATE REQUEST", "NEW CERTIFICATE REQUEST"],
+        )?;
+        Ok(Self::parse_der(der)?)
+    }
+
+    /// Return the DER encoding of the certification request
+    pub fn as_der(&self) -> &[u8] {
+        self.owned.as_raw()
+    }
+
+    /// Return the parsed certification request, to access all fields
+    ///
+    /// The request is parsed again on each call.
+    pub fn parsed(&self) -> X509CertificationRequest<'_> {
+        // the encoding was checked when building the object
+        self.owned
+            .request()
+            .expect("stored certification request is valid")
+    }
+
+    /// Return the subject name, as a human-readable string (for ex. `C=FR, CN=example.com`)
+    pub fn subject(&self) -> String {
+        self.subject.clone()
+    }
+}
+
+impl TryFrom<X509CertificationRequestOwned> for Csr {
+    type Error = X509Error;
+
+    fn try_from(owned: X509CertificationRequestOwned) -> Result<Self, X509Error> {
+        let subject = owned
+            .request()?
+            .certification_request_info
+            .subject
+            .to_string();
+        Ok(Csr { owned, subject })
+    }
+}
+
+impl From<Csr> for X509CertificationRequestOwned {
+    fn from(csr: Csr) -> Self {
+        csr.owned
+    }
+}
+
+// Decode the first PEM block, which must have one of the given labels
+fn read_pem(i: &[u8], labels: &[&str]) -> Result<Vec<u8>, PEMError> {
+    let (_, pem) = parse_x509_pem(i).map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e,
+        nom::Err::Incomplete(_) => PEMError::IncompletePEM,
+    })?;
+    if !labels.contains(&pem.label.as_str()) {
+        return Err(PEMError::UnexpectedLabel(pem.label));
+    }
+    Ok(pem.contents)
+}
@@ -0,0 +1,513 @@
+//! Interfaces to fetch missing issuers and revocation information
+//!
+//! This crate does not perform any network access. Applications which need to complete
+//! certificate chains (using the Authority Information Access `caIssuers` locations) or check
+//! revocation (using OCSP responders and CRL distribution points) implement [`ChainFetcher`] and
+//! [`RevocationProvider`] with their HTTP client, and use [`fetch_missing_issuers`] and
+//! [`check_revocation`] to drive the requests and interpret the responses.
+//!
+//! When the `async` feature is enabled, [`AsyncChainFetcher`] and [`AsyncRevocationProvider`]
+//! (declared using `async-trait`) can be used from asynchronous services instead, with
+//! [`fetch_missing_issuers_async`] and [`check_revocation_async`].
+//!
+//! Errors returned by the fetchers are propagated to the caller. Responses which cannot be
+//! parsed, or do not match the certificate, are ignored.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use x509_parser::fetch::{check_revocation, RevocationProvider, RevocationStatus};
+//! use x509_parser::prelude::*;
+//!
+//! # static CA: &'static [u8] = include_bytes!("../assets/test-ca.der");
+//! # static LEAF: &'static [u8] = include_bytes!("../assets/test-leaf.der");
+//! # static CRL: &'static [u8] = include_bytes!("../assets/test-ca.crl");
+//! // a provider serving files from memory, instead of using a HTTP client
+//! struct StaticProvider(HashMap<&'static str, &'static [u8]>);
+//!
+//! impl RevocationProvider for StaticProvider {
+//!     type Error = String;
+//!
+//!     fn fetch_crl(&self, uri: &str) -> Result<Vec<u8>, String> {
+//!         self.0.get(uri).map(|data| data.to_vec()).ok_or_else(|| format!("not found: {}", uri))
+//!     }
+//!
+//!     fn fetch_ocsp(&self, uri: &str, _request: &[u8]) -> Result<Vec<u8>, String> {
+//!         Err(format!("not found: {}", uri))
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let (_, issuer) = X509Certificate::from_der(CA).expect("could not parse issuer");
+//! let (_, cert) = X509Certificate::from_der(LEAF).expect("could not parse certificate");
+//! let mut files = HashMap::new();
+//! files.insert("http://pki.example.com/test-ca.crl", CRL);
+//! let status = check_revocation(&StaticProvider(files), &cert, &issuer);
+//! // the CRL signature can only be verified with the `verify` feature
+//! if cfg!(feature = "verify") {
+//!     assert!(matches!(status, Ok(RevocationStatus::Revoked { .. })));
+//! } else {
+//!     assert_eq!(status, Ok(RevocationStatus::Unknown));
+//! }
+//! # }
+//! ```
+
+use crate::certificate::{X509Certificate, X509CertificateOwned};
+use crate::chain::order_chain;
+use crate::extensions::GeneralName;
+use crate::pkcs7::parse_pkcs7_signed_data;
+use crate::revocation_list::{crl_covers_certificate, CertificateRevocationList, NextUpdatePolicy};
+use crate::time::ASN1Time;
+use asn1_rs::FromDer;
+use oid_registry::{OID_PKIX_ACCESS_DESCRIPTOR_CA_ISSUERS, OID_PKIX_ACCESS_DESCRIPTOR_OCSP};
+
+#[cfg(feature = "async")]
+use async_trait::async_trait;
+
+/// A source of issuer certificates, for ex. a HTTP client
+pub trait ChainFetcher {
+    type Error;
+
+    /// Fetch the certificates published at `uri` (an AIA `caIssuers` location)
+    ///
+    /// The returned data is usually a DER-encoded certificate or a "certs-only" PKCS#7
+    /// `SignedData`, see [`parse_fetched_certificates`].
+    fn fetch_certificates(&self, uri: &str) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// A source of revocation information, for ex. a HTTP client
+pub trait RevocationProvider {
+    type Error;
+
+    /// Fetch the DER-encoded CRL published at `uri` (a CRL distribution point)
+    fn fetch_crl(&self, uri: &str) -> Result<Vec<u8>, Self::Error>;
+
+    /// Send the DER-encoded OCSP `request` to the responder at `uri`, and return the
+    /// DER-encoded response
+    ///
+    /// Requests are usually sent using a HTTP POST, with content type
+    /// `application/ocsp-request`.
+    fn fetch_ocsp(&self, uri: &str, request: &[u8]) -> Result<Vec<u8>, Self::Error>;
+
+    /// The policy for CRLs and OCSP responses without a usable `nextUpdate`
+    ///
+    /// By default, such revocation information is rejected
+    /// ([`NextUpdatePolicy::RequireNextUpdate`]): since no maximum age is enforced, it would
+    /// otherwise be accepted forever.
+    fn next_update_policy(&self) -> NextUpdatePolicy {
+        NextUpdatePolicy::RequireNextUpdate
+    }
+}
+
+/// Asynchronous version of [`ChainFetcher`]
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[async_trait]
+pub trait AsyncChainFetcher {
+    type Error;
+
+    /// Fetch the certificates published at `uri` (an AIA `caIssuers` location)
+    async fn fetch_certificates(&self, uri: &str) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Asynchronous version of [`RevocationProvider`]
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[async_trait]
+pub trait AsyncRevocationProvider {
+    type Error;
+
+    /// Fetch the DER-encoded CRL published at `uri` (a CRL distribution point)
+    async fn fetch_crl(&self, uri: &str) -> Result<Vec<u8>, Self::Error>;
+
+    /// Send the DER-encoded OCSP `request` to the responder at `uri`, and return the
+    /// DER-encoded response
+    async fn fetch_ocsp(&self, uri: &str, request: &[u8]) -> Result<Vec<u8>, Self::Error>;
+
+    /// The policy for CRLs and OCSP responses without a usable `nextUpdate`
+    ///
+    /// By default, such revocation information is rejected
+    /// ([`NextUpdatePolicy::RequireNextUpdate`]): since no maximum age is enforced, it would
+    /// otherwise be accepted forever.
+    fn next_update_policy(&self) -> NextUpdatePolicy {
+        NextUpdatePolicy::RequireNextUpdate
+    }
+}
+
+/// The revocation status of a certificate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationStatus {
+    /// A valid OCSP response or CRL was found, and the certificate is not revoked
+    Good,
+    /// The certificate is revoked
+    Revoked { revocation_time: ASN1Time },
+    /// No valid revocation information was found
+    Unknown,
+}
+
+// Return the URIs of the AIA access descriptions with method `method`
+fn aia_uris<'a>(cert: &'a X509Certificate, method: &asn1_rs::Oid) -> Vec<&'a str> {
+    match cert.authority_info_access() {
+        Ok(Some(aia)) => aia
+            .value
+            .iter()
+            .filter(|desc| desc.access_method == *method)
+            .filter_map(|desc| match desc.access_location {
+                GeneralName::URI(uri) => Some(uri),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Return the URIs of the `caIssuers` locations from the Authority Information Access extension
+pub fn ca_issuers_uris<'a>(cert: &'a X509Certificate) -> Vec<&'a str> {
+    aia_uris(cert, &OID_PKIX_ACCESS_DESCRIPTOR_CA_ISSUERS)
+}
+
+/// Return the URIs of the OCSP responders from the Authority Information Access extension
+pub fn ocsp_uris<'a>(cert: &'a X509Certificate) -> Vec<&'a str> {
+    aia_uris(cert, &OID_PKIX_ACCESS_DESCRIPTOR_OCSP)
+}
+
+/// Return the URIs of the full names of the CRL distribution points
+///
+/// See also [`CRLDistributionPoints::urls`](crate::extensions::CRLDistributionPoints::urls), to
+/// get only HTTP and LDAP URLs.
+pub fn crl_uris<'a>(cert: &'a X509Certificate) -> Vec<&'a str> {
+    match cert.crl_distribution_points() {
+        Ok(Some(points)) => points.value.iter().flat_map(|point| point.uris()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse the certificates returned from a `caIssuers` location
+///
+/// [RFC5280](https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.2.1) allows a single
+/// DER-encoded certificate, or a "certs-only" PKCS#7 `SignedData`. Returns an empty list if the
+/// data is not recognized.
+pub fn parse_fetched_certificates(data: &[u8]) -> Vec<X509CertificateOwned> {
+    if let Ok((rem, _)) = X509Certificate::from_der(data) {
+        if rem.is_empty() {
+            return X509CertificateOwned::new(data.to_vec())
+                .into_iter()
+                .collect();
+        }
+    }
+    match parse_pkcs7_signed_data(data) {
+        Ok((_, signed_data)) => signed_data
+            .iter_certificates()
+            .filter_map(|cert| X509CertificateOwned::new(cert.as_raw().to_vec()).ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+// Return the `caIssuers` URIs of the last certificate of the chain, if the chain is not complete
+fn missing_issuer_uris(certs: &[X509Certificate], fetched: &[X509CertificateOwned]) -> Vec<String> {
+    let all: Vec<_> = certs
+        .iter()
+        .cloned()
+        .chain(fetched.iter().filter_map(|cert| cert.certificate().ok()))
+        .collect();
+    let ordered = order_chain(all.first(), &all);
+    match ordered.chain.last() {
+        Some(last) if !ordered.is_complete() => ca_issuers_uris(last)
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Add the certificates of `data` which issued the last certificate of the chain to `fetched`
+//
+// Returns `true` if at least one certificate was added.
+fn add_fetched_issuers(
+    certs: &[X509Certificate],
+    fetched: &mut Vec<X509CertificateOwned>,
+    data: &[u8],
+) -> bool {
+    let mut added = Vec::new();
+    {
+        let all: Vec<_> = certs
+            .iter()
+            .cloned()
+            .chain(fetched.iter().filter_map(|cert| cert.certificate().ok()))
+            .collect();
+        let ordered = order_chain(all.first(), &all);
+        let last = match ordered.chain.last() {
+            Some(last) => last,
+            None => return false,
+        };
+        for candidate in parse_fetched_certificates(data) {
+            let parsed = match candidate.certificate() {
+                Ok(parsed) => parsed,
+                Err(_) => continue,
+            };
+            let known = all.iter().any(|cert| cert.eq_der(&parsed));
+            if !known && last.is_issued_by(&parsed) {
+                added.push(candidate);
+            }
+        }
+    }
+    let found = !added.is_empty();
+    fetched.extend(added);
+    found
+}
+
+/// Fetch the missing issuers of a chain, using the AIA `caIssuers` locations
+///
+/// The first certificate of `certs` is the leaf, the other certificates are the known
+/// intermediate certificates (in any order). While the chain (see
+/// [`order_chain`](crate::chain::order_chain)) does not end with a self-issued certificate, the
+/// `caIssuers` locations of the last certificate are fetched, until a location returns an issuer.
+/// At most `max_depth` issuers are fetched.
+///
+/// Returns the fetched certificates. Note that the fetched certificates are not trusted: their
+/// signatures and constraints must be validated like the rest of the chain.
+pub fn fetch_missing_issuers<F: ChainFetcher>(
+    fetcher: &F,
+    certs: &[X509Certificate],
+    max_depth: usize,
+) -> Result<Vec<X509CertificateOwned>, F::Error> {
+    let mut fetched = Vec::new();
+    'depth: while fetched.len() < max_depth {
+        for uri in missing_issuer_uris(certs, &fetched) {
+            let data = fetcher.fetch_certificates(&uri)?;
+            if add_fetched_issuers(certs, &mut fetched, &data) {
+                continue 'depth;
+            }
+        }
+        break;
+    }
+    Ok(fetched)
+}
+
+/// Asynchronous version of [`fetch_missing_issuers`]
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn fetch_missing_issuers_async<F: AsyncChainFetcher + Sync>(
+    fetcher: &F,
+    certs: &[X509Certificate<'_>],
+    max_depth: usize,
+) -> Result<Vec<X509CertificateOwned>, F::Error> {
+    let mut fetched = Vec::new();
+    'depth: while fetched.len() < max_depth {
+        for uri in missing_issuer_uris(certs, &fetched) {
+            let data = fetcher.fetch_certificates(&uri).await?;
+            if add_fetched_issuers(certs, &mut fetched, &data) {
+                continue 'depth;
+            }
+        }
+        break;
+    }
+    Ok(fetched)
+}
+
+// Return the status of `cert` from the DER-encoded CRL `data`, if it is a valid CRL from `issuer`
+fn crl_status(
+    data: &[u8],
+    cert: &X509Certificate,
+    issuer: &X509Certificate,
+    policy: NextUpdatePolicy,
+) -> Option<RevocationStatus> {
+    let (_, crl) = CertificateRevocationList::from_der(data).ok()?;
+    if !crl.is_issued_by(issuer)
+        || !crl_covers_certificate(&crl, cert)
+        || crl.is_stale_with_policy(policy)
+    {
+        return None;
+    }
+    verify_crl(&crl, issuer)?;
+    let status = match crl.find_revoked_for_issuer(cert.raw_serial(), cert.issuer()) {
+        Some(revoked) => RevocationStatus::Revoked {
+            revocation_time: revoked.revocation_date,
+        },
+        None => RevocationStatus::Good,
+    };
+    Some(status)
+}
+
+// Verify the CRL signature, using the key of `issuer`
+#[cfg(feature = "verify")]
+fn verify_crl(crl: &CertificateRevocationList, issuer: &X509Certificate) -> Option<()> {
+    crl.verify_signature(issuer.public_key()).ok()
+}
+
+// Without the `verify` feature, the CRL cannot be authenticated and is never used
+#[cfg(not(feature = "verify"))]
+fn verify_crl(_crl: &CertificateRevocationList, _issuer: &X509Certificate) -> Option<()> {
+    None
+}
+
+#[cfg(feature = "hash")]
+mod ocsp_check {
+    use super::RevocationStatus;
+    use crate::certificate::X509Certificate;
+    use crate::ocsp::{CertStatus, OCSPResponse, OcspRequestBuilder};
+    use crate::revocation_list::NextUpdatePolicy;
+    use crate::time::ASN1Time;
+    use asn1_rs::FromDer;
+    use sha1::{Digest, Sha1};
+
+    pub(super) fn ocsp_request(
+        cert: &X509Certificate,
+        issuer: &X509Certificate,
+    ) -> Option<Vec<u8>> {
+        OcspRequestBuilder::new()
+            .add_certificate(cert, issuer)
+            .ok()
+            .map(|builder| builder.build())
+    }
+
+    // Return the status of `cert` from the DER-encoded OCSP response `data`, if it is a valid
+    // response from `issuer` (or a responder delegated by `issuer`)
+    pub(super) fn ocsp_status(
+        data: &[u8],
+        cert: &X509Certificate,
+        issuer: &X509Certificate,
+        policy: NextUpdatePolicy,
+    ) -> Option<RevocationStatus> {
+        let (_, response) = OCSPResponse::from_der(data).ok()?;
+        let basic = response.basic_response()?;
+        verify_responder(basic, issuer)?;
+        let key_hash = Sha1::digest(&issuer.public_key().subject_public_key.data);
+        let now = ASN1Time::now();
+        let single = basic.iter_responses().find(|single| {
+            single.cert_id.issuer_key_hash == key_hash.as_slice()
+                && single.cert_id.raw_serial_ct_eq(cert.raw_serial())
+        })?;
+        let stale = match single.next_update {
+            Some(next_update) if next_update > single.this_update => next_update < now,
+            _ => policy == NextUpdatePolicy::RequireNextUpdate,
+        };
+        if stale {
+            return None;
+        }
+        match &single.cert_status {
+            CertStatus::Good => Some(RevocationStatus::Good),
+            CertStatus::Revoked(info) => Some(RevocationStatus::Revoked {
+                revocation_time: info.revocation_time,
+            }),
+            CertStatus::Unknown => None,
+        }
+    }
+
+    // Verify the response signature, using the key of `issuer` or of a delegated responder
+    // certificate (issued by `issuer`, with the OCSP signing extended key usage)
+    #[cfg(feature = "verify")]
+    fn verify_responder(
+        basic: &crate::ocsp::BasicOCSPResponse,
+        issuer: &X509Certificate,
+    ) -> Option<()> {
+        if basic.verify_signature(issuer.public_key()).is_ok() {
+            return Some(());
+        }
+        basic
+            .certificates()
+            .iter()
+            .filter(|responder| {
+                responder
+                    .verify_signature(Some(issuer.public_key()))
+                    .is_ok()
+                    && matches!(
+                        responder.extended_key_usage(),
+                        Ok(Some(eku)) if eku.value.ocsp_signing
+                    )
+            })
+            .find(|responder| basic.verify_signature(responder.public_key()).is_ok())
+            .map(|_| ())
+    }
+
+    // Without the `verify` feature, the response cannot be authenticated and is never used
+    #[cfg(not(feature = "verify"))]
+    fn verify_responder(
+        _basic: &crate::ocsp::BasicOCSPResponse,
+        _issuer: &X509Certificate,
+    ) -> Option<()> {
+        None
+    }
+}
+
+/// Check the revocation status of `cert`, issued by `issuer`
+///
+/// If the `hash` feature is enabled, the OCSP responders from the Authority Information Access
+/// extension are queried first. Then, the CRLs from the CRL distribution points are fetched.
+/// The first valid response (matching the certificate and issuer, and not expired) is used.
+/// Responses without a usable `nextUpdate` are handled according to
+/// [`RevocationProvider::next_update_policy`].
+///
+/// The signatures of CRLs and OCSP responses are verified, which requires the `verify` feature.
+/// Without it, the revocation information cannot be authenticated and the status is always
+/// [`RevocationStatus::Unknown`].
+pub fn check_revocation<P: RevocationProvider>(
+    provider: &P,
+    cert: &X509Certificate,
+    issuer: &X509Certificate,
+) -> Result<RevocationStatus, P::Error> {
+    #[cfg(feature = "hash")]
+    {
+        let uris = ocsp_uris(cert);
+        let request = if uris.is_empty() {
+            None
+        } else {
+            ocsp_check::ocsp_request(cert, issuer)
+        };
+        if let Some(request) = request {
+            for uri in uris {
+                let data = provider.fetch_ocsp(uri, &request)?;
+                if let Some(status) =
+                    ocsp_check::ocsp_status(&data, cert, issuer, provider.next_update_policy())
+                {
+                    return Ok(status);
+                }
+            }
+        }
+    }
+    for uri in crl_uris(cert) {
+        let data = provider.fetch_crl(uri)?;
+        if let Some(status) = crl_status(&data, cert, issuer, provider.next_update_policy()) {
+            return Ok(status);
+        }
+    }
+    Ok(RevocationStatus::Unknown)
+}
+
+/// Asynchronous version of [`check_revocation`]
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn check_revocation_async<P: AsyncRevocationProvider + Sync>(
+    provider: &P,
+    cert: &X509Certificate<'_>,
+    issuer: &X509Certificate<'_>,
+) -> Result<RevocationStatus, P::Error> {
+    #[cfg(feature = "hash")]
+    {
+        let uris = ocsp_uris(cert);
+        let request = if uris.is_empty() {
+            None
+        } else {
+            ocsp_check::ocsp_request(cert, issuer)
+        };
+        if let Some(request) = request {
+            for uri in uris {
+                let data = provider.fetch_ocsp(uri, &request).await?;
+                if let Some(status) =
+                    ocsp_check::ocsp_status(&data, cert, issuer, provider.next_update_policy())
+                {
+                    return Ok(status);
+                }
+            }
+        }
+    }
+    for uri in crl_uris(cert) {
+        let data = provider.fetch_crl(uri).await?;
+        if let Some(status) = crl_status(&data, cert, issuer, provider.next_update_policy()) {
+            return Ok(status);
+        }
+    }
+    Ok(RevocationStatus::Unknown)
+}
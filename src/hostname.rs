@@ -0,0 +1,290 @@
+//! Hostname verification, following RFC 6125
+//!
+//! [`verify_hostname`] checks that a certificate is valid for a DNS name or an IP address, using
+//! the subject alternative names of the certificate. The matching rules are configured using a
+//! [`HostnamePolicy`]:
+//!
+//! - the subject common name is only used if enabled, and if the certificate has no DNS name
+//! - wildcards (`*.example.com`) are only accepted as the complete leftmost label, and match
+//!   exactly one label. Wildcards directly under a top-level domain (`*.com`) never match, and
+//!   wildcards under other public suffixes (`*.co.uk`) are rejected if a public suffix function
+//!   is provided (for ex. based on the [Public Suffix List](https://publicsuffix.org/))
+//! - internationalized names must be given as A-labels (`xn--...`). How they are matched is set
+//!   by [`IdnPolicy`].
+//!
+//! Note that this only checks names: the certificate must also be validated.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::hostname::{verify_hostname, HostnamePolicy};
+//! use x509_parser::prelude::*;
+//!
+//! # static LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+//! let (_, x509) = parse_x509_certificate(LEAF).expect("could not parse certificate");
+//! let policy = HostnamePolicy::default();
+//! assert!(verify_hostname(&x509, "leaf.example.com", &policy).is_ok());
+//! assert!(verify_hostname(&x509, "other.example.com", &policy).is_err());
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::error::X509Error;
+use crate::extensions::GeneralName;
+use std::net::IpAddr;
+
+/// How internationalized labels (A-labels, starting with `xn--`) are matched
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdnPolicy {
+    /// A-labels are matched like other labels, including by wildcards (default)
+    Allow,
+    /// Wildcards do not match A-labels (RFC 6125 section 7.2)
+    NoWildcard,
+    /// Names containing A-labels never match
+    Reject,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for IdnPolicy {
+    fn default() -> Self {
+        IdnPolicy::Allow
+    }
+}
+
+/// Options of the hostname matching rules, see [`verify_hostname`]
+#[derive(Clone, Copy, Debug)]
+pub struct HostnamePolicy {
+    /// Match the subject common name, if the certificate has no DNS name in its subject
+    /// alternative names (default: `false`, as required by the CA/Browser Forum)
+    pub cn_fallback: bool,
+    /// Accept wildcard names (default: `true`)
+    pub wildcards: bool,
+    /// Function returning `true` if the domain (for ex. `co.uk`) is a public suffix
+    ///
+    /// Wildcards directly under a public suffix are rejected. If not set (default), only the
+    /// wildcards directly under a top-level domain are rejected.
+    pub public_suffix: Option<fn(&str) -> bool>,
+    /// Matching of internationalized labels
+    pub idn: IdnPolicy,
+}
+
+impl HostnamePolicy {
+    /// Create the default policy
+    pub const fn new() -> Self {
+        HostnamePolicy {
+            cn_fallback: false,
+            wildcards: true,
+            public_suffix: None,
+            idn: IdnPolicy::Allow,
+        }
+    }
+
+    /// Enable or disable the subject common name fallback
+    pub const fn with_cn_fallback(self, cn_fallback: bool) -> Self {
+        HostnamePolicy {
+            cn_fallback,
+            ..self
+        }
+    }
+
+    /// Enable or disable wildcard names
+    pub const fn with_wildcards(self, wildcards: bool) -> Self {
+        HostnamePolicy { wildcards, ..self }
+    }
+
+    /// Set the function testing if a domain is a public suffix
+    pub const fn with_public_suffix(self, public_suffix: fn(&str) -> bool) -> Self {
+        HostnamePolicy {
+            public_suffix: Some(public_suffix),
+            ..self
+        }
+    }
+
+    /// Set the matching policy of internationalized labels
+    pub const fn with_idn(self, idn: IdnPolicy) -> Self {
+        HostnamePolicy { idn, ..self }
+    }
+}
+
+impl Default for HostnamePolicy {
+    fn default() -> Self {
+        HostnamePolicy::new()
+    }
+}
+
+/// Verify that the certificate is valid for `hostname` (a DNS name, or an IP address)
+///
+/// DNS names are compared case-insensitively, and a trailing dot is ignored. IP addresses are
+/// only compared to the IP addresses of the subject alternative names.
+///
+/// Returns [`X509Error::InvalidHostname`] if `hostname` is not a valid DNS name (for ex. if it
+/// contains non-ASCII characters), and [`X509Error::HostnameMismatch`] if no name of the
+/// certificate matches.
+pub fn verify_hostname(
+    cert: &X509Certificate,
+    hostname: &str,
+    policy: &HostnamePolicy,
+) -> Result<(), X509Error> {
+    let san = cert
+        .subject_alternative_name()
+        .map_err(|_| X509Error::InvalidExtensions)?;
+    let names = san.map(|ext| &ext.value.general_names[..]).unwrap_or(&[]);
+
+    if let Ok(ip) = hostname.parse::<IpAddr>() {
+        let matched = names.iter().any(|name| match name {
+            GeneralName::IPAddress(bytes) => ip_matches(&ip, bytes),
+            _ => false,
+        });
+        return if matched {
+            Ok(())
+        } else {
+            Err(X509Error::HostnameMismatch)
+        };
+    }
+
+    let hostname = normalize(hostname).ok_or(X509Error::InvalidHostname)?;
+    let labels: Vec<&str> = hostname.split('.').collect();
+    if labels
+        .iter()
+        .any(|label| label.is_empty() || label.contains('*'))
+    {
+        return Err(X509Error::InvalidHostname);
+    }
+    if policy.idn == IdnPolicy::Reject && labels.iter().any(|label| is_a_label(label)) {
+        return Err(X509Error::HostnameMismatch);
+    }
+
+    let dns_names: Vec<&str> = names
+        .iter()
+        .filter_map(|name| match name {
+            GeneralName::DNSName(s) => Some(*s),
+            _ => None,
+        })
+        .collect();
+    let matched = if !dns_names.is_empty() || !policy.cn_fallback {
+        dns_names
+            .iter()
+            .any(|name| name_matches(name, &labels, policy))
+    } else {
+        cert.subject()
+            .iter_common_name()
+            .filter_map(|cn| cn.as_str().ok())
+            .any(|name| name_matches(name, &labels, policy))
+    };
+    if matched {
+        Ok(())
+    } else {
+        Err(X509Error::HostnameMismatch)
+    }
+}
+
+// Lowercase, and remove the trailing dot. Returns `None` for empty or non-ASCII names.
+fn normalize(name: &str) -> Option<String> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.is_empty() || !name.is_ascii() {
+        return None;
+    }
+    Some(name.to_ascii_lowercase())
+}
+
+fn is_a_label(label: &str) -> bool {
+    label.len() >= 4 && label[..4].eq_ignore_ascii_case("xn--")
+}
+
+// Match a presented name (from the certificate) with the labels of the reference name
+fn name_matches(presented: &str, labels: &[&str], policy: &HostnamePolicy) -> bool {
+    let presented = match normalize(presented) {
+        Some(name) => name,
+        None => return false,
+    };
+    let presented: Vec<&str> = presented.split('.').collect();
+    if presented.iter().any(|label| label.is_empty())
+        || (policy.idn == IdnPolicy::Reject && presented.iter().any(|label| is_a_label(label)))
+    {
+        return false;
+    }
+    let (first, rest) = presented.split_first().expect("split is never empty");
+    if *first != "*" {
+        // partial wildcards (`f*.example.com`) and wildcards in other labels are not accepted
+        return !presented.iter().any(|label| label.contains('*')) && presented == labels;
+    }
+    if !policy.wildcards || rest.len() < 2 || rest.iter().any(|label| label.contains('*')) {
+        return false;
+    }
+    if let Some(public_suffix) = policy.public_suffix {
+        if public_suffix(&rest.join(".")) {
+            return false;
+        }
+    }
+    if policy.idn != IdnPolicy::Allow && is_a_label(labels[0]) {
+        return false;
+    }
+    labels.len() == presented.len() && labels[1..] == *rest
+}
+
+fn ip_matches(ip: &IpAddr, bytes: &[u8]) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.octets()[..] == *bytes,
+        IpAddr::V6(ip) => ip.octets()[..] == *bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(presented: &str, hostname: &str, policy: &HostnamePolicy) -> bool {
+        let hostname = normalize(hostname).unwrap();
+        let labels: Vec<&str> = hostname.split('.').collect();
+        name_matches(presented, &labels, policy)
+    }
+
+    #[test]
+    fn test_name_matches() {
+        let policy = HostnamePolicy::new();
+        assert!(matches("www.example.com", "www.example.com", &policy));
+        assert!(matches("WWW.Example.COM.", "www.example.com", &policy));
+        assert!(!matches("example.com", "www.example.com", &policy));
+        assert!(matches("*.example.com", "www.example.com", &policy));
+        assert!(!matches("*.example.com", "example.com", &policy));
+        assert!(!matches("*.example.com", "a.b.example.com", &policy));
+        assert!(!matches("w*.example.com", "www.example.com", &policy));
+        assert!(!matches("www.*.com", "www.example.com", &policy));
+        assert!(!matches("*.com", "example.com", &policy));
+        assert!(!matches(
+            "*.example.com",
+            "www.example.com",
+            &policy.with_wildcards(false)
+        ));
+    }
+
+    #[test]
+    fn test_name_matches_public_suffix() {
+        fn public_suffix(domain: &str) -> bool {
+            domain == "co.uk"
+        }
+        let policy = HostnamePolicy::new();
+        assert!(matches("*.co.uk", "example.co.uk", &policy));
+        let policy = policy.with_public_suffix(public_suffix);
+        assert!(!matches("*.co.uk", "example.co.uk", &policy));
+        assert!(matches("*.example.co.uk", "www.example.co.uk", &policy));
+    }
+
+    #[test]
+    fn test_name_matches_idn() {
+        let policy = HostnamePolicy::new();
+        let host = "xn--bcher-kva.example.com";
+        assert!(matches("*.example.com", host, &policy));
+        assert!(matches(host, host, &policy));
+        let policy = policy.with_idn(IdnPolicy::NoWildcard);
+        assert!(!matches("*.example.com", host, &policy));
+        assert!(matches(host, host, &policy));
+        let policy = policy.with_idn(IdnPolicy::Reject);
+        assert!(!matches(host, host, &policy));
+        assert!(!matches(
+            "*.xn--e1afmkfd.example",
+            "a.xn--e1afmkfd.example",
+            &policy
+        ));
+    }
+}
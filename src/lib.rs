@@ -38,6 +38,11 @@
 //!
 //! For PEM-encoded certificates, use the [`pem`](pem/index.html) module.
 //!
+//! Most types can be imported at once using the [`prelude`](prelude/index.html) module.
+//! Applications which only need the main fields of certificates, CRLs and certification
+//! requests can use the owned types of the [`facade`](facade/index.html) module, which is kept
+//! stable while the lower-level API evolves.
+//!
 //! # Examples
 //!
 //! Parsing a certificate in DER format:
@@ -107,6 +112,33 @@
 //!   and values using the [`Validate`](validate/trait.Validate.html) trait.
 //!   It does not validate any cryptographic parameter (see `verify` above).
 //!
+//! - The `bigint` feature (enabled by default) parses serial numbers and CRL numbers to `BigUint`.
+//!   When disabled, the `num-bigint` dependency is dropped, and serial numbers are only available
+//!   using `raw_serial()` and `serial_u128()`.
+//!
+//! - The `trace` feature adds the [`trace`](trace/index.html) module, to record the fields parsed
+//!   before an error and locate errors in malformed certificates.
+//!
+//! - The `io` feature adds conversions from `X509Error` and `PEMError` to `std::io::Error`.
+//!
+//! - The `serde` feature implements `serde::Serialize` for certificates, CRLs, CSRs and their
+//!   components, using readable representations (strings for names, OIDs and times).
+//!
+//! - The `hash` feature adds `X509Name::openssl_hash()` and `openssl_hash_old()`, computing
+//!   the subject hashes used by OpenSSL for hashed certificate directories, and the computation
+//!   of certificate transparency leaf hashes in the [`ct`](ct/index.html) module, SPKI
+//!   pinning in the [`pinning`](pinning/index.html) module, and the fingerprint-indexed
+//!   certificate store of the [`store`](store/index.html) module.
+//!
+//! - The `decrypt` feature adds the decryption of password-encrypted contents (PBES2 with AES-CBC)
+//!   in PKCS#12 files, see [`pkcs12`](pkcs12/index.html).
+//!
+//! - The `async` feature adds asynchronous versions of the issuer and revocation fetching
+//!   interfaces, see [`fetch`](fetch/index.html).
+//!
+//! - The `bench` feature adds the [`bench`](bench/index.html) module, to measure the parsing
+//!   throughput over a corpus of certificates.
+//!
 //! ## Rust version requirements
 //!
 //! `x509-parser` requires **Rustc version 1.57 or greater**, based on der-parser
@@ -135,19 +167,55 @@
 ))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+/// Record the field entered by the parser, if the `trace` feature is enabled
+macro_rules! trace_field {
+    ($field:expr, $i:expr) => {
+        #[cfg(feature = "trace")]
+        crate::trace::record($field, $i);
+    };
+}
+
+pub mod attribute_certificate;
+#[cfg(feature = "bench")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bench")))]
+pub mod bench;
 pub mod certificate;
 pub mod certification_request;
+pub mod chain;
 pub mod cri_attributes;
+pub mod ct;
+pub mod display;
 pub mod error;
 pub mod extensions;
+pub mod facade;
+pub mod fetch;
+pub mod hostname;
 pub mod objects;
+pub mod ocsp;
+pub mod oids;
 pub mod pem;
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+pub mod pinning;
+pub mod pkcs12;
+pub mod pkcs7;
 pub mod prelude;
 pub mod public_key;
+pub mod report;
 pub mod revocation_list;
+#[cfg(feature = "serde")]
+mod serialize;
 pub mod signature_algorithm;
 pub mod signature_value;
+pub mod snapshot;
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+pub mod store;
 pub mod time;
+pub mod tls;
+#[cfg(feature = "trace")]
+#[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
+pub mod trace;
 pub mod utils;
 #[cfg(feature = "validate")]
 #[cfg_attr(docsrs, doc(cfg(feature = "validate")))]
@@ -159,14 +227,17 @@ pub mod x509;
 
 // reexports
 pub use der_parser;
+#[cfg(feature = "bigint")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
 pub use der_parser::num_bigint;
 pub use nom;
 pub use oid_registry;
 
 use asn1_rs::FromDer;
-use certificate::X509Certificate;
-use error::X509Result;
+use certificate::{X509Certificate, X509CertificateOwned};
+use error::{X509Error, X509Result};
 use revocation_list::CertificateRevocationList;
+use x509::{AlgorithmIdentifier, SubjectPublicKeyInfo};
 
 /// Parse a **DER-encoded** X.509 Certificate, and return the remaining of the input and the built
 /// object.
@@ -181,6 +252,20 @@ pub fn parse_x509_certificate(i: &[u8]) -> X509Result<X509Certificate> {
     X509Certificate::from_der(i)
 }
 
+/// Parse an X.509 Certificate from a hex dump of its DER encoding
+///
+/// The dump can be contiguous hex, or bytes separated by colons or whitespace, as copied from
+/// a packet capture or a log (see [`utils::decode_hex`]). Trailing data after the certificate is
+/// ignored.
+///
+/// The decoded buffer is owned by the returned object, see
+/// [`X509CertificateOwned::certificate`](certificate::X509CertificateOwned::certificate) to access
+/// its fields.
+pub fn parse_x509_hex(s: &str) -> Result<X509CertificateOwned, X509Error> {
+    let data = utils::decode_hex(s)?;
+    X509CertificateOwned::new(data)
+}
+
 /// Parse a DER-encoded X.509 v2 CRL, and return the remaining of the input and the built
 /// object.
 ///
@@ -191,6 +276,27 @@ pub fn parse_x509_crl(i: &[u8]) -> X509Result<CertificateRevocationList> {
     CertificateRevocationList::from_der(i)
 }
 
+/// Parse a DER-encoded SubjectPublicKeyInfo, and return the remaining of the input and the built
+/// object.
+///
+/// This function is an alias to [SubjectPublicKeyInfo::from_der](x509::SubjectPublicKeyInfo::from_der).
+///
+/// For PEM-encoded public keys (`PUBLIC KEY` blocks), use
+/// [`Pem::parse_subject_public_key_info`](pem::Pem::parse_subject_public_key_info).
+#[inline]
+pub fn parse_subject_public_key_info(i: &[u8]) -> X509Result<SubjectPublicKeyInfo> {
+    SubjectPublicKeyInfo::from_der(i)
+}
+
+/// Parse a DER-encoded AlgorithmIdentifier, and return the remaining of the input and the built
+/// object.
+///
+/// This function is an alias to [AlgorithmIdentifier::from_der](x509::AlgorithmIdentifier::from_der).
+#[inline]
+pub fn parse_algorithm_identifier(i: &[u8]) -> X509Result<AlgorithmIdentifier> {
+    AlgorithmIdentifier::from_der(i)
+}
+
 /// Parse a DER-encoded X.509 Certificate, and return the remaining of the input and the built
 #[deprecated(
     since = "0.9.0",
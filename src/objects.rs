@@ -30,6 +30,18 @@ lazy_static! {
         // OIDs not in the default registry can be added here
         let entry = OidEntry::new("id-mgf1", "Mask Generator Function 1 (MGF1)");
         reg.insert(oid! {1.2.840.113549.1.1.8}, entry);
+        // symmetric ciphers, for ex. in S/MIME capabilities
+        let ciphers = [
+            (oid! {2.16.840.1.101.3.4.1.2}, "aes-128-cbc", "128-bit AES in CBC mode"),
+            (oid! {2.16.840.1.101.3.4.1.22}, "aes-192-cbc", "192-bit AES in CBC mode"),
+            (oid! {2.16.840.1.101.3.4.1.6}, "aes-128-gcm", "128-bit AES in GCM mode"),
+            (oid! {2.16.840.1.101.3.4.1.46}, "aes-256-gcm", "256-bit AES in GCM mode"),
+            (oid! {1.2.840.113549.3.7}, "des-ede3-cbc", "Triple DES in CBC mode"),
+            (oid! {1.2.840.113549.3.2}, "rc2-cbc", "RC2 in CBC mode"),
+        ];
+        for (oid, sn, description) in ciphers {
+            reg.insert(oid, OidEntry::new(sn, description));
+        }
         reg
     };
     static ref ABBREV_MAP: HashMap<Oid<'static>, &'static str> = {
@@ -54,6 +66,20 @@ pub fn oid2abbrev<'a>(oid: &'a Oid, registry: &'a OidRegistry) -> Result<&'a str
     registry.get(oid).map(|entry| entry.sn()).ok_or(NidError)
 }
 
+/// Return the abbreviation from `abbrevs` if present, or else fall back to [`oid2abbrev`]
+///
+/// This can be used to render attributes not known to this crate with a custom abbreviation.
+pub fn oid2abbrev_with<'a>(
+    oid: &'a Oid,
+    abbrevs: &'a HashMap<Oid, &str>,
+    registry: &'a OidRegistry,
+) -> Result<&'a str, NidError> {
+    if let Some(abbrev) = abbrevs.get(oid) {
+        return Ok(abbrev);
+    }
+    oid2abbrev(oid, registry)
+}
+
 /// Returns the short name corresponding to the OID
 pub fn oid2sn<'a>(oid: &'a Oid, registry: &'a OidRegistry) -> Result<&'a str, NidError> {
     registry.get(oid).map(|o| o.sn()).ok_or(NidError)
@@ -89,4 +115,18 @@ mod tests {
         //     _ => (),
         // }
     }
+
+    #[test]
+    fn test_oid2abbrev_with() {
+        let custom = oid!(1.2.3 .4);
+        let mut abbrevs = HashMap::new();
+        abbrevs.insert(custom.clone(), "CUSTOM");
+        let registry = oid_registry();
+        assert_eq!(oid2abbrev_with(&custom, &abbrevs, registry), Ok("CUSTOM"));
+        assert_eq!(
+            oid2abbrev_with(&OID_X509_COMMON_NAME, &abbrevs, registry),
+            Ok("CN")
+        );
+        assert!(oid2abbrev(&custom, registry).is_err());
+    }
 }
@@ -0,0 +1,894 @@
+//! Online Certificate Status Protocol (OCSP) requests and responses
+//!
+//! OCSP is defined in [RFC6960](https://datatracker.ietf.org/doc/html/rfc6960).
+//! An [`OCSPResponse`] contains a status, and (if successful) a [`BasicOCSPResponse`] signed
+//! by the responder, with the status of one or more certificates.
+//!
+//! Requests can be built using [`OcspRequestBuilder`], and sent using any HTTP client.
+//!
+//! # Example
+//!
+//! To parse an OCSP response and print the status of certificates:
+//!
+//! ```rust
+//! use x509_parser::ocsp::{CertStatus, OCSPResponse};
+//! use x509_parser::prelude::FromDer;
+//!
+//! # static DER: &'static [u8] = include_bytes!("../assets/ocsp-response.der");
+//! #
+//! # fn main() {
+//! let (_, response) = OCSPResponse::from_der(DER).expect("OCSP response parsing failed");
+//! println!("Status: {}", response.response_status);
+//! if let Some(basic) = response.basic_response() {
+//!     for single in basic.iter_responses() {
+//!         let serial = single.cert_id.raw_serial_as_string();
+//!         match &single.cert_status {
+//!             CertStatus::Good => println!("{}: good", serial),
+//!             CertStatus::Revoked(info) => println!("{}: revoked on {}", serial, info.revocation_time),
+//!             CertStatus::Unknown => println!("{}: unknown", serial),
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::error::{X509Error, X509Result};
+use crate::extensions::*;
+use crate::time::ASN1Time;
+use crate::utils::{constant_time_eq, der_tlv, format_serial, many0_lazy};
+use crate::x509::{
+    parse_serial, parse_signature_value, serial_to_u128, AlgorithmIdentifier, ReasonCode, X509Name,
+    X509Version,
+};
+
+#[cfg(feature = "verify")]
+use crate::verify::verify_signature;
+#[cfg(feature = "verify")]
+use crate::x509::SubjectPublicKeyInfo;
+use asn1_rs::{oid, Any, BitString, Class, Enumerated, FromDer, OptTaggedParser, Tag};
+use der_parser::der::*;
+#[cfg(feature = "bigint")]
+use der_parser::num_bigint::BigUint;
+use der_parser::oid::Oid;
+use nom::combinator::{all_consuming, complete};
+use nom::{Err, Offset};
+use oid_registry::OID_HASH_SHA1;
+use rusticata_macros::newtype_enum;
+use std::convert::TryFrom;
+use time::Duration;
+
+/// id-pkix-ocsp-basic, the type of [`BasicOCSPResponse`]
+pub const OID_PKIX_OCSP_BASIC: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .48 .1 .1);
+/// id-pkix-ocsp-nonce, the OCSP nonce extension
+pub const OID_PKIX_OCSP_NONCE: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .48 .1 .2);
+/// id-pkix-ocsp-nocheck, the OCSP No Check extension of delegated responder certificates
+pub const OID_PKIX_OCSP_NOCHECK: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .48 .1 .5);
+/// id-pe-tlsfeature, the TLS Feature extension ([RFC7633](https://datatracker.ietf.org/doc/html/rfc7633))
+pub const OID_PE_TLS_FEATURE: Oid<'static> = oid!(1.3.6 .1 .5 .5 .7 .1 .24);
+
+/// The status of an OCSP response
+///
+/// Only successful responses contain response bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OCSPResponseStatus(pub u8);
+
+newtype_enum! {
+impl display OCSPResponseStatus {
+    Successful = 0,
+    MalformedRequest = 1,
+    InternalError = 2,
+    TryLater = 3,
+    // 4 is not used
+    SigRequired = 5,
+    Unauthorized = 6,
+}
+}
+
+/// An OCSP response
+///
+/// <pre>
+/// OCSPResponse ::= SEQUENCE {
+///    responseStatus         OCSPResponseStatus,
+///    responseBytes          [0] EXPLICIT ResponseBytes OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct OCSPResponse<'a> {
+    pub response_status: OCSPResponseStatus,
+    pub response_bytes: Option<ResponseBytes<'a>>,
+}
+
+impl<'a> OCSPResponse<'a> {
+    /// Test if the response status is `successful`
+    pub fn is_successful(&self) -> bool {
+        self.response_status == OCSPResponseStatus::Successful
+    }
+
+    /// Get the basic OCSP response, if present
+    pub fn basic_response(&self) -> Option<&BasicOCSPResponse<'a>> {
+        self.response_bytes
+            .as_ref()
+            .and_then(|bytes| bytes.basic_response.as_ref())
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for OCSPResponse<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, status) = Enumerated::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+            let response_status = u8::try_from(status.0)
+                .map(OCSPResponseStatus)
+                .or(Err(X509Error::InvalidOCSPResponse))?;
+            let (i, response_bytes) =
+                OptTaggedParser::from(0).parse_der(i, |_, data| ResponseBytes::from_der(data))?;
+            let response = OCSPResponse {
+                response_status,
+                response_bytes,
+            };
+            Ok((i, response))
+        })(i)
+    }
+}
+
+/// The type and contents of an OCSP response
+///
+/// <pre>
+/// ResponseBytes ::=       SEQUENCE {
+///     responseType   OBJECT IDENTIFIER,
+///     response       OCTET STRING }
+/// </pre>
+///
+/// If the response type is `id-pkix-ocsp-basic`, the contents are parsed as a
+/// [`BasicOCSPResponse`].
+#[derive(Clone, Debug)]
+pub struct ResponseBytes<'a> {
+    pub response_type: Oid<'a>,
+    /// The raw contents of the response
+    pub response: &'a [u8],
+    pub basic_response: Option<BasicOCSPResponse<'a>>,
+}
+
+impl<'a> FromDer<'a, X509Error> for ResponseBytes<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, response_type) = Oid::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+            let (i, response) = <&[u8]>::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+            let basic_response = if response_type == OID_PKIX_OCSP_BASIC {
+                let (_, basic) = all_consuming(BasicOCSPResponse::from_der)(response)?;
+                Some(basic)
+            } else {
+                None
+            };
+            let bytes = ResponseBytes {
+                response_type,
+                response,
+                basic_response,
+            };
+            Ok((i, bytes))
+        })(i)
+    }
+}
+
+/// A basic OCSP response, signed by the responder
+///
+/// <pre>
+/// BasicOCSPResponse       ::= SEQUENCE {
+///    tbsResponseData      ResponseData,
+///    signatureAlgorithm   AlgorithmIdentifier,
+///    signature            BIT STRING,
+///    certs            [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct BasicOCSPResponse<'a> {
+    pub tbs_response_data: ResponseData<'a>,
+    pub signature_algorithm: AlgorithmIdentifier<'a>,
+    pub signature_value: BitString<'a>,
+    /// Certificates provided to help verifying the signature (usually the responder certificate)
+    pub certs: Vec<X509Certificate<'a>>,
+}
+
+impl<'a> BasicOCSPResponse<'a> {
+    /// Get the responder identifier
+    pub fn responder_id(&self) -> &ResponderID<'a> {
+        &self.tbs_response_data.responder_id
+    }
+
+    /// Get the time at which the response was signed
+    pub fn produced_at(&self) -> ASN1Time {
+        self.tbs_response_data.produced_at
+    }
+
+    /// Return an iterator over the `SingleResponse` objects
+    pub fn iter_responses(&self) -> impl Iterator<Item = &SingleResponse<'a>> {
+        self.tbs_response_data.responses.iter()
+    }
+
+    /// Get the response extensions.
+    #[inline]
+    pub fn extensions(&self) -> &[X509Extension<'a>] {
+        &self.tbs_response_data.extensions
+    }
+
+    /// Get the embedded certificates
+    pub fn certificates(&self) -> &[X509Certificate<'a>] {
+        &self.certs
+    }
+
+    /// Verify the cryptographic signature of this OCSP response
+    ///
+    /// `public_key` is the public key of the **responder** (either the issuer of the
+    /// certificates, or a delegated responder).
+    ///
+    /// Not all algorithms are supported, this function is limited to what `ring` supports.
+    #[cfg(feature = "verify")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+    pub fn verify_signature(&self, public_key: &SubjectPublicKeyInfo) -> Result<(), X509Error> {
+        verify_signature(
+            public_key,
+            &self.signature_algorithm,
+            &self.signature_value,
+            self.tbs_response_data.raw,
+        )
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for BasicOCSPResponse<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, tbs_response_data) = ResponseData::from_der(i)?;
+            let (i, signature_algorithm) = AlgorithmIdentifier::from_der(i)?;
+            let (i, signature_value) = parse_signature_value(i)?;
+            let (i, certs) = OptTaggedParser::from(0).parse_der(i, |_, data| {
+                parse_der_sequence_defined_g(|a, _| {
+                    all_consuming(many0_lazy(complete(X509Certificate::from_der)))(a)
+                })(data)
+            })?;
+            let basic = BasicOCSPResponse {
+                tbs_response_data,
+                signature_algorithm,
+                signature_value,
+                certs: certs.unwrap_or_default(),
+            };
+            Ok((i, basic))
+        })(i)
+    }
+}
+
+/// The signed data of a basic OCSP response
+///
+/// <pre>
+/// ResponseData ::= SEQUENCE {
+///    version              [0] EXPLICIT Version DEFAULT v1,
+///    responderID              ResponderID,
+///    producedAt               GeneralizedTime,
+///    responses                SEQUENCE OF SingleResponse,
+///    responseExtensions   [1] EXPLICIT Extensions OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct ResponseData<'a> {
+    pub version: X509Version,
+    pub responder_id: ResponderID<'a>,
+    pub produced_at: ASN1Time,
+    pub responses: Vec<SingleResponse<'a>>,
+    extensions: Vec<X509Extension<'a>>,
+    pub(crate) raw: &'a [u8],
+}
+
+impl<'a> ResponseData<'a> {
+    /// Get the response extensions.
+    #[inline]
+    pub fn extensions(&self) -> &[X509Extension<'a>] {
+        &self.extensions
+    }
+
+    /// Searches for a response extension with the given `Oid`.
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error `DuplicateExtensions` if the extension is present twice or more.
+    #[inline]
+    pub fn get_extension_unique(&self, oid: &Oid) -> Result<Option<&X509Extension<'a>>, X509Error> {
+        get_extension_unique(&self.extensions, oid)
+    }
+
+    /// Return the raw DER encoding of the signed data
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for ResponseData<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        let start_i = i;
+        parse_der_sequence_defined_g(move |i, _| {
+            let (i, version) = X509Version::from_der_tagged_0(i)?;
+            let (i, responder_id) = ResponderID::from_der(i)?;
+            let (i, produced_at) = ASN1Time::from_der(i)?;
+            let (i, responses) = parse_der_sequence_defined_g(|a, _| {
+                all_consuming(many0_lazy(complete(SingleResponse::from_der)))(a)
+            })(i)?;
+            let (i, extensions) = parse_extensions(i, Tag(1))?;
+            let len = start_i.offset(i);
+            let data = ResponseData {
+                version,
+                responder_id,
+                produced_at,
+                responses,
+                extensions,
+                raw: &start_i[..len],
+            };
+            Ok((i, data))
+        })(i)
+    }
+}
+
+/// The identifier of an OCSP responder
+///
+/// <pre>
+/// ResponderID ::= CHOICE {
+///    byName   [1] Name,
+///    byKey    [2] KeyHash }
+///
+/// KeyHash ::= OCTET STRING -- SHA-1 hash of responder's public key
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResponderID<'a> {
+    ByName(X509Name<'a>),
+    ByKey(&'a [u8]),
+}
+
+impl<'a> FromDer<'a, X509Error> for ResponderID<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+        if any.class() != Class::ContextSpecific {
+            return Err(Err::Error(X509Error::InvalidOCSPResponse));
+        }
+        let id = match any.tag().0 {
+            1 => {
+                let (_, name) = all_consuming(X509Name::from_der)(any.data)?;
+                ResponderID::ByName(name)
+            }
+            2 => {
+                let (_, key_hash) = all_consuming(<&[u8]>::from_der)(any.data)
+                    .or(Err(X509Error::InvalidOCSPResponse))?;
+                ResponderID::ByKey(key_hash)
+            }
+            _ => return Err(Err::Error(X509Error::InvalidOCSPResponse)),
+        };
+        Ok((rem, id))
+    }
+}
+
+/// The status of a single certificate
+///
+/// <pre>
+/// SingleResponse ::= SEQUENCE {
+///    certID                       CertID,
+///    certStatus                   CertStatus,
+///    thisUpdate                   GeneralizedTime,
+///    nextUpdate         [0]       EXPLICIT GeneralizedTime OPTIONAL,
+///    singleExtensions   [1]       EXPLICIT Extensions OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct SingleResponse<'a> {
+    pub cert_id: CertID<'a>,
+    pub cert_status: CertStatus,
+    pub this_update: ASN1Time,
+    pub next_update: Option<ASN1Time>,
+    extensions: Vec<X509Extension<'a>>,
+}
+
+impl<'a> SingleResponse<'a> {
+    /// Get the single response extensions.
+    #[inline]
+    pub fn extensions(&self) -> &[X509Extension<'a>] {
+        &self.extensions
+    }
+
+    /// Searches for a single response extension with the given `Oid`.
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error `DuplicateExtensions` if the extension is present twice or more.
+    #[inline]
+    pub fn get_extension_unique(&self, oid: &Oid) -> Result<Option<&X509Extension<'a>>, X509Error> {
+        get_extension_unique(&self.extensions, oid)
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for SingleResponse<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, cert_id) = CertID::from_der(i)?;
+            let (i, cert_status) = CertStatus::from_der(i)?;
+            let (i, this_update) = ASN1Time::from_der(i)?;
+            let (i, next_update) =
+                OptTaggedParser::from(0).parse_der(i, |_, data| ASN1Time::from_der(data))?;
+            let (i, extensions) = parse_extensions(i, Tag(1))?;
+            let response = SingleResponse {
+                cert_id,
+                cert_status,
+                this_update,
+                next_update,
+                extensions,
+            };
+            Ok((i, response))
+        })(i)
+    }
+}
+
+/// The identifier of a certificate, using hashes of its issuer
+///
+/// <pre>
+/// CertID          ::=     SEQUENCE {
+///     hashAlgorithm       AlgorithmIdentifier,
+///     issuerNameHash      OCTET STRING, -- Hash of issuer's DN
+///     issuerKeyHash       OCTET STRING, -- Hash of issuer's public key
+///     serialNumber        CertificateSerialNumber }
+/// </pre>
+#[derive(Clone, Debug, PartialEq)]
+pub struct CertID<'a> {
+    pub hash_algorithm: AlgorithmIdentifier<'a>,
+    pub issuer_name_hash: &'a [u8],
+    pub issuer_key_hash: &'a [u8],
+    pub(crate) raw_serial: &'a [u8],
+}
+
+impl<'a> CertID<'a> {
+    /// Return the serial number of the certificate
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
+    pub fn serial_number(&self) -> BigUint {
+        BigUint::from_bytes_be(self.raw_serial)
+    }
+
+    /// Return the serial number of the certificate as `u128`
+    ///
+    /// Returns `None` if the serial number does not fit in 128 bits.
+    pub fn serial_u128(&self) -> Option<u128> {
+        serial_to_u128(self.raw_serial)
+    }
+
+    /// Get the raw bytes of the certificate serial number
+    pub fn raw_serial(&self) -> &'a [u8] {
+        self.raw_serial
+    }
+
+    /// Compare the raw bytes of the certificate serial number with `serial`, in constant time
+    pub fn raw_serial_ct_eq(&self, serial: &[u8]) -> bool {
+        constant_time_eq(self.raw_serial, serial)
+    }
+
+    /// Get a formatted string of the certificate serial number, separated by ':'
+    pub fn raw_serial_as_string(&self) -> String {
+        format_serial(self.raw_serial)
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for CertID<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, hash_algorithm) = AlgorithmIdentifier::from_der(i)?;
+            let (i, issuer_name_hash) =
+                <&[u8]>::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+            let (i, issuer_key_hash) =
+                <&[u8]>::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+            let (i, raw_serial) = parse_serial(i)?;
+            let cert_id = CertID {
+                hash_algorithm,
+                issuer_name_hash,
+                issuer_key_hash,
+                raw_serial,
+            };
+            Ok((i, cert_id))
+        })(i)
+    }
+}
+
+/// The revocation status of a certificate
+///
+/// <pre>
+/// CertStatus ::= CHOICE {
+///     good        [0]     IMPLICIT NULL,
+///     revoked     [1]     IMPLICIT RevokedInfo,
+///     unknown     [2]     IMPLICIT UnknownInfo }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CertStatus {
+    Good,
+    Revoked(RevokedInfo),
+    Unknown,
+}
+
+impl CertStatus {
+    /// Test if the certificate is revoked
+    pub fn is_revoked(&self) -> bool {
+        matches!(self, CertStatus::Revoked(_))
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for CertStatus {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidOCSPResponse))?;
+        if any.class() != Class::ContextSpecific {
+            return Err(Err::Error(X509Error::InvalidOCSPResponse));
+        }
+        let status = match any.tag().0 {
+            // good and unknown are (implicitly tagged) NULL values, with an empty content
+            0 if any.data.is_empty() => CertStatus::Good,
+            1 => {
+                let (_, info) = all_consuming(RevokedInfo::parse_content)(any.data)?;
+                CertStatus::Revoked(info)
+            }
+            2 if any.data.is_empty() => CertStatus::Unknown,
+            _ => return Err(Err::Error(X509Error::InvalidOCSPResponse)),
+        };
+        Ok((rem, status))
+    }
+}
+
+/// Revocation time and reason of a revoked certificate
+///
+/// <pre>
+/// RevokedInfo ::= SEQUENCE {
+///     revocationTime              GeneralizedTime,
+///     revocationReason    [0]     EXPLICIT CRLReason OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokedInfo {
+    pub revocation_time: ASN1Time,
+    pub revocation_reason: Option<ReasonCode>,
+}
+
+impl RevokedInfo {
+    // the tag of RevokedInfo is implicit, so only the content is parsed
+    fn parse_content(i: &[u8]) -> X509Result<Self> {
+        let (i, revocation_time) = ASN1Time::from_der(i)?;
+        let (i, revocation_reason) = OptTaggedParser::from(0).parse_der(i, |_, data| {
+            let (rem, code) = Enumerated::from_der(data).or(Err(X509Error::InvalidOCSPResponse))?;
+            match u8::try_from(code.0) {
+                // value 7 is not used
+                Ok(code) if code <= 10 && code != 7 => Ok((rem, ReasonCode(code))),
+                _ => Err(Err::Error(X509Error::InvalidOCSPResponse)),
+            }
+        })?;
+        let info = RevokedInfo {
+            revocation_time,
+            revocation_reason,
+        };
+        Ok((i, info))
+    }
+}
+
+/// Builder for DER-encoded OCSP requests
+///
+/// Each request contains the `CertID` of one or more certificates, using SHA-1 hashes (as
+/// recommended by [RFC5019](https://datatracker.ietf.org/doc/html/rfc5019)), and optionally a
+/// nonce. Requests are not signed.
+///
+/// <pre>
+/// OCSPRequest     ::=     SEQUENCE {
+///     tbsRequest                  TBSRequest,
+///     optionalSignature   [0]     EXPLICIT Signature OPTIONAL }
+///
+/// TBSRequest      ::=     SEQUENCE {
+///     version             [0]     EXPLICIT Version DEFAULT v1,
+///     requestorName       [1]     EXPLICIT GeneralName OPTIONAL,
+///     requestList                 SEQUENCE OF Request,
+///     requestExtensions   [2]     EXPLICIT Extensions OPTIONAL }
+///
+/// Request         ::=     SEQUENCE {
+///     reqCert                     CertID,
+///     singleRequestExtensions     [0] EXPLICIT Extensions OPTIONAL }
+/// </pre>
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "hash")]
+/// # {
+/// use x509_parser::ocsp::OcspRequestBuilder;
+/// use x509_parser::prelude::*;
+///
+/// # static CA: &'static [u8] = include_bytes!("../assets/test-ca.der");
+/// # static LEAF: &'static [u8] = include_bytes!("../assets/test-leaf.der");
+/// let (_, issuer) = X509Certificate::from_der(CA).expect("could not parse issuer");
+/// let (_, cert) = X509Certificate::from_der(LEAF).expect("could not parse certificate");
+/// let request = OcspRequestBuilder::new()
+///     .add_certificate(&cert, &issuer)
+///     .expect("invalid serial")
+///     .build();
+/// // POST `request` to the responder, with content type `application/ocsp-request`
+/// # assert_eq!(request[0], 0x30);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OcspRequestBuilder {
+    // encoded CertID of each request
+    cert_ids: Vec<Vec<u8>>,
+    nonce: Option<Vec<u8>>,
+}
+
+impl OcspRequestBuilder {
+    /// Create a new builder, without any certificate
+    pub fn new() -> Self {
+        OcspRequestBuilder::default()
+    }
+
+    /// Add a request for the status of `certificate`, issued by `issuer`
+    ///
+    /// The hashes of the issuer name and public key are computed from `issuer`. The issuer
+    /// is not checked against the certificate.
+    ///
+    /// Returns an error if the certificate serial number is empty.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+    pub fn add_certificate(
+        self,
+        certificate: &X509Certificate,
+        issuer: &X509Certificate,
+    ) -> Result<Self, X509Error> {
+        use sha1::{Digest, Sha1};
+        let name_hash = Sha1::digest(issuer.subject().as_raw());
+        let key_hash = Sha1::digest(&issuer.public_key().subject_public_key.data);
+        self.add_cert_id(&name_hash, &key_hash, certificate.raw_serial())
+    }
+
+    /// Add a request using precomputed SHA-1 hashes of the issuer name and public key
+    ///
+    /// `issuer_name_hash` is the hash of the DER encoding of the issuer name, and
+    /// `issuer_key_hash` is the hash of the issuer public key (the value of the
+    /// `subjectPublicKey` BIT STRING). `serial` is the content of the DER integer encoding of
+    /// the certificate serial number (see [`X509Certificate::raw_serial`]).
+    ///
+    /// Returns [`X509Error::InvalidSerial`] if `serial` is empty (this is not a valid INTEGER
+    /// encoding).
+    pub fn add_cert_id(
+        mut self,
+        issuer_name_hash: &[u8],
+        issuer_key_hash: &[u8],
+        serial: &[u8],
+    ) -> Result<Self, X509Error> {
+        if serial.is_empty() {
+            return Err(X509Error::InvalidSerial);
+        }
+        let mut algorithm = der_tlv(0x06, OID_HASH_SHA1.as_bytes());
+        algorithm.extend_from_slice(&[0x05, 0x00]);
+        let mut content = der_tlv(0x30, &algorithm);
+        content.extend(der_tlv(0x04, issuer_name_hash));
+        content.extend(der_tlv(0x04, issuer_key_hash));
+        content.extend(der_tlv(0x02, serial));
+        self.cert_ids.push(der_tlv(0x30, &content));
+        Ok(self)
+    }
+
+    /// Add a nonce extension to the request
+    ///
+    /// The nonce is echoed by the responder, to prevent replay attacks. Note that many
+    /// responders ignore nonces, and return pre-signed responses.
+    pub fn with_nonce(mut self, nonce: &[u8]) -> Self {
+        self.nonce = Some(nonce.to_vec());
+        self
+    }
+
+    /// Build the DER encoding of the `OCSPRequest`
+    pub fn build(&self) -> Vec<u8> {
+        let requests: Vec<u8> = self
+            .cert_ids
+            .iter()
+            .flat_map(|cert_id| der_tlv(0x30, cert_id))
+            .collect();
+        let mut tbs_request = der_tlv(0x30, &requests);
+        if let Some(nonce) = &self.nonce {
+            let mut extension = der_tlv(0x06, OID_PKIX_OCSP_NONCE.as_bytes());
+            extension.extend(der_tlv(0x04, &der_tlv(0x04, nonce)));
+            let extensions = der_tlv(0x30, &der_tlv(0x30, &extension));
+            tbs_request.extend(der_tlv(0xa2, &extensions));
+        }
+        der_tlv(0x30, &der_tlv(0x30, &tbs_request))
+    }
+}
+
+/// Policy used to compute how long a verified OCSP response may be cached
+///
+/// Servers implementing OCSP stapling should fetch a new response when the duration returned by
+/// [`OcspCachePolicy::cache_duration`] has elapsed.
+///
+/// # Example
+///
+/// ```rust
+/// use ::time::Duration;
+/// use x509_parser::ocsp::{OCSPResponse, OcspCachePolicy};
+/// use x509_parser::prelude::*;
+///
+/// # static DER: &'static [u8] = include_bytes!("../assets/ocsp-response.der");
+/// # fn main() {
+/// let (_, response) = OCSPResponse::from_der(DER).expect("OCSP response parsing failed");
+/// // `max-age` from the HTTP `Cache-Control` header
+/// let policy = OcspCachePolicy::new().with_max_age(Duration::hours(12));
+/// for single in response.basic_response().unwrap().iter_responses() {
+///     let duration = policy.cache_duration(single, ASN1Time::now());
+///     println!("Refresh in {} seconds", duration.whole_seconds());
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OcspCachePolicy {
+    /// The `max-age` directive of the HTTP response which carried the OCSP response, if any
+    /// (see [RFC5019 section 6](https://datatracker.ietf.org/doc/html/rfc5019#section-6))
+    pub max_age: Option<Duration>,
+    /// The lifetime of responses without `nextUpdate` (default: 1 hour)
+    pub default_lifetime: Duration,
+    /// Set if the certificate requires a stapled response (TLS Feature `status_request`)
+    ///
+    /// An expired stapled response makes the connections fail, so the response is refreshed
+    /// halfway through its validity period, leaving time to retry.
+    pub must_staple: bool,
+}
+
+impl OcspCachePolicy {
+    /// Create a new policy, without `max-age` and must-staple
+    pub fn new() -> Self {
+        OcspCachePolicy::default()
+    }
+
+    /// Create a new policy for the responses of `cert`, setting `must_staple` if the certificate
+    /// has a TLS Feature extension containing `status_request`
+    pub fn for_certificate(cert: &X509Certificate) -> Self {
+        OcspCachePolicy {
+            must_staple: has_must_staple(cert),
+            ..OcspCachePolicy::default()
+        }
+    }
+
+    /// Set the `max-age` directive of the HTTP response
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        OcspCachePolicy {
+            max_age: Some(max_age),
+            ..self
+        }
+    }
+
+    /// Compute how long `response` may be cached, starting at `now`
+    ///
+    /// The duration is bounded by the `nextUpdate` of the response (or `default_lifetime`
+    /// after `thisUpdate`), by `max_age`, and by the middle of the validity period if
+    /// `must_staple` is set. A zero duration means that a new response must be fetched.
+    ///
+    /// The signature of the response must have been verified before.
+    pub fn cache_duration(&self, response: &SingleResponse, now: ASN1Time) -> Duration {
+        let expires = match response.next_update {
+            Some(next_update) => Some(next_update),
+            None => response.this_update + self.default_lifetime,
+        };
+        let refresh_at = match expires {
+            Some(expires) if self.must_staple => (expires - response.this_update)
+                .and_then(|validity| response.this_update + validity / 2),
+            _ => expires,
+        };
+        let duration = refresh_at
+            .and_then(|refresh_at| refresh_at - now)
+            .unwrap_or(Duration::ZERO);
+        match self.max_age {
+            Some(max_age) => duration.min(max_age),
+            None => duration,
+        }
+    }
+}
+
+impl Default for OcspCachePolicy {
+    fn default() -> Self {
+        OcspCachePolicy {
+            max_age: None,
+            default_lifetime: Duration::hours(1),
+            must_staple: false,
+        }
+    }
+}
+
+// Test if the TLS Feature extension of `cert` contains `status_request` (5)
+fn has_must_staple(cert: &X509Certificate) -> bool {
+    let ext = match cert.get_extension_unique(&OID_PE_TLS_FEATURE) {
+        Ok(Some(ext)) => ext,
+        _ => return false,
+    };
+    match parse_der_sequence_of_v(parse_der_u32)(ext.value) {
+        Ok((_, features)) => features.contains(&5),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ocsp_response_error_status() {
+        // OCSPResponse with status tryLater, and no response bytes
+        let data = &[0x30, 0x03, 0x0a, 0x01, 0x03];
+        let (rem, response) = OCSPResponse::from_der(data).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(response.response_status, OCSPResponseStatus::TryLater);
+        assert!(!response.is_successful());
+        assert!(response.basic_response().is_none());
+        // invalid status
+        let data = &[0x30, 0x04, 0x0a, 0x02, 0x01, 0x00];
+        assert!(OCSPResponse::from_der(data).is_err());
+    }
+
+    #[test]
+    fn test_ocsp_cert_status() {
+        let (_, status) = CertStatus::from_der(&[0x80, 0x00]).expect("parsing failed");
+        assert_eq!(status, CertStatus::Good);
+        let (_, status) = CertStatus::from_der(&[0x82, 0x00]).expect("parsing failed");
+        assert_eq!(status, CertStatus::Unknown);
+        // revoked, GeneralizedTime 20230101000000Z, reason keyCompromise
+        let data = &[
+            0xa1, 0x16, 0x18, 0x0f, 0x32, 0x30, 0x32, 0x33, 0x30, 0x31, 0x30, 0x31, 0x30, 0x30,
+            0x30, 0x30, 0x30, 0x30, 0x5a, 0xa0, 0x03, 0x0a, 0x01, 0x01,
+        ];
+        let (rem, status) = CertStatus::from_der(data).expect("parsing failed");
+        assert!(rem.is_empty());
+        match status {
+            CertStatus::Revoked(info) => {
+                assert_eq!(info.revocation_time.timestamp(), 1_672_531_200);
+                assert_eq!(info.revocation_reason, Some(ReasonCode::KeyCompromise));
+            }
+            _ => panic!("unexpected status {:?}", status),
+        }
+        assert!(CertStatus::from_der(&[0x83, 0x00]).is_err());
+        // NULL with a content
+        assert!(CertStatus::from_der(&[0x80, 0x01, 0x00]).is_err());
+        assert!(CertStatus::from_der(&[0x82, 0x01, 0x00]).is_err());
+        // unused reason code 7
+        let mut data = data.to_vec();
+        data[23] = 0x07;
+        assert!(CertStatus::from_der(&data).is_err());
+    }
+
+    #[test]
+    fn test_ocsp_request_builder() {
+        // generated by `openssl ocsp -issuer test-ca.pem -cert test-leaf.pem -no_nonce`
+        const EXPECTED: &[u8] = &[
+            0x30, 0x43, 0x30, 0x41, 0x30, 0x3f, 0x30, 0x3d, 0x30, 0x3b, 0x30, 0x09, 0x06, 0x05,
+            0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14, 0x23, 0x1a, 0xba, 0xf9, 0x4e,
+            0x31, 0x6c, 0xc3, 0x2e, 0x1b, 0x59, 0x5d, 0x6c, 0x89, 0xe7, 0x50, 0xaa, 0x1f, 0xa2,
+            0x8e, 0x04, 0x14, 0x12, 0x5b, 0xcd, 0x17, 0xd4, 0xa1, 0x0a, 0xa7, 0x3c, 0xdf, 0xb1,
+            0x6a, 0xb1, 0x7f, 0xba, 0xc2, 0xad, 0xc0, 0x96, 0xf8, 0x02, 0x02, 0x10, 0x01,
+        ];
+        let name_hash = &EXPECTED[23..43];
+        let key_hash = &EXPECTED[45..65];
+        let request = OcspRequestBuilder::new()
+            .add_cert_id(name_hash, key_hash, &[0x10, 0x01])
+            .unwrap()
+            .build();
+        assert_eq!(request, EXPECTED);
+
+        #[cfg(feature = "hash")]
+        {
+            let (_, issuer) =
+                X509Certificate::from_der(include_bytes!("../assets/test-ca.der")).unwrap();
+            let (_, leaf) =
+                X509Certificate::from_der(include_bytes!("../assets/test-leaf.der")).unwrap();
+            let request = OcspRequestBuilder::new()
+                .add_certificate(&leaf, &issuer)
+                .unwrap()
+                .build();
+            assert_eq!(request, EXPECTED);
+        }
+
+        let request = OcspRequestBuilder::new()
+            .add_cert_id(name_hash, key_hash, &[0x10, 0x01])
+            .unwrap()
+            .with_nonce(&[0xaa; 16])
+            .build();
+        assert_eq!(&request[..2], &[0x30, 0x68]);
+        let mut nonce_ext = vec![0xa2, 0x23, 0x30, 0x21, 0x30, 0x1f, 0x06, 0x09];
+        nonce_ext.extend_from_slice(OID_PKIX_OCSP_NONCE.as_bytes());
+        nonce_ext.extend_from_slice(&[0x04, 0x12, 0x04, 0x10]);
+        nonce_ext.extend_from_slice(&[0xaa; 16]);
+        assert!(request.ends_with(&nonce_ext));
+
+        // empty serial
+        let res = OcspRequestBuilder::new().add_cert_id(name_hash, key_hash, &[]);
+        assert_eq!(res.unwrap_err(), X509Error::InvalidSerial);
+    }
+}
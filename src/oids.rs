@@ -0,0 +1,388 @@
+//! OIDs of the extensions and attributes understood by this crate
+//!
+//! This module re-exports the OIDs of all extensions and certification request attributes
+//! which are parsed by this crate, so they can be used without depending on `oid_registry`.
+//! Each OID is paired with the kind of parsed object it maps to, using [`ExtensionKind`]
+//! (variants of [`ParsedExtension`]) and [`AttributeKind`] (variants of [`ParsedCriAttribute`]).
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::oids::*;
+//!
+//! assert_eq!(
+//!     ExtensionKind::from_oid(&OID_X509_EXT_KEY_USAGE),
+//!     Some(ExtensionKind::KeyUsage)
+//! );
+//! for (oid, kind) in EXTENSION_OIDS {
+//!     println!("{}: {:?}", oid, kind);
+//! }
+//! ```
+
+use crate::cri_attributes::ParsedCriAttribute;
+use crate::extensions::ParsedExtension;
+use der_parser::oid::Oid;
+
+pub use crate::cri_attributes::{
+    OID_MS_OS_VERSION, OID_MS_RENEWAL_CERTIFICATE, OID_MS_REQUEST_CLIENT_INFO,
+};
+pub use crate::extensions::{
+    OID_CAN_SIGN_HTTP_EXCHANGES, OID_CT_PRECERT_POISON, OID_ISISMTT_AT_ADMISSION,
+    OID_MS_CERTIFICATE_TEMPLATE, OID_MS_ENROLL_CERTTYPE, OID_MS_NTDS_CA_SECURITY_EXT,
+    OID_PKIX_SUBJECT_INFO_ACCESS,
+};
+pub use crate::ocsp::OID_PKIX_OCSP_NOCHECK;
+pub use oid_registry::{
+    OID_CT_LIST_SCT, OID_PKCS9_CHALLENGE_PASSWORD, OID_PKCS9_EXTENSION_REQUEST,
+    OID_PKCS9_SMIME_CAPABILITIES, OID_PKIX_AUTHORITY_INFO_ACCESS,
+    OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER, OID_X509_EXT_BASE_URL, OID_X509_EXT_BASIC_CONSTRAINTS,
+    OID_X509_EXT_CERTIFICATE_POLICIES, OID_X509_EXT_CERT_COMMENT, OID_X509_EXT_CERT_TYPE,
+    OID_X509_EXT_CRL_DISTRIBUTION_POINTS, OID_X509_EXT_CRL_NUMBER, OID_X509_EXT_EXTENDED_KEY_USAGE,
+    OID_X509_EXT_FRESHEST_CRL, OID_X509_EXT_HOLD_INSTRUCTION_CODE,
+    OID_X509_EXT_INHIBITANT_ANY_POLICY, OID_X509_EXT_INVALIDITY_DATE, OID_X509_EXT_ISSUER,
+    OID_X509_EXT_ISSUER_ALT_NAME, OID_X509_EXT_ISSUER_DISTRIBUTION_POINT, OID_X509_EXT_KEY_USAGE,
+    OID_X509_EXT_NAME_CONSTRAINTS, OID_X509_EXT_POLICY_CONSTRAINTS, OID_X509_EXT_POLICY_MAPPINGS,
+    OID_X509_EXT_PRIVATE_KEY_USAGE_PERIOD, OID_X509_EXT_REASON_CODE, OID_X509_EXT_SUBJECT_ALT_NAME,
+    OID_X509_EXT_SUBJECT_KEY_IDENTIFIER,
+};
+
+/// The kind of a parsed extension, named after the corresponding [`ParsedExtension`] variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExtensionKind {
+    AuthorityKeyIdentifier,
+    SubjectKeyIdentifier,
+    KeyUsage,
+    CertificatePolicies,
+    PolicyMappings,
+    SubjectAlternativeName,
+    IssuerAlternativeName,
+    BasicConstraints,
+    NameConstraints,
+    PolicyConstraints,
+    ExtendedKeyUsage,
+    CRLDistributionPoints,
+    InhibitAnyPolicy,
+    FreshestCRL,
+    PrivateKeyUsagePeriod,
+    AuthorityInfoAccess,
+    SubjectInfoAccess,
+    NSCertType,
+    NsCertComment,
+    NsBaseUrl,
+    CRLNumber,
+    IssuingDistributionPoint,
+    ReasonCode,
+    InvalidityDate,
+    CertificateIssuer,
+    HoldInstructionCode,
+    SCT,
+    CtPrecertPoison,
+    OcspNoCheck,
+    CanSignHttpExchanges,
+    NtdsCaSecurity,
+    EnrollCertType,
+    CertificateTemplate,
+    SMIMECapabilities,
+    Admission,
+}
+
+/// The extensions parsed by this crate, and the kind of the parsed value
+///
+/// The CRL number extension is only parsed if the `bigint` feature is enabled.
+pub const EXTENSION_OIDS: &[(Oid<'static>, ExtensionKind)] = &[
+    (
+        OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER,
+        ExtensionKind::AuthorityKeyIdentifier,
+    ),
+    (
+        OID_X509_EXT_SUBJECT_KEY_IDENTIFIER,
+        ExtensionKind::SubjectKeyIdentifier,
+    ),
+    (OID_X509_EXT_KEY_USAGE, ExtensionKind::KeyUsage),
+    (
+        OID_X509_EXT_CERTIFICATE_POLICIES,
+        ExtensionKind::CertificatePolicies,
+    ),
+    (OID_X509_EXT_POLICY_MAPPINGS, ExtensionKind::PolicyMappings),
+    (
+        OID_X509_EXT_SUBJECT_ALT_NAME,
+        ExtensionKind::SubjectAlternativeName,
+    ),
+    (
+        OID_X509_EXT_ISSUER_ALT_NAME,
+        ExtensionKind::IssuerAlternativeName,
+    ),
+    (
+        OID_X509_EXT_BASIC_CONSTRAINTS,
+        ExtensionKind::BasicConstraints,
+    ),
+    (
+        OID_X509_EXT_NAME_CONSTRAINTS,
+        ExtensionKind::NameConstraints,
+    ),
+    (
+        OID_X509_EXT_POLICY_CONSTRAINTS,
+        ExtensionKind::PolicyConstraints,
+    ),
+    (
+        OID_X509_EXT_EXTENDED_KEY_USAGE,
+        ExtensionKind::ExtendedKeyUsage,
+    ),
+    (
+        OID_X509_EXT_CRL_DISTRIBUTION_POINTS,
+        ExtensionKind::CRLDistributionPoints,
+    ),
+    (
+        OID_X509_EXT_INHIBITANT_ANY_POLICY,
+        ExtensionKind::InhibitAnyPolicy,
+    ),
+    (OID_X509_EXT_FRESHEST_CRL, ExtensionKind::FreshestCRL),
+    (
+        OID_X509_EXT_PRIVATE_KEY_USAGE_PERIOD,
+        ExtensionKind::PrivateKeyUsagePeriod,
+    ),
+    (
+        OID_PKIX_AUTHORITY_INFO_ACCESS,
+        ExtensionKind::AuthorityInfoAccess,
+    ),
+    (
+        OID_PKIX_SUBJECT_INFO_ACCESS,
+        ExtensionKind::SubjectInfoAccess,
+    ),
+    (OID_X509_EXT_CERT_TYPE, ExtensionKind::NSCertType),
+    (OID_X509_EXT_CERT_COMMENT, ExtensionKind::NsCertComment),
+    (OID_X509_EXT_BASE_URL, ExtensionKind::NsBaseUrl),
+    (OID_X509_EXT_CRL_NUMBER, ExtensionKind::CRLNumber),
+    (
+        OID_X509_EXT_ISSUER_DISTRIBUTION_POINT,
+        ExtensionKind::IssuingDistributionPoint,
+    ),
+    (OID_X509_EXT_REASON_CODE, ExtensionKind::ReasonCode),
+    (OID_X509_EXT_INVALIDITY_DATE, ExtensionKind::InvalidityDate),
+    (OID_X509_EXT_ISSUER, ExtensionKind::CertificateIssuer),
+    (
+        OID_X509_EXT_HOLD_INSTRUCTION_CODE,
+        ExtensionKind::HoldInstructionCode,
+    ),
+    (OID_CT_LIST_SCT, ExtensionKind::SCT),
+    (OID_CT_PRECERT_POISON, ExtensionKind::CtPrecertPoison),
+    (OID_PKIX_OCSP_NOCHECK, ExtensionKind::OcspNoCheck),
+    (
+        OID_CAN_SIGN_HTTP_EXCHANGES,
+        ExtensionKind::CanSignHttpExchanges,
+    ),
+    (OID_MS_NTDS_CA_SECURITY_EXT, ExtensionKind::NtdsCaSecurity),
+    (OID_MS_ENROLL_CERTTYPE, ExtensionKind::EnrollCertType),
+    (
+        OID_MS_CERTIFICATE_TEMPLATE,
+        ExtensionKind::CertificateTemplate,
+    ),
+    (
+        OID_PKCS9_SMIME_CAPABILITIES,
+        ExtensionKind::SMIMECapabilities,
+    ),
+    (OID_ISISMTT_AT_ADMISSION, ExtensionKind::Admission),
+];
+
+impl ExtensionKind {
+    /// Returns the kind of the extension with the given `Oid`, if it is parsed by this crate
+    pub fn from_oid(oid: &Oid) -> Option<ExtensionKind> {
+        EXTENSION_OIDS
+            .iter()
+            .find(|(o, _)| o == oid)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Returns the `Oid` of this kind of extension
+    pub fn oid(self) -> Oid<'static> {
+        match self {
+            ExtensionKind::AuthorityKeyIdentifier => OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER,
+            ExtensionKind::SubjectKeyIdentifier => OID_X509_EXT_SUBJECT_KEY_IDENTIFIER,
+            ExtensionKind::KeyUsage => OID_X509_EXT_KEY_USAGE,
+            ExtensionKind::CertificatePolicies => OID_X509_EXT_CERTIFICATE_POLICIES,
+            ExtensionKind::PolicyMappings => OID_X509_EXT_POLICY_MAPPINGS,
+            ExtensionKind::SubjectAlternativeName => OID_X509_EXT_SUBJECT_ALT_NAME,
+            ExtensionKind::IssuerAlternativeName => OID_X509_EXT_ISSUER_ALT_NAME,
+            ExtensionKind::BasicConstraints => OID_X509_EXT_BASIC_CONSTRAINTS,
+            ExtensionKind::NameConstraints => OID_X509_EXT_NAME_CONSTRAINTS,
+            ExtensionKind::PolicyConstraints => OID_X509_EXT_POLICY_CONSTRAINTS,
+            ExtensionKind::ExtendedKeyUsage => OID_X509_EXT_EXTENDED_KEY_USAGE,
+            ExtensionKind::CRLDistributionPoints => OID_X509_EXT_CRL_DISTRIBUTION_POINTS,
+            ExtensionKind::InhibitAnyPolicy => OID_X509_EXT_INHIBITANT_ANY_POLICY,
+            ExtensionKind::FreshestCRL => OID_X509_EXT_FRESHEST_CRL,
+            ExtensionKind::PrivateKeyUsagePeriod => OID_X509_EXT_PRIVATE_KEY_USAGE_PERIOD,
+            ExtensionKind::AuthorityInfoAccess => OID_PKIX_AUTHORITY_INFO_ACCESS,
+            ExtensionKind::SubjectInfoAccess => OID_PKIX_SUBJECT_INFO_ACCESS,
+            ExtensionKind::NSCertType => OID_X509_EXT_CERT_TYPE,
+            ExtensionKind::NsCertComment => OID_X509_EXT_CERT_COMMENT,
+            ExtensionKind::NsBaseUrl => OID_X509_EXT_BASE_URL,
+            ExtensionKind::CRLNumber => OID_X509_EXT_CRL_NUMBER,
+            ExtensionKind::IssuingDistributionPoint => OID_X509_EXT_ISSUER_DISTRIBUTION_POINT,
+            ExtensionKind::ReasonCode => OID_X509_EXT_REASON_CODE,
+            ExtensionKind::InvalidityDate => OID_X509_EXT_INVALIDITY_DATE,
+            ExtensionKind::CertificateIssuer => OID_X509_EXT_ISSUER,
+            ExtensionKind::HoldInstructionCode => OID_X509_EXT_HOLD_INSTRUCTION_CODE,
+            ExtensionKind::SCT => OID_CT_LIST_SCT,
+            ExtensionKind::CtPrecertPoison => OID_CT_PRECERT_POISON,
+            ExtensionKind::OcspNoCheck => OID_PKIX_OCSP_NOCHECK,
+            ExtensionKind::CanSignHttpExchanges => OID_CAN_SIGN_HTTP_EXCHANGES,
+            ExtensionKind::NtdsCaSecurity => OID_MS_NTDS_CA_SECURITY_EXT,
+            ExtensionKind::EnrollCertType => OID_MS_ENROLL_CERTTYPE,
+            ExtensionKind::CertificateTemplate => OID_MS_CERTIFICATE_TEMPLATE,
+            ExtensionKind::SMIMECapabilities => OID_PKCS9_SMIME_CAPABILITIES,
+            ExtensionKind::Admission => OID_ISISMTT_AT_ADMISSION,
+        }
+    }
+}
+
+impl<'a> ParsedExtension<'a> {
+    /// Returns the kind of this parsed extension
+    ///
+    /// Returns `None` for unsupported, unparsed or invalid extensions.
+    pub fn kind(&self) -> Option<ExtensionKind> {
+        let kind = match self {
+            ParsedExtension::AuthorityKeyIdentifier(_) => ExtensionKind::AuthorityKeyIdentifier,
+            ParsedExtension::SubjectKeyIdentifier(_) => ExtensionKind::SubjectKeyIdentifier,
+            ParsedExtension::KeyUsage(_) => ExtensionKind::KeyUsage,
+            ParsedExtension::CertificatePolicies(_) => ExtensionKind::CertificatePolicies,
+            ParsedExtension::PolicyMappings(_) => ExtensionKind::PolicyMappings,
+            ParsedExtension::SubjectAlternativeName(_) => ExtensionKind::SubjectAlternativeName,
+            ParsedExtension::IssuerAlternativeName(_) => ExtensionKind::IssuerAlternativeName,
+            ParsedExtension::BasicConstraints(_) => ExtensionKind::BasicConstraints,
+            ParsedExtension::NameConstraints(_) => ExtensionKind::NameConstraints,
+            ParsedExtension::PolicyConstraints(_) => ExtensionKind::PolicyConstraints,
+            ParsedExtension::ExtendedKeyUsage(_) => ExtensionKind::ExtendedKeyUsage,
+            ParsedExtension::CRLDistributionPoints(_) => ExtensionKind::CRLDistributionPoints,
+            ParsedExtension::InhibitAnyPolicy(_) => ExtensionKind::InhibitAnyPolicy,
+            ParsedExtension::FreshestCRL(_) => ExtensionKind::FreshestCRL,
+            ParsedExtension::PrivateKeyUsagePeriod(_) => ExtensionKind::PrivateKeyUsagePeriod,
+            ParsedExtension::AuthorityInfoAccess(_) => ExtensionKind::AuthorityInfoAccess,
+            ParsedExtension::SubjectInfoAccess(_) => ExtensionKind::SubjectInfoAccess,
+            ParsedExtension::NSCertType(_) => ExtensionKind::NSCertType,
+            ParsedExtension::NsCertComment(_) => ExtensionKind::NsCertComment,
+            ParsedExtension::NsBaseUrl(_) => ExtensionKind::NsBaseUrl,
+            ParsedExtension::CRLNumber(_) => ExtensionKind::CRLNumber,
+            ParsedExtension::IssuingDistributionPoint(_) => ExtensionKind::IssuingDistributionPoint,
+            ParsedExtension::ReasonCode(_) => ExtensionKind::ReasonCode,
+            ParsedExtension::InvalidityDate(_) => ExtensionKind::InvalidityDate,
+            ParsedExtension::CertificateIssuer(_) => ExtensionKind::CertificateIssuer,
+            ParsedExtension::HoldInstructionCode(_) => ExtensionKind::HoldInstructionCode,
+            ParsedExtension::SCT(_) => ExtensionKind::SCT,
+            ParsedExtension::CtPrecertPoison => ExtensionKind::CtPrecertPoison,
+            ParsedExtension::OcspNoCheck => ExtensionKind::OcspNoCheck,
+            ParsedExtension::CanSignHttpExchanges => ExtensionKind::CanSignHttpExchanges,
+            ParsedExtension::NtdsCaSecurity(_) => ExtensionKind::NtdsCaSecurity,
+            ParsedExtension::EnrollCertType(_) => ExtensionKind::EnrollCertType,
+            ParsedExtension::CertificateTemplate(_) => ExtensionKind::CertificateTemplate,
+            ParsedExtension::SMIMECapabilities(_) => ExtensionKind::SMIMECapabilities,
+            ParsedExtension::Admission(_) => ExtensionKind::Admission,
+            ParsedExtension::UnsupportedExtension { .. }
+            | ParsedExtension::ParseError { .. }
+            | ParsedExtension::Unparsed => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// The kind of a parsed CRI attribute, named after the corresponding [`ParsedCriAttribute`]
+/// variant
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AttributeKind {
+    ChallengePassword,
+    ExtensionRequest,
+    OsVersion,
+    RequestClientInfo,
+    RenewalCertificate,
+    SMIMECapabilities,
+}
+
+/// The certification request attributes parsed by this crate, and the kind of the parsed value
+pub const ATTRIBUTE_OIDS: &[(Oid<'static>, AttributeKind)] = &[
+    (
+        OID_PKCS9_CHALLENGE_PASSWORD,
+        AttributeKind::ChallengePassword,
+    ),
+    (OID_PKCS9_EXTENSION_REQUEST, AttributeKind::ExtensionRequest),
+    (OID_MS_OS_VERSION, AttributeKind::OsVersion),
+    (OID_MS_REQUEST_CLIENT_INFO, AttributeKind::RequestClientInfo),
+    (
+        OID_MS_RENEWAL_CERTIFICATE,
+        AttributeKind::RenewalCertificate,
+    ),
+    (
+        OID_PKCS9_SMIME_CAPABILITIES,
+        AttributeKind::SMIMECapabilities,
+    ),
+];
+
+impl AttributeKind {
+    /// Returns the kind of the attribute with the given `Oid`, if it is parsed by this crate
+    pub fn from_oid(oid: &Oid) -> Option<AttributeKind> {
+        ATTRIBUTE_OIDS
+            .iter()
+            .find(|(o, _)| o == oid)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// Returns the `Oid` of this kind of attribute
+    pub fn oid(self) -> Oid<'static> {
+        match self {
+            AttributeKind::ChallengePassword => OID_PKCS9_CHALLENGE_PASSWORD,
+            AttributeKind::ExtensionRequest => OID_PKCS9_EXTENSION_REQUEST,
+            AttributeKind::OsVersion => OID_MS_OS_VERSION,
+            AttributeKind::RequestClientInfo => OID_MS_REQUEST_CLIENT_INFO,
+            AttributeKind::RenewalCertificate => OID_MS_RENEWAL_CERTIFICATE,
+            AttributeKind::SMIMECapabilities => OID_PKCS9_SMIME_CAPABILITIES,
+        }
+    }
+}
+
+impl<'a> ParsedCriAttribute<'a> {
+    /// Returns the kind of this parsed attribute
+    ///
+    /// Returns `None` for unsupported attributes.
+    pub fn kind(&self) -> Option<AttributeKind> {
+        let kind = match self {
+            ParsedCriAttribute::ChallengePassword(_) => AttributeKind::ChallengePassword,
+            ParsedCriAttribute::ExtensionRequest(_) => AttributeKind::ExtensionRequest,
+            ParsedCriAttribute::OsVersion(_) => AttributeKind::OsVersion,
+            ParsedCriAttribute::RequestClientInfo(_) => AttributeKind::RequestClientInfo,
+            ParsedCriAttribute::RenewalCertificate(_) => AttributeKind::RenewalCertificate,
+            ParsedCriAttribute::SMIMECapabilities(_) => AttributeKind::SMIMECapabilities,
+            ParsedCriAttribute::UnsupportedAttribute => return None,
+        };
+        Some(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_oids_match_parsers() {
+        let table: HashSet<_> = EXTENSION_OIDS.iter().map(|(oid, _)| oid).collect();
+        let parsers: HashSet<_> = crate::extensions::parser::supported_extensions().collect();
+        assert_eq!(table, parsers);
+        let table: HashSet<_> = ATTRIBUTE_OIDS.iter().map(|(oid, _)| oid).collect();
+        let parsers: HashSet<_> = crate::cri_attributes::parser::supported_attributes().collect();
+        assert_eq!(table, parsers);
+    }
+
+    #[test]
+    fn test_oids_kind_roundtrip() {
+        for (oid, kind) in EXTENSION_OIDS {
+            assert_eq!(ExtensionKind::from_oid(oid), Some(*kind));
+            assert_eq!(&kind.oid(), oid);
+        }
+        for (oid, kind) in ATTRIBUTE_OIDS {
+            assert_eq!(AttributeKind::from_oid(oid), Some(*kind));
+            assert_eq!(&kind.oid(), oid);
+        }
+        assert_eq!(
+            ExtensionKind::from_oid(&oid_registry::OID_X509_COMMON_NAME),
+            None
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Public key pinning, using SPKI pins
+//!
+//! A pin is the SHA-256 hash of the DER-encoded `SubjectPublicKeyInfo` of a certificate, as
+//! defined in [RFC7469](https://datatracker.ietf.org/doc/html/rfc7469) (HTTP Public Key Pinning).
+//! Pins are usually written in base64, like the `pin-sha256` directive:
+//!
+//! ```text
+//! openssl x509 -in cert.pem -pubkey -noout | openssl pkey -pubin -outform der \
+//!     | openssl dgst -sha256 -binary | base64
+//! ```
+//!
+//! A chain is accepted if the key of any of its certificates (leaf, intermediate or root)
+//! matches a pin. Following RFC7469, a pin set must also contain at least one backup pin, which
+//! does not match any key of the chain, so that a lost or compromised key can be replaced.
+//!
+//! Note that pinning is an additional check: the chain must still be validated.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::pinning::{validate_chain_pins, PinSet};
+//! use x509_parser::prelude::*;
+//!
+//! # static CA: &'static [u8] = include_bytes!("../assets/test-ca.der");
+//! # static LEAF: &'static [u8] = include_bytes!("../assets/test-leaf.der");
+//! # fn main() {
+//! let (_, leaf) = X509Certificate::from_der(LEAF).expect("could not parse certificate");
+//! let (_, ca) = X509Certificate::from_der(CA).expect("could not parse certificate");
+//! let pins = PinSet::from_base64(&[
+//!     // the key of the CA
+//!     "cbb9PGWcxSnl00Sr9zusb9RAFFXkIP3Upv5xkh4g20w=",
+//!     // a backup key, stored offline
+//!     "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=",
+//! ])
+//! .expect("invalid pin");
+//! assert!(validate_chain_pins(&[leaf, ca], &pins).is_ok());
+//! # }
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::error::X509Error;
+use crate::x509::SubjectPublicKeyInfo;
+use data_encoding::BASE64;
+use sha2::{Digest, Sha256};
+
+/// Compute the SPKI pin (SHA-256 of the DER-encoded `SubjectPublicKeyInfo`) of a public key
+pub fn spki_pin(spki: &SubjectPublicKeyInfo) -> [u8; 32] {
+    Sha256::digest(spki.raw).into()
+}
+
+/// Compute the SPKI pin of a public key, encoded in base64 (the `pin-sha256` format)
+pub fn spki_pin_base64(spki: &SubjectPublicKeyInfo) -> String {
+    BASE64.encode(&spki_pin(spki))
+}
+
+/// A set of expected SPKI pins
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PinSet {
+    pins: Vec<[u8; 32]>,
+}
+
+impl PinSet {
+    /// Create an empty pin set
+    pub fn new() -> Self {
+        PinSet::default()
+    }
+
+    /// Create a pin set from base64-encoded pins
+    ///
+    /// Returns `InvalidPin` if a pin is not valid base64, or is not 32 bytes long.
+    pub fn from_base64(pins: &[&str]) -> Result<Self, X509Error> {
+        let mut set = PinSet::new();
+        for pin in pins {
+            set.add_base64(pin)?;
+        }
+        Ok(set)
+    }
+
+    /// Add a pin (SHA-256 hash of a DER-encoded `SubjectPublicKeyInfo`)
+    pub fn add_pin(&mut self, pin: [u8; 32]) {
+        if !self.pins.contains(&pin) {
+            self.pins.push(pin);
+        }
+    }
+
+    /// Add a base64-encoded pin
+    pub fn add_base64(&mut self, pin: &str) -> Result<(), X509Error> {
+        let bytes = BASE64
+            .decode(pin.trim().as_bytes())
+            .or(Err(X509Error::InvalidPin))?;
+        let mut pin = [0u8; 32];
+        if bytes.len() != pin.len() {
+            return Err(X509Error::InvalidPin);
+        }
+        pin.copy_from_slice(&bytes);
+        self.add_pin(pin);
+        Ok(())
+    }
+
+    /// Add the pin of a public key
+    pub fn add_spki(&mut self, spki: &SubjectPublicKeyInfo) {
+        self.add_pin(spki_pin(spki));
+    }
+
+    /// Return an iterator over the pins
+    pub fn iter(&self) -> impl Iterator<Item = &[u8; 32]> {
+        self.pins.iter()
+    }
+
+    /// Return the number of pins
+    pub fn len(&self) -> usize {
+        self.pins.len()
+    }
+
+    /// Return `true` if the pin set is empty
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    /// Test if the public key `spki` matches one of the pins
+    pub fn contains(&self, spki: &SubjectPublicKeyInfo) -> bool {
+        self.pins.contains(&spki_pin(spki))
+    }
+
+    /// Return the first certificate of `chain` whose public key matches one of the pins
+    pub fn find_match<'a, 'b>(
+        &self,
+        chain: &'b [X509Certificate<'a>],
+    ) -> Option<&'b X509Certificate<'a>> {
+        chain.iter().find(|cert| self.contains(cert.public_key()))
+    }
+
+    /// Test if the pin set contains a backup pin, matching none of the keys of `chain`
+    pub fn has_backup_pin(&self, chain: &[X509Certificate]) -> bool {
+        let chain_pins: Vec<_> = chain
+            .iter()
+            .map(|cert| spki_pin(cert.public_key()))
+            .collect();
+        self.pins.iter().any(|pin| !chain_pins.contains(pin))
+    }
+}
+
+/// Check the keys of a certificate chain against a pin set (HPKP-style)
+///
+/// The chain is accepted if the public key of any of its certificates matches a pin.
+/// The pin set must also contain a backup pin (see [`PinSet::has_backup_pin`]).
+///
+/// Returns `MissingBackupPin` if the pin set has no backup pin, or `PinMismatch` if no key of the
+/// chain matches the pin set.
+pub fn validate_chain_pins(chain: &[X509Certificate], pinset: &PinSet) -> Result<(), X509Error> {
+    if !pinset.has_backup_pin(chain) {
+        return Err(X509Error::MissingBackupPin);
+    }
+    match pinset.find_match(chain) {
+        Some(_) => Ok(()),
+        None => Err(X509Error::PinMismatch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_x509_certificate;
+
+    #[test]
+    fn test_spki_pin() {
+        let (_, ca) = parse_x509_certificate(include_bytes!("../assets/test-ca.der")).unwrap();
+        assert_eq!(
+            spki_pin_base64(ca.public_key()),
+            "cbb9PGWcxSnl00Sr9zusb9RAFFXkIP3Upv5xkh4g20w="
+        );
+    }
+
+    #[test]
+    fn test_pinset_invalid() {
+        let mut pins = PinSet::new();
+        assert_eq!(pins.add_base64("not base64!"), Err(X509Error::InvalidPin));
+        // valid base64, but not a SHA-256 hash
+        assert_eq!(pins.add_base64("AAAA"), Err(X509Error::InvalidPin));
+        assert!(pins.is_empty());
+    }
+}
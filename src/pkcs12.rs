@@ -0,0 +1,546 @@
+//! PKCS#12 (`.p12` / `.pfx`) containers
+//!
+//! PKCS#12 files bundle a certificate chain with the corresponding private key, and are the
+//! usual format to import or export identities. The structures are defined in
+//! [RFC7292](https://datatracker.ietf.org/doc/html/rfc7292).
+//!
+//! Only the password integrity mode is supported. The MAC is not verified, and private key bags
+//! are kept in raw form: this module is only meant to extract certificates. The input must be
+//! DER-encoded.
+//!
+//! The contents of the `AuthenticatedSafe` can be encrypted. Unencrypted certificate bags are
+//! available directly from [`Pfx::iter_certificates`]. If the `decrypt` feature is enabled,
+//! [`Pfx::decrypt_certificates`] also decrypts the encrypted contents, which is required for
+//! files created with default options by most tools. Only PBES2 (PBKDF2 and AES-CBC) is
+//! supported: the legacy PKCS#12 algorithms (RC2, RC4 or 3DES) return an error.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::pkcs12::parse_pkcs12;
+//!
+//! # static DER: &'static [u8] = include_bytes!("../assets/test-nocrypt.p12");
+//! #
+//! # fn main() {
+//! let (_, pfx) = parse_pkcs12(DER).expect("PKCS#12 parsing failed");
+//! for cert in pfx.iter_certificates() {
+//!     println!("Certificate: {}", cert.subject());
+//! }
+//! # }
+//! ```
+
+use crate::certificate::X509Certificate;
+#[cfg(feature = "decrypt")]
+use crate::certificate::X509CertificateOwned;
+use crate::error::{X509Error, X509Result};
+use crate::x509::AlgorithmIdentifier;
+use asn1_rs::{oid, Any, BmpString, Class, FromDer, Oid, Tag};
+use der_parser::der::*;
+use nom::combinator::{all_consuming, complete};
+use nom::multi::many0;
+use nom::Err;
+use oid_registry::{OID_PKCS7_ID_DATA, OID_PKCS7_ID_ENCRYPTED_DATA, OID_PKCS9_FRIENDLY_NAME};
+
+/// PKCS#12 certificate bag
+pub const OID_PKCS12_CERT_BAG: Oid<'static> = oid!(1.2.840 .113549 .1 .12 .10 .1 .3);
+/// Certificate type of a certificate bag containing a DER-encoded X.509 certificate
+pub const OID_PKCS9_X509_CERTIFICATE: Oid<'static> = oid!(1.2.840 .113549 .1 .9 .22 .1);
+
+/// A PKCS#12 PFX (the top-level structure of a `.p12` file)
+///
+/// <pre>
+/// PFX ::= SEQUENCE {
+///     version     INTEGER {v3(3)}(v3,...),
+///     authSafe    ContentInfo,
+///     macData     MacData OPTIONAL }
+///
+/// AuthenticatedSafe ::= SEQUENCE OF ContentInfo
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct Pfx<'a> {
+    pub version: u32,
+    /// The items of the `AuthenticatedSafe`
+    pub auth_safe: Vec<AuthenticatedSafeContent<'a>>,
+    /// The raw `MacData` sequence, if present (the MAC is not verified)
+    pub mac_data: Option<&'a [u8]>,
+}
+
+impl<'a> Pfx<'a> {
+    /// Return an iterator over the bags of the unencrypted contents
+    pub fn iter_safe_bags(&self) -> impl Iterator<Item = &SafeBag<'a>> {
+        self.auth_safe
+            .iter()
+            .filter_map(|content| match content {
+                AuthenticatedSafeContent::Data(bags) => Some(bags.iter()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Return an iterator over the certificates of the unencrypted contents
+    pub fn iter_certificates(&self) -> impl Iterator<Item = &X509Certificate<'a>> {
+        self.iter_safe_bags().filter_map(SafeBag::certificate)
+    }
+
+    /// Test if some contents are encrypted
+    ///
+    /// Certificates stored in encrypted contents are not returned by
+    /// [`iter_certificates`](Self::iter_certificates).
+    pub fn has_encrypted_content(&self) -> bool {
+        self.auth_safe
+            .iter()
+            .any(|content| matches!(content, AuthenticatedSafeContent::EncryptedData(_)))
+    }
+
+    /// Return all certificates, decrypting the encrypted contents using `password`
+    ///
+    /// Certificates are returned in the order of the file. Contents of unsupported types are
+    /// ignored.
+    #[cfg(feature = "decrypt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "decrypt")))]
+    pub fn decrypt_certificates(
+        &self,
+        password: &str,
+    ) -> Result<Vec<X509CertificateOwned>, X509Error> {
+        let mut certificates = Vec::new();
+        for content in &self.auth_safe {
+            match content {
+                AuthenticatedSafeContent::Data(bags) => {
+                    certificates.extend(
+                        bags.iter()
+                            .filter_map(SafeBag::certificate)
+                            .filter_map(|cert| cert.to_owned().ok()),
+                    );
+                }
+                AuthenticatedSafeContent::EncryptedData(info) => {
+                    let data = info.decrypt(password)?;
+                    let (_, bags) =
+                        parse_safe_contents(&data).or(Err(X509Error::PKCS12DecryptionError))?;
+                    certificates.extend(
+                        bags.iter()
+                            .filter_map(SafeBag::certificate)
+                            .filter_map(|cert| cert.to_owned().ok()),
+                    );
+                }
+                AuthenticatedSafeContent::Unsupported(_) => (),
+            }
+        }
+        Ok(certificates)
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for Pfx<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, version) = parse_der_u32(i).or(Err(X509Error::InvalidPKCS12))?;
+            if version != 3 {
+                return Err(Err::Error(X509Error::InvalidVersion));
+            }
+            let (i, (content_type, content)) = parse_content_info(i)?;
+            // the public-key integrity mode (signedData) is not supported
+            if content_type != OID_PKCS7_ID_DATA {
+                return Err(Err::Error(X509Error::InvalidPKCS12));
+            }
+            let auth_safe_data = parse_octet_string_content(content)?;
+            let (_, auth_safe) = parse_der_sequence_defined_g(|a, _| {
+                all_consuming(many0(complete(AuthenticatedSafeContent::from_der)))(a)
+            })(auth_safe_data)?;
+            let (i, mac_data) = if i.is_empty() {
+                (i, None)
+            } else {
+                let (rem, _) = Any::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+                (rem, Some(&i[..i.len() - rem.len()]))
+            };
+            let pfx = Pfx {
+                version,
+                auth_safe,
+                mac_data,
+            };
+            Ok((i, pfx))
+        })(i)
+    }
+}
+
+/// An item of the PKCS#12 `AuthenticatedSafe`
+#[derive(Clone, Debug)]
+pub enum AuthenticatedSafeContent<'a> {
+    /// Unencrypted `SafeContents` (`pkcs7-data`)
+    Data(Vec<SafeBag<'a>>),
+    /// Password-encrypted `SafeContents` (`pkcs7-encryptedData`)
+    EncryptedData(EncryptedContentInfo<'a>),
+    /// Other content type (for ex. public-key encrypted `pkcs7-envelopedData`)
+    Unsupported(Oid<'a>),
+}
+
+impl<'a> FromDer<'a, X509Error> for AuthenticatedSafeContent<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        let (rem, (content_type, content)) = parse_content_info(i)?;
+        let item = if content_type == OID_PKCS7_ID_DATA {
+            let data = parse_octet_string_content(content)?;
+            let (_, bags) = parse_safe_contents(data)?;
+            AuthenticatedSafeContent::Data(bags)
+        } else if content_type == OID_PKCS7_ID_ENCRYPTED_DATA {
+            // EncryptedData ::= SEQUENCE { version, encryptedContentInfo }
+            let (_, info) = parse_der_sequence_defined_g(|a, _| {
+                let (a, _version) = parse_der_u32(a).or(Err(X509Error::InvalidPKCS12))?;
+                EncryptedContentInfo::from_der(a)
+            })(content)?;
+            AuthenticatedSafeContent::EncryptedData(info)
+        } else {
+            AuthenticatedSafeContent::Unsupported(content_type)
+        };
+        Ok((rem, item))
+    }
+}
+
+/// Encrypted content of a PKCS#7 `EncryptedData`
+///
+/// <pre>
+/// EncryptedContentInfo ::= SEQUENCE {
+///     contentType ContentType,
+///     contentEncryptionAlgorithm ContentEncryptionAlgorithmIdentifier,
+///     encryptedContent [0] IMPLICIT EncryptedContent OPTIONAL }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct EncryptedContentInfo<'a> {
+    pub content_type: Oid<'a>,
+    pub content_encryption_algorithm: AlgorithmIdentifier<'a>,
+    /// The encrypted content (empty if absent)
+    pub encrypted_content: &'a [u8],
+}
+
+impl<'a> FromDer<'a, X509Error> for EncryptedContentInfo<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, content_type) = Oid::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+            let (i, content_encryption_algorithm) = AlgorithmIdentifier::from_der(i)?;
+            let (i, encrypted_content) = if i.is_empty() {
+                (i, &[][..])
+            } else {
+                let (rem, any) = Any::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+                if any.class() != Class::ContextSpecific || any.tag() != Tag(0) {
+                    return Err(Err::Error(X509Error::InvalidPKCS12));
+                }
+                (rem, any.data)
+            };
+            let info = EncryptedContentInfo {
+                content_type,
+                content_encryption_algorithm,
+                encrypted_content,
+            };
+            Ok((i, info))
+        })(i)
+    }
+}
+
+#[cfg(feature = "decrypt")]
+impl<'a> EncryptedContentInfo<'a> {
+    /// Decrypt the content using `password`, and return the decrypted `SafeContents`
+    ///
+    /// Only PBES2 with PBKDF2 and AES-CBC is supported.
+    pub fn decrypt(&self, password: &str) -> Result<Vec<u8>, X509Error> {
+        decrypt::pbes2_decrypt(
+            &self.content_encryption_algorithm,
+            password.as_bytes(),
+            self.encrypted_content,
+        )
+    }
+}
+
+/// A PKCS#12 bag
+///
+/// <pre>
+/// SafeBag ::= SEQUENCE {
+///     bagId          BAG-TYPE.&id ({PKCS12BagSet}),
+///     bagValue       [0] EXPLICIT BAG-TYPE.&Type({PKCS12BagSet}{@bagId}),
+///     bagAttributes  SET OF PKCS12Attribute OPTIONAL }
+///
+/// CertBag ::= SEQUENCE {
+///     certId      BAG-TYPE.&id   ({CertTypes}),
+///     certValue   [0] EXPLICIT BAG-TYPE.&Type ({CertTypes}{@certId}) }
+/// </pre>
+#[derive(Clone, Debug)]
+pub struct SafeBag<'a> {
+    pub bag_id: Oid<'a>,
+    pub bag_value: SafeBagValue<'a>,
+    /// The raw content of the `bagAttributes` set, if present
+    pub attributes: Option<&'a [u8]>,
+}
+
+impl<'a> SafeBag<'a> {
+    /// Return the certificate, if this is a bag containing an X.509 certificate
+    pub fn certificate(&self) -> Option<&X509Certificate<'a>> {
+        match &self.bag_value {
+            SafeBagValue::Certificate(cert) => Some(cert),
+            SafeBagValue::Unknown(_) => None,
+        }
+    }
+
+    /// Return the value of the `friendlyName` attribute, if present and valid
+    pub fn friendly_name(&self) -> Option<String> {
+        let mut data = self.attributes?;
+        while !data.is_empty() {
+            let (rem, (attr_id, values)) = parse_der_sequence_defined_g(|a, _| {
+                let (a, attr_id) = Oid::from_der(a)?;
+                Ok((&[], (attr_id, a)))
+            })(data)
+            .ok()?;
+            if attr_id == OID_PKCS9_FRIENDLY_NAME {
+                let (_, name) =
+                    parse_der_set_defined_g(|a, _| BmpString::from_der(a))(values).ok()?;
+                return Some(name.string());
+            }
+            data = rem;
+        }
+        None
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for SafeBag<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, bag_id) = Oid::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+            let (i, any) = Any::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+            if any.class() != Class::ContextSpecific || any.tag() != Tag(0) {
+                return Err(Err::Error(X509Error::InvalidPKCS12));
+            }
+            let bag_value = if bag_id == OID_PKCS12_CERT_BAG {
+                parse_cert_bag(any.data)?
+            } else {
+                let (_, value) = Any::from_der(any.data).or(Err(X509Error::InvalidPKCS12))?;
+                SafeBagValue::Unknown(value)
+            };
+            let (i, attributes) = if i.is_empty() {
+                (i, None)
+            } else {
+                parse_der_set_defined_g(|a, _| Ok((&[], Some(a))))(i)?
+            };
+            let bag = SafeBag {
+                bag_id,
+                bag_value,
+                attributes,
+            };
+            Ok((i, bag))
+        })(i)
+    }
+}
+
+/// The value of a PKCS#12 bag
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum SafeBagValue<'a> {
+    /// A certificate bag containing an X.509 certificate
+    Certificate(X509Certificate<'a>),
+    /// Other bag types (keys, CRLs, secrets, etc.) or certificate types, in raw form
+    Unknown(Any<'a>),
+}
+
+fn parse_cert_bag(i: &[u8]) -> Result<SafeBagValue, Err<X509Error>> {
+    let (_, (cert_id, value)) = parse_der_sequence_defined_g(|a, _| {
+        let (a, cert_id) = Oid::from_der(a).or(Err(X509Error::InvalidPKCS12))?;
+        let (a, value) = Any::from_der(a).or(Err(X509Error::InvalidPKCS12))?;
+        if value.class() != Class::ContextSpecific || value.tag() != Tag(0) {
+            return Err(Err::Error(X509Error::InvalidPKCS12));
+        }
+        Ok((a, (cert_id, value)))
+    })(i)?;
+    if cert_id != OID_PKCS9_X509_CERTIFICATE {
+        let (_, value) = Any::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+        return Ok(SafeBagValue::Unknown(value));
+    }
+    let data = parse_octet_string_content(value.data)?;
+    let (_, cert) = X509Certificate::from_der(data)?;
+    Ok(SafeBagValue::Certificate(cert))
+}
+
+// SafeContents ::= SEQUENCE OF SafeBag
+fn parse_safe_contents(i: &[u8]) -> X509Result<Vec<SafeBag>> {
+    parse_der_sequence_defined_g(|a, _| all_consuming(many0(complete(SafeBag::from_der)))(a))(i)
+}
+
+// ContentInfo ::= SEQUENCE { contentType, content [0] EXPLICIT ANY OPTIONAL }
+// Returns the content type, and the content of the [0] element
+fn parse_content_info(i: &[u8]) -> X509Result<(Oid, &[u8])> {
+    parse_der_sequence_defined_g(|i, _| {
+        let (i, content_type) = Oid::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+        let (i, any) = Any::from_der(i).or(Err(X509Error::InvalidPKCS12))?;
+        if any.class() != Class::ContextSpecific || any.tag() != Tag(0) {
+            return Err(Err::Error(X509Error::InvalidPKCS12));
+        }
+        Ok((i, (content_type, any.data)))
+    })(i)
+}
+
+// Return the content of a (primitive) OCTET STRING
+fn parse_octet_string_content(i: &[u8]) -> Result<&[u8], Err<X509Error>> {
+    match Any::from_der(i) {
+        Ok((_, any)) if any.class() == Class::Universal && any.tag() == Tag::OctetString => {
+            Ok(any.data)
+        }
+        _ => Err(Err::Error(X509Error::InvalidPKCS12)),
+    }
+}
+
+/// Parse a DER-encoded PKCS#12 PFX
+pub fn parse_pkcs12(i: &[u8]) -> X509Result<Pfx> {
+    Pfx::from_der(i)
+}
+
+#[cfg(feature = "decrypt")]
+mod decrypt {
+    use crate::error::X509Error;
+    use crate::x509::AlgorithmIdentifier;
+    use asn1_rs::{oid, Any, Class, FromDer, Oid, Tag};
+    use cbc::cipher::block_padding::Pkcs7;
+    use cbc::cipher::{BlockCipher, BlockDecryptMut, KeyInit, KeyIvInit};
+    use der_parser::der::parse_der_u32;
+    use hmac::Hmac;
+
+    const OID_PKCS5_PBES2: Oid<'static> = oid!(1.2.840 .113549 .1 .5 .13);
+    const OID_PKCS5_PBKDF2: Oid<'static> = oid!(1.2.840 .113549 .1 .5 .12);
+    const OID_HMAC_SHA1: Oid<'static> = oid!(1.2.840 .113549 .2 .7);
+    const OID_HMAC_SHA256: Oid<'static> = oid!(1.2.840 .113549 .2 .9);
+    const OID_HMAC_SHA384: Oid<'static> = oid!(1.2.840 .113549 .2 .10);
+    const OID_HMAC_SHA512: Oid<'static> = oid!(1.2.840 .113549 .2 .11);
+    const OID_AES128_CBC: Oid<'static> = oid!(2.16.840 .1 .101 .3 .4 .1 .2);
+    const OID_AES192_CBC: Oid<'static> = oid!(2.16.840 .1 .101 .3 .4 .1 .22);
+    const OID_AES256_CBC: Oid<'static> = oid!(2.16.840 .1 .101 .3 .4 .1 .42);
+
+    /// Maximum PBKDF2 iteration count: the count is read from the (untrusted) input, and
+    /// larger values would allow a file to make the key derivation run for a very long time
+    const PBKDF2_MAX_ITERATIONS: u32 = 10_000_000;
+
+    /// Decrypt data encrypted using PBES2 (RFC 8018)
+    ///
+    /// <pre>
+    /// PBES2-params ::= SEQUENCE {
+    ///     keyDerivationFunc AlgorithmIdentifier {{PBES2-KDFs}},
+    ///     encryptionScheme AlgorithmIdentifier {{PBES2-Encs}} }
+    ///
+    /// PBKDF2-params ::= SEQUENCE {
+    ///     salt CHOICE { specified OCTET STRING, otherSource AlgorithmIdentifier },
+    ///     iterationCount INTEGER (1..MAX),
+    ///     keyLength INTEGER (1..MAX) OPTIONAL,
+    ///     prf AlgorithmIdentifier {{PBKDF2-PRFs}} DEFAULT algid-hmacWithSHA1 }
+    /// </pre>
+    ///
+    /// An iteration count of 0, or larger than `PBKDF2_MAX_ITERATIONS`, is rejected.
+    pub(super) fn pbes2_decrypt(
+        alg: &AlgorithmIdentifier,
+        password: &[u8],
+        data: &[u8],
+    ) -> Result<Vec<u8>, X509Error> {
+        if alg.algorithm != OID_PKCS5_PBES2 {
+            return Err(X509Error::PKCS12UnsupportedAlgorithm);
+        }
+        let params = sequence_content(alg.parameters.as_ref())?;
+        let (rem, kdf) = AlgorithmIdentifier::from_der(params).or(Err(X509Error::InvalidPKCS12))?;
+        let (_, enc) = AlgorithmIdentifier::from_der(rem).or(Err(X509Error::InvalidPKCS12))?;
+        if kdf.algorithm != OID_PKCS5_PBKDF2 {
+            return Err(X509Error::PKCS12UnsupportedAlgorithm);
+        }
+        // PBKDF2 parameters
+        let kdf_params = sequence_content(kdf.parameters.as_ref())?;
+        let (rem, salt) = Any::from_der(kdf_params).or(Err(X509Error::InvalidPKCS12))?;
+        if salt.class() != Class::Universal || salt.tag() != Tag::OctetString {
+            return Err(X509Error::PKCS12UnsupportedAlgorithm);
+        }
+        let (mut rem, iterations) = parse_der_u32(rem).or(Err(X509Error::InvalidPKCS12))?;
+        if iterations == 0 || iterations > PBKDF2_MAX_ITERATIONS {
+            return Err(X509Error::InvalidPKCS12);
+        }
+        if let Ok((r, _key_length)) = parse_der_u32(rem) {
+            rem = r;
+        }
+        let prf = if rem.is_empty() {
+            OID_HMAC_SHA1
+        } else {
+            let (_, prf) = AlgorithmIdentifier::from_der(rem).or(Err(X509Error::InvalidPKCS12))?;
+            prf.algorithm
+        };
+        // encryption scheme parameters
+        let iv = match &enc.parameters {
+            Some(any) if any.class() == Class::Universal && any.tag() == Tag::OctetString => {
+                any.data
+            }
+            _ => return Err(X509Error::InvalidPKCS12),
+        };
+        let key_len = match &enc.algorithm {
+            oid if *oid == OID_AES128_CBC => 16,
+            oid if *oid == OID_AES192_CBC => 24,
+            oid if *oid == OID_AES256_CBC => 32,
+            _ => return Err(X509Error::PKCS12UnsupportedAlgorithm),
+        };
+        let mut key = vec![0u8; key_len];
+        match prf {
+            oid if oid == OID_HMAC_SHA1 => {
+                pbkdf2::pbkdf2::<Hmac<sha1::Sha1>>(password, salt.data, iterations, &mut key)
+            }
+            oid if oid == OID_HMAC_SHA256 => {
+                pbkdf2::pbkdf2::<Hmac<sha2::Sha256>>(password, salt.data, iterations, &mut key)
+            }
+            oid if oid == OID_HMAC_SHA384 => {
+                pbkdf2::pbkdf2::<Hmac<sha2::Sha384>>(password, salt.data, iterations, &mut key)
+            }
+            oid if oid == OID_HMAC_SHA512 => {
+                pbkdf2::pbkdf2::<Hmac<sha2::Sha512>>(password, salt.data, iterations, &mut key)
+            }
+            _ => return Err(X509Error::PKCS12UnsupportedAlgorithm),
+        }
+        match key_len {
+            16 => cbc_decrypt::<aes::Aes128>(&key, iv, data),
+            24 => cbc_decrypt::<aes::Aes192>(&key, iv, data),
+            _ => cbc_decrypt::<aes::Aes256>(&key, iv, data),
+        }
+    }
+
+    fn cbc_decrypt<C>(key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>, X509Error>
+    where
+        C: BlockCipher + BlockDecryptMut + KeyInit,
+    {
+        let decryptor =
+            cbc::Decryptor::<C>::new_from_slices(key, iv).or(Err(X509Error::InvalidPKCS12))?;
+        decryptor
+            .decrypt_padded_vec_mut::<Pkcs7>(data)
+            .or(Err(X509Error::PKCS12DecryptionError))
+    }
+
+    fn sequence_content<'a>(any: Option<&'a Any<'a>>) -> Result<&'a [u8], X509Error> {
+        match any {
+            Some(any) if any.class() == Class::Universal && any.tag() == Tag::Sequence => {
+                Ok(any.data)
+            }
+            _ => Err(X509Error::InvalidPKCS12),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkcs12_invalid_version() {
+        // PFX with version 1
+        let data = &[0x30, 0x03, 0x02, 0x01, 0x01];
+        let res = parse_pkcs12(data);
+        assert_eq!(res.unwrap_err(), Err::Error(X509Error::InvalidVersion));
+    }
+
+    #[cfg(feature = "decrypt")]
+    #[test]
+    fn test_pbes2_iterations_limit() {
+        // PBES2 with PBKDF2 (10_000_001 iterations) and AES-128-CBC
+        let data = &[
+            0x30, 0x4b, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0d, 0x30,
+            0x3e, 0x30, 0x1d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x05, 0x0c,
+            0x30, 0x10, 0x04, 0x08, 0, 0, 0, 0, 0, 0, 0, 0, 0x02, 0x04, 0x00, 0x98, 0x96, 0x81,
+            0x30, 0x1d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x01, 0x02, 0x04,
+            0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let (rem, alg) = AlgorithmIdentifier::from_der(data).expect("parsing failed");
+        assert!(rem.is_empty());
+        let res = decrypt::pbes2_decrypt(&alg, b"password", &[0; 16]);
+        assert_eq!(res.unwrap_err(), X509Error::InvalidPKCS12);
+    }
+}
@@ -0,0 +1,194 @@
+//! PKCS#7 / CMS `SignedData` containers, used to distribute certificates and CRLs
+//!
+//! Certificate bundles and enrollment responses are often shipped as a "degenerate"
+//! `SignedData` (`.p7b` or `.p7c` files, or `certs-only` responses), which has no content and no
+//! signers, and is only used as a container for certificates and CRLs.
+//!
+//! The structures are defined in [RFC5652](https://datatracker.ietf.org/doc/html/rfc5652).
+//! Only the fields required to extract certificates and CRLs are parsed: signer infos are kept
+//! in raw form, and the signature is not verified. The input must be DER-encoded.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::pkcs7::parse_pkcs7_signed_data;
+//!
+//! # static DER: &'static [u8] = include_bytes!("../assets/certs.p7b");
+//! #
+//! # fn main() {
+//! let (_, signed_data) = parse_pkcs7_signed_data(DER).expect("PKCS#7 parsing failed");
+//! for cert in signed_data.iter_certificates() {
+//!     println!("Certificate: {}", cert.subject());
+//! }
+//! for crl in signed_data.iter_crls() {
+//!     println!("CRL: {}", crl.issuer());
+//! }
+//! # }
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::error::{X509Error, X509Result};
+use crate::revocation_list::CertificateRevocationList;
+use crate::x509::AlgorithmIdentifier;
+use asn1_rs::{Any, Class, FromDer, Oid, Tag};
+use der_parser::der::*;
+use nom::combinator::{all_consuming, complete};
+use nom::multi::many0;
+use nom::Err;
+use oid_registry::OID_PKCS7_ID_SIGNED_DATA;
+
+/// A PKCS#7 / CMS signed data
+///
+/// <pre>
+/// SignedData ::= SEQUENCE {
+///     version CMSVersion,
+///     digestAlgorithms DigestAlgorithmIdentifiers,
+///     encapContentInfo EncapsulatedContentInfo,
+///     certificates [0] IMPLICIT CertificateSet OPTIONAL,
+///     crls [1] IMPLICIT RevocationInfoChoices OPTIONAL,
+///     signerInfos SignerInfos }
+///
+/// EncapsulatedContentInfo ::= SEQUENCE {
+///     eContentType ContentType,
+///     eContent [0] EXPLICIT OCTET STRING OPTIONAL }
+/// </pre>
+///
+/// Only X.509 certificates and CRLs are extracted, other choices (attribute certificates,
+/// other revocation formats) are ignored.
+#[derive(Clone, Debug)]
+pub struct SignedData<'a> {
+    pub version: u32,
+    pub digest_algorithms: Vec<AlgorithmIdentifier<'a>>,
+    /// The type of the encapsulated content (usually `pkcs7-data`)
+    pub content_type: Oid<'a>,
+    /// The raw encapsulated content (the `[0]` tagged element), if present
+    pub content: Option<&'a [u8]>,
+    pub certificates: Vec<X509Certificate<'a>>,
+    pub crls: Vec<CertificateRevocationList<'a>>,
+    /// The raw content of the `SignerInfos` set
+    pub signer_infos: &'a [u8],
+}
+
+impl<'a> SignedData<'a> {
+    /// Return an iterator over the embedded certificates
+    pub fn iter_certificates(&self) -> impl Iterator<Item = &X509Certificate<'a>> {
+        self.certificates.iter()
+    }
+
+    /// Return an iterator over the embedded CRLs
+    pub fn iter_crls(&self) -> impl Iterator<Item = &CertificateRevocationList<'a>> {
+        self.crls.iter()
+    }
+
+    /// Test if this is a degenerate ("certs-only") signed data, without content and signers
+    pub fn is_certs_only(&self) -> bool {
+        self.content.is_none() && self.signer_infos.is_empty()
+    }
+}
+
+impl<'a> FromDer<'a, X509Error> for SignedData<'a> {
+    fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        parse_der_sequence_defined_g(|i, _| {
+            let (i, version) = parse_der_u32(i).or(Err(X509Error::InvalidPKCS7))?;
+            let (i, digest_algorithms) = parse_der_set_defined_g(|a, _| {
+                all_consuming(many0(complete(AlgorithmIdentifier::from_der)))(a)
+            })(i)?;
+            let (i, (content_type, content)) = parse_der_sequence_defined_g(|a, _| {
+                let (a, content_type) = Oid::from_der(a).or(Err(X509Error::InvalidPKCS7))?;
+                let content = if a.is_empty() { None } else { Some(a) };
+                Ok((&[], (content_type, content)))
+            })(i)?;
+            let (i, certificates) = parse_optional_set(i, 0, X509Certificate::from_der)?;
+            let (i, crls) = parse_optional_set(i, 1, CertificateRevocationList::from_der)?;
+            let (i, signer_infos) = parse_der_set_defined_g(|a, _| Ok((&[], a)))(i)?;
+            let signed_data = SignedData {
+                version,
+                digest_algorithms,
+                content_type,
+                content,
+                certificates,
+                crls,
+                signer_infos,
+            };
+            Ok((i, signed_data))
+        })(i)
+    }
+}
+
+// Parse an optional [tag] IMPLICIT SET OF CHOICE, keeping only the (untagged) SEQUENCE items
+fn parse_optional_set<'a, T, F>(i: &'a [u8], tag: u32, f: F) -> X509Result<'a, Vec<T>>
+where
+    F: Fn(&'a [u8]) -> X509Result<'a, T>,
+{
+    let (rem, any) = match Any::from_der(i) {
+        Ok((rem, any)) if any.class() == Class::ContextSpecific && any.tag() == Tag(tag) => {
+            (rem, any)
+        }
+        _ => return Ok((i, Vec::new())),
+    };
+    let mut items = Vec::new();
+    let mut data = any.data;
+    while !data.is_empty() {
+        let (next, item) = Any::from_der(data).or(Err(X509Error::InvalidPKCS7))?;
+        if item.class() == Class::Universal && item.tag() == Tag::Sequence {
+            let (_, value) = f(&data[..data.len() - next.len()])?;
+            items.push(value);
+        }
+        data = next;
+    }
+    Ok((rem, items))
+}
+
+/// Parse a DER-encoded PKCS#7 / CMS `ContentInfo` containing a `SignedData`
+///
+/// <pre>
+/// ContentInfo ::= SEQUENCE {
+///     contentType ContentType,
+///     content [0] EXPLICIT ANY DEFINED BY contentType }
+/// </pre>
+///
+/// Returns an error if the content type is not `pkcs7-signedData`.
+pub fn parse_pkcs7_signed_data(i: &[u8]) -> X509Result<SignedData> {
+    parse_der_sequence_defined_g(|i, _| {
+        let (i, content_type) = Oid::from_der(i).or(Err(X509Error::InvalidPKCS7))?;
+        if content_type != OID_PKCS7_ID_SIGNED_DATA {
+            return Err(Err::Error(X509Error::InvalidPKCS7));
+        }
+        let (i, any) = Any::from_der(i).or(Err(X509Error::InvalidPKCS7))?;
+        if any.class() != Class::ContextSpecific || any.tag() != Tag(0) {
+            return Err(Err::Error(X509Error::InvalidPKCS7));
+        }
+        let (_, signed_data) = all_consuming(SignedData::from_der)(any.data)?;
+        Ok((i, signed_data))
+    })(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkcs7_not_signed_data() {
+        // ContentInfo with type pkcs7-data
+        let data = &[
+            0x30, 0x0f, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x01, 0xa0,
+            0x02, 0x04, 0x00,
+        ];
+        let res = parse_pkcs7_signed_data(data);
+        assert_eq!(res.unwrap_err(), Err::Error(X509Error::InvalidPKCS7));
+    }
+
+    #[test]
+    fn test_pkcs7_empty_signed_data() {
+        // SignedData without certificates or CRLs
+        let data = &[
+            0x30, 0x14, 0x02, 0x01, 0x01, 0x31, 0x00, 0x30, 0x0b, 0x06, 0x09, 0x2a, 0x86, 0x48,
+            0x86, 0xf7, 0x0d, 0x01, 0x07, 0x01, 0x31, 0x00,
+        ];
+        let (rem, signed_data) = SignedData::from_der(data).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert!(signed_data.is_certs_only());
+        assert!(signed_data.certificates.is_empty());
+        assert!(signed_data.crls.is_empty());
+    }
+}
@@ -2,11 +2,13 @@
 
 pub use crate::certificate::*;
 pub use crate::certification_request::*;
+pub use crate::chain::*;
 pub use crate::cri_attributes::*;
 pub use crate::error::*;
 pub use crate::extensions::*;
 pub use crate::objects::*;
 pub use crate::pem::*;
+pub use crate::report::*;
 pub use crate::revocation_list::*;
 pub use crate::time::*;
 pub use crate::utils::*;
@@ -0,0 +1,331 @@
+//! A flat, stable summary of a certificate, with JSON export
+//!
+//! [`CertificateReport`] gathers the most commonly used fields of a certificate, using normalized
+//! names and formats, so it can be ingested by log processing tools (for ex. ELK or SIEM).
+//! Unlike the `serde` implementations of the parsed structures, the format of the report does
+//! not depend on the internal representation of the certificate.
+//!
+//! JSON fields:
+//!
+//! | Field | Type | Description |
+//! |-------|------|-------------|
+//! | `version` | number | Certificate version (1, 2 or 3) |
+//! | `serial` | string | Serial number, as colon-separated hexadecimal (for ex. `01:02:ff`) |
+//! | `subject` | string | Subject name (for ex. `CN=example.com, O=Org`) |
+//! | `issuer` | string | Issuer name |
+//! | `not_before` | string | Start of validity, RFC 3339 in UTC (for ex. `2023-01-01T00:00:00Z`) |
+//! | `not_after` | string | End of validity, RFC 3339 in UTC |
+//! | `subject_alt_names` | array of strings | Subject alternative names (for ex. `DNS:example.com`) |
+//! | `public_key_algorithm` | string | Short name of the public key algorithm, or its OID |
+//! | `signature_algorithm` | string | Short name of the signature algorithm, or its OID |
+//! | `is_ca` | boolean | `true` if the basic constraints extension has `CA:TRUE` |
+//! | `key_usage` | array of strings | Key usage flags, using RFC 5280 names (for ex. `digitalSignature`) |
+//! | `extended_key_usage` | array of strings | Extended key usages, using RFC 5280 names (for ex. `serverAuth`) or OIDs |
+//! | `sha1_fingerprint` | string or null | SHA-1 of the DER encoding, lowercase hexadecimal |
+//! | `sha256_fingerprint` | string or null | SHA-256 of the DER encoding, lowercase hexadecimal |
+//!
+//! **The fingerprints depend on the enabled features**: they are only computed if the `hash`
+//! feature is enabled, and are `null` otherwise. The same certificate gives a different JSON
+//! output in builds with and without this feature. All other fields are always set.
+//! Invalid or duplicate extensions are ignored.
+//!
+//! With the `hash` feature, a redacted report can be built for privacy-preserving telemetry
+//! (see [`CertificateReport::redacted`]): the values of the subject and issuer attributes are
+//! replaced by keyed hashes, the subject alternative names are reduced to their types, and the serial
+//! number and fingerprints are removed. The structure, algorithms and validity are kept.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::prelude::*;
+//!
+//! fn log_certificate(der: &[u8]) {
+//!     let (_, x509) = parse_x509_certificate(der).expect("invalid certificate");
+//!     println!("{}", x509.to_json());
+//! }
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::extensions::general_name_to_string;
+use crate::objects::{oid2sn, oid_registry};
+use der_parser::oid::Oid;
+use std::fmt::Write;
+
+// RFC 5280 names of the key usage flags, in the same order as the bits
+const KEY_USAGE_NAMES: &[&str] = &[
+    "digitalSignature",
+    "nonRepudiation",
+    "keyEncipherment",
+    "dataEncipherment",
+    "keyAgreement",
+    "keyCertSign",
+    "cRLSign",
+    "encipherOnly",
+    "decipherOnly",
+];
+
+/// A flat summary of a certificate, see the [module documentation](index.html) for the format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateReport {
+    pub version: u32,
+    pub serial: String,
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub subject_alt_names: Vec<String>,
+    pub public_key_algorithm: String,
+    pub signature_algorithm: String,
+    pub is_ca: bool,
+    pub key_usage: Vec<String>,
+    pub extended_key_usage: Vec<String>,
+    /// SHA-1 fingerprint (`None` if the `hash` feature is disabled, or in redacted reports)
+    pub sha1_fingerprint: Option<String>,
+    /// SHA-256 fingerprint (`None` if the `hash` feature is disabled, or in redacted reports)
+    pub sha256_fingerprint: Option<String>,
+}
+
+impl CertificateReport {
+    /// Build the report for the given certificate
+    pub fn new(x509: &X509Certificate) -> Self {
+        let subject_alt_names = match x509.subject_alternative_name() {
+            Ok(Some(san)) => san
+                .value
+                .general_names
+                .iter()
+                .map(general_name_to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let key_usage = match x509.key_usage() {
+            Ok(Some(ku)) => KEY_USAGE_NAMES
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| (ku.value.flags >> idx) & 1 == 1)
+                .map(|(_, name)| name.to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let extended_key_usage = match x509.extended_key_usage() {
+            Ok(Some(eku)) => {
+                let eku = eku.value;
+                let flags = [
+                    (eku.any, "anyExtendedKeyUsage"),
+                    (eku.server_auth, "serverAuth"),
+                    (eku.client_auth, "clientAuth"),
+                    (eku.code_signing, "codeSigning"),
+                    (eku.email_protection, "emailProtection"),
+                    (eku.time_stamping, "timeStamping"),
+                    (eku.ocsp_signing, "OCSPSigning"),
+                ];
+                flags
+                    .iter()
+                    .filter(|(set, _)| *set)
+                    .map(|(_, name)| name.to_string())
+                    .chain(eku.other.iter().map(|oid| oid.to_id_string()))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        let (sha1_fingerprint, sha256_fingerprint) = fingerprints(x509.as_raw());
+        CertificateReport {
+            version: x509.version().0 + 1,
+            serial: x509.raw_serial_as_string(),
+            subject: x509.subject().to_string(),
+            issuer: x509.issuer().to_string(),
+            not_before: x509.validity().not_before.to_rfc3339(),
+            not_after: x509.validity().not_after.to_rfc3339(),
+            subject_alt_names,
+            public_key_algorithm: algorithm_name(&x509.public_key().algorithm.algorithm),
+            signature_algorithm: algorithm_name(&x509.signature_algorithm.algorithm),
+            is_ca: x509.is_ca(),
+            key_usage,
+            extended_key_usage,
+            sha1_fingerprint,
+            sha256_fingerprint,
+        }
+    }
+
+    /// Serialize the report to a (compact) JSON object
+    pub fn to_json(&self) -> String {
+        let mut s = String::from("{");
+        let _ = write!(s, "\"version\":{}", self.version);
+        json_field(&mut s, "serial", &self.serial);
+        json_field(&mut s, "subject", &self.subject);
+        json_field(&mut s, "issuer", &self.issuer);
+        json_field(&mut s, "not_before", &self.not_before);
+        json_field(&mut s, "not_after", &self.not_after);
+        json_array(&mut s, "subject_alt_names", &self.subject_alt_names);
+        json_field(&mut s, "public_key_algorithm", &self.public_key_algorithm);
+        json_field(&mut s, "signature_algorithm", &self.signature_algorithm);
+        let _ = write!(s, ",\"is_ca\":{}", self.is_ca);
+        json_array(&mut s, "key_usage", &self.key_usage);
+        json_array(&mut s, "extended_key_usage", &self.extended_key_usage);
+        json_option(&mut s, "sha1_fingerprint", &self.sha1_fingerprint);
+        json_option(&mut s, "sha256_fingerprint", &self.sha256_fingerprint);
+        s.push('}');
+        s
+    }
+}
+
+#[cfg(feature = "hash")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+impl CertificateReport {
+    /// Build a redacted report for the given certificate, suitable for telemetry
+    ///
+    /// Subject and issuer attribute values are replaced by `hmac-sha256:` followed by the first
+    /// 16 hexadecimal digits of the HMAC-SHA256 of the value, keyed by `salt`, so the same name
+    /// gives the same report for a given salt (for ex. `CN=hmac-sha256:1f2e...`). Use a secret
+    /// salt, since attribute values (for ex. common names) can be guessed from their hashes.
+    /// The issuer is redacted too, since it is equal to the subject for self-signed
+    /// certificates.
+    ///
+    /// Subject alternative names only keep their type (for ex. `DNS:<redacted>`), and the
+    /// serial number is replaced by `<redacted>`. Fingerprints are removed, since they identify
+    /// the certificate.
+    pub fn redacted(x509: &X509Certificate, salt: &[u8]) -> Self {
+        let report = CertificateReport::new(x509);
+        let subject = redacted_name(x509.subject(), salt);
+        let issuer = redacted_name(x509.issuer(), salt);
+        let subject_alt_names = report
+            .subject_alt_names
+            .iter()
+            .map(|name| match name.find(':') {
+                Some(pos) => format!("{}:{}", &name[..pos], REDACTED),
+                None => REDACTED.to_string(),
+            })
+            .collect();
+        CertificateReport {
+            serial: REDACTED.to_string(),
+            subject,
+            issuer,
+            subject_alt_names,
+            sha1_fingerprint: None,
+            sha256_fingerprint: None,
+            ..report
+        }
+    }
+}
+
+#[cfg(feature = "hash")]
+const REDACTED: &str = "<redacted>";
+
+#[cfg(feature = "hash")]
+fn attribute_name(oid: &Oid) -> String {
+    crate::objects::oid2abbrev(oid, oid_registry())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+// Replace the attribute values of `name` by their keyed hashes
+#[cfg(feature = "hash")]
+fn redacted_name(name: &crate::x509::X509Name, salt: &[u8]) -> String {
+    name.iter()
+        .map(|rdn| {
+            rdn.iter()
+                .map(|attr| {
+                    let value = keyed_hash(salt, attr.as_str_lossy().as_bytes());
+                    format!("{}={}", attribute_name(attr.attr_type()), value)
+                })
+                .collect::<Vec<_>>()
+                .join(" + ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(feature = "hash")]
+fn keyed_hash(key: &[u8], value: &[u8]) -> String {
+    use data_encoding::HEXLOWER;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    // HMAC accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("invalid HMAC key length");
+    mac.update(value);
+    let digest = mac.finalize().into_bytes();
+    format!("hmac-sha256:{}", HEXLOWER.encode(&digest[..8]))
+}
+
+impl<'a> From<&X509Certificate<'a>> for CertificateReport {
+    fn from(x509: &X509Certificate<'a>) -> Self {
+        CertificateReport::new(x509)
+    }
+}
+
+fn algorithm_name(oid: &Oid) -> String {
+    oid2sn(oid, oid_registry())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+#[cfg(feature = "hash")]
+fn fingerprints(der: &[u8]) -> (Option<String>, Option<String>) {
+    use data_encoding::HEXLOWER;
+    use sha1::{Digest, Sha1};
+    use sha2::Sha256;
+    (
+        Some(HEXLOWER.encode(&Sha1::digest(der))),
+        Some(HEXLOWER.encode(&Sha256::digest(der))),
+    )
+}
+
+#[cfg(not(feature = "hash"))]
+fn fingerprints(_der: &[u8]) -> (Option<String>, Option<String>) {
+    (None, None)
+}
+
+/// Write a string as a JSON string literal, escaping special characters
+fn json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn json_field(out: &mut String, name: &str, value: &str) {
+    let _ = write!(out, ",\"{}\":", name);
+    json_string(out, value);
+}
+
+fn json_option(out: &mut String, name: &str, value: &Option<String>) {
+    match value {
+        Some(value) => json_field(out, name, value),
+        None => {
+            let _ = write!(out, ",\"{}\":null", name);
+        }
+    }
+}
+
+fn json_array(out: &mut String, name: &str, values: &[String]) {
+    let _ = write!(out, ",\"{}\":[", name);
+    for (idx, value) in values.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        json_string(out, value);
+    }
+    out.push(']');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_string_escape() {
+        let mut s = String::new();
+        json_string(&mut s, "CN=\"a\\b\"\n\u{1}é");
+        assert_eq!(s, r#""CN=\"a\\b\"\n\u0001é""#);
+    }
+}
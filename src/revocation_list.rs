@@ -1,9 +1,11 @@
+use crate::certificate::X509Certificate;
 use crate::error::{X509Error, X509Result};
 use crate::extensions::*;
 use crate::time::ASN1Time;
-use crate::utils::format_serial;
+use crate::utils::{constant_time_eq, format_serial, format_serial_with, many0_lazy, SerialFormat};
 use crate::x509::{
-    parse_serial, parse_signature_value, AlgorithmIdentifier, ReasonCode, X509Name, X509Version,
+    parse_serial, parse_signature_value, serial_to_u128, AlgorithmIdentifier, ReasonCode,
+    RelativeDistinguishedName, X509Name, X509Version,
 };
 
 #[cfg(feature = "verify")]
@@ -13,13 +15,39 @@ use crate::x509::SubjectPublicKeyInfo;
 use asn1_rs::{BitString, FromDer};
 use der_parser::ber::Tag;
 use der_parser::der::*;
+#[cfg(feature = "bigint")]
 use der_parser::num_bigint::BigUint;
 use der_parser::oid::Oid;
 use nom::combinator::{all_consuming, complete, map, opt};
-use nom::multi::many0;
 use nom::Offset;
 use oid_registry::*;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use time::Duration;
+
+/// How to handle revocation information without a usable `nextUpdate`
+///
+/// `nextUpdate` is optional in CRLs (and OCSP responses), but RFC5280 requires conforming CAs to
+/// include it. A `nextUpdate` which is not later than `thisUpdate` (a zero-length CRL) is
+/// handled the same way as an absent one.
+///
+/// The default is [`RequireNextUpdate`](Self::RequireNextUpdate): otherwise, without a maximum
+/// age, the information would be accepted forever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NextUpdatePolicy {
+    /// The information never becomes stale (it can still be rejected using a maximum age)
+    TreatAsFresh,
+    /// The information is always stale
+    RequireNextUpdate,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for NextUpdatePolicy {
+    fn default() -> Self {
+        NextUpdatePolicy::RequireNextUpdate
+    }
+}
 
 /// An X.509 v2 Certificate Revocation List (CRL).
 ///
@@ -53,9 +81,43 @@ pub struct CertificateRevocationList<'a> {
     pub tbs_cert_list: TbsCertList<'a>,
     pub signature_algorithm: AlgorithmIdentifier<'a>,
     pub signature_value: BitString<'a>,
+    pub(crate) raw: &'a [u8],
 }
 
 impl<'a> CertificateRevocationList<'a> {
+    /// Build a CRL from its parts, for ex. a separately parsed [`TbsCertList`]
+    ///
+    /// The CRL has no DER encoding: [`as_raw`](Self::as_raw) returns an empty slice, and it
+    /// cannot be converted to a [`CertificateRevocationListOwned`].
+    pub fn from_parts(
+        tbs_cert_list: TbsCertList<'a>,
+        signature_algorithm: AlgorithmIdentifier<'a>,
+        signature_value: BitString<'a>,
+    ) -> Self {
+        CertificateRevocationList {
+            tbs_cert_list,
+            signature_algorithm,
+            signature_value,
+            raw: &[],
+        }
+    }
+
+    /// Return the raw DER encoding of the CRL
+    ///
+    /// This is the encoding the CRL was parsed from. It is empty if the CRL was built using
+    /// [`from_parts`](Self::from_parts).
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// Return a copy of this CRL, owning its DER encoding
+    ///
+    /// Returns [`X509Error::MissingEncoding`] if the CRL has no encoding (see
+    /// [`from_parts`](Self::from_parts)).
+    pub fn to_owned(&self) -> Result<CertificateRevocationListOwned, X509Error> {
+        CertificateRevocationListOwned::try_from(self)
+    }
+
     /// Get the version of the encoded certificate
     pub fn version(&self) -> Option<X509Version> {
         self.tbs_cert_list.version
@@ -79,11 +141,189 @@ impl<'a> CertificateRevocationList<'a> {
         self.tbs_cert_list.next_update
     }
 
+    /// Get the date and time of the next update, if present and later than `thisUpdate`
+    ///
+    /// A zero-length CRL (`nextUpdate` not after `thisUpdate`) has no usable `nextUpdate`, and
+    /// is handled like a CRL without `nextUpdate` by the `_with_policy` freshness functions.
+    pub fn effective_next_update(&self) -> Option<ASN1Time> {
+        self.next_update()
+            .filter(|next_update| *next_update > self.last_update())
+    }
+
+    /// Test if the CRL is fresh at the given date/time
+    ///
+    /// The CRL is fresh if `time` is after `thisUpdate`, at most `max_age` after `thisUpdate`,
+    /// and not after `nextUpdate`. A CRL without a usable `nextUpdate` is handled using the
+    /// default [`NextUpdatePolicy`], and is never fresh.
+    pub fn is_fresh_at(&self, time: ASN1Time, max_age: Duration) -> bool {
+        self.is_fresh_at_with_policy(time, max_age, NextUpdatePolicy::default())
+    }
+
+    /// Test if the CRL is fresh at the given date/time, using `policy` if the CRL has no usable
+    /// `nextUpdate` (see [`effective_next_update`](Self::effective_next_update))
+    pub fn is_fresh_at_with_policy(
+        &self,
+        time: ASN1Time,
+        max_age: Duration,
+        policy: NextUpdatePolicy,
+    ) -> bool {
+        self.is_fresh_within(time, max_age) && !self.is_stale_at_with_policy(time, policy)
+    }
+
+    fn is_fresh_within(&self, time: ASN1Time, max_age: Duration) -> bool {
+        let this_update = self.last_update();
+        if time < this_update {
+            return false;
+        }
+        if let Some(age) = time - this_update {
+            if age > max_age {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Test if the CRL is stale at the given date/time, i.e. `time` is after `nextUpdate`
+    ///
+    /// A CRL without a usable `nextUpdate` is handled using the default [`NextUpdatePolicy`],
+    /// and is always stale.
+    pub fn is_stale_at(&self, time: ASN1Time) -> bool {
+        self.is_stale_at_with_policy(time, NextUpdatePolicy::default())
+    }
+
+    /// Test if the CRL is stale, i.e. `nextUpdate` is in the past
+    ///
+    /// A CRL without a usable `nextUpdate` is always stale, see [`is_stale_at`](Self::is_stale_at).
+    pub fn is_stale(&self) -> bool {
+        self.is_stale_at(ASN1Time::now())
+    }
+
+    /// Test if the CRL is stale at the given date/time, using `policy` if the CRL has no usable
+    /// `nextUpdate` (absent, or not after `thisUpdate`)
+    pub fn is_stale_at_with_policy(&self, time: ASN1Time, policy: NextUpdatePolicy) -> bool {
+        match self.effective_next_update() {
+            Some(next_update) => time > next_update,
+            None => policy == NextUpdatePolicy::RequireNextUpdate,
+        }
+    }
+
+    /// Test if the CRL is stale now, using `policy` if the CRL has no usable `nextUpdate`
+    pub fn is_stale_with_policy(&self, policy: NextUpdatePolicy) -> bool {
+        self.is_stale_at_with_policy(ASN1Time::now(), policy)
+    }
+
     /// Return an iterator over the `RevokedCertificate` objects
     pub fn iter_revoked_certificates(&self) -> impl Iterator<Item = &RevokedCertificate<'a>> {
         self.tbs_cert_list.revoked_certificates.iter()
     }
 
+    /// Search for the revoked certificate entry with the given serial number
+    ///
+    /// The raw bytes of the serial numbers are compared, without conversion to `BigUint`.
+    /// Leading zero bytes are ignored, so `serial` can be either the content of the DER
+    /// integer (for ex. [`X509Certificate::raw_serial`]), or the unsigned big-endian value.
+    pub fn find_revoked_raw_serial(&self, serial: &[u8]) -> Option<&RevokedCertificate<'a>> {
+        let serial = strip_leading_zeros(serial);
+        self.iter_revoked_certificates()
+            .find(|revoked| strip_leading_zeros(revoked.raw_serial) == serial)
+    }
+
+    /// Return an iterator over the revoked certificates, with the names of their issuer
+    ///
+    /// In an indirect CRL, the Certificate Issuer entry extension identifies the issuer of the
+    /// revoked certificate. It applies to this entry and to the following entries, until another
+    /// Certificate Issuer extension is found. The issuer is `None` for the first entries without
+    /// this extension, which were issued by the CRL issuer.
+    pub fn iter_revoked_certificates_with_issuer(
+        &self,
+    ) -> impl Iterator<Item = (&RevokedCertificate<'a>, Option<&GeneralNames>)> {
+        self.iter_revoked_certificates()
+            .scan(None, |issuer, revoked| {
+                if let Some((_, names)) = revoked.certificate_issuer() {
+                    *issuer = Some(names);
+                }
+                Some((revoked, *issuer))
+            })
+    }
+
+    /// Search for the revoked certificate entry with the given serial number, issued by `issuer`
+    ///
+    /// Unlike [`Self::find_revoked_raw_serial`], the issuer of the entries of indirect CRLs is
+    /// checked (see [`Self::iter_revoked_certificates_with_issuer`]). Names are compared using
+    /// [`X509Name::eq_normalized`].
+    pub fn find_revoked_for_issuer(
+        &self,
+        serial: &[u8],
+        issuer: &X509Name,
+    ) -> Option<&RevokedCertificate<'a>> {
+        let serial = strip_leading_zeros(serial);
+        self.iter_revoked_certificates_with_issuer()
+            .find(|(revoked, names)| {
+                entry_issuer_matches(self.issuer(), *names, issuer)
+                    && strip_leading_zeros(revoked.raw_serial) == serial
+            })
+            .map(|(revoked, _)| revoked)
+    }
+
+    /// Build an index of the revoked certificates, sorted by serial number
+    ///
+    /// Use the index to search for many serial numbers (see [`RevokedSerialIndex`]).
+    pub fn serial_index(&self) -> RevokedSerialIndex<'a, '_> {
+        RevokedSerialIndex::new(self)
+    }
+
+    /// Return the revoked items of an inventory of serial numbers
+    ///
+    /// Each serial number is searched in a sorted index of the CRL entries (see
+    /// [`Self::serial_index`]), so the cost is `O((n + m) log n)` for a CRL of `n` entries and
+    /// an inventory of `m` items. The result contains the position of each revoked item in the
+    /// inventory, with its CRL entry, in inventory order.
+    ///
+    /// See [`Self::find_revoked_raw_serial`] for the format of the serial numbers. The issuers
+    /// of the certificates are not checked, see [`Self::match_revoked_certificates`].
+    pub fn match_revoked_serials<I, S>(&self, serials: I) -> Vec<(usize, &RevokedCertificate<'a>)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let index = self.serial_index();
+        serials
+            .into_iter()
+            .enumerate()
+            .filter_map(|(n, serial)| index.find(serial.as_ref()).map(|revoked| (n, revoked)))
+            .collect()
+    }
+
+    /// Return the revoked certificates of an inventory
+    ///
+    /// Like [`Self::match_revoked_serials`], but the issuer of each certificate must also match
+    /// the issuer of the CRL entry (see [`Self::find_revoked_for_issuer`]).
+    pub fn match_revoked_certificates<'c, I>(
+        &self,
+        certificates: I,
+    ) -> Vec<(usize, &RevokedCertificate<'a>)>
+    where
+        I: IntoIterator<Item = &'c X509Certificate<'c>>,
+    {
+        let index = self.serial_index();
+        certificates
+            .into_iter()
+            .enumerate()
+            .filter_map(|(n, cert)| {
+                index
+                    .find_for_issuer(cert.raw_serial(), cert.issuer())
+                    .map(|revoked| (n, revoked))
+            })
+            .collect()
+    }
+
+    /// Test if the certificate with the given serial number is listed in this CRL
+    ///
+    /// See [`Self::find_revoked_raw_serial`] for the format of `serial`.
+    pub fn contains_raw_serial(&self, serial: &[u8]) -> bool {
+        self.find_revoked_raw_serial(serial).is_some()
+    }
+
     /// Get the CRL extensions.
     #[inline]
     pub fn extensions(&self) -> &[X509Extension] {
@@ -92,22 +332,110 @@ impl<'a> CertificateRevocationList<'a> {
 
     /// Get the CRL number, if present
     ///
-    /// Note that the returned value is a `BigUint`, because of the following RFC specification:
+    /// CRL numbers can be long integers, following the RFC specification:
     /// <pre>
     /// Given the requirements above, CRL numbers can be expected to contain long integers.  CRL
     /// verifiers MUST be able to handle CRLNumber values up to 20 octets.  Conformant CRL issuers
     /// MUST NOT use CRLNumber values longer than 20 octets.
     /// </pre>
-    pub fn crl_number(&self) -> Option<&BigUint> {
-        self.extensions()
-            .iter()
-            .find(|&ext| ext.oid == OID_X509_EXT_BASIC_CONSTRAINTS)
+    pub fn crl_number(&self) -> Option<&CRLNumber> {
+        self.tbs_cert_list
+            .find_extension(&OID_X509_EXT_CRL_NUMBER)
             .and_then(|ext| match ext.parsed_extension {
                 ParsedExtension::CRLNumber(ref num) => Some(num),
                 _ => None,
             })
     }
 
+    /// Test if this CRL supersedes `other`
+    ///
+    /// If both CRLs have a CRL number, the numbers are compared. Otherwise, the `thisUpdate`
+    /// dates are compared.
+    ///
+    /// Both CRLs are expected to come from the same issuer, with the same scope: this is not
+    /// checked by this function.
+    pub fn is_newer_than(&self, other: &CertificateRevocationList) -> bool {
+        match (self.crl_number(), other.crl_number()) {
+            (Some(n1), Some(n2)) => {
+                let (n1, n2) = (n1.as_bytes(), n2.as_bytes());
+                (n1.len(), n1) > (n2.len(), n2)
+            }
+            _ => self.last_update() > other.last_update(),
+        }
+    }
+
+    /// Get the CRL Authority Key Identifier extension, if present
+    pub fn authority_key_identifier(&self) -> Option<&AuthorityKeyIdentifier> {
+        self.tbs_cert_list
+            .find_extension(&OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER)
+            .and_then(|ext| match ext.parsed_extension {
+                ParsedExtension::AuthorityKeyIdentifier(ref aki) => Some(aki),
+                _ => None,
+            })
+    }
+
+    /// Get the CRL Freshest CRL extension, if present
+    ///
+    /// In a complete CRL, this extension contains the locations of the delta CRLs.
+    pub fn freshest_crl(&self) -> Option<&CRLDistributionPoints> {
+        self.tbs_cert_list
+            .find_extension(&OID_X509_EXT_FRESHEST_CRL)
+            .and_then(|ext| match ext.parsed_extension {
+                ParsedExtension::FreshestCRL(ref points) => Some(points),
+                _ => None,
+            })
+    }
+
+    /// Get the CRL Issuing Distribution Point extension, if present
+    ///
+    /// This extension must be checked to correctly interpret partitioned and indirect CRLs.
+    pub fn issuing_distribution_point(&self) -> Option<&IssuingDistributionPoint> {
+        self.tbs_cert_list
+            .find_extension(&OID_X509_EXT_ISSUER_DISTRIBUTION_POINT)
+            .and_then(|ext| match ext.parsed_extension {
+                ParsedExtension::IssuingDistributionPoint(ref idp) => Some(idp),
+                _ => None,
+            })
+    }
+
+    /// Test if this CRL is a delta CRL (it has a Delta CRL Indicator extension)
+    ///
+    /// A delta CRL only lists the changes since a base CRL, and must be combined with it.
+    pub fn is_delta_crl(&self) -> bool {
+        self.tbs_cert_list
+            .find_extension(&OID_X509_EXT_DELTA_CRL_INDICATOR)
+            .is_some()
+    }
+
+    /// Test if this CRL was issued by the given certificate
+    ///
+    /// The CRL issuer must be equal to the certificate subject. If the CRL has an Authority Key
+    /// Identifier extension, it must also match the certificate: the key identifier is compared
+    /// to the Subject Key Identifier of the certificate (if both are present), and the issuer
+    /// and serial number are compared to the certificate issuer and serial (if present).
+    ///
+    /// This function does not verify the signature, or the key usage of the certificate.
+    pub fn is_issued_by(&self, issuer: &X509Certificate) -> bool {
+        if self.issuer().as_raw() != issuer.subject().as_raw() {
+            return false;
+        }
+        let aki = match self.authority_key_identifier() {
+            Some(aki) => aki,
+            None => return true,
+        };
+        if let Some(key_id) = &aki.key_identifier {
+            if let Ok(Some(ski)) = issuer.subject_key_identifier() {
+                if key_id != ski.value {
+                    return false;
+                }
+            }
+        }
+        if aki.authority_cert_issuer.is_some() || aki.authority_cert_serial.is_some() {
+            return aki.matches_issuer_certificate(&issuer.tbs_certificate);
+        }
+        true
+    }
+
     /// Verify the cryptographic signature of this certificate revocation list
     ///
     /// `public_key` is the public key of the **signer**.
@@ -132,18 +460,161 @@ impl<'a> CertificateRevocationList<'a> {
 ///      signatureValue       BIT STRING  }
 /// </pre>
 impl<'a> FromDer<'a, X509Error> for CertificateRevocationList<'a> {
-    fn from_der(i: &'a [u8]) -> X509Result<Self> {
-        parse_der_sequence_defined_g(|i, _| {
+    fn from_der(input: &'a [u8]) -> X509Result<Self> {
+        let (rem, mut crl) = parse_der_sequence_defined_g(|i, _| {
             let (i, tbs_cert_list) = TbsCertList::from_der(i)?;
             let (i, signature_algorithm) = AlgorithmIdentifier::from_der(i)?;
             let (i, signature_value) = parse_signature_value(i)?;
-            let crl = CertificateRevocationList {
+            let crl = CertificateRevocationList::from_parts(
                 tbs_cert_list,
                 signature_algorithm,
                 signature_value,
-            };
+            );
             Ok((i, crl))
-        })(i)
+        })(input)?;
+        crl.raw = &input[..input.offset(rem)];
+        Ok((rem, crl))
+    }
+}
+
+/// An index of the revoked certificates of a CRL, sorted by serial number
+///
+/// Searching a serial number in a CRL is a linear scan of the entries. For large inventories of
+/// certificates checked against large CRLs, build this index once with
+/// [`CertificateRevocationList::serial_index`]: each search is then a binary search.
+///
+/// ```rust
+/// use x509_parser::prelude::*;
+///
+/// # static DER: &[u8] = include_bytes!("../assets/example.crl");
+/// let (_, crl) = parse_x509_crl(DER).expect("could not parse CRL");
+/// let index = crl.serial_index();
+/// for serial in [&[0x14, 0x79, 0x47][..], &[0x01]] {
+///     if let Some(revoked) = index.find(serial) {
+///         println!("revoked on {}", revoked.revocation_date);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RevokedSerialIndex<'a, 'b> {
+    crl_issuer: &'b X509Name<'a>,
+    // serial number without leading zeros, issuer of indirect CRL entries, and entry
+    entries: Vec<(
+        &'a [u8],
+        Option<&'b GeneralNames<'b>>,
+        &'b RevokedCertificate<'a>,
+    )>,
+}
+
+impl<'a, 'b> RevokedSerialIndex<'a, 'b> {
+    /// Build the index of the entries of `crl`
+    pub fn new(crl: &'b CertificateRevocationList<'a>) -> Self {
+        let mut entries: Vec<_> = crl
+            .iter_revoked_certificates_with_issuer()
+            .map(|(revoked, names)| (strip_leading_zeros(revoked.raw_serial), names, revoked))
+            .collect();
+        // stable sort: entries with the same serial keep the CRL order
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        RevokedSerialIndex {
+            crl_issuer: &crl.tbs_cert_list.issuer,
+            entries,
+        }
+    }
+
+    // Return the entries with the given serial number
+    fn entries_for(
+        &self,
+        serial: &[u8],
+    ) -> &[(
+        &'a [u8],
+        Option<&'b GeneralNames<'b>>,
+        &'b RevokedCertificate<'a>,
+    )] {
+        let serial = strip_leading_zeros(serial);
+        let start = self.entries.partition_point(|entry| entry.0 < serial);
+        let len = self.entries[start..].partition_point(|entry| entry.0 == serial);
+        &self.entries[start..start + len]
+    }
+
+    /// Search for the revoked certificate entry with the given serial number
+    ///
+    /// See [`CertificateRevocationList::find_revoked_raw_serial`] for the format of `serial`.
+    pub fn find(&self, serial: &[u8]) -> Option<&'b RevokedCertificate<'a>> {
+        self.entries_for(serial).first().map(|entry| entry.2)
+    }
+
+    /// Search for the revoked certificate entry with the given serial number, issued by `issuer`
+    ///
+    /// See [`CertificateRevocationList::find_revoked_for_issuer`].
+    pub fn find_for_issuer(
+        &self,
+        serial: &[u8],
+        issuer: &X509Name,
+    ) -> Option<&'b RevokedCertificate<'a>> {
+        self.entries_for(serial)
+            .iter()
+            .find(|entry| entry_issuer_matches(self.crl_issuer, entry.1, issuer))
+            .map(|entry| entry.2)
+    }
+
+    /// Return the number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if the CRL has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// A CRL owning its DER encoding
+///
+/// [`CertificateRevocationList`] borrows the input buffer, which makes it difficult to store in long-lived
+/// structures. This type stores a copy of the encoding, and can be sent to other threads.
+/// The parsed object is obtained using [`Self::crl`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CertificateRevocationListOwned {
+    raw: Vec<u8>,
+}
+
+impl CertificateRevocationListOwned {
+    /// Parse a DER-encoded CRL, taking ownership of the buffer
+    ///
+    /// Trailing data after the CRL is removed.
+    pub fn new(mut raw: Vec<u8>) -> Result<Self, X509Error> {
+        let (rem, _) = CertificateRevocationList::from_der(&raw)?;
+        let len = raw.len() - rem.len();
+        raw.truncate(len);
+        Ok(CertificateRevocationListOwned { raw })
+    }
+
+    /// Return the raw DER encoding of the CRL
+    pub fn as_raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Return the parsed CRL, borrowing the stored buffer
+    ///
+    /// The stored encoding is parsed on each call, so the returned object should be kept if
+    /// several fields are accessed. The encoding is checked when the object is built, so this
+    /// does not fail in practice.
+    pub fn crl(&self) -> Result<CertificateRevocationList<'_>, X509Error> {
+        let (_, obj) = CertificateRevocationList::from_der(&self.raw)?;
+        Ok(obj)
+    }
+}
+
+impl<'a> TryFrom<&CertificateRevocationList<'a>> for CertificateRevocationListOwned {
+    type Error = X509Error;
+
+    fn try_from(obj: &CertificateRevocationList<'a>) -> Result<Self, Self::Error> {
+        if obj.raw.is_empty() {
+            return Err(X509Error::MissingEncoding);
+        }
+        Ok(CertificateRevocationListOwned {
+            raw: obj.raw.to_vec(),
+        })
     }
 }
 
@@ -195,6 +666,20 @@ impl<'a> TbsCertList<'a> {
         self.extensions.iter()
     }
 
+    /// Searches for an extension with the given `Oid`.
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error `DuplicateExtensions` if the extension is present twice or more.
+    #[inline]
+    pub fn get_extension_unique(&self, oid: &Oid) -> Result<Option<&X509Extension<'a>>, X509Error> {
+        get_extension_unique(&self.extensions, oid)
+    }
+
+    /// Returns the `Oid`s of extensions present twice or more, in order of first occurrence
+    pub fn duplicate_extensions(&self) -> Vec<&Oid<'a>> {
+        duplicate_extensions(&self.extensions)
+    }
+
     /// Searches for an extension with the given `Oid`.
     ///
     /// Note: if there are several extensions with the same `Oid`, the first one is returned.
@@ -255,6 +740,8 @@ impl<'a> FromDer<'a, X509Error> for TbsCertList<'a> {
 #[derive(Clone, Debug, PartialEq)]
 pub struct RevokedCertificate<'a> {
     /// The Serial number of the revoked certificate
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
     pub user_certificate: BigUint,
     /// The date on which the revocation occurred is specified.
     pub revocation_date: ASN1Time,
@@ -265,10 +752,19 @@ pub struct RevokedCertificate<'a> {
 
 impl<'a> RevokedCertificate<'a> {
     /// Return the serial number of the revoked certificate
+    #[cfg(feature = "bigint")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bigint")))]
     pub fn serial(&self) -> &BigUint {
         &self.user_certificate
     }
 
+    /// Return the serial number of the revoked certificate as `u128`
+    ///
+    /// Returns `None` if the serial number does not fit in 128 bits.
+    pub fn serial_u128(&self) -> Option<u128> {
+        serial_to_u128(self.raw_serial)
+    }
+
     /// Get the CRL entry extensions.
     #[inline]
     pub fn extensions(&self) -> &[X509Extension] {
@@ -281,6 +777,20 @@ impl<'a> RevokedCertificate<'a> {
         self.extensions.iter()
     }
 
+    /// Searches for a CRL entry extension with the given `Oid`.
+    ///
+    /// Return `Ok(Some(extension))` if exactly one was found, `Ok(None)` if none was found,
+    /// or an error `DuplicateExtensions` if the extension is present twice or more.
+    #[inline]
+    pub fn get_extension_unique(&self, oid: &Oid) -> Result<Option<&X509Extension<'a>>, X509Error> {
+        get_extension_unique(&self.extensions, oid)
+    }
+
+    /// Returns the `Oid`s of extensions present twice or more, in order of first occurrence
+    pub fn duplicate_extensions(&self) -> Vec<&Oid<'a>> {
+        duplicate_extensions(&self.extensions)
+    }
+
     /// Searches for a CRL entry extension with the given `Oid`.
     ///
     /// Note: if there are several extensions with the same `Oid`, the first one is returned.
@@ -308,11 +818,21 @@ impl<'a> RevokedCertificate<'a> {
         self.raw_serial
     }
 
+    /// Compare the raw bytes of the revoked certificate serial number with `serial`, in constant time
+    pub fn raw_serial_ct_eq(&self, serial: &[u8]) -> bool {
+        constant_time_eq(self.raw_serial, serial)
+    }
+
     /// Get a formatted string of the certificate serial number, separated by ':'
     pub fn raw_serial_as_string(&self) -> String {
         format_serial(self.raw_serial)
     }
 
+    /// Get a formatted string of the certificate serial number, using the given style
+    pub fn format_serial(&self, style: SerialFormat) -> String {
+        format_serial_with(self.raw_serial, style)
+    }
+
     /// Get the code identifying the reason for the revocation, if present
     pub fn reason_code(&self) -> Option<(bool, ReasonCode)> {
         self.find_extension(&OID_X509_EXT_REASON_CODE)
@@ -322,6 +842,19 @@ impl<'a> RevokedCertificate<'a> {
             })
     }
 
+    /// Get the names of the issuer of the revoked certificate, if present
+    ///
+    /// This extension is only used in indirect CRLs. See
+    /// [`CertificateRevocationList::iter_revoked_certificates_with_issuer`] to get the issuer
+    /// of entries without this extension.
+    pub fn certificate_issuer(&self) -> Option<(bool, &GeneralNames)> {
+        self.find_extension(&OID_X509_EXT_ISSUER)
+            .and_then(|ext| match ext.parsed_extension {
+                ParsedExtension::CertificateIssuer(ref names) => Some((ext.critical, names)),
+                _ => None,
+            })
+    }
+
     /// Get the invalidity date, if present
     ///
     /// The invalidity date is the date on which it is known or suspected that the private
@@ -333,6 +866,77 @@ impl<'a> RevokedCertificate<'a> {
                 _ => None,
             })
     }
+
+    /// Get the hold instruction code, if present
+    ///
+    /// This extension is only meaningful for entries with the `CertificateHold` reason code.
+    pub fn hold_instruction(&self) -> Option<(bool, &HoldInstruction)> {
+        self.find_extension(&OID_X509_EXT_HOLD_INSTRUCTION_CODE)
+            .and_then(|ext| match ext.parsed_extension {
+                ParsedExtension::HoldInstructionCode(ref hold) => Some((ext.critical, hold)),
+                _ => None,
+            })
+    }
+
+    /// Get all the CRL entry extensions, normalized into a single struct
+    ///
+    /// Returns `DuplicateExtensions` if an extension is present twice or more, or
+    /// `InvalidExtensions` if a known extension could not be parsed.
+    ///
+    /// The `certificate_issuer` field is only set if the entry has the extension. See
+    /// [`CertificateRevocationList::iter_revoked_certificates_with_issuer`] to get the issuer
+    /// of entries without this extension.
+    pub fn entry_extensions(&self) -> Result<CrlEntryExtensions<'a, '_>, X509Error> {
+        if !self.duplicate_extensions().is_empty() {
+            return Err(X509Error::DuplicateExtensions);
+        }
+        let mut entry = CrlEntryExtensions::default();
+        for ext in &self.extensions {
+            match &ext.parsed_extension {
+                ParsedExtension::ReasonCode(code) => entry.reason_code = Some(*code),
+                ParsedExtension::InvalidityDate(date) => entry.invalidity_date = Some(*date),
+                ParsedExtension::CertificateIssuer(names) => entry.certificate_issuer = Some(names),
+                ParsedExtension::HoldInstructionCode(hold) => {
+                    entry.hold_instruction = Some(hold.clone())
+                }
+                _ if ext.oid == OID_X509_EXT_REASON_CODE
+                    || ext.oid == OID_X509_EXT_INVALIDITY_DATE
+                    || ext.oid == OID_X509_EXT_ISSUER
+                    || ext.oid == OID_X509_EXT_HOLD_INSTRUCTION_CODE =>
+                {
+                    return Err(X509Error::InvalidExtensions);
+                }
+                _ => entry.other.push(ext),
+            }
+        }
+        Ok(entry)
+    }
+}
+
+/// The CRL entry extensions of a revoked certificate
+///
+/// See [`RevokedCertificate::entry_extensions`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CrlEntryExtensions<'a, 'b> {
+    /// The reason for the revocation (Section 5.3.1 of rfc 5280)
+    pub reason_code: Option<ReasonCode>,
+    /// The date on which the certificate became invalid (Section 5.3.2 of rfc 5280)
+    pub invalidity_date: Option<ASN1Time>,
+    /// The issuer of the certificate, for indirect CRLs (Section 5.3.3 of rfc 5280)
+    pub certificate_issuer: Option<&'b GeneralNames<'a>>,
+    /// The hold instruction code (Section 5.3.2 of rfc 3280)
+    pub hold_instruction: Option<HoldInstruction<'a>>,
+    /// The other entry extensions, in order of appearance
+    pub other: Vec<&'b X509Extension<'a>>,
+}
+
+impl<'a, 'b> CrlEntryExtensions<'a, 'b> {
+    /// Returns true if one of the other (unknown) entry extensions is marked critical
+    ///
+    /// Rfc 5280 requires rejecting a CRL with an unrecognized critical entry extension.
+    pub fn has_unknown_critical(&self) -> bool {
+        self.other.iter().any(|ext| ext.critical)
+    }
 }
 
 // revokedCertificates     SEQUENCE OF SEQUENCE  {
@@ -344,11 +948,12 @@ impl<'a> RevokedCertificate<'a> {
 impl<'a> FromDer<'a, X509Error> for RevokedCertificate<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<Self> {
         parse_der_sequence_defined_g(|i, _| {
-            let (i, (raw_serial, user_certificate)) = parse_serial(i)?;
+            let (i, raw_serial) = parse_serial(i)?;
             let (i, revocation_date) = ASN1Time::from_der(i)?;
             let (i, extensions) = opt(complete(parse_extension_sequence))(i)?;
             let revoked = RevokedCertificate {
-                user_certificate,
+                #[cfg(feature = "bigint")]
+                user_certificate: BigUint::from_bytes_be(raw_serial),
                 revocation_date,
                 extensions: extensions.unwrap_or_default(),
                 raw_serial,
@@ -358,8 +963,127 @@ impl<'a> FromDer<'a, X509Error> for RevokedCertificate<'a> {
     }
 }
 
+/// Test if the scope of `crl` covers the certificate `cert`
+///
+/// This implements the scope checks of RFC5280 section 6.3.3 (steps a and b), required to
+/// process partitioned and indirect CRLs:
+///
+/// - the CRL issuer must be the certificate issuer or, for an indirect CRL, the `cRLIssuer` of
+///   a distribution point of the certificate;
+/// - if the CRL has an Issuing Distribution Point with a name, it must match the name (or
+///   `cRLIssuer`) of a CRL distribution point of the certificate;
+/// - `onlyContainsUserCerts` and `onlyContainsCACerts` must match the type of the certificate.
+///
+/// A CRL which cannot establish the revocation status of the certificate on its own is
+/// rejected: a CRL covering only some revocation reasons (`onlySomeReasons`), a CRL containing
+/// only attribute certificates (`onlyContainsAttributeCerts`), and a delta CRL.
+/// Names are compared using [`X509Name::eq_normalized`]. Neither the signature nor the validity
+/// period of the CRL are checked.
+pub fn crl_covers_certificate(crl: &CertificateRevocationList, cert: &X509Certificate) -> bool {
+    if crl.is_delta_crl() {
+        return false;
+    }
+    let idp = crl.issuing_distribution_point();
+    let indirect = matches!(idp, Some(idp) if idp.indirect_crl);
+    if let Some(idp) = idp {
+        let is_ca = matches!(cert.basic_constraints(), Ok(Some(bc)) if bc.value.ca);
+        if idp.only_some_reasons.is_some()
+            || idp.only_contains_attribute_certs
+            || (idp.only_contains_user_certs && is_ca)
+            || (idp.only_contains_ca_certs && !is_ca)
+        {
+            return false;
+        }
+    }
+    let points = match cert.crl_distribution_points() {
+        Ok(Some(ext)) => &ext.value.points[..],
+        _ => &[],
+    };
+    let crl_issuer = crl.issuer();
+    // the CRL issuer must match the cRLIssuer of the distribution point, if present
+    let issuer_matches = |dp: &CRLDistributionPoint| match &dp.crl_issuer {
+        Some(names) => indirect && names_contain_directory_name(names, crl_issuer),
+        None => crl_issuer.eq_normalized(cert.issuer()),
+    };
+    match idp.and_then(|idp| idp.distribution_point.as_ref()) {
+        None => crl_issuer.eq_normalized(cert.issuer()) || points.iter().any(issuer_matches),
+        Some(idp_name) => points.iter().any(|dp| {
+            if !issuer_matches(dp) {
+                return false;
+            }
+            match (&dp.distribution_point, &dp.crl_issuer) {
+                (Some(dp_name), _) => distribution_point_names_match(dp_name, idp_name, crl_issuer),
+                (None, Some(names)) => names.iter().any(|name| {
+                    let name = DistributionPointName::FullName(vec![name.clone()]);
+                    distribution_point_names_match(&name, idp_name, crl_issuer)
+                }),
+                (None, None) => false,
+            }
+        }),
+    }
+}
+
+fn names_contain_directory_name(names: &[GeneralName], dn: &X509Name) -> bool {
+    names.iter().any(|name| match name {
+        GeneralName::DirectoryName(name) => name.eq_normalized(dn),
+        _ => false,
+    })
+}
+
+// Test if `name` is the concatenation of `issuer` and `rdn`
+fn is_name_relative_to(
+    name: &X509Name,
+    issuer: &X509Name,
+    rdn: &RelativeDistinguishedName,
+) -> bool {
+    name.iter().count() == issuer.iter().count() + 1
+        && name.starts_with(issuer)
+        && matches!(name.iter().last(), Some(last) if last.eq_normalized(rdn))
+}
+
+// Names relative to the CRL issuer are expanded using `issuer`
+fn distribution_point_names_match(
+    a: &DistributionPointName,
+    b: &DistributionPointName,
+    issuer: &X509Name,
+) -> bool {
+    use DistributionPointName::{FullName, NameRelativeToCRLIssuer};
+    match (a, b) {
+        (FullName(a), FullName(b)) => a.iter().any(|a| match a {
+            GeneralName::DirectoryName(dn) => names_contain_directory_name(b, dn),
+            _ => b.contains(a),
+        }),
+        (NameRelativeToCRLIssuer(a), NameRelativeToCRLIssuer(b)) => a.eq_normalized(b),
+        (FullName(names), NameRelativeToCRLIssuer(rdn))
+        | (NameRelativeToCRLIssuer(rdn), FullName(names)) => names.iter().any(|name| match name {
+            GeneralName::DirectoryName(dn) => is_name_relative_to(dn, issuer, rdn),
+            _ => false,
+        }),
+    }
+}
+
+// Test if a CRL entry applies to certificates issued by `issuer`, given the Certificate Issuer
+// names of the entry (in indirect CRLs). Names are compared like in `crl_covers_certificate`.
+fn entry_issuer_matches(
+    crl_issuer: &X509Name,
+    names: Option<&GeneralNames>,
+    issuer: &X509Name,
+) -> bool {
+    match names {
+        Some(names) => names
+            .iter()
+            .any(|name| matches!(name, GeneralName::DirectoryName(dn) if dn.eq_normalized(issuer))),
+        None => crl_issuer.eq_normalized(issuer),
+    }
+}
+
+fn strip_leading_zeros(serial: &[u8]) -> &[u8] {
+    let start = serial.iter().position(|&b| b != 0).unwrap_or(serial.len());
+    &serial[start..]
+}
+
 fn parse_revoked_certificates(i: &[u8]) -> X509Result<Vec<RevokedCertificate>> {
     parse_der_sequence_defined_g(|a, _| {
-        all_consuming(many0(complete(RevokedCertificate::from_der)))(a)
+        all_consuming(many0_lazy(complete(RevokedCertificate::from_der)))(a)
     })(i)
 }
@@ -0,0 +1,411 @@
+//! Implementation of `serde::Serialize` for the parsed structures (`serde` feature)
+//!
+//! The representation is meant to be readable in text formats (for ex. JSON):
+//!
+//! - object identifiers are serialized as dotted-decimal strings
+//! - names are serialized as strings (for ex. `"CN=example.com, O=Org"`)
+//! - times are serialized as RFC 3339 strings, in UTC
+//! - serial numbers, bit strings and raw values are serialized as hexadecimal strings
+//! - the common parsed extensions (key identifiers, key usages, basic constraints, alternative
+//!   names, policies, distribution points, access descriptions and CRL entry extensions) are
+//!   serialized as structured values, other extensions using their one-line description
+
+use crate::certificate::{TbsCertificate, UniqueIdentifier, Validity, X509Certificate};
+use crate::certification_request::{X509CertificationRequest, X509CertificationRequestInfo};
+use crate::cri_attributes::X509CriAttribute;
+use crate::extensions::{
+    AccessDescription, AuthorityInfoAccess, AuthorityKeyIdentifier, BasicConstraints,
+    CRLDistributionPoint, CRLDistributionPoints, ExtendedKeyUsage, GeneralName,
+    IssuerAlternativeName, KeyIdentifier, KeyUsage, ParsedExtension, PolicyInformation,
+    PolicyQualifierInfo, SubjectAlternativeName, SubjectInfoAccess, X509Extension, KEY_USAGE_FLAGS,
+};
+use crate::report::CertificateReport;
+use crate::revocation_list::{CertificateRevocationList, RevokedCertificate, TbsCertList};
+use crate::time::ASN1Time;
+use crate::utils::format_serial;
+use crate::x509::{AlgorithmIdentifier, ReasonCode, SubjectPublicKeyInfo, X509Name, X509Version};
+use asn1_rs::{BitString, ToDer};
+use data_encoding::HEXLOWER;
+use serde_crate::ser::{Serialize, SerializeSeq, SerializeStruct, Serializer};
+
+/// Wrapper to serialize bytes as a hexadecimal string
+struct Hex<'a>(&'a [u8]);
+
+impl<'a> Serialize for Hex<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&HEXLOWER.encode(self.0))
+    }
+}
+
+/// Wrapper to serialize a bit string as a hexadecimal string
+struct Bits<'a, 'b>(&'b BitString<'a>);
+
+impl<'a, 'b> Serialize for Bits<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex(&self.0.data).serialize(serializer)
+    }
+}
+
+/// Wrapper to serialize a serial number as a colon-separated hexadecimal string
+struct Serial<'a>(&'a [u8]);
+
+impl<'a> Serialize for Serial<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_serial(self.0))
+    }
+}
+
+impl Serialize for X509Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.0)
+    }
+}
+
+impl Serialize for ASN1Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'a> Serialize for X509Name<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'a> Serialize for AlgorithmIdentifier<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let parameters = self
+            .parameters
+            .as_ref()
+            .and_then(|any| any.to_der_vec().ok())
+            .map(|v| HEXLOWER.encode(&v));
+        let mut s = serializer.serialize_struct("AlgorithmIdentifier", 2)?;
+        s.serialize_field("algorithm", &self.algorithm.to_id_string())?;
+        s.serialize_field("parameters", &parameters)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for SubjectPublicKeyInfo<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("SubjectPublicKeyInfo", 2)?;
+        s.serialize_field("algorithm", &self.algorithm)?;
+        s.serialize_field("subject_public_key", &Bits(&self.subject_public_key))?;
+        s.end()
+    }
+}
+
+impl Serialize for Validity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Validity", 2)?;
+        s.serialize_field("not_before", &self.not_before)?;
+        s.serialize_field("not_after", &self.not_after)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for UniqueIdentifier<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Bits(&self.0).serialize(serializer)
+    }
+}
+
+/// Wrapper to serialize a list of object identifiers as dotted-decimal strings
+struct Oids<'a, 'b>(&'b [asn1_rs::Oid<'a>]);
+
+impl<'a, 'b> Serialize for Oids<'a, 'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|oid| oid.to_id_string()))
+    }
+}
+
+impl<'a> Serialize for GeneralName<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'a> Serialize for KeyIdentifier<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex(self.0).serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for AuthorityKeyIdentifier<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AuthorityKeyIdentifier", 3)?;
+        s.serialize_field("key_identifier", &self.key_identifier)?;
+        s.serialize_field("authority_cert_issuer", &self.authority_cert_issuer)?;
+        s.serialize_field(
+            "authority_cert_serial",
+            &self.authority_cert_serial.map(Serial),
+        )?;
+        s.end()
+    }
+}
+
+impl Serialize for KeyUsage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for (idx, name) in KEY_USAGE_FLAGS.iter().enumerate() {
+            if self.flags >> idx & 1 != 0 {
+                seq.serialize_element(name)?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<'a> Serialize for ExtendedKeyUsage<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ExtendedKeyUsage", 8)?;
+        s.serialize_field("any", &self.any)?;
+        s.serialize_field("server_auth", &self.server_auth)?;
+        s.serialize_field("client_auth", &self.client_auth)?;
+        s.serialize_field("code_signing", &self.code_signing)?;
+        s.serialize_field("email_protection", &self.email_protection)?;
+        s.serialize_field("time_stamping", &self.time_stamping)?;
+        s.serialize_field("ocsp_signing", &self.ocsp_signing)?;
+        s.serialize_field("other", &Oids(&self.other))?;
+        s.end()
+    }
+}
+
+impl Serialize for BasicConstraints {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("BasicConstraints", 2)?;
+        s.serialize_field("ca", &self.ca)?;
+        s.serialize_field("path_len_constraint", &self.path_len_constraint)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for SubjectAlternativeName<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.general_names.serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for IssuerAlternativeName<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.general_names.serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for PolicyQualifierInfo<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PolicyQualifierInfo", 2)?;
+        s.serialize_field(
+            "policy_qualifier_id",
+            &self.policy_qualifier_id.to_id_string(),
+        )?;
+        s.serialize_field("qualifier", &Hex(self.qualifier))?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for PolicyInformation<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PolicyInformation", 2)?;
+        s.serialize_field("policy_id", &self.policy_id.to_id_string())?;
+        s.serialize_field("policy_qualifiers", &self.policy_qualifiers)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for CRLDistributionPoint<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let distribution_point = self.distribution_point.as_ref().map(|p| p.to_string());
+        let reasons = self.reasons.as_ref().map(|r| r.to_string());
+        let mut s = serializer.serialize_struct("CRLDistributionPoint", 3)?;
+        s.serialize_field("distribution_point", &distribution_point)?;
+        s.serialize_field("reasons", &reasons)?;
+        s.serialize_field("crl_issuer", &self.crl_issuer)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for CRLDistributionPoints<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.points.serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for AccessDescription<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AccessDescription", 2)?;
+        s.serialize_field("access_method", &self.access_method.to_id_string())?;
+        s.serialize_field("access_location", &self.access_location)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for AuthorityInfoAccess<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.accessdescs.serialize(serializer)
+    }
+}
+
+impl<'a> Serialize for SubjectInfoAccess<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.accessdescs.serialize(serializer)
+    }
+}
+
+impl Serialize for ReasonCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'a> Serialize for ParsedExtension<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ParsedExtension::AuthorityKeyIdentifier(aki) => aki.serialize(serializer),
+            ParsedExtension::SubjectKeyIdentifier(id) => id.serialize(serializer),
+            ParsedExtension::KeyUsage(ku) => ku.serialize(serializer),
+            ParsedExtension::CertificatePolicies(policies) => policies.serialize(serializer),
+            ParsedExtension::SubjectAlternativeName(san) => san.serialize(serializer),
+            ParsedExtension::IssuerAlternativeName(ian) => ian.serialize(serializer),
+            ParsedExtension::BasicConstraints(bc) => bc.serialize(serializer),
+            ParsedExtension::ExtendedKeyUsage(eku) => eku.serialize(serializer),
+            ParsedExtension::CRLDistributionPoints(points)
+            | ParsedExtension::FreshestCRL(points) => points.serialize(serializer),
+            ParsedExtension::AuthorityInfoAccess(aia) => aia.serialize(serializer),
+            ParsedExtension::SubjectInfoAccess(sia) => sia.serialize(serializer),
+            // serialized as a string, the value may not fit in a JSON number
+            ParsedExtension::CRLNumber(num) => serializer.collect_str(num),
+            ParsedExtension::ReasonCode(code) => code.serialize(serializer),
+            ParsedExtension::InvalidityDate(date) => date.serialize(serializer),
+            ParsedExtension::CertificateIssuer(names) => names.general_names.serialize(serializer),
+            _ => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'a> Serialize for X509Extension<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("X509Extension", 4)?;
+        s.serialize_field("oid", &self.oid.to_id_string())?;
+        s.serialize_field("critical", &self.critical)?;
+        s.serialize_field("value", &Hex(self.value))?;
+        s.serialize_field("parsed_extension", self.parsed_extension())?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for TbsCertificate<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("TbsCertificate", 10)?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("serial", &Serial(self.raw_serial()))?;
+        s.serialize_field("signature", &self.signature)?;
+        s.serialize_field("issuer", &self.issuer)?;
+        s.serialize_field("validity", &self.validity)?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("subject_pki", &self.subject_pki)?;
+        s.serialize_field("issuer_uid", &self.issuer_uid)?;
+        s.serialize_field("subject_uid", &self.subject_uid)?;
+        s.serialize_field("extensions", self.extensions())?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for X509Certificate<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("X509Certificate", 3)?;
+        s.serialize_field("tbs_certificate", &self.tbs_certificate)?;
+        s.serialize_field("signature_algorithm", &self.signature_algorithm)?;
+        s.serialize_field("signature_value", &Bits(&self.signature_value))?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for RevokedCertificate<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("RevokedCertificate", 3)?;
+        s.serialize_field("user_certificate", &Serial(self.raw_serial()))?;
+        s.serialize_field("revocation_date", &self.revocation_date)?;
+        s.serialize_field("extensions", self.extensions())?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for TbsCertList<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("TbsCertList", 7)?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("signature", &self.signature)?;
+        s.serialize_field("issuer", &self.issuer)?;
+        s.serialize_field("this_update", &self.this_update)?;
+        s.serialize_field("next_update", &self.next_update)?;
+        s.serialize_field("revoked_certificates", &self.revoked_certificates)?;
+        s.serialize_field("extensions", self.extensions())?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for CertificateRevocationList<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("CertificateRevocationList", 3)?;
+        s.serialize_field("tbs_cert_list", &self.tbs_cert_list)?;
+        s.serialize_field("signature_algorithm", &self.signature_algorithm)?;
+        s.serialize_field("signature_value", &Bits(&self.signature_value))?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for X509CriAttribute<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("X509CriAttribute", 2)?;
+        s.serialize_field("oid", &self.oid.to_id_string())?;
+        s.serialize_field("value", &Hex(self.value))?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for X509CertificationRequestInfo<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("X509CertificationRequestInfo", 4)?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("subject_pki", &self.subject_pki)?;
+        s.serialize_field("attributes", self.attributes())?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for X509CertificationRequest<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("X509CertificationRequest", 3)?;
+        s.serialize_field(
+            "certification_request_info",
+            &self.certification_request_info,
+        )?;
+        s.serialize_field("signature_algorithm", &self.signature_algorithm)?;
+        s.serialize_field("signature_value", &Bits(&self.signature_value))?;
+        s.end()
+    }
+}
+
+impl Serialize for CertificateReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("CertificateReport", 14)?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("serial", &self.serial)?;
+        s.serialize_field("subject", &self.subject)?;
+        s.serialize_field("issuer", &self.issuer)?;
+        s.serialize_field("not_before", &self.not_before)?;
+        s.serialize_field("not_after", &self.not_after)?;
+        s.serialize_field("subject_alt_names", &self.subject_alt_names)?;
+        s.serialize_field("public_key_algorithm", &self.public_key_algorithm)?;
+        s.serialize_field("signature_algorithm", &self.signature_algorithm)?;
+        s.serialize_field("is_ca", &self.is_ca)?;
+        s.serialize_field("key_usage", &self.key_usage)?;
+        s.serialize_field("extended_key_usage", &self.extended_key_usage)?;
+        s.serialize_field("sha1_fingerprint", &self.sha1_fingerprint)?;
+        s.serialize_field("sha256_fingerprint", &self.sha256_fingerprint)?;
+        s.end()
+    }
+}
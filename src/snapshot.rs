@@ -0,0 +1,610 @@
+//! Deterministic text representation of parsed objects, for snapshot testing
+//!
+//! The derived `Debug` output of the parser types exposes internal representations (borrowed
+//! slices, lazily parsed fields), and changes when fields are added. The wrappers of this module
+//! produce a stable, line-based text instead, suitable for comparison against stored snapshots.
+//!
+//! The format is:
+//!
+//! - one field per line, as `name: value`, nested blocks indented by 2 spaces
+//! - algorithms as `short-name (dotted-oid)`, or only the dotted OID if the name is unknown
+//! - times in RFC 3339 format (UTC)
+//! - serial numbers as colon-separated lowercase hex
+//! - extensions sorted by OID (not in encoding order), with a `critical` marker, and their
+//!   value (see [Extensions](#extensions))
+//! - blobs (public keys, signatures, raw values) as `[N bytes] hex`, where blobs longer than 16
+//!   bytes are truncated to their first and last 8 bytes. The alternate flag (`{:#}`) prints
+//!   blobs in full.
+//!
+//! The output only depends on the input, and not on the run or the platform. It does not use the
+//! `Display` implementations of the parser types, which are meant for humans and may change.
+//! Any change to the output increments [`FORMAT_VERSION`].
+//!
+//! # Extensions
+//!
+//! Extension values are printed on one line, as a comma-separated list of items:
+//!
+//! - flags (key usage, revocation reasons, ...) using the identifiers of the ASN.1 modules (for
+//!   ex. `digitalSignature`)
+//! - general names as `type:value`, using the OpenSSL prefixes (for ex. `DNS:example.com`)
+//! - object identifiers in dotted-decimal form
+//! - optional fields as `name=value`, omitted when absent
+//!
+//! Extensions without a dedicated format (unsupported, invalid, unparsed, and Admission) are
+//! printed as a blob of their raw value.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::prelude::*;
+//! use x509_parser::snapshot::CertificateSnapshot;
+//!
+//! # let der = include_bytes!("../assets/test-leaf.der");
+//! let (_, x509) = parse_x509_certificate(der).expect("invalid certificate");
+//! let snapshot = CertificateSnapshot(&x509).to_string();
+//! assert!(snapshot.starts_with("certificate\n  version: 3\n"));
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::certification_request::X509CertificationRequest;
+use crate::cri_attributes::ParsedCriAttribute;
+use crate::extensions::*;
+use crate::objects::{oid2sn, oid_registry};
+use crate::revocation_list::CertificateRevocationList;
+use crate::x509::{AlgorithmIdentifier, ReasonCode, X509Name};
+use der_parser::oid::Oid;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Version of the snapshot format
+///
+/// This is incremented whenever the output of the wrappers changes, so stored snapshots
+/// can be regenerated explicitly.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Number of bytes printed at each end of a truncated blob
+const BLOB_EDGE: usize = 8;
+
+/// Deterministic text wrapper for a certificate (see the [module documentation](self))
+#[derive(Debug)]
+pub struct CertificateSnapshot<'a, 'b>(pub &'b X509Certificate<'a>);
+
+impl<'a, 'b> fmt::Display for CertificateSnapshot<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x509 = self.0;
+        let full = f.alternate();
+        writeln!(f, "certificate")?;
+        writeln!(f, "  version: {}", x509.version().0 + 1)?;
+        writeln!(f, "  serial: {}", hex(x509.raw_serial()))?;
+        writeln!(f, "  signature_algorithm: {}", algorithm(&x509.signature))?;
+        writeln!(f, "  issuer: {}", name(x509.issuer()))?;
+        writeln!(
+            f,
+            "  not_before: {}",
+            x509.validity().not_before.to_rfc3339()
+        )?;
+        writeln!(f, "  not_after: {}", x509.validity().not_after.to_rfc3339())?;
+        writeln!(f, "  subject: {}", name(x509.subject()))?;
+        let spki = x509.public_key();
+        writeln!(f, "  public_key_algorithm: {}", algorithm(&spki.algorithm))?;
+        writeln!(
+            f,
+            "  public_key: {}",
+            blob(&spki.subject_public_key.data, full)
+        )?;
+        write_extensions(f, "extensions", x509.extensions(), 2, full)?;
+        writeln!(f, "  signature: {}", blob(&x509.signature_value.data, full))
+    }
+}
+
+/// Deterministic text wrapper for a CRL (see the [module documentation](self))
+#[derive(Debug)]
+pub struct RevocationListSnapshot<'a, 'b>(pub &'b CertificateRevocationList<'a>);
+
+impl<'a, 'b> fmt::Display for RevocationListSnapshot<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let crl = self.0;
+        let full = f.alternate();
+        writeln!(f, "crl")?;
+        let version = crl.version().map_or(1, |v| v.0 + 1);
+        writeln!(f, "  version: {}", version)?;
+        writeln!(
+            f,
+            "  signature_algorithm: {}",
+            algorithm(&crl.tbs_cert_list.signature)
+        )?;
+        writeln!(f, "  issuer: {}", name(crl.issuer()))?;
+        writeln!(f, "  this_update: {}", crl.last_update().to_rfc3339())?;
+        if let Some(next_update) = crl.next_update() {
+            writeln!(f, "  next_update: {}", next_update.to_rfc3339())?;
+        }
+        let revoked = &crl.tbs_cert_list.revoked_certificates;
+        if !revoked.is_empty() {
+            writeln!(f, "  revoked:")?;
+            for entry in revoked {
+                writeln!(f, "    - serial: {}", hex(entry.raw_serial()))?;
+                writeln!(f, "      date: {}", entry.revocation_date.to_rfc3339())?;
+                write_extensions(f, "extensions", entry.extensions(), 6, full)?;
+            }
+        }
+        write_extensions(f, "extensions", crl.extensions(), 2, full)?;
+        writeln!(f, "  signature: {}", blob(&crl.signature_value.data, full))
+    }
+}
+
+/// Deterministic text wrapper for a certification request (see the
+/// [module documentation](self))
+///
+/// Attributes are sorted by OID, and printed as raw values. The requested extensions are
+/// printed like the extensions of certificates.
+#[derive(Debug)]
+pub struct CertificationRequestSnapshot<'a, 'b>(pub &'b X509CertificationRequest<'a>);
+
+impl<'a, 'b> fmt::Display for CertificationRequestSnapshot<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let csr = self.0;
+        let full = f.alternate();
+        let info = &csr.certification_request_info;
+        writeln!(f, "certification_request")?;
+        writeln!(f, "  version: {}", info.version.0 + 1)?;
+        writeln!(f, "  subject: {}", name(&info.subject))?;
+        writeln!(
+            f,
+            "  public_key_algorithm: {}",
+            algorithm(&info.subject_pki.algorithm)
+        )?;
+        writeln!(
+            f,
+            "  public_key: {}",
+            blob(&info.subject_pki.subject_public_key.data, full)
+        )?;
+        let mut attributes: Vec<_> = info.attributes().iter().collect();
+        if !attributes.is_empty() {
+            attributes.sort_by(|a, b| a.oid.as_bytes().cmp(b.oid.as_bytes()));
+            writeln!(f, "  attributes:")?;
+            for attr in attributes {
+                writeln!(
+                    f,
+                    "    - {}: {}",
+                    oid_text(&attr.oid),
+                    blob(attr.value, full)
+                )?;
+            }
+        }
+        let requested = info
+            .iter_attributes()
+            .find_map(|attr| match &attr.parsed_attribute {
+                ParsedCriAttribute::ExtensionRequest(request) => Some(&request.extensions),
+                _ => None,
+            });
+        if let Some(extensions) = requested {
+            write_extensions(f, "requested_extensions", extensions, 2, full)?;
+        }
+        writeln!(
+            f,
+            "  signature_algorithm: {}",
+            algorithm(&csr.signature_algorithm)
+        )?;
+        writeln!(f, "  signature: {}", blob(&csr.signature_value.data, full))
+    }
+}
+
+/// Return the deterministic text of a certificate, with truncated blobs
+pub fn certificate_snapshot(x509: &X509Certificate) -> String {
+    CertificateSnapshot(x509).to_string()
+}
+
+fn oid_text(oid: &Oid) -> String {
+    match oid2sn(oid, oid_registry()) {
+        Ok(sn) => format!("{} ({})", sn, oid.to_id_string()),
+        Err(_) => oid.to_id_string(),
+    }
+}
+
+fn algorithm(alg: &AlgorithmIdentifier) -> String {
+    oid_text(&alg.algorithm)
+}
+
+fn name(name: &X509Name) -> String {
+    name.to_string_with_registry(oid_registry())
+        .unwrap_or_else(|_| name.to_string())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Format bytes as `[N bytes] hex`, keeping only both ends if `full` is not set
+fn blob(bytes: &[u8], full: bool) -> String {
+    if bytes.is_empty() {
+        "[0 bytes]".to_string()
+    } else if full || bytes.len() <= 2 * BLOB_EDGE {
+        format!("[{} bytes] {}", bytes.len(), hex(bytes))
+    } else {
+        format!(
+            "[{} bytes] {}..{}",
+            bytes.len(),
+            hex(&bytes[..BLOB_EDGE]),
+            hex(&bytes[bytes.len() - BLOB_EDGE..])
+        )
+    }
+}
+
+/// Return the names of the bits set in `flags`, `names` being ordered by bit position
+fn flags(flags: u16, names: &[&str]) -> Vec<String> {
+    names
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| flags >> idx & 1 != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+// KeyUsage ::= BIT STRING (rfc 5280)
+const KEY_USAGE: &[&str] = &[
+    "digitalSignature",
+    "nonRepudiation",
+    "keyEncipherment",
+    "dataEncipherment",
+    "keyAgreement",
+    "keyCertSign",
+    "cRLSign",
+    "encipherOnly",
+    "decipherOnly",
+];
+
+// ReasonFlags ::= BIT STRING (rfc 5280)
+const REASON_FLAGS: &[&str] = &[
+    "unused",
+    "keyCompromise",
+    "cACompromise",
+    "affiliationChanged",
+    "superseded",
+    "cessationOfOperation",
+    "certificateHold",
+    "privilegeWithdrawn",
+    "aACompromise",
+];
+
+fn reason_code(code: &ReasonCode) -> String {
+    // CRLReason ::= ENUMERATED (rfc 5280)
+    let name = match code.0 {
+        0 => "unspecified",
+        1 => "keyCompromise",
+        2 => "cACompromise",
+        3 => "affiliationChanged",
+        4 => "superseded",
+        5 => "cessationOfOperation",
+        6 => "certificateHold",
+        8 => "removeFromCRL",
+        9 => "privilegeWithdrawn",
+        10 => "aACompromise",
+        n => return format!("unknown({})", n),
+    };
+    name.to_string()
+}
+
+fn ns_cert_type(t: &NSCertType) -> Vec<String> {
+    let bits = [
+        (t.ssl_client(), "sslClient"),
+        (t.ssl_server(), "sslServer"),
+        (t.smime(), "smime"),
+        (t.object_signing(), "objectSigning"),
+        (t.ssl_ca(), "sslCA"),
+        (t.smime_ca(), "smimeCA"),
+        (t.object_signing_ca(), "objectSigningCA"),
+    ];
+    bits.iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn extended_key_usage(eku: &ExtendedKeyUsage) -> Vec<String> {
+    let purposes = [
+        (eku.any, "anyExtendedKeyUsage"),
+        (eku.server_auth, "serverAuth"),
+        (eku.client_auth, "clientAuth"),
+        (eku.code_signing, "codeSigning"),
+        (eku.email_protection, "emailProtection"),
+        (eku.time_stamping, "timeStamping"),
+        (eku.ocsp_signing, "OCSPSigning"),
+    ];
+    purposes
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| name.to_string())
+        .chain(eku.other.iter().map(|oid| oid.to_id_string()))
+        .collect()
+}
+
+fn general_name(gn: &GeneralName, full: bool) -> String {
+    match gn {
+        GeneralName::OtherName(oid, value) => {
+            format!("othername:{}:{}", oid.to_id_string(), blob(value, full))
+        }
+        GeneralName::RFC822Name(s) => format!("email:{}", s),
+        GeneralName::SmtpUTF8Mailbox(s) => format!("othername:SmtpUTF8Mailbox:{}", s),
+        GeneralName::UserPrincipalName(s) => format!("othername:UPN:{}", s),
+        GeneralName::DNSName(s) => format!("DNS:{}", s),
+        GeneralName::X400Address(any) => format!("X400Name:{}", blob(any.data, full)),
+        GeneralName::DirectoryName(dn) => format!("DirName:{}", name(dn)),
+        GeneralName::EDIPartyName(any) => format!("EdiPartyName:{}", blob(any.data, full)),
+        GeneralName::URI(s) => format!("URI:{}", s),
+        GeneralName::IPAddress(b) => match b.len() {
+            4 => format!("IP:{}", Ipv4Addr::new(b[0], b[1], b[2], b[3])),
+            16 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(b);
+                format!("IP:{}", Ipv6Addr::from(octets))
+            }
+            // address and mask (name constraints), or invalid
+            _ => format!("IP:{}", blob(b, full)),
+        },
+        GeneralName::RegisteredID(oid) => format!("RID:{}", oid.to_id_string()),
+    }
+}
+
+fn general_names(names: &[GeneralName], full: bool) -> String {
+    let names: Vec<_> = names.iter().map(|gn| general_name(gn, full)).collect();
+    format!("[{}]", names.join(", "))
+}
+
+fn distribution_point_name(dpn: &DistributionPointName, full: bool) -> String {
+    match dpn {
+        DistributionPointName::FullName(names) => general_names(names, full),
+        DistributionPointName::NameRelativeToCRLIssuer(rdn) => {
+            let attrs: Vec<_> = rdn
+                .iter()
+                .map(|attr| {
+                    format!(
+                        "{}={}",
+                        attr.attr_type().to_id_string(),
+                        attr.as_str_lossy()
+                    )
+                })
+                .collect();
+            format!("relative:{}", attrs.join("+"))
+        }
+    }
+}
+
+/// Format the value of an extension, using the versioned format described in the
+/// [module documentation](self#extensions)
+fn extension_value(ext: &X509Extension, full: bool) -> String {
+    let mut items = Vec::new();
+    match ext.parsed_extension() {
+        ParsedExtension::AuthorityKeyIdentifier(aki) => {
+            if let Some(id) = &aki.key_identifier {
+                items.push(format!("keyid={}", hex(id.0)));
+            }
+            if let Some(issuer) = &aki.authority_cert_issuer {
+                items.push(format!("issuer={}", general_names(issuer, full)));
+            }
+            if let Some(serial) = aki.authority_cert_serial {
+                items.push(format!("serial={}", hex(serial)));
+            }
+        }
+        ParsedExtension::SubjectKeyIdentifier(id) => items.push(hex(id.0)),
+        ParsedExtension::KeyUsage(ku) => items = flags(ku.flags, KEY_USAGE),
+        ParsedExtension::CertificatePolicies(policies) => {
+            items.extend(policies.iter().map(|p| p.policy_id.to_id_string()));
+        }
+        ParsedExtension::PolicyMappings(pm) => {
+            items.extend(pm.mappings.iter().map(|m| {
+                format!(
+                    "{}->{}",
+                    m.issuer_domain_policy.to_id_string(),
+                    m.subject_domain_policy.to_id_string()
+                )
+            }));
+        }
+        ParsedExtension::SubjectAlternativeName(san) => {
+            items.extend(san.general_names.iter().map(|gn| general_name(gn, full)));
+        }
+        ParsedExtension::IssuerAlternativeName(ian) => {
+            items.extend(ian.general_names.iter().map(|gn| general_name(gn, full)));
+        }
+        ParsedExtension::CertificateIssuer(names) => {
+            items.extend(names.iter().map(|gn| general_name(gn, full)));
+        }
+        ParsedExtension::BasicConstraints(bc) => {
+            items.push(format!("ca={}", bc.ca));
+            if let Some(len) = bc.path_len_constraint {
+                items.push(format!("path_len={}", len));
+            }
+        }
+        ParsedExtension::NameConstraints(nc) => {
+            let subtrees = [
+                ("permitted", &nc.permitted_subtrees),
+                ("excluded", &nc.excluded_subtrees),
+            ];
+            for (label, subtrees) in subtrees.iter() {
+                if let Some(subtrees) = subtrees {
+                    let names: Vec<_> = subtrees.iter().map(|s| s.base.clone()).collect();
+                    items.push(format!("{}={}", label, general_names(&names, full)));
+                }
+            }
+        }
+        ParsedExtension::PolicyConstraints(pc) => {
+            if let Some(n) = pc.require_explicit_policy {
+                items.push(format!("require_explicit_policy={}", n));
+            }
+            if let Some(n) = pc.inhibit_policy_mapping {
+                items.push(format!("inhibit_policy_mapping={}", n));
+            }
+        }
+        ParsedExtension::ExtendedKeyUsage(eku) => items = extended_key_usage(eku),
+        ParsedExtension::CRLDistributionPoints(points) | ParsedExtension::FreshestCRL(points) => {
+            for point in points.iter() {
+                let mut fields = Vec::new();
+                if let Some(name) = &point.distribution_point {
+                    fields.push(format!("name={}", distribution_point_name(name, full)));
+                }
+                if let Some(reasons) = &point.reasons {
+                    let reasons = flags(reasons.flags, REASON_FLAGS);
+                    fields.push(format!("reasons=[{}]", reasons.join(", ")));
+                }
+                if let Some(issuer) = &point.crl_issuer {
+                    fields.push(format!("crl_issuer={}", general_names(issuer, full)));
+                }
+                items.push(format!("{{{}}}", fields.join(", ")));
+            }
+        }
+        ParsedExtension::IssuingDistributionPoint(idp) => {
+            if let Some(name) = &idp.distribution_point {
+                items.push(format!("name={}", distribution_point_name(name, full)));
+            }
+            let booleans = [
+                (idp.only_contains_user_certs, "onlyContainsUserCerts"),
+                (idp.only_contains_ca_certs, "onlyContainsCACerts"),
+                (idp.indirect_crl, "indirectCRL"),
+                (
+                    idp.only_contains_attribute_certs,
+                    "onlyContainsAttributeCerts",
+                ),
+            ];
+            items.extend(
+                booleans
+                    .iter()
+                    .filter(|(set, _)| *set)
+                    .map(|(_, name)| name.to_string()),
+            );
+            if let Some(reasons) = &idp.only_some_reasons {
+                let reasons = flags(reasons.flags, REASON_FLAGS);
+                items.push(format!("onlySomeReasons=[{}]", reasons.join(", ")));
+            }
+        }
+        ParsedExtension::InhibitAnyPolicy(iap) => items.push(iap.skip_certs.to_string()),
+        ParsedExtension::AuthorityInfoAccess(AuthorityInfoAccess { accessdescs })
+        | ParsedExtension::SubjectInfoAccess(SubjectInfoAccess { accessdescs }) => {
+            items.extend(accessdescs.iter().map(|ad| {
+                format!(
+                    "{}={}",
+                    ad.access_method.to_id_string(),
+                    general_name(&ad.access_location, full)
+                )
+            }));
+        }
+        ParsedExtension::NSCertType(t) => items = ns_cert_type(t),
+        ParsedExtension::NsCertComment(s) | ParsedExtension::NsBaseUrl(s) => {
+            items.push(s.to_string())
+        }
+        ParsedExtension::CRLNumber(n) => match n.as_u128() {
+            Some(n) => items.push(n.to_string()),
+            None => items.push(hex(n.as_bytes())),
+        },
+        ParsedExtension::ReasonCode(code) => items.push(reason_code(code)),
+        ParsedExtension::InvalidityDate(date) => items.push(date.to_rfc3339()),
+        ParsedExtension::HoldInstructionCode(hold) => items.push(match hold {
+            HoldInstruction::None => "holdInstructionNone".to_string(),
+            HoldInstruction::CallIssuer => "holdInstructionCallIssuer".to_string(),
+            HoldInstruction::Reject => "holdInstructionReject".to_string(),
+            HoldInstruction::Other(oid) => oid.to_id_string(),
+        }),
+        ParsedExtension::PrivateKeyUsagePeriod(period) => {
+            if let Some(not_before) = period.not_before {
+                items.push(format!("not_before={}", not_before.to_rfc3339()));
+            }
+            if let Some(not_after) = period.not_after {
+                items.push(format!("not_after={}", not_after.to_rfc3339()));
+            }
+        }
+        ParsedExtension::SCT(scts) => {
+            items.extend(scts.iter().map(|sct| {
+                format!(
+                    "{{log={}, timestamp={}}}",
+                    hex(sct.id.key_id),
+                    sct.timestamp
+                )
+            }));
+        }
+        ParsedExtension::CtPrecertPoison
+        | ParsedExtension::OcspNoCheck
+        | ParsedExtension::CanSignHttpExchanges => items.push("null".to_string()),
+        ParsedExtension::NtdsCaSecurity(ext) => items.push(format!("objectSid={}", ext.object_sid)),
+        ParsedExtension::EnrollCertType(name) => items.push(name.clone()),
+        ParsedExtension::CertificateTemplate(template) => {
+            items.push(template.template_id.to_id_string());
+            if let Some(major) = template.major_version {
+                items.push(format!("major_version={}", major));
+            }
+            if let Some(minor) = template.minor_version {
+                items.push(format!("minor_version={}", minor));
+            }
+        }
+        ParsedExtension::SMIMECapabilities(caps) => {
+            items.extend(caps.iter().map(|c| c.capability_id.to_id_string()));
+        }
+        ParsedExtension::UnsupportedExtension { .. }
+        | ParsedExtension::ParseError { .. }
+        | ParsedExtension::Admission(_)
+        | ParsedExtension::Unparsed => return blob(ext.value, full),
+    }
+    items.join(", ")
+}
+
+fn write_extensions(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    extensions: &[X509Extension],
+    indent: usize,
+    full: bool,
+) -> fmt::Result {
+    if extensions.is_empty() {
+        return Ok(());
+    }
+    let mut sorted: Vec<_> = extensions.iter().collect();
+    // stable sort: duplicate extensions keep their encoding order
+    sorted.sort_by(|a, b| a.oid.as_bytes().cmp(b.oid.as_bytes()));
+    writeln!(f, "{:indent$}{}:", "", label, indent = indent)?;
+    for ext in sorted {
+        let critical = if ext.critical { " critical" } else { "" };
+        let value = extension_value(ext, full);
+        writeln!(
+            f,
+            "{:indent$}  - {}{}: {}",
+            "",
+            oid_text(&ext.oid),
+            critical,
+            value,
+            indent = indent
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_truncation() {
+        let bytes: Vec<u8> = (0..20).collect();
+        assert_eq!(
+            blob(&bytes, false),
+            "[20 bytes] 00:01:02:03:04:05:06:07..0c:0d:0e:0f:10:11:12:13"
+        );
+        assert!(blob(&bytes, true).ends_with("11:12:13"));
+        assert_eq!(blob(&bytes[..16], false), blob(&bytes[..16], true));
+        assert_eq!(blob(&[], false), "[0 bytes]");
+    }
+
+    #[test]
+    fn test_extension_flags() {
+        assert_eq!(
+            flags(0x0061, KEY_USAGE),
+            ["digitalSignature", "keyCertSign", "cRLSign"]
+        );
+        assert_eq!(
+            flags(0x0006, REASON_FLAGS),
+            ["keyCompromise", "cACompromise"]
+        );
+        assert_eq!(reason_code(&ReasonCode(8)), "removeFromCRL");
+        assert_eq!(reason_code(&ReasonCode(7)), "unknown(7)");
+    }
+}
@@ -0,0 +1,271 @@
+//! A set of certificates, indexed by SHA-256 fingerprint
+//!
+//! Long-running services (TLS terminators, crawlers, CT monitors) often manage pools of
+//! certificates which change over time. [`X509Store`] keeps owned copies of the certificates,
+//! without duplicates, and allows looking them up using the SHA-256 hash of their DER encoding.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::prelude::*;
+//! use x509_parser::store::X509Store;
+//!
+//! # static CA: &[u8] = include_bytes!("../assets/test-ca.der");
+//! let (_, ca) = parse_x509_certificate(CA).expect("could not parse certificate");
+//! let mut store = X509Store::new();
+//! assert!(store.insert_unique(&ca));
+//! // the certificate is already present
+//! assert!(!store.insert_unique(&ca));
+//! assert_eq!(store.len_by_kind().roots, 1);
+//! ```
+//!
+//! # Snapshots
+//!
+//! Services loading thousands of trust anchors at startup can save the store to a compact binary
+//! snapshot using [`X509Store::save_snapshot`], and reload it using
+//! [`X509Store::load_snapshot`]. Loading a snapshot does not parse the certificates: only their
+//! SHA-256 fingerprints are checked, and their kinds are read from the snapshot.
+//!
+//! The snapshot contains a header, followed by the certificates in fingerprint order. Integers
+//! are big-endian.
+//!
+//! | Field | Size | Description |
+//! |-------|------|-------------|
+//! | magic | 8 | `X509STOR` |
+//! | version | 1 | Format version (1) |
+//! | count | 4 | Number of certificates |
+//! | fingerprint | 32 | SHA-256 fingerprint of the certificate |
+//! | kind | 1 | Kind of the certificate (see [`CertificateKind`]) |
+//! | length | 4 | Length of the DER encoding |
+//! | der | length | DER encoding of the certificate |
+
+use crate::certificate::{CertificateKind, LeafKind, X509Certificate, X509CertificateOwned};
+use crate::error::X509Error;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+/// The SHA-256 hash of the DER encoding of a certificate
+pub type Sha256Fingerprint = [u8; 32];
+
+/// Compute the SHA-256 fingerprint of a DER-encoded certificate
+pub fn sha256_fingerprint(der: &[u8]) -> Sha256Fingerprint {
+    Sha256::digest(der).into()
+}
+
+/// A set of certificates, indexed by SHA-256 fingerprint
+///
+/// Iteration is in fingerprint order, so it does not depend on the insertion order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct X509Store {
+    certificates: BTreeMap<Sha256Fingerprint, StoreEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct StoreEntry {
+    cert: X509CertificateOwned,
+    // computed when the certificate is added, to avoid parsing it again
+    kind: CertificateKind,
+}
+
+/// Number of certificates of a store, by kind (see [`X509Store::len_by_kind`])
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StoreCounts {
+    pub roots: usize,
+    pub intermediates: usize,
+    pub leaves: usize,
+}
+
+impl X509Store {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a certificate, if no certificate with the same fingerprint is present
+    ///
+    /// Returns `true` if the certificate was added. Certificates without an encoding (see
+    /// [`X509Certificate::from_parts`]) are never added.
+    pub fn insert_unique<C: TryInto<X509CertificateOwned>>(&mut self, cert: C) -> bool {
+        let cert = match cert.try_into() {
+            Ok(cert) => cert,
+            Err(_) => return false,
+        };
+        let fingerprint = sha256_fingerprint(cert.as_raw());
+        if self.certificates.contains_key(&fingerprint) {
+            return false;
+        }
+        let kind = match cert.certificate() {
+            Ok(parsed) => parsed.certificate_kind(),
+            Err(_) => return false,
+        };
+        self.certificates
+            .insert(fingerprint, StoreEntry { cert, kind });
+        true
+    }
+
+    /// Get the certificate with the given fingerprint
+    pub fn get(&self, fingerprint: &Sha256Fingerprint) -> Option<&X509CertificateOwned> {
+        self.certificates.get(fingerprint).map(|entry| &entry.cert)
+    }
+
+    /// Test if the certificate is present in the store
+    pub fn contains(&self, cert: &X509Certificate) -> bool {
+        self.certificates
+            .contains_key(&sha256_fingerprint(cert.as_raw()))
+    }
+
+    /// Remove the certificate with the given fingerprint, and return it
+    pub fn remove(&mut self, fingerprint: &Sha256Fingerprint) -> Option<X509CertificateOwned> {
+        self.certificates
+            .remove(fingerprint)
+            .map(|entry| entry.cert)
+    }
+
+    /// Return an iterator over the certificates, and their fingerprints
+    pub fn iter(&self) -> impl Iterator<Item = (&Sha256Fingerprint, &X509CertificateOwned)> {
+        self.certificates
+            .iter()
+            .map(|(fingerprint, entry)| (fingerprint, &entry.cert))
+    }
+
+    /// Return the number of certificates
+    pub fn len(&self) -> usize {
+        self.certificates.len()
+    }
+
+    /// Return `true` if the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.certificates.is_empty()
+    }
+
+    /// Count the certificates by kind, using [`X509Certificate::certificate_kind`]
+    ///
+    /// The kind is computed when the certificate is added, so the certificates are not parsed
+    /// again.
+    pub fn len_by_kind(&self) -> StoreCounts {
+        let mut counts = StoreCounts::default();
+        for entry in self.certificates.values() {
+            match entry.kind {
+                CertificateKind::Root => counts.roots += 1,
+                CertificateKind::Intermediate => counts.intermediates += 1,
+                CertificateKind::Leaf(_) => counts.leaves += 1,
+            }
+        }
+        counts
+    }
+
+    /// Serialize the store to a binary snapshot, see the [module documentation](self)
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        let size: usize = self
+            .certificates
+            .values()
+            .map(|entry| ENTRY_HEADER_LEN + entry.cert.as_raw().len())
+            .sum();
+        let mut out = Vec::with_capacity(SNAPSHOT_HEADER_LEN + size);
+        out.extend_from_slice(SNAPSHOT_MAGIC);
+        out.push(SNAPSHOT_VERSION);
+        out.extend_from_slice(&(self.certificates.len() as u32).to_be_bytes());
+        for (fingerprint, entry) in &self.certificates {
+            let der = entry.cert.as_raw();
+            out.extend_from_slice(fingerprint);
+            out.push(kind_to_byte(entry.kind));
+            out.extend_from_slice(&(der.len() as u32).to_be_bytes());
+            out.extend_from_slice(der);
+        }
+        out
+    }
+
+    /// Load a store from a binary snapshot written by [`save_snapshot`](Self::save_snapshot)
+    ///
+    /// The certificates are not parsed. Returns [`X509Error::InvalidStoreSnapshot`] if the
+    /// snapshot is truncated, has trailing data, has an unknown version, or if a certificate does
+    /// not match its fingerprint.
+    pub fn load_snapshot(data: &[u8]) -> Result<Self, X509Error> {
+        let mut reader = SnapshotReader(data);
+        if reader.take(SNAPSHOT_MAGIC.len())? != SNAPSHOT_MAGIC
+            || reader.take(1)?[0] != SNAPSHOT_VERSION
+        {
+            return Err(X509Error::InvalidStoreSnapshot);
+        }
+        let count = reader.read_u32()?;
+        let mut store = X509Store::new();
+        for _ in 0..count {
+            let mut fingerprint = [0u8; 32];
+            fingerprint.copy_from_slice(reader.take(32)?);
+            let kind = kind_from_byte(reader.take(1)?[0])?;
+            let len = reader.read_u32()? as usize;
+            let der = reader.take(len)?;
+            if sha256_fingerprint(der) != fingerprint
+                || store.certificates.contains_key(&fingerprint)
+            {
+                return Err(X509Error::InvalidStoreSnapshot);
+            }
+            let cert = X509CertificateOwned::from_raw_unchecked(der.to_vec());
+            store
+                .certificates
+                .insert(fingerprint, StoreEntry { cert, kind });
+        }
+        if !reader.0.is_empty() {
+            return Err(X509Error::InvalidStoreSnapshot);
+        }
+        Ok(store)
+    }
+}
+
+const SNAPSHOT_MAGIC: &[u8] = b"X509STOR";
+const SNAPSHOT_VERSION: u8 = 1;
+// magic, version and count
+const SNAPSHOT_HEADER_LEN: usize = 8 + 1 + 4;
+// fingerprint, kind and length
+const ENTRY_HEADER_LEN: usize = 32 + 1 + 4;
+
+// Roots are 0, intermediates are 1, and leaves start at 0x10
+fn kind_to_byte(kind: CertificateKind) -> u8 {
+    match kind {
+        CertificateKind::Root => 0,
+        CertificateKind::Intermediate => 1,
+        CertificateKind::Leaf(LeafKind::TlsServer) => 0x10,
+        CertificateKind::Leaf(LeafKind::TlsClient) => 0x11,
+        CertificateKind::Leaf(LeafKind::SMime) => 0x12,
+        CertificateKind::Leaf(LeafKind::CodeSigning) => 0x13,
+        CertificateKind::Leaf(LeafKind::OcspResponder) => 0x14,
+        CertificateKind::Leaf(LeafKind::TimeStamping) => 0x15,
+        CertificateKind::Leaf(LeafKind::Unknown) => 0x16,
+    }
+}
+
+fn kind_from_byte(byte: u8) -> Result<CertificateKind, X509Error> {
+    let kind = match byte {
+        0 => CertificateKind::Root,
+        1 => CertificateKind::Intermediate,
+        0x10 => CertificateKind::Leaf(LeafKind::TlsServer),
+        0x11 => CertificateKind::Leaf(LeafKind::TlsClient),
+        0x12 => CertificateKind::Leaf(LeafKind::SMime),
+        0x13 => CertificateKind::Leaf(LeafKind::CodeSigning),
+        0x14 => CertificateKind::Leaf(LeafKind::OcspResponder),
+        0x15 => CertificateKind::Leaf(LeafKind::TimeStamping),
+        0x16 => CertificateKind::Leaf(LeafKind::Unknown),
+        _ => return Err(X509Error::InvalidStoreSnapshot),
+    };
+    Ok(kind)
+}
+
+struct SnapshotReader<'a>(&'a [u8]);
+
+impl<'a> SnapshotReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], X509Error> {
+        if self.0.len() < len {
+            return Err(X509Error::InvalidStoreSnapshot);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, X509Error> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(buf))
+    }
+}
@@ -1,16 +1,32 @@
 use asn1_rs::nom::Err;
-use asn1_rs::{Error, FromDer, GeneralizedTime, Header, ParseResult, UtcTime};
+use asn1_rs::{Any, Error, FromDer, GeneralizedTime, Header, ParseResult, UtcTime};
 use der_parser::ber::{Tag, MAX_OBJECT_SIZE};
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub};
 use time::macros::format_description;
 use time::{Duration, OffsetDateTime};
 
 use crate::error::{X509Error, X509Result};
 
+/// The ASN.1 type used to encode a timestamp
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ASN1TimeEncoding {
+    UtcTime,
+    GeneralizedTime,
+}
+
 /// An ASN.1 timestamp.
-#[derive(Copy, Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
-pub struct ASN1Time(OffsetDateTime);
+///
+/// The encoding (`UTCTime` or `GeneralizedTime`) is kept if the object was parsed, but is not
+/// used in comparisons: two `ASN1Time` objects are equal if they designate the same time.
+#[derive(Copy, Clone)]
+pub struct ASN1Time {
+    dt: OffsetDateTime,
+    encoding: Option<ASN1TimeEncoding>,
+    rfc5280_format: bool,
+}
 
 impl ASN1Time {
     pub(crate) fn from_der_opt(i: &[u8]) -> X509Result<Option<Self>> {
@@ -18,7 +34,7 @@ impl ASN1Time {
             return Ok((i, None));
         }
         match parse_choice_of_time(i) {
-            Ok((rem, dt)) => Ok((rem, Some(ASN1Time(dt)))),
+            Ok((rem, time)) => Ok((rem, Some(time))),
             Err(Err::Error(Error::InvalidTag)) | Err(Err::Error(Error::UnexpectedTag { .. })) => {
                 Ok((i, None))
             }
@@ -28,30 +44,42 @@ impl ASN1Time {
 
     #[inline]
     pub const fn new(dt: OffsetDateTime) -> Self {
-        Self(dt)
+        ASN1Time {
+            dt,
+            encoding: None,
+            rfc5280_format: true,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn with_encoding(self, encoding: ASN1TimeEncoding) -> Self {
+        ASN1Time {
+            encoding: Some(encoding),
+            ..self
+        }
     }
 
     #[inline]
     pub const fn to_datetime(&self) -> OffsetDateTime {
-        self.0
+        self.dt
     }
 
     /// Makes a new `ASN1Time` from the number of non-leap seconds since Epoch
     pub fn from_timestamp(secs: i64) -> Result<Self, X509Error> {
         let dt = OffsetDateTime::from_unix_timestamp(secs).map_err(|_| X509Error::InvalidDate)?;
-        Ok(ASN1Time(dt))
+        Ok(ASN1Time::new(dt))
     }
 
     /// Returns the number of non-leap seconds since January 1, 1970 0:00:00 UTC (aka "UNIX timestamp").
     #[inline]
     pub fn timestamp(&self) -> i64 {
-        self.0.unix_timestamp()
+        self.dt.unix_timestamp()
     }
 
     /// Returns a `ASN1Time` which corresponds to the current date.
     #[inline]
     pub fn now() -> Self {
-        ASN1Time(OffsetDateTime::now_utc())
+        ASN1Time::new(OffsetDateTime::now_utc())
     }
 
     /// Returns an RFC 2822 date and time string such as `Tue, 1 Jul 2003 10:52:37 +0200`.
@@ -62,29 +90,73 @@ impl ASN1Time {
     /// For an infallible conversion to string, use `.to_string()`.
     #[inline]
     pub fn to_rfc2822(self) -> Result<String, String> {
-        self.0
+        self.dt
             .format(&time::format_description::well_known::Rfc2822)
             .map_err(|e| e.to_string())
     }
+
+    /// Returns an RFC 3339 date and time string in UTC, such as `2025-01-01T00:00:00Z`.
+    ///
+    /// This is the format used by `Display`.
+    pub fn to_rfc3339(&self) -> String {
+        let format = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
+        self.dt
+            .to_offset(time::UtcOffset::UTC)
+            .format(format)
+            .unwrap_or_else(|e| format!("Invalid date: {}", e))
+    }
+
+    /// Returns the ASN.1 type used to encode this timestamp, or `None` if the object was not
+    /// parsed (for ex. built using [`ASN1Time::now`]).
+    #[inline]
+    pub const fn encoding(&self) -> Option<ASN1TimeEncoding> {
+        self.encoding
+    }
+
+    /// Returns `false` if the time was parsed from a value which does not use the format required
+    /// by RFC 5280 (seconds present, no fractional seconds, and `Z` suffix)
+    #[inline]
+    pub const fn is_rfc5280_format(&self) -> bool {
+        self.rfc5280_format
+    }
 }
 
 impl<'a> FromDer<'a, X509Error> for ASN1Time {
     fn from_der(i: &[u8]) -> X509Result<Self> {
-        let (rem, dt) = parse_choice_of_time(i).map_err(|_| X509Error::InvalidDate)?;
-        Ok((rem, ASN1Time(dt)))
+        parse_choice_of_time(i).map_err(|_| X509Error::InvalidDate.into())
     }
 }
 
-pub(crate) fn parse_choice_of_time(i: &[u8]) -> ParseResult<OffsetDateTime> {
+pub(crate) fn parse_choice_of_time(i: &[u8]) -> ParseResult<ASN1Time> {
     if let Ok((rem, t)) = UtcTime::from_der(i) {
         let dt = t.utc_adjusted_datetime()?;
-        return Ok((rem, dt));
+        let time = ASN1Time::new(dt).with_encoding(ASN1TimeEncoding::UtcTime);
+        return Ok((rem, time.with_rfc5280_format(i, 13)));
     }
     if let Ok((rem, t)) = GeneralizedTime::from_der(i) {
         let dt = t.utc_datetime()?;
-        return Ok((rem, dt));
+        let time = ASN1Time::new(dt).with_encoding(ASN1TimeEncoding::GeneralizedTime);
+        return Ok((rem, time.with_rfc5280_format(i, 15)));
+    }
+    parse_malformed_date(i).map(|(rem, dt)| (rem, ASN1Time::new(dt)))
+}
+
+impl ASN1Time {
+    // RFC 5280: YYMMDDHHMMSSZ (UTCTime) or YYYYMMDDHHMMSSZ (GeneralizedTime)
+    fn with_rfc5280_format(self, i: &[u8], expected_len: usize) -> Self {
+        let rfc5280_format = match Any::from_der(i) {
+            Ok((_, any)) => {
+                any.data.len() == expected_len
+                    && any.data[..expected_len - 1].iter().all(u8::is_ascii_digit)
+                    && any.data[expected_len - 1] == b'Z'
+            }
+            Err(_) => false,
+        };
+        ASN1Time {
+            rfc5280_format,
+            ..self
+        }
     }
-    parse_malformed_date(i)
 }
 
 // allow relaxed parsing of UTCTime (ex: 370116130016+0000)
@@ -96,7 +168,7 @@ fn parse_malformed_date(i: &[u8]) -> ParseResult<OffsetDateTime> {
     let (_rem, hdr) = Header::from_der(i)?;
     let len = hdr.length().definite()?;
     if len > MAX_OBJECT_SIZE {
-        return Err(nom::Err::Error(Error::InvalidLength));
+        return Err(Err::Error(Error::InvalidLength));
     }
     match hdr.tag() {
         Tag::UtcTime => {
@@ -111,20 +183,50 @@ fn parse_malformed_date(i: &[u8]) -> ParseResult<OffsetDateTime> {
             // let content = BerObjectContent::UTCTime(s);
             // let obj = DerObject::from_header_and_content(hdr, content);
             // Ok((rem, obj))
-            Err(nom::Err::Error(Error::BerValueError))
+            Err(Err::Error(Error::BerValueError))
         }
-        _ => Err(nom::Err::Error(Error::unexpected_tag(None, hdr.tag()))),
+        _ => Err(Err::Error(Error::unexpected_tag(None, hdr.tag()))),
     }
 }
 
 impl fmt::Display for ASN1Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let format = format_description!("[month repr:short] [day padding:space] [hour]:[minute]:[second] [year padding:none] [offset_hour sign:mandatory]:[offset_minute]");
-        let s = self
-            .0
-            .format(format)
-            .unwrap_or_else(|e| format!("Invalid date: {}", e));
-        f.write_str(&s)
+        f.write_str(&self.to_rfc3339())
+    }
+}
+
+impl fmt::Debug for ASN1Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.encoding {
+            Some(encoding) => write!(f, "ASN1Time({}, {:?})", self.to_rfc3339(), encoding),
+            None => write!(f, "ASN1Time({})", self.to_rfc3339()),
+        }
+    }
+}
+
+impl PartialEq for ASN1Time {
+    fn eq(&self, other: &Self) -> bool {
+        self.dt == other.dt
+    }
+}
+
+impl Eq for ASN1Time {}
+
+impl PartialOrd for ASN1Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ASN1Time {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dt.cmp(&other.dt)
+    }
+}
+
+impl Hash for ASN1Time {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dt.hash(state);
     }
 }
 
@@ -133,7 +235,7 @@ impl Add<Duration> for ASN1Time {
 
     #[inline]
     fn add(self, rhs: Duration) -> Option<ASN1Time> {
-        Some(ASN1Time(self.0 + rhs))
+        Some(ASN1Time::new(self.dt + rhs))
     }
 }
 
@@ -142,8 +244,8 @@ impl Sub<ASN1Time> for ASN1Time {
 
     #[inline]
     fn sub(self, rhs: ASN1Time) -> Option<Duration> {
-        if self.0 > rhs.0 {
-            Some(self.0 - rhs.0)
+        if self.dt > rhs.dt {
+            Some(self.dt - rhs.dt)
         } else {
             None
         }
@@ -152,7 +254,7 @@ impl Sub<ASN1Time> for ASN1Time {
 
 impl From<OffsetDateTime> for ASN1Time {
     fn from(dt: OffsetDateTime) -> Self {
-        ASN1Time(dt)
+        ASN1Time::new(dt)
     }
 }
 
@@ -166,7 +268,28 @@ mod tests {
     fn test_time_to_string() {
         let d = datetime!(1 - 1 - 1 12:34:56 UTC);
         let t = ASN1Time::from(d);
-        assert_eq!(t.to_string(), "Jan  1 12:34:56 1 +00:00".to_string());
+        assert_eq!(t.to_string(), "0001-01-01T12:34:56Z".to_string());
+        let d = datetime!(2025-01-01 02:00:00 +02:00);
+        let t = ASN1Time::from(d);
+        assert_eq!(t.to_string(), "2025-01-01T00:00:00Z".to_string());
+        assert_eq!(format!("{:?}", t), "ASN1Time(2025-01-01T00:00:00Z)");
+    }
+
+    #[test]
+    fn test_time_encoding() {
+        use super::ASN1TimeEncoding;
+        use asn1_rs::FromDer;
+
+        let (_, utc) = ASN1Time::from_der(b"\x17\x0d250101000000Z").unwrap();
+        assert_eq!(utc.encoding(), Some(ASN1TimeEncoding::UtcTime));
+        let (_, gen) = ASN1Time::from_der(b"\x18\x0f20250101000000Z").unwrap();
+        assert_eq!(gen.encoding(), Some(ASN1TimeEncoding::GeneralizedTime));
+        // encoding is ignored in comparisons
+        assert_eq!(utc, gen);
+        assert_eq!(
+            format!("{:?}", utc),
+            "ASN1Time(2025-01-01T00:00:00Z, UtcTime)"
+        );
     }
 
     #[test]
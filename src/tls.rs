@@ -0,0 +1,194 @@
+//! Parsing of certificates from TLS handshake `Certificate` messages
+//!
+//! These functions parse the body of a `Certificate` handshake message (without the 4-bytes
+//! handshake header), as found for ex. in packet captures, and give access to the
+//! DER-encoded certificates.
+//!
+//! TLS 1.2 ([RFC5246] section 7.4.2):
+//!
+//! <pre>
+//! opaque ASN.1Cert<1..2^24-1>;
+//!
+//! struct {
+//!     ASN.1Cert certificate_list<0..2^24-1>;
+//! } Certificate;
+//! </pre>
+//!
+//! TLS 1.3 ([RFC8446] section 4.4.2):
+//!
+//! <pre>
+//! struct {
+//!     opaque cert_data<1..2^24-1>;
+//!     Extension extensions<0..2^16-1>;
+//! } CertificateEntry;
+//!
+//! struct {
+//!     opaque certificate_request_context<0..2^8-1>;
+//!     CertificateEntry certificate_list<0..2^24-1>;
+//! } Certificate;
+//! </pre>
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::tls::parse_tls12_certificate_message;
+//!
+//! fn print_chain(payload: &[u8]) {
+//!     let (_, msg) = parse_tls12_certificate_message(payload).expect("invalid message");
+//!     for cert in msg.iter_certificates() {
+//!         match cert {
+//!             Ok(x509) => println!("Subject: {}", x509.subject()),
+//!             Err(e) => println!("Invalid certificate: {}", e),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! [RFC5246]: https://tools.ietf.org/html/rfc5246
+//! [RFC8446]: https://tools.ietf.org/html/rfc8446
+
+use crate::certificate::X509Certificate;
+use crate::error::{X509Error, X509Result};
+use asn1_rs::FromDer;
+use nom::combinator::{all_consuming, complete, verify};
+use nom::multi::{length_data, many0};
+use nom::number::complete::{be_u16, be_u24, be_u8};
+
+/// An entry of a TLS `Certificate` message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsCertificateEntry<'a> {
+    /// The DER-encoded certificate
+    pub cert_data: &'a [u8],
+    /// The raw extensions of the entry (TLS 1.3 only, empty for TLS 1.2)
+    pub extensions: &'a [u8],
+}
+
+impl<'a> TlsCertificateEntry<'a> {
+    /// Parse the certificate of this entry
+    pub fn parse(&self) -> Result<X509Certificate<'a>, X509Error> {
+        X509Certificate::from_der(self.cert_data)
+            .map(|(_, x509)| x509)
+            .map_err(|e| e.into())
+    }
+}
+
+/// A TLS `Certificate` message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsCertificateMessage<'a> {
+    /// The certificate request context (TLS 1.3 only, `None` for TLS 1.2)
+    pub certificate_request_context: Option<&'a [u8]>,
+    /// The certificate entries, starting with the end-entity certificate
+    pub entries: Vec<TlsCertificateEntry<'a>>,
+}
+
+impl<'a> TlsCertificateMessage<'a> {
+    /// Return an iterator over the parsed certificates of the message
+    pub fn iter_certificates(
+        &self,
+    ) -> impl Iterator<Item = Result<X509Certificate<'a>, X509Error>> + '_ {
+        self.entries.iter().map(|entry| entry.parse())
+    }
+}
+
+fn parse_cert_data(i: &[u8]) -> X509Result<&[u8]> {
+    verify(length_data(be_u24), |b: &[u8]| !b.is_empty())(i)
+}
+
+/// Parse the body of a TLS 1.2 (or earlier) `Certificate` handshake message
+///
+/// Certificates are not parsed, see [`TlsCertificateMessage::iter_certificates`].
+pub fn parse_tls12_certificate_message(i: &[u8]) -> X509Result<TlsCertificateMessage> {
+    let (rem, list) = length_data(be_u24)(i)?;
+    let (_, entries) = all_consuming(many0(complete(|i| {
+        let (i, cert_data) = parse_cert_data(i)?;
+        let entry = TlsCertificateEntry {
+            cert_data,
+            extensions: &[],
+        };
+        Ok((i, entry))
+    })))(list)?;
+    let msg = TlsCertificateMessage {
+        certificate_request_context: None,
+        entries,
+    };
+    Ok((rem, msg))
+}
+
+/// Parse the body of a TLS 1.3 `Certificate` handshake message
+///
+/// Certificates are not parsed, see [`TlsCertificateMessage::iter_certificates`].
+pub fn parse_tls13_certificate_message(i: &[u8]) -> X509Result<TlsCertificateMessage> {
+    let (i, context) = length_data(be_u8)(i)?;
+    let (rem, list) = length_data(be_u24)(i)?;
+    let (_, entries) = all_consuming(many0(complete(|i| {
+        let (i, cert_data) = parse_cert_data(i)?;
+        let (i, extensions) = length_data(be_u16)(i)?;
+        let entry = TlsCertificateEntry {
+            cert_data,
+            extensions,
+        };
+        Ok((i, entry))
+    })))(list)?;
+    let msg = TlsCertificateMessage {
+        certificate_request_context: Some(context),
+        entries,
+    };
+    Ok((rem, msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static IGCA_DER: &[u8] = include_bytes!("../assets/IGC_A.der");
+    static CERT_DER: &[u8] = include_bytes!("../assets/certificate.der");
+
+    fn u24(len: usize) -> [u8; 3] {
+        let b = (len as u32).to_be_bytes();
+        [b[1], b[2], b[3]]
+    }
+
+    #[test]
+    fn test_tls12_certificate_message() {
+        let mut list = Vec::new();
+        for cert in &[CERT_DER, IGCA_DER] {
+            list.extend_from_slice(&u24(cert.len()));
+            list.extend_from_slice(cert);
+        }
+        let mut payload = u24(list.len()).to_vec();
+        payload.extend_from_slice(&list);
+
+        let (rem, msg) = parse_tls12_certificate_message(&payload).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(msg.certificate_request_context, None);
+        assert_eq!(msg.entries.len(), 2);
+        let certs: Vec<_> = msg
+            .iter_certificates()
+            .collect::<Result<_, _>>()
+            .expect("invalid certificate");
+        assert!(certs[1].subject().to_string().contains("CN=IGC/A"));
+
+        // truncated message
+        assert!(parse_tls12_certificate_message(&payload[..100]).is_err());
+    }
+
+    #[test]
+    fn test_tls13_certificate_message() {
+        let mut list = Vec::new();
+        list.extend_from_slice(&u24(IGCA_DER.len()));
+        list.extend_from_slice(IGCA_DER);
+        // one extension: status_request (5), empty
+        list.extend_from_slice(&[0x00, 0x04, 0x00, 0x05, 0x00, 0x00]);
+        let mut payload = vec![0x01, 0xaa];
+        payload.extend_from_slice(&u24(list.len()));
+        payload.extend_from_slice(&list);
+
+        let (rem, msg) = parse_tls13_certificate_message(&payload).expect("parsing failed");
+        assert!(rem.is_empty());
+        assert_eq!(msg.certificate_request_context, Some(&[0xaa][..]));
+        assert_eq!(msg.entries.len(), 1);
+        assert_eq!(msg.entries[0].cert_data, IGCA_DER);
+        assert_eq!(msg.entries[0].extensions, &[0x00, 0x05, 0x00, 0x00]);
+        assert!(msg.entries[0].parse().is_ok());
+    }
+}
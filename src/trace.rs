@@ -0,0 +1,158 @@
+//! Parse tracing, to locate errors in malformed objects
+//!
+//! When a parsing function is called through [`trace_parse`], the parser records the sequence
+//! of fields it enters, with their offset in the input. If parsing fails, the trace is attached
+//! to the returned [`TracedError`], so the last entries show where the error happened.
+//!
+//! Recording only happens inside `trace_parse`: other parsing functions are not affected.
+//!
+//! # Example
+//!
+//! ```rust
+//! use x509_parser::trace::parse_x509_certificate_traced;
+//!
+//! # static DER: &'static [u8] = include_bytes!("../assets/IGC_A.der");
+//! // truncated certificate
+//! let res = parse_x509_certificate_traced(&DER[..60]);
+//! let err = res.expect_err("parsing should fail");
+//! for entry in &err.trace {
+//!     println!("{} at offset {}", entry.field, entry.offset);
+//! }
+//! println!("{}", err);
+//! ```
+
+use crate::certificate::X509Certificate;
+use crate::error::{X509Error, X509Result};
+use asn1_rs::FromDer;
+use std::cell::RefCell;
+use std::fmt;
+
+thread_local! {
+    // const initializers require rustc 1.59
+    #[allow(clippy::missing_const_for_thread_local)]
+    static TRACE: RefCell<Option<Vec<(&'static str, usize)>>> = RefCell::new(None);
+}
+
+/// A field entered by the parser, with its offset in the input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// Name of the field (for ex. `tbsCertificate.issuer`)
+    pub field: &'static str,
+    /// Offset of the field, relative to the start of the input
+    pub offset: usize,
+}
+
+/// A parsing error, with the trace of the parsed fields
+#[derive(Clone, Debug, PartialEq)]
+pub struct TracedError {
+    /// The parsing error
+    pub error: X509Error,
+    /// The fields entered by the parser, in order
+    pub trace: Vec<TraceEntry>,
+}
+
+impl fmt::Display for TracedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.trace.last() {
+            Some(entry) => write!(
+                f,
+                "{} (in {} at offset {})",
+                self.error, entry.field, entry.offset
+            ),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for TracedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Record that the parser enters `field`, starting at `i`
+///
+/// This has no effect outside of [`trace_parse`].
+pub(crate) fn record(field: &'static str, i: &[u8]) {
+    TRACE.with(|t| {
+        if let Some(v) = t.borrow_mut().as_mut() {
+            v.push((field, i.as_ptr() as usize));
+        }
+    });
+}
+
+/// Run the parsing function `f` on input `i`, recording the fields entered by the parser
+///
+/// If parsing fails, the error is returned with the trace of fields.
+pub fn trace_parse<'a, T, F>(i: &'a [u8], f: F) -> Result<(&'a [u8], T), TracedError>
+where
+    F: FnOnce(&'a [u8]) -> X509Result<'a, T>,
+{
+    // save previous state, in case of nested calls
+    let previous = TRACE.with(|t| t.replace(Some(Vec::new())));
+    let res = f(i);
+    let records = TRACE.with(|t| t.replace(previous)).unwrap_or_default();
+    res.map_err(|e| {
+        let start = i.as_ptr() as usize;
+        let trace = records
+            .into_iter()
+            .filter(|&(_, ptr)| ptr >= start && ptr <= start + i.len())
+            .map(|(field, ptr)| TraceEntry {
+                field,
+                offset: ptr - start,
+            })
+            .collect();
+        TracedError {
+            error: e.into(),
+            trace,
+        }
+    })
+}
+
+/// Parse a DER-encoded X.509 Certificate, recording the trace of parsed fields
+///
+/// See [`trace_parse`].
+pub fn parse_x509_certificate_traced(
+    i: &[u8],
+) -> Result<(&[u8], X509Certificate<'_>), TracedError> {
+    trace_parse(i, X509Certificate::from_der)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static IGCA_DER: &[u8] = include_bytes!("../assets/IGC_A.der");
+
+    #[test]
+    fn test_trace_error() {
+        // replace the issuer SEQUENCE tag with a SET
+        let (_, x509) = X509Certificate::from_der(IGCA_DER).unwrap();
+        let offset = x509.issuer().as_raw().as_ptr() as usize - IGCA_DER.as_ptr() as usize;
+        let mut data = IGCA_DER.to_vec();
+        data[offset] = 0x31;
+        let err = parse_x509_certificate_traced(&data).unwrap_err();
+        let fields: Vec<_> = err.trace.iter().map(|e| e.field).collect();
+        assert_eq!(
+            fields,
+            &[
+                "certificate",
+                "tbsCertificate",
+                "tbsCertificate.version",
+                "tbsCertificate.serialNumber",
+                "tbsCertificate.signature",
+                "tbsCertificate.issuer",
+            ]
+        );
+        assert_eq!(err.trace[0].offset, 0);
+        assert_eq!(err.trace[5].offset, offset);
+        assert!(matches!(err.error, X509Error::Der(_)));
+    }
+
+    #[test]
+    fn test_trace_ok() {
+        assert!(parse_x509_certificate_traced(IGCA_DER).is_ok());
+        // no recording outside of trace_parse
+        TRACE.with(|t| assert!(t.borrow().is_none()));
+    }
+}
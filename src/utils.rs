@@ -1,19 +1,222 @@
+use crate::error::X509Error;
+use asn1_rs::{Any, FromDer};
+use nom::error::ParseError;
+use nom::multi::many0;
+use nom::{IResult, InputLength, Parser};
+
 /// Formats a slice to a colon-separated hex string (for ex `01:02:ff:ff`)
 pub fn format_serial(i: &[u8]) -> String {
-    let mut s = i.iter().fold(String::with_capacity(3 * i.len()), |a, b| {
-        a + &format!("{:02x}:", b)
-    });
-    s.pop();
-    s
+    format_serial_with(i, SerialFormat::Colon)
+}
+
+/// Output style for serial numbers, see [`format_serial_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerialFormat {
+    /// Colon-separated lowercase hex (for ex `01:02:ff:ff`)
+    Colon,
+    /// Lowercase hex without separators (for ex `0102ffff`)
+    Lower,
+    /// Uppercase hex without separators (for ex `0102FFFF`)
+    Upper,
+    /// `0x`-prefixed lowercase hex (for ex `0x0102ffff`)
+    Hex,
+    /// Decimal value of the serial, interpreted as an unsigned big-endian integer (for ex `16973823`)
+    Decimal,
+}
+
+/// Formats a slice to a string, using the given style
+pub fn format_serial_with(i: &[u8], style: SerialFormat) -> String {
+    match style {
+        SerialFormat::Colon => {
+            let mut s = i.iter().fold(String::with_capacity(3 * i.len()), |a, b| {
+                a + &format!("{:02x}:", b)
+            });
+            s.pop();
+            s
+        }
+        SerialFormat::Lower => data_encoding::HEXLOWER.encode(i),
+        SerialFormat::Upper => data_encoding::HEXUPPER.encode(i),
+        SerialFormat::Hex => format!("0x{}", data_encoding::HEXLOWER.encode(i)),
+        SerialFormat::Decimal => format_decimal(i),
+    }
+}
+
+/// Formats a big-endian unsigned integer to its decimal representation
+fn format_decimal(i: &[u8]) -> String {
+    let mut n: Vec<u8> = i.iter().skip_while(|&&b| b == 0).copied().collect();
+    let mut digits = Vec::new();
+    while !n.is_empty() {
+        // divide n by 10, keeping the remainder
+        let mut rem = 0u32;
+        for b in n.iter_mut() {
+            let acc = (rem << 8) | u32::from(*b);
+            *b = (acc / 10) as u8;
+            rem = acc % 10;
+        }
+        digits.push(b'0' + rem as u8);
+        let start = n.iter().position(|&b| b != 0).unwrap_or(n.len());
+        n.drain(..start);
+    }
+    if digits.is_empty() {
+        return "0".to_string();
+    }
+    digits.iter().rev().map(|&d| d as char).collect()
+}
+
+/// Compare two byte slices in constant time
+///
+/// The time taken depends only on the length of the inputs, not on their contents, so this can
+/// be used to compare names or serials in authorization paths without leaking (through timing)
+/// how many bytes match. The lengths are not considered secret.
+///
+/// This is a best-effort implementation (all bytes are accumulated without branching), which
+/// does not use compiler barriers.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    diff == 0
+}
+
+/// Decode a hex dump to bytes
+///
+/// The input can be contiguous hex (for ex `0102ffff`), or groups separated by colons or
+/// whitespace (for ex `01:02:ff:ff`, or a multi-line dump from a log). Both lowercase and
+/// uppercase digits are accepted, and every group must contain complete bytes.
+pub fn decode_hex(s: &str) -> Result<Vec<u8>, X509Error> {
+    let mut v = Vec::with_capacity(s.len() / 2);
+    for group in s.split(|c: char| c == ':' || c.is_whitespace()) {
+        let bytes = data_encoding::HEXLOWER_PERMISSIVE
+            .decode(group.as_bytes())
+            .or(Err(X509Error::InvalidHex))?;
+        v.extend_from_slice(&bytes);
+    }
+    Ok(v)
+}
+
+// Encode a DER tag-length-value, with a single-byte tag
+pub(crate) fn der_tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut v = vec![tag];
+    let len = content.len();
+    if len < 0x80 {
+        v.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let start = bytes
+            .iter()
+            .position(|&b| b != 0)
+            .unwrap_or(bytes.len() - 1);
+        v.push(0x80 | (bytes.len() - start) as u8);
+        v.extend_from_slice(&bytes[start..]);
+    }
+    v.extend_from_slice(content);
+    v
+}
+
+// Return the maximum nesting depth of the elements of a DER encoding
+//
+// Top-level elements have depth 1. Invalid elements (and their following siblings) are ignored.
+// The traversal uses an explicit stack, so deeply nested inputs cannot overflow the call stack.
+pub(crate) fn der_max_depth(i: &[u8]) -> usize {
+    let mut max_depth = 0;
+    let mut stack = vec![(i, 1)];
+    while let Some((data, depth)) = stack.pop() {
+        let (rem, any) = match Any::from_der(data) {
+            Ok(res) => res,
+            Err(_) => continue,
+        };
+        max_depth = max_depth.max(depth);
+        if !rem.is_empty() {
+            stack.push((rem, depth));
+        }
+        if any.header.is_constructed() && !any.data.is_empty() {
+            stack.push((any.data, depth + 1));
+        }
+    }
+    max_depth
+}
+
+/// Same as `nom::multi::many0`, but returns an empty `Vec` without allocating if input is empty
+///
+/// `many0` preallocates its result, which is wasted for the (common) empty sequences.
+pub(crate) fn many0_lazy<I, O, E, F>(f: F) -> impl FnMut(I) -> IResult<I, Vec<O>, E>
+where
+    I: Clone + InputLength,
+    F: Parser<I, O, E>,
+    E: ParseError<I>,
+{
+    let mut parser = many0(f);
+    move |i: I| {
+        if i.input_len() == 0 {
+            Ok((i, Vec::new()))
+        } else {
+            parser(i)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(constant_time_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn test_der_tlv_long_length() {
+        let v = der_tlv(0x04, &[0u8; 300]);
+        assert_eq!(&v[..4], &[0x04, 0x82, 0x01, 0x2c]);
+        assert_eq!(v.len(), 304);
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        let expected = vec![0x30, 0x82, 0x01, 0xff];
+        assert_eq!(decode_hex("308201ff"), Ok(expected.clone()));
+        assert_eq!(decode_hex("30:82:01:FF"), Ok(expected.clone()));
+        assert_eq!(decode_hex(" 30 82\n01 ff\n"), Ok(expected.clone()));
+        assert_eq!(decode_hex("3082 01ff"), Ok(expected));
+        assert_eq!(decode_hex(""), Ok(vec![]));
+        assert_eq!(decode_hex("30:8:01"), Err(X509Error::InvalidHex));
+        assert_eq!(decode_hex("30:zz"), Err(X509Error::InvalidHex));
+    }
+
+    #[test]
+    fn test_der_max_depth() {
+        assert_eq!(der_max_depth(&[]), 0);
+        assert_eq!(der_max_depth(&[0x05, 0x00]), 1);
+        // SEQUENCE { SEQUENCE { NULL } }, INTEGER 1
+        let data = &[0x30, 0x04, 0x30, 0x02, 0x05, 0x00, 0x02, 0x01, 0x01];
+        assert_eq!(der_max_depth(data), 3);
+        // truncated inner element
+        assert_eq!(der_max_depth(&[0x30, 0x02, 0x30, 0x05]), 1);
+    }
+
     #[test]
     fn test_format_serial() {
         let b: &[u8] = &[1, 2, 3, 4, 0xff];
         assert_eq!("01:02:03:04:ff", format_serial(b));
     }
+
+    #[test]
+    fn test_format_serial_with() {
+        let b: &[u8] = &[1, 2, 0xff, 0xff];
+        assert_eq!("01:02:ff:ff", format_serial_with(b, SerialFormat::Colon));
+        assert_eq!("0102ffff", format_serial_with(b, SerialFormat::Lower));
+        assert_eq!("0102FFFF", format_serial_with(b, SerialFormat::Upper));
+        assert_eq!("0x0102ffff", format_serial_with(b, SerialFormat::Hex));
+        assert_eq!("16973823", format_serial_with(b, SerialFormat::Decimal));
+        assert_eq!("0", format_serial_with(&[0], SerialFormat::Decimal));
+        let b: &[u8] = &[0x01, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            "18446744073709551616",
+            format_serial_with(b, SerialFormat::Decimal)
+        );
+    }
 }
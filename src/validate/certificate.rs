@@ -2,6 +2,7 @@ use crate::certificate::*;
 use crate::validate::*;
 
 use extensions::X509ExtensionsValidator;
+use time::Duration;
 
 #[derive(Debug)]
 pub struct X509CertificateValidator;
@@ -12,6 +13,162 @@ impl<'a> Validator<'a> for X509CertificateValidator {
     fn validate<L: Logger>(&self, item: &'a Self::Item, l: &'_ mut L) -> bool {
         let mut res = true;
         res &= X509ExtensionsValidator.validate(&item.extensions(), l);
+        res &= X509KeyUsagePeriodValidator.validate(item, l);
         res
     }
 }
+
+/// Validator checking that the validity period of a certificate does not exceed a maximum
+///
+/// The validity period is computed as defined in RFC 5280 section 4.1.2.5 (the period includes
+/// both `notBefore` and `notAfter`, so it is one second longer than the difference between the
+/// two dates).
+///
+/// Certificates with a longer validity period are reported as errors.
+///
+/// # Examples
+///
+/// ```
+/// use x509_parser::certificate::X509Certificate;
+/// use x509_parser::validate::*;
+///
+/// fn check_tls_leaf(x509: &X509Certificate<'_>) -> bool {
+///     let mut logger = VecLogger::default();
+///     X509MaxValidityValidator::CABF_TLS_SERVER.validate(x509, &mut logger)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct X509MaxValidityValidator {
+    /// The maximum allowed validity period
+    pub max_validity: Duration,
+}
+
+impl X509MaxValidityValidator {
+    /// CA/Browser Forum Baseline Requirements: 398 days for TLS server certificates
+    pub const CABF_TLS_SERVER: X509MaxValidityValidator =
+        X509MaxValidityValidator::new(Duration::days(398));
+
+    /// Create a validator with the given maximum validity period
+    pub const fn new(max_validity: Duration) -> Self {
+        X509MaxValidityValidator { max_validity }
+    }
+}
+
+impl<'a> Validator<'a> for X509MaxValidityValidator {
+    type Item = X509Certificate<'a>;
+
+    fn validate<L: Logger>(&self, item: &'a Self::Item, l: &'_ mut L) -> bool {
+        let validity = item.validity();
+        let period = match validity.lifetime() {
+            Some(d) => d + Duration::seconds(1),
+            None => {
+                l.err("Validity: notAfter is before notBefore");
+                return false;
+            }
+        };
+        if period > self.max_validity {
+            l.err(&format!(
+                "Validity period ({} days) exceeds the maximum allowed ({} days)",
+                period.whole_days(),
+                self.max_validity.whole_days()
+            ));
+            return false;
+        }
+        true
+    }
+}
+
+/// Validator checking the consistency of the validity and private key usage periods
+///
+/// Errors are reported if `notAfter` is before `notBefore`, or if the Private Key Usage Period
+/// extension (when present) is empty, is inverted, or is not contained in the validity period.
+///
+/// This validator is also run by [`X509CertificateValidator`].
+#[derive(Clone, Copy, Debug)]
+pub struct X509KeyUsagePeriodValidator;
+
+impl<'a> Validator<'a> for X509KeyUsagePeriodValidator {
+    type Item = X509Certificate<'a>;
+
+    fn validate<L: Logger>(&self, item: &'a Self::Item, l: &'_ mut L) -> bool {
+        let validity = item.validity();
+        let mut res = true;
+        if validity.not_after < validity.not_before {
+            l.err("Validity: notAfter is before notBefore");
+            res = false;
+        }
+        let period = match item.private_key_usage_period() {
+            Ok(Some(ext)) => ext.value,
+            _ => return res,
+        };
+        match (period.not_before, period.not_after) {
+            (None, None) => {
+                l.err("PrivateKeyUsagePeriod: notBefore and notAfter are both absent");
+                res = false;
+            }
+            (Some(not_before), Some(not_after)) if not_after < not_before => {
+                l.err("PrivateKeyUsagePeriod: notAfter is before notBefore");
+                res = false;
+            }
+            _ => (),
+        }
+        if let Some(not_before) = period.not_before {
+            if not_before < validity.not_before || not_before > validity.not_after {
+                l.err("PrivateKeyUsagePeriod: notBefore is outside of the validity period");
+                res = false;
+            }
+        }
+        if let Some(not_after) = period.not_after {
+            if not_after < validity.not_before || not_after > validity.not_after {
+                l.err("PrivateKeyUsagePeriod: notAfter is outside of the validity period");
+                res = false;
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asn1_rs::FromDer;
+
+    static IGCA_DER: &[u8] = include_bytes!("../../assets/IGC_A.der");
+
+    #[test]
+    fn test_max_validity() {
+        let (_, x509) = X509Certificate::from_der(IGCA_DER).unwrap();
+        let mut logger = VecLogger::default();
+        assert!(!X509MaxValidityValidator::CABF_TLS_SERVER.validate(&x509, &mut logger));
+        assert_eq!(
+            logger.errors(),
+            &["Validity period (6518 days) exceeds the maximum allowed (398 days)"]
+        );
+        // exactly 6518 days, including notBefore and notAfter
+        let mut logger = VecLogger::default();
+        assert!(X509MaxValidityValidator::new(Duration::days(6518)).validate(&x509, &mut logger));
+        assert!(logger.errors().is_empty());
+    }
+
+    #[test]
+    fn test_key_usage_period() {
+        // private key usage period ends in 2040, after the certificate
+        let (_, x509) =
+            X509Certificate::from_der(include_bytes!("../../assets/test-pkup.der")).unwrap();
+        let period = x509.private_key_usage_period().unwrap().unwrap().value;
+        assert_eq!(period.not_before, Some(x509.validity().not_before));
+        let mut logger = VecLogger::default();
+        assert!(!X509KeyUsagePeriodValidator.validate(&x509, &mut logger));
+        assert_eq!(
+            logger.errors(),
+            &["PrivateKeyUsagePeriod: notAfter is outside of the validity period"]
+        );
+        let mut logger = VecLogger::default();
+        assert!(!X509CertificateValidator.validate(&x509, &mut logger));
+        // no private key usage period
+        let (_, x509) = X509Certificate::from_der(IGCA_DER).unwrap();
+        let mut logger = VecLogger::default();
+        assert!(X509KeyUsagePeriodValidator.validate(&x509, &mut logger));
+        assert!(logger.errors().is_empty());
+    }
+}
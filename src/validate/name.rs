@@ -1,7 +1,14 @@
 use crate::validate::*;
 use crate::x509::*;
 use asn1_rs::Tag;
+use oid_registry::OID_X509_COUNTRY_NAME;
 
+/// Default X.509 structure validator for `X509Name`
+///
+/// Reports as warnings:
+/// - `PrintableString` values with characters outside of the allowed character set
+/// - `IA5String` values with non-ASCII characters
+/// - `countryName` values that are not exactly two letters
 #[derive(Debug)]
 pub struct X509NameStructureValidator;
 
@@ -14,19 +21,79 @@ impl<'a> Validator<'a> for X509NameStructureValidator {
         // - wildcards in PrintableString
         // - non-IA5 in IA5String
         for attr in item.iter_attributes() {
-            match attr.attr_value().tag() {
-                Tag::PrintableString | Tag::Ia5String => {
-                    let b = attr.attr_value().as_bytes();
-                    if !b.iter().all(u8::is_ascii) {
-                        l.warn(&format!(
-                            "Invalid charset in X.509 Name, component {}",
-                            attr.attr_type()
-                        ));
-                    }
-                }
-                _ => (),
+            let b = attr.attr_value().as_bytes();
+            let valid_charset = match attr.attr_value().tag() {
+                Tag::PrintableString => b.iter().all(|&c| is_printablestring_char(c)),
+                Tag::Ia5String => b.iter().all(u8::is_ascii),
+                _ => true,
+            };
+            if !valid_charset {
+                l.warn(&format!(
+                    "Invalid charset in X.509 Name, component {}",
+                    attr.attr_type()
+                ));
+            }
+            // RFC 5280: countryName is a two-letter ISO 3166 code
+            if attr.attr_type() == &OID_X509_COUNTRY_NAME
+                && !(b.len() == 2 && b.iter().all(u8::is_ascii_alphabetic))
+            {
+                l.warn("Invalid countryName in X.509 Name (must be two letters)");
             }
         }
         res
     }
 }
+
+/// Test if `c` is in the `PrintableString` character set (X.680 41.4)
+fn is_printablestring_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric()
+        || matches!(
+            c,
+            b' ' | b'\'' | b'(' | b')' | b'+' | b',' | b'-' | b'.' | b'/' | b':' | b'=' | b'?'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asn1_rs::Any;
+    use oid_registry::OID_X509_COMMON_NAME;
+
+    fn name(attrs: Vec<AttributeTypeAndValue<'static>>) -> X509Name<'static> {
+        attrs
+            .into_iter()
+            .map(|attr| RelativeDistinguishedName::new(vec![attr]))
+            .collect()
+    }
+
+    #[test]
+    fn test_name_validator_charset() {
+        let item = name(vec![
+            AttributeTypeAndValue::new(
+                OID_X509_COUNTRY_NAME,
+                Any::from_tag_and_data(Tag::PrintableString, b"FR"),
+            ),
+            AttributeTypeAndValue::new(
+                OID_X509_COMMON_NAME,
+                Any::from_tag_and_data(Tag::PrintableString, b"Test CA (1)"),
+            ),
+        ]);
+        let mut logger = VecLogger::default();
+        assert!(X509NameStructureValidator.validate(&item, &mut logger));
+        assert!(logger.warnings().is_empty());
+
+        let item = name(vec![
+            AttributeTypeAndValue::new(
+                OID_X509_COUNTRY_NAME,
+                Any::from_tag_and_data(Tag::PrintableString, b"FRA"),
+            ),
+            AttributeTypeAndValue::new(
+                OID_X509_COMMON_NAME,
+                Any::from_tag_and_data(Tag::PrintableString, b"*.example.com"),
+            ),
+        ]);
+        let mut logger = VecLogger::default();
+        X509NameStructureValidator.validate(&item, &mut logger);
+        assert_eq!(logger.warnings().len(), 2);
+    }
+}
@@ -2,6 +2,7 @@ use super::{Logger, Validator, X509NameStructureValidator};
 use crate::certificate::*;
 use crate::extensions::{GeneralName, ParsedExtension};
 use crate::public_key::PublicKey;
+use crate::time::{ASN1Time, ASN1TimeEncoding};
 use crate::x509::{SubjectPublicKeyInfo, X509Version};
 
 /// Default X.509 structure validator for `X509Certificate`
@@ -54,6 +55,10 @@ impl<'a> Validator<'a> for X509StructureValidator {
     fn validate<L: Logger>(&self, item: &'a Self::Item, l: &'_ mut L) -> bool {
         let mut res = true;
         res &= TbsCertificateStructureValidator.validate(&item.tbs_certificate, l);
+        if item.signature_algorithm.has_forbidden_parameters() {
+            l.err("Parameters present in signature algorithm (forbidden by RFC 8410)");
+            res = false;
+        }
         res
     }
 }
@@ -91,6 +96,13 @@ impl<'a> Validator<'a> for TbsCertificateStructureValidator {
                 l.warn("Leading zeroes in serial number");
             }
         }
+        if item.signature.has_forbidden_parameters() {
+            l.err("Parameters present in TBS signature algorithm (forbidden by RFC 8410)");
+            res = false;
+        }
+        // validity: check time encoding
+        check_time_encoding("notBefore", &item.validity.not_before, l);
+        check_time_encoding("notAfter", &item.validity.not_after, l);
         // subject/issuer: verify charsets
         res &= X509NameStructureValidator.validate(&item.subject, l);
         res &= X509NameStructureValidator.validate(&item.issuer, l);
@@ -128,6 +140,31 @@ impl<'a> Validator<'a> for TbsCertificateStructureValidator {
     }
 }
 
+// RFC 5280 section 4.1.2.5: dates through 2049 MUST be encoded as UTCTime, dates in 2050 or
+// later MUST be encoded as GeneralizedTime, with seconds and in Zulu time
+fn check_time_encoding<L: Logger>(field: &str, time: &ASN1Time, l: &mut L) {
+    let expected = if time.to_datetime().year() < 2050 {
+        ASN1TimeEncoding::UtcTime
+    } else {
+        ASN1TimeEncoding::GeneralizedTime
+    };
+    match time.encoding() {
+        Some(encoding) if encoding != expected => {
+            l.warn(&format!(
+                "Validity {}: {} should be encoded as {:?}",
+                field, time, expected
+            ));
+        }
+        _ => (),
+    }
+    if !time.is_rfc5280_format() {
+        l.warn(&format!(
+            "Validity {}: time must include seconds and use the Z suffix",
+            field
+        ));
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct X509PublicKeyValidator;
 
@@ -137,6 +174,10 @@ impl<'a> Validator<'a> for X509PublicKeyValidator {
     fn validate<L: Logger>(&self, item: &'a Self::Item, l: &'_ mut L) -> bool {
         let mut res = true;
         // res &= TbsCertificateStructureValidator.validate(&item.tbs_certificate, l);
+        if item.algorithm.has_forbidden_parameters() {
+            l.err("Public key: parameters present in algorithm (forbidden by RFC 8410)");
+            res = false;
+        }
         match item.parsed() {
             Ok(PublicKey::RSA(rsa)) => {
                 if rsa.modulus[0] & 0x80 != 0 {
@@ -158,3 +199,47 @@ impl<'a> Validator<'a> for X509PublicKeyValidator {
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::VecLogger;
+    use asn1_rs::FromDer;
+
+    static ED25519_SPKI_PARAMS: &[u8] = include_bytes!("../../assets/ed25519-spki-params.der");
+
+    #[test]
+    fn test_ed25519_forbidden_parameters() {
+        let (_, x509) = X509Certificate::from_der(ED25519_SPKI_PARAMS).unwrap();
+        let mut logger = VecLogger::default();
+        assert!(!X509StructureValidator.validate(&x509, &mut logger));
+        assert_eq!(
+            logger.errors(),
+            &["Public key: parameters present in algorithm (forbidden by RFC 8410)"]
+        );
+    }
+
+    #[test]
+    fn test_time_encoding() {
+        let mut logger = VecLogger::default();
+        let (_, t) = ASN1Time::from_der(b"\x17\x0d250101000000Z").unwrap();
+        check_time_encoding("notBefore", &t, &mut logger);
+        // 2050 or later must be encoded as GeneralizedTime
+        let (_, t) = ASN1Time::from_der(b"\x18\x0f20500101000000Z").unwrap();
+        check_time_encoding("notAfter", &t, &mut logger);
+        assert!(logger.warnings().is_empty());
+
+        let (_, t) = ASN1Time::from_der(b"\x18\x0f20250101000000Z").unwrap();
+        check_time_encoding("notBefore", &t, &mut logger);
+        // seconds missing
+        let (_, t) = ASN1Time::from_der(b"\x17\x0b2501010000Z").unwrap();
+        check_time_encoding("notAfter", &t, &mut logger);
+        assert_eq!(
+            logger.warnings(),
+            &[
+                "Validity notBefore: 2025-01-01T00:00:00Z should be encoded as UtcTime",
+                "Validity notAfter: time must include seconds and use the Z suffix",
+            ]
+        );
+    }
+}
@@ -5,6 +5,7 @@ use oid_registry::{
     OID_PKCS1_SHA384WITHRSA, OID_PKCS1_SHA512WITHRSA, OID_SHA1_WITH_RSA, OID_SIG_ECDSA_WITH_SHA256,
     OID_SIG_ECDSA_WITH_SHA384, OID_SIG_ED25519,
 };
+use std::collections::HashMap;
 
 /// Verify the cryptographic signature of the raw data (can be a certificate, a CRL or a CSR).
 ///
@@ -76,3 +77,214 @@ fn get_ec_curve_sha(
         None
     }
 }
+
+type Fingerprint = [u8; 32];
+
+fn fingerprint(data: &[u8]) -> Fingerprint {
+    let digest = ring::digest::digest(&ring::digest::SHA256, data);
+    let mut fp = [0u8; 32];
+    fp.copy_from_slice(digest.as_ref());
+    fp
+}
+
+// Return the fingerprint of the signed content of a certificate: the TBS certificate, the
+// signature algorithm and the signature value
+//
+// The encoding of the whole certificate cannot be used, since it is empty if the certificate
+// was built from a separately parsed `TbsCertificate`.
+fn signed_fingerprint(cert: &X509Certificate) -> Fingerprint {
+    let (tbs, signature_value, signature_algorithm) = cert.signature_input();
+    let parameters = signature_algorithm
+        .parameters
+        .as_ref()
+        .map_or(&[][..], |params| params.data);
+    let parts: [&[u8]; 5] = [
+        tbs,
+        signature_algorithm.algorithm.as_bytes(),
+        parameters,
+        &[signature_value.unused_bits],
+        &signature_value.data,
+    ];
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+    for part in parts.iter() {
+        // length-prefixed, so that distinct parts cannot produce the same input
+        context.update(&(part.len() as u64).to_be_bytes());
+        context.update(part);
+    }
+    let mut fp = [0u8; 32];
+    fp.copy_from_slice(context.finish().as_ref());
+    fp
+}
+
+/// Constraints of a certificate relevant for chain validation, as stored by
+/// [`VerificationCache`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CachedConstraints {
+    /// `true` if the basic constraints extension has `CA:true`, or if the certificate is
+    /// accepted as a CA by the [`V1CaPolicy`] of the cache
+    pub is_ca: bool,
+    /// The path length constraint from the basic constraints extension
+    pub path_len_constraint: Option<u32>,
+    /// The `keyCertSign` bit of the key usage extension, or `None` if the extension is absent
+    pub key_cert_sign: Option<bool>,
+    /// `false` if the basic constraints or key usage extensions are invalid or duplicated
+    pub extensions_valid: bool,
+}
+
+impl CachedConstraints {
+    fn new(cert: &X509Certificate, v1_ca_policy: V1CaPolicy) -> Self {
+        let basic_constraints = cert.basic_constraints();
+        let key_usage = cert.key_usage();
+        let extensions_valid = basic_constraints.is_ok() && key_usage.is_ok();
+        let basic_constraints = basic_constraints.unwrap_or(None).map(|ext| ext.value);
+        CachedConstraints {
+            is_ca: cert.assume_v1_ca(v1_ca_policy),
+            path_len_constraint: basic_constraints.and_then(|bc| bc.path_len_constraint),
+            key_cert_sign: key_usage
+                .unwrap_or(None)
+                .map(|ext| ext.value.key_cert_sign()),
+            extensions_valid,
+        }
+    }
+}
+
+/// A cache of signature verification and constraint results, for repeated chain validations
+///
+/// Servers validating many chains usually see the same intermediate certificates again and
+/// again. This object memoizes the results of the expensive checks, keyed by the SHA-256
+/// fingerprints of the signed content of the certificate (the TBS certificate, signature
+/// algorithm and signature value) and of the issuer public key, for signatures, so they are
+/// computed only once per certificate.
+///
+/// The cache is not bounded: use [`clear`](Self::clear) to drop the stored results, for ex.
+/// periodically or when the trust configuration changes. Wrap it in a `Mutex` to share it
+/// between threads.
+///
+/// ```rust
+/// use x509_parser::prelude::*;
+/// use x509_parser::verify::VerificationCache;
+///
+/// # static LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+/// # static CA: &[u8] = include_bytes!("../assets/test-ca.der");
+/// let (_, leaf) = parse_x509_certificate(LEAF).unwrap();
+/// let (_, ca) = parse_x509_certificate(CA).unwrap();
+/// let mut cache = VerificationCache::new();
+/// // the second call is answered from the cache
+/// assert!(cache.verify_signature(&leaf, ca.public_key()).is_ok());
+/// assert!(cache.verify_signature(&leaf, ca.public_key()).is_ok());
+/// assert_eq!(cache.hits(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct VerificationCache {
+    signatures: HashMap<(Fingerprint, Fingerprint), Result<(), X509Error>>,
+    constraints: HashMap<Fingerprint, CachedConstraints>,
+    v1_ca_policy: V1CaPolicy,
+    hits: usize,
+    misses: usize,
+}
+
+impl VerificationCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how v1 and v2 certificates are handled, when computing [`CachedConstraints::is_ca`]
+    ///
+    /// The default is [`V1CaPolicy::Never`]. The policy must be set before computing constraints.
+    pub fn with_v1_ca_policy(self, v1_ca_policy: V1CaPolicy) -> Self {
+        VerificationCache {
+            v1_ca_policy,
+            ..self
+        }
+    }
+
+    /// Verify the signature of `cert` using the issuer key `public_key`, or return the cached
+    /// result
+    ///
+    /// See [`X509Certificate::verify_signature`].
+    pub fn verify_signature(
+        &mut self,
+        cert: &X509Certificate,
+        public_key: &SubjectPublicKeyInfo,
+    ) -> Result<(), X509Error> {
+        let key = (signed_fingerprint(cert), fingerprint(public_key.raw));
+        if let Some(res) = self.signatures.get(&key) {
+            self.hits += 1;
+            return res.clone();
+        }
+        self.misses += 1;
+        let res = cert.verify_signature(Some(public_key));
+        self.signatures.insert(key, res.clone());
+        res
+    }
+
+    /// Verify the signatures of a chain ordered from the leaf to the root, using the cache
+    ///
+    /// Each certificate is verified using the public key of the next one. The last certificate
+    /// is verified using its own key if it is self-issued, and is not verified otherwise.
+    ///
+    /// The error contains the index of the certificate that could not be verified, and the
+    /// expected (issuer of the certificate) and found (subject of the next certificate) issuer
+    /// names.
+    #[allow(clippy::result_large_err)]
+    pub fn verify_chain_signatures(
+        &mut self,
+        chain: &[X509Certificate],
+    ) -> Result<(), X509ErrorContext> {
+        for (idx, cert) in chain.iter().enumerate() {
+            let issuer = match chain.get(idx + 1) {
+                Some(issuer) => issuer,
+                None if cert.subject().as_raw() == cert.issuer().as_raw() => cert,
+                None => continue,
+            };
+            self.verify_signature(cert, issuer.public_key())
+                .map_err(|e| {
+                    X509ErrorContext::new(e)
+                        .with_chain_index(idx)
+                        .with_expected_found(cert.issuer(), issuer.subject())
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Return the constraints of `cert`, from the cache if already computed
+    pub fn constraints(&mut self, cert: &X509Certificate) -> CachedConstraints {
+        let key = signed_fingerprint(cert);
+        if let Some(constraints) = self.constraints.get(&key) {
+            self.hits += 1;
+            return constraints.clone();
+        }
+        self.misses += 1;
+        let constraints = CachedConstraints::new(cert, self.v1_ca_policy);
+        self.constraints.insert(key, constraints.clone());
+        constraints
+    }
+
+    /// Return the number of results answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Return the number of results which had to be computed
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Return the number of stored results
+    pub fn len(&self) -> usize {
+        self.signatures.len() + self.constraints.len()
+    }
+
+    /// Return `true` if no result is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all stored results, and reset the statistics
+    ///
+    /// The v1 CA policy is kept.
+    pub fn clear(&mut self) {
+        *self = Self::new().with_v1_ca_policy(self.v1_ca_policy);
+    }
+}
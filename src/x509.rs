@@ -6,22 +6,27 @@
 use crate::error::{X509Error, X509Result};
 use crate::objects::*;
 use crate::public_key::*;
+use crate::utils::{constant_time_eq, many0_lazy};
 
-use asn1_rs::{Any, BitString, DerSequence, FromBer, FromDer, Oid, OptTaggedParser, ParseResult};
+use asn1_rs::{
+    oid, Any, BitString, DerSequence, FromBer, FromDer, Oid, OptTaggedParser, ParseResult,
+};
 use data_encoding::HEXUPPER;
 use der_parser::ber::MAX_OBJECT_SIZE;
 use der_parser::der::*;
 use der_parser::error::*;
-use der_parser::num_bigint::BigUint;
 use der_parser::*;
 use nom::branch::alt;
 use nom::bytes::complete::take;
 use nom::combinator::{complete, map};
-use nom::multi::{many0, many1};
+use nom::multi::many1;
 use nom::{Err, Offset};
 use oid_registry::*;
 use rusticata_macros::newtype_enum;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 
 /// The version of the encoded certificate.
@@ -92,6 +97,28 @@ impl<'a> AttributeTypeAndValue<'a> {
         &self.attr_value
     }
 
+    /// Compare two attributes, using the name comparison rules of RFC5280 section 7.1
+    ///
+    /// Attribute types must be equal. String values are compared after conversion to UTF-8,
+    /// ASCII case folding and whitespace normalization, so for ex. a `PrintableString` and a
+    /// `UTF8String` can match. Other values are compared byte-by-byte.
+    pub fn eq_normalized(&self, other: &AttributeTypeAndValue) -> bool {
+        if self.attr_type != other.attr_type {
+            return false;
+        }
+        match (
+            attribute_value_canonical(&self.attr_value),
+            attribute_value_canonical(&other.attr_value),
+        ) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                self.attr_value.tag() == other.attr_value.tag()
+                    && self.attr_value.data == other.attr_value.data
+            }
+            _ => false,
+        }
+    }
+
     /// Attempt to get the content as `str`.
     /// This can fail if the object does not contain a string type.
     ///
@@ -113,11 +140,100 @@ impl<'a> AttributeTypeAndValue<'a> {
         }
     }
 
+    /// Get the content as a displayable string, replacing or converting invalid data.
+    ///
+    /// Contrary to [`as_str`](Self::as_str), this function never fails:
+    /// - UTF-8 compatible strings are returned without copy (invalid sequences are replaced)
+    /// - BMPString and UniversalString are decoded from UCS-2 and UCS-4
+    /// - TeletexString is decoded as Latin-1
+    /// - other types are hex-encoded
+    pub fn as_str_lossy(&self) -> Cow<'a, str> {
+        let data = self.attr_value.data;
+        match self.attr_value.tag() {
+            Tag::NumericString
+            | Tag::VisibleString
+            | Tag::PrintableString
+            | Tag::GeneralString
+            | Tag::ObjectDescriptor
+            | Tag::GraphicString
+            | Tag::Utf8String
+            | Tag::Ia5String => String::from_utf8_lossy(data),
+            Tag::T61String | Tag::VideotexString => data.iter().map(|&b| b as char).collect(),
+            Tag::BmpString => data
+                .chunks(2)
+                .map(|c| match c {
+                    [a, b] => u16::from_be_bytes([*a, *b]),
+                    _ => 0xfffd,
+                })
+                .map(|c| char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+            Tag::UniversalString => data
+                .chunks(4)
+                .map(|c| match c {
+                    [a, b, c, d] => u32::from_be_bytes([*a, *b, *c, *d]),
+                    _ => 0xfffd,
+                })
+                .map(|c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+            _ => Cow::Owned(HEXUPPER.encode(self.attr_value.as_bytes())),
+        }
+    }
+
     /// Get the content as a slice.
     #[inline]
     pub fn as_slice(&'a self) -> &'a [u8] {
         self.attr_value.as_bytes()
     }
+
+    /// Returns the attribute value, typed according to its encoding.
+    ///
+    /// This allows matching on the string type (for ex. `DirectoryString` variants) without
+    /// depending on `asn1-rs` types.
+    /// Values with an unknown type, or with an invalid encoding, are returned as
+    /// [`AttributeValue::Raw`].
+    pub fn typed_value(&self) -> AttributeValue<'a> {
+        let data = self.attr_value.data;
+        let utf8 = || core::str::from_utf8(data).ok();
+        let value = match self.attr_value.tag() {
+            Tag::PrintableString => utf8().map(AttributeValue::PrintableString),
+            Tag::Utf8String => utf8().map(AttributeValue::Utf8String),
+            Tag::Ia5String => utf8().map(AttributeValue::Ia5String),
+            Tag::BmpString => Some(AttributeValue::BmpString(data)),
+            Tag::T61String => Some(AttributeValue::TeletexString(data)),
+            _ => None,
+        };
+        value.unwrap_or_else(|| AttributeValue::Raw(self.attr_value.clone()))
+    }
+}
+
+/// A typed attribute value
+///
+/// Most attribute values in a X.509 Name are a `DirectoryString`:
+///
+/// <pre>
+/// DirectoryString ::= CHOICE {
+///       teletexString           TeletexString (SIZE (1..MAX)),
+///       printableString         PrintableString (SIZE (1..MAX)),
+///       universalString         UniversalString (SIZE (1..MAX)),
+///       utf8String              UTF8String (SIZE (1..MAX)),
+///       bmpString               BMPString (SIZE (1..MAX)) }
+/// </pre>
+///
+/// Other types (for ex. `IA5String` for email addresses or domain components) are also used.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeValue<'a> {
+    /// PrintableString (restricted ASCII subset: letters, digits, space and `'()+,-./:=?`)
+    PrintableString(&'a str),
+    /// UTF8String
+    Utf8String(&'a str),
+    /// IA5String (ASCII), used for ex. for email addresses and domain components
+    Ia5String(&'a str),
+    /// BMPString, raw bytes (encoded as UCS-2 big-endian)
+    BmpString(&'a [u8]),
+    /// TeletexString (T61String), raw bytes
+    TeletexString(&'a [u8]),
+    /// Any other type, or a string with an invalid encoding
+    Raw(Any<'a>),
 }
 
 impl<'a, 'b> core::convert::TryFrom<&'a AttributeTypeAndValue<'b>> for &'a str {
@@ -158,7 +274,7 @@ fn parse_malformed_string(i: &[u8]) -> ParseResult<Any, Error> {
     let (rem, hdr) = Header::from_der(i)?;
     let len = hdr.length().definite()?;
     if len > MAX_OBJECT_SIZE {
-        return Err(nom::Err::Error(Error::InvalidLength));
+        return Err(Err::Error(Error::InvalidLength));
     }
     match hdr.tag() {
         Tag::PrintableString => {
@@ -171,7 +287,7 @@ fn parse_malformed_string(i: &[u8]) -> ParseResult<Any, Error> {
             let obj = Any::new(hdr, data);
             Ok((rem, obj))
         }
-        t => Err(nom::Err::Error(Error::unexpected_tag(
+        t => Err(Err::Error(Error::unexpected_tag(
             Some(Tag::PrintableString),
             t,
         ))),
@@ -197,6 +313,23 @@ impl<'a> RelativeDistinguishedName<'a> {
     pub fn iter(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
         self.set.iter()
     }
+
+    /// Compare two RDNs, using the name comparison rules of RFC5280 section 7.1
+    ///
+    /// RDNs match if they have the same number of attributes, and each attribute matches an
+    /// attribute of the other RDN (see [`AttributeTypeAndValue::eq_normalized`]). The order of
+    /// attributes in multi-valued RDNs is not significant.
+    pub fn eq_normalized(&self, other: &RelativeDistinguishedName) -> bool {
+        self.set.len() == other.set.len()
+            && self
+                .set
+                .iter()
+                .all(|a| other.set.iter().any(|b| a.eq_normalized(b)))
+            && other
+                .set
+                .iter()
+                .all(|b| self.set.iter().any(|a| a.eq_normalized(b)))
+    }
 }
 
 impl<'a> FromIterator<AttributeTypeAndValue<'a>> for RelativeDistinguishedName<'a> {
@@ -208,6 +341,7 @@ impl<'a> FromIterator<AttributeTypeAndValue<'a>> for RelativeDistinguishedName<'
 
 impl<'a> FromDer<'a, X509Error> for RelativeDistinguishedName<'a> {
     fn from_der(i: &'a [u8]) -> X509Result<Self> {
+        trace_field!("relativeDistinguishedName", i);
         parse_der_set_defined_g(|i, _| {
             let (i, set) = many1(complete(AttributeTypeAndValue::from_der))(i)?;
             let rdn = RelativeDistinguishedName { set };
@@ -216,7 +350,13 @@ impl<'a> FromDer<'a, X509Error> for RelativeDistinguishedName<'a> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Public key information: algorithm and encoded key
+///
+/// Two `SubjectPublicKeyInfo` objects are equal if they have the same algorithm, parameters and
+/// key, regardless of the exact encoding. For ex., absent and `NULL` parameters (which are both
+/// used for RSA keys) are considered equal. `Hash` is consistent with this equality, so these
+/// objects can be used as keys in maps (for ex. to detect key reuse).
+#[derive(Clone, Debug)]
 pub struct SubjectPublicKeyInfo<'a> {
     pub algorithm: AlgorithmIdentifier<'a>,
     pub subject_public_key: BitString<'a>,
@@ -253,6 +393,34 @@ impl<'a> SubjectPublicKeyInfo<'a> {
             Ok(PublicKey::Unknown(b))
         }
     }
+
+    /// Return the algorithm parameters as (tag, content), or `None` if absent or `NULL`
+    fn normalized_parameters(&self) -> Option<(u32, &[u8])> {
+        match &self.algorithm.parameters {
+            Some(any) if any.tag() == Tag::Null && any.data.is_empty() => None,
+            Some(any) => Some((any.tag().0, any.data)),
+            None => None,
+        }
+    }
+}
+
+impl<'a> PartialEq for SubjectPublicKeyInfo<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm.algorithm == other.algorithm.algorithm
+            && self.normalized_parameters() == other.normalized_parameters()
+            && self.subject_public_key == other.subject_public_key
+    }
+}
+
+impl<'a> Eq for SubjectPublicKeyInfo<'a> {}
+
+impl<'a> Hash for SubjectPublicKeyInfo<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.algorithm.algorithm.hash(state);
+        self.normalized_parameters().hash(state);
+        self.subject_public_key.unused_bits.hash(state);
+        self.subject_public_key.data.hash(state);
+    }
 }
 
 impl<'a> FromDer<'a, X509Error> for SubjectPublicKeyInfo<'a> {
@@ -274,6 +442,48 @@ impl<'a> FromDer<'a, X509Error> for SubjectPublicKeyInfo<'a> {
     }
 }
 
+/// A `SubjectPublicKeyInfo` stored as raw DER, which can be borrowed or owned
+///
+/// This allows keeping the public key of a certificate without keeping the certificate buffer.
+/// Use [`Self::to_spki`] to get the parsed object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CowSubjectPublicKeyInfo<'a> {
+    raw: Cow<'a, [u8]>,
+}
+
+impl<'a> CowSubjectPublicKeyInfo<'a> {
+    /// Return the raw DER encoding of the `SubjectPublicKeyInfo`
+    pub fn as_raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Convert to an object owning its data, detached from the input buffer
+    pub fn into_owned(self) -> CowSubjectPublicKeyInfo<'static> {
+        CowSubjectPublicKeyInfo {
+            raw: Cow::Owned(self.raw.into_owned()),
+        }
+    }
+
+    /// Parse the stored `SubjectPublicKeyInfo`
+    pub fn to_spki(&self) -> Result<SubjectPublicKeyInfo<'_>, X509Error> {
+        SubjectPublicKeyInfo::from_der(&self.raw)
+            .map(|(_, spki)| spki)
+            .map_err(|_| X509Error::InvalidSPKI)
+    }
+}
+
+impl<'a> From<&SubjectPublicKeyInfo<'a>> for CowSubjectPublicKeyInfo<'a> {
+    fn from(spki: &SubjectPublicKeyInfo<'a>) -> Self {
+        CowSubjectPublicKeyInfo {
+            raw: Cow::Borrowed(spki.raw),
+        }
+    }
+}
+
+// RFC 8410 key agreement algorithms, not in the OID registry
+const OID_KEY_TYPE_X25519: Oid<'static> = oid!(1.3.101 .110);
+const OID_KEY_TYPE_X448: Oid<'static> = oid!(1.3.101 .111);
+
 /// Algorithm identifier
 ///
 /// An algorithm identifier is defined by the following ASN.1 structure:
@@ -315,6 +525,17 @@ impl<'a> AlgorithmIdentifier<'a> {
     pub const fn parameters(&'a self) -> Option<&'a Any> {
         self.parameters.as_ref()
     }
+
+    /// Return `true` if the parameters field is present while it is forbidden for this algorithm
+    ///
+    /// RFC 8410 requires the parameters to be absent for Ed25519, Ed448, X25519 and X448.
+    pub fn has_forbidden_parameters(&self) -> bool {
+        self.parameters.is_some()
+            && (self.algorithm == OID_SIG_ED25519
+                || self.algorithm == OID_SIG_ED448
+                || self.algorithm == OID_KEY_TYPE_X25519
+                || self.algorithm == OID_KEY_TYPE_X448)
+    }
 }
 
 /// X.509 Name (as used in `Issuer` and `Subject` fields)
@@ -331,7 +552,7 @@ pub struct X509Name<'a> {
 
 impl<'a> fmt::Display for X509Name<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match x509name_to_string(&self.rdn_seq, oid_registry()) {
+        match x509name_to_string(&self.rdn_seq, &HashMap::new(), oid_registry()) {
             Ok(o) => write!(f, "{}", o),
             Err(_) => write!(f, "<X509Error: Invalid X.509 name>"),
         }
@@ -350,7 +571,51 @@ impl<'a> X509Name<'a> {
     /// Note: a default registry is provided with this crate, and is returned by the
     /// [`oid_registry()`] method.
     pub fn to_string_with_registry(&self, oid_registry: &OidRegistry) -> Result<String, X509Error> {
-        x509name_to_string(&self.rdn_seq, oid_registry)
+        x509name_to_string(&self.rdn_seq, &HashMap::new(), oid_registry)
+    }
+
+    /// Attempt to format the current name, using the given abbreviations for attribute types.
+    ///
+    /// Abbreviations from `abbrevs` take precedence over the default ones (for ex. `CN`), and
+    /// the short names from the registry. This can be used to render attributes not known to
+    /// this crate, for ex. mapping `1.3.6.1.4.1.311.60.2.1.3` to `jurisdictionC`.
+    ///
+    /// Attribute types not found are rendered using their dotted-decimal representation.
+    pub fn to_string_with_abbrevs(
+        &self,
+        abbrevs: &HashMap<Oid, &str>,
+        oid_registry: &OidRegistry,
+    ) -> Result<String, X509Error> {
+        x509name_to_string(&self.rdn_seq, abbrevs, oid_registry)
+    }
+
+    /// Compute the hash of the name, compatible with `openssl x509 -subject_hash`
+    ///
+    /// This hash is used by OpenSSL to name the files of certificate directories (for ex.
+    /// `/etc/ssl/certs/<hash>.0`, created by `c_rehash`). The name is first canonicalized
+    /// (strings are converted to lowercase UTF-8, and whitespace is collapsed), then hashed with
+    /// SHA-1.
+    ///
+    /// Use `format!("{:08x}", hash)` to get the hexadecimal form used by OpenSSL.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+    pub fn openssl_hash(&self) -> u32 {
+        use sha1::{Digest, Sha1};
+        let digest = Sha1::digest(x509name_canonical_encoding(&self.rdn_seq));
+        u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+    }
+
+    /// Compute the hash of the name, compatible with `openssl x509 -subject_hash_old`
+    ///
+    /// This is the hash used by OpenSSL before version 1.0.0 (MD5 of the DER-encoded name).
+    ///
+    /// Use `format!("{:08x}", hash)` to get the hexadecimal form used by OpenSSL.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hash")))]
+    pub fn openssl_hash_old(&self) -> u32 {
+        use md5::{Digest, Md5};
+        let digest = Md5::digest(self.raw);
+        u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
     }
 
     // Not using the AsRef trait, as that would not give back the full 'a lifetime
@@ -358,6 +623,37 @@ impl<'a> X509Name<'a> {
         self.raw
     }
 
+    /// Compare the raw DER encoding of two names, in constant time
+    ///
+    /// Unlike `==`, the comparison time does not depend on the position of the first difference.
+    /// Note that names are compared byte-by-byte, without any normalization.
+    pub fn ct_eq(&self, other: &X509Name) -> bool {
+        constant_time_eq(self.raw, other.raw)
+    }
+
+    /// Compare two names, using the name comparison rules of RFC5280 section 7.1
+    ///
+    /// Names match if they have the same number of RDNs, and the RDNs match pairwise (see
+    /// [`RelativeDistinguishedName::eq_normalized`]). Unlike `==`, this ignores differences in
+    /// string types, ASCII case, whitespace and order of attributes in multi-valued RDNs.
+    pub fn eq_normalized(&self, other: &X509Name) -> bool {
+        self.rdn_seq.len() == other.rdn_seq.len() && self.starts_with(other)
+    }
+
+    /// Test if the first RDNs of this name match all the RDNs of `prefix`
+    ///
+    /// This is the X.501 subtree matching used for `directoryName` name constraints: a name is
+    /// inside the subtree of `prefix` if it starts with the same RDNs. RDNs are compared using
+    /// [`RelativeDistinguishedName::eq_normalized`]. An empty prefix matches all names.
+    pub fn starts_with(&self, prefix: &X509Name) -> bool {
+        prefix.rdn_seq.len() <= self.rdn_seq.len()
+            && self
+                .rdn_seq
+                .iter()
+                .zip(prefix.rdn_seq.iter())
+                .all(|(a, b)| a.eq_normalized(b))
+    }
+
     /// Return an iterator over the `RelativeDistinguishedName` components of the name
     pub fn iter(&self) -> impl Iterator<Item = &RelativeDistinguishedName<'a>> {
         self.rdn_seq.iter()
@@ -439,11 +735,74 @@ impl<'a> X509Name<'a> {
         self.iter_by_oid(&OID_X509_STATE_OR_PROVINCE_NAME)
     }
 
+    /// Return an iterator over the `StreetAddress` attributes of the X.509 Name.
+    pub fn iter_street_address(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_X509_STREET_ADDRESS)
+    }
+
     /// Return an iterator over the `Locality` attributes of the X.509 Name.
     pub fn iter_locality(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
         self.iter_by_oid(&OID_X509_LOCALITY_NAME)
     }
 
+    /// Return an iterator over the `SerialNumber` attributes of the X.509 Name.
+    ///
+    /// Note: this is the `serialNumber` attribute of the name (for ex. a device or person
+    /// identifier), not the serial number of the certificate.
+    pub fn iter_serial_number(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_X509_SERIALNUMBER)
+    }
+
+    /// Return an iterator over the `GivenName` attributes of the X.509 Name.
+    pub fn iter_given_name(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_X509_GIVEN_NAME)
+    }
+
+    /// Return an iterator over the `Surname` attributes of the X.509 Name.
+    pub fn iter_surname(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_X509_SURNAME)
+    }
+
+    /// Return an iterator over the `Title` attributes of the X.509 Name.
+    pub fn iter_title(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_X509_TITLE)
+    }
+
+    /// Return an iterator over the `DNQualifier` attributes of the X.509 Name.
+    pub fn iter_dn_qualifier(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_X509_DN_QUALIFIER)
+    }
+
+    /// Return an iterator over the `Pseudonym` attributes of the X.509 Name.
+    pub fn iter_pseudonym(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        // id-at-pseudonym, not present in oid-registry
+        self.iter_by_oid(&oid! {2.5.4.65})
+    }
+
+    /// Return an iterator over the `DomainComponent` attributes of the X.509 Name.
+    pub fn iter_domain_component(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
+        self.iter_by_oid(&OID_DOMAIN_COMPONENT)
+    }
+
+    /// Return the domain name built from the `DomainComponent` attributes of the X.509 Name.
+    ///
+    /// Components are encoded from the most significant to the least significant, so they
+    /// are joined in reverse order (for ex. `DC=com, DC=example, DC=corp` gives
+    /// `corp.example.com`).
+    ///
+    /// Returns `None` if there is no `DomainComponent`, or if a component is not a string.
+    pub fn dc_domain(&self) -> Option<String> {
+        let components = self
+            .iter_domain_component()
+            .map(|attr| attr.as_str().ok())
+            .collect::<Option<Vec<_>>>()?;
+        if components.is_empty() {
+            return None;
+        }
+        let labels: Vec<_> = components.into_iter().rev().collect();
+        Some(labels.join("."))
+    }
+
     /// Return an iterator over the `EmailAddress` attributes of the X.509 Name.
     pub fn iter_email(&self) -> impl Iterator<Item = &AttributeTypeAndValue<'a>> {
         self.iter_by_oid(&OID_PKCS9_EMAIL_ADDRESS)
@@ -463,12 +822,50 @@ impl<'a> From<X509Name<'a>> for Vec<RelativeDistinguishedName<'a>> {
     }
 }
 
+/// A `X509Name` stored as raw DER, which can be borrowed or owned
+///
+/// This allows keeping a name (for ex. the subject of a certificate) without keeping the
+/// certificate buffer. Use [`Self::to_name`] to get the parsed object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CowX509Name<'a> {
+    raw: Cow<'a, [u8]>,
+}
+
+impl<'a> CowX509Name<'a> {
+    /// Return the raw DER encoding of the name
+    pub fn as_raw(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Convert to an object owning its data, detached from the input buffer
+    pub fn into_owned(self) -> CowX509Name<'static> {
+        CowX509Name {
+            raw: Cow::Owned(self.raw.into_owned()),
+        }
+    }
+
+    /// Parse the stored name
+    pub fn to_name(&self) -> Result<X509Name<'_>, X509Error> {
+        X509Name::from_der(&self.raw)
+            .map(|(_, name)| name)
+            .map_err(|_| X509Error::InvalidX509Name)
+    }
+}
+
+impl<'a> From<&X509Name<'a>> for CowX509Name<'a> {
+    fn from(name: &X509Name<'a>) -> Self {
+        CowX509Name {
+            raw: Cow::Borrowed(name.raw),
+        }
+    }
+}
+
 impl<'a> FromDer<'a, X509Error> for X509Name<'a> {
     /// Parse the X.501 type Name, used for ex in issuer and subject of a X.509 certificate
     fn from_der(i: &'a [u8]) -> X509Result<Self> {
         let start_i = i;
         parse_der_sequence_defined_g(move |i, _| {
-            let (i, rdn_seq) = many0(complete(RelativeDistinguishedName::from_der))(i)?;
+            let (i, rdn_seq) = many0_lazy(complete(RelativeDistinguishedName::from_der))(i)?;
             let len = start_i.offset(i);
             let name = X509Name {
                 rdn_seq,
@@ -530,6 +927,81 @@ fn attribute_value_to_string(attr: &Any, _attr_type: &Oid) -> Result<String, X50
     }
 }
 
+/// Decode a string value of type `DirectoryString` or `DisplayText`
+///
+/// UTF8String, PrintableString, IA5String and VisibleString must be valid UTF-8,
+/// TeletexString is decoded as Latin-1 (as OpenSSL does), BMPString as UCS-2 and
+/// UniversalString as UCS-4. Returns `None` for other types or invalid contents.
+pub(crate) fn decode_directory_string(value: &Any) -> Option<String> {
+    let s = match value.tag() {
+        Tag::Utf8String | Tag::PrintableString | Tag::Ia5String | Tag::VisibleString => {
+            std::str::from_utf8(value.data).ok()?.to_owned()
+        }
+        Tag::T61String => value.data.iter().map(|&b| b as char).collect(),
+        Tag::BmpString => {
+            let v: Vec<u16> = value
+                .data
+                .chunks(2)
+                .map(|c| Some(u16::from_be_bytes([c[0], *c.get(1)?])))
+                .collect::<Option<_>>()?;
+            String::from_utf16(&v).ok()?
+        }
+        Tag::UniversalString => value
+            .data
+            .chunks(4)
+            .map(|c| match *c {
+                [a, b, c, d] => char::from_u32(u32::from_be_bytes([a, b, c, d])),
+                _ => None,
+            })
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    Some(s)
+}
+
+/// Convert a string attribute value to its canonical form, for name comparisons
+///
+/// The value is decoded to UTF-8, lowercased (ASCII only), leading and trailing whitespace is
+/// removed and internal whitespace is collapsed. Returns `None` if the value is not a string.
+fn attribute_value_canonical(value: &Any) -> Option<String> {
+    let s = decode_directory_string(value)?;
+    let words: Vec<_> = s
+        .split(|c: char| c.is_ascii_whitespace() || c == '\x0b')
+        .filter(|w| !w.is_empty())
+        .collect();
+    Some(words.join(" ").to_ascii_lowercase())
+}
+
+/// Build the canonical encoding of a name, as used by OpenSSL for name hashes
+///
+/// String attributes are converted to UTF8String, lowercased (ASCII only), leading and trailing
+/// whitespace is removed and internal whitespace is collapsed. The encoded RDNs are concatenated,
+/// without the enclosing SEQUENCE.
+#[cfg(feature = "hash")]
+fn x509name_canonical_encoding(rdn_seq: &[RelativeDistinguishedName]) -> Vec<u8> {
+    use crate::utils::der_tlv;
+    use asn1_rs::ToDer;
+
+    let mut out = Vec::new();
+    for rdn in rdn_seq {
+        let mut entries: Vec<Vec<u8>> = rdn
+            .iter()
+            .map(|attr| {
+                let mut content = attr.attr_type().to_der_vec().unwrap_or_default();
+                match attribute_value_canonical(attr.attr_value()) {
+                    Some(s) => content.extend(der_tlv(0x0c, s.as_bytes())),
+                    None => content.extend(attr.attr_value().to_der_vec().unwrap_or_default()),
+                }
+                der_tlv(0x30, &content)
+            })
+            .collect();
+        // DER: elements of SET OF are sorted
+        entries.sort();
+        out.extend(der_tlv(0x31, &entries.concat()));
+    }
+    out
+}
+
 /// Convert a DER representation of a X.509 name to a human-readable string
 ///
 /// RDNs are separated with ","
@@ -538,6 +1010,7 @@ fn attribute_value_to_string(attr: &Any, _attr_type: &Oid) -> Result<String, X50
 /// Attributes that cannot be represented by a string are hex-encoded
 fn x509name_to_string(
     rdn_seq: &[RelativeDistinguishedName],
+    abbrevs: &HashMap<Oid, &str>,
     oid_registry: &OidRegistry,
 ) -> Result<String, X509Error> {
     rdn_seq.iter().fold(Ok(String::new()), |acc, rdn| {
@@ -548,9 +1021,9 @@ fn x509name_to_string(
                     acc2.and_then(|mut _vec2| {
                         let val_str = attribute_value_to_string(&attr.attr_value, &attr.attr_type)?;
                         // look ABBREV, and if not found, use shortname
-                        let abbrev = match oid2abbrev(&attr.attr_type, oid_registry) {
+                        let abbrev = match oid2abbrev_with(&attr.attr_type, abbrevs, oid_registry) {
                             Ok(s) => String::from(s),
-                            _ => format!("{:?}", attr.attr_type),
+                            _ => attr.attr_type.to_id_string(),
                         };
                         let rdn = format!("{}={}", abbrev, val_str);
                         match _vec2.len() {
@@ -571,7 +1044,7 @@ pub(crate) fn parse_signature_value(i: &[u8]) -> X509Result<BitString> {
     BitString::from_der(i).or(Err(Err::Error(X509Error::InvalidSignatureValue)))
 }
 
-pub(crate) fn parse_serial(i: &[u8]) -> X509Result<(&[u8], BigUint)> {
+pub(crate) fn parse_serial(i: &[u8]) -> X509Result<&[u8]> {
     let (rem, any) = Any::from_ber(i).map_err(|_| X509Error::InvalidSerial)?;
     // RFC 5280 4.1.2.2: "The serial number MUST be a positive integer"
     // however, many CAs do not respect this and send integers with MSB set,
@@ -579,15 +1052,66 @@ pub(crate) fn parse_serial(i: &[u8]) -> X509Result<(&[u8], BigUint)> {
     any.tag()
         .assert_eq(Tag::Integer)
         .map_err(|_| X509Error::InvalidSerial)?;
-    let slice = any.data;
-    let big = BigUint::from_bytes_be(slice);
-    Ok((rem, (slice, big)))
+    Ok((rem, any.data))
+}
+
+/// Convert the raw bytes of a serial number to `u128`
+///
+/// Returns `None` if the value does not fit in 128 bits.
+pub(crate) fn serial_to_u128(raw_serial: &[u8]) -> Option<u128> {
+    let start = raw_serial
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(raw_serial.len());
+    let bytes = &raw_serial[start..];
+    if bytes.len() > 16 {
+        return None;
+    }
+    Some(bytes.iter().fold(0, |acc, &b| (acc << 8) | u128::from(b)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_spki_eq_hash() {
+        use std::collections::HashSet;
+
+        static IGCA_DER: &[u8] = include_bytes!("../assets/IGC_A.der");
+        static CERT_DER: &[u8] = include_bytes!("../assets/certificate.der");
+        let (_, x509) = crate::parse_x509_certificate(IGCA_DER).unwrap();
+        let spki = x509.public_key().clone();
+        assert!(spki.algorithm.parameters.is_some());
+        // absent parameters are equivalent to NULL
+        let mut spki_noparams = spki.clone();
+        spki_noparams.algorithm.parameters = None;
+        assert_eq!(spki, spki_noparams);
+        // different key
+        let (_, x509) = crate::parse_x509_certificate(CERT_DER).unwrap();
+        let other = x509.public_key().clone();
+        assert_ne!(spki, other);
+
+        let mut set = HashSet::new();
+        set.insert(spki);
+        assert!(set.contains(&spki_noparams));
+        assert!(!set.contains(&other));
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn test_x509name_openssl_hash() {
+        static IGCA_DER: &[u8] = include_bytes!("../assets/IGC_A.der");
+        static CERT_DER: &[u8] = include_bytes!("../assets/certificate.der");
+        // reference values from `openssl x509 -subject_hash -subject_hash_old`
+        let (_, x509) = crate::parse_x509_certificate(IGCA_DER).unwrap();
+        assert_eq!(x509.subject().openssl_hash(), 0x3ee7_e181);
+        assert_eq!(x509.subject().openssl_hash_old(), 0x58a4_4af1);
+        let (_, x509) = crate::parse_x509_certificate(CERT_DER).unwrap();
+        assert_eq!(x509.subject().openssl_hash(), 0x9ac2_c172);
+        assert_eq!(x509.subject().openssl_hash_old(), 0x7088_ba35);
+    }
+
     #[test]
     fn test_x509_version() {
         // correct version
@@ -611,6 +1135,114 @@ mod tests {
         assert!(r.is_err());
     }
 
+    #[test]
+    fn test_attribute_typed_value() {
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::PrintableString, b"Test"),
+        );
+        assert_eq!(attr.typed_value(), AttributeValue::PrintableString("Test"));
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::BmpString, b"\x00T"),
+        );
+        assert_eq!(attr.typed_value(), AttributeValue::BmpString(b"\x00T"));
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::Utf8String, b"\xff"),
+        );
+        assert!(matches!(attr.typed_value(), AttributeValue::Raw(_)));
+    }
+
+    #[test]
+    fn test_attribute_as_str_lossy() {
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::BmpString, b"\x00T\x00\xe9"),
+        );
+        assert!(attr.as_str().is_err());
+        assert_eq!(attr.as_str_lossy(), "T\u{e9}");
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::T61String, b"caf\xe9"),
+        );
+        assert_eq!(attr.as_str_lossy(), "caf\u{e9}");
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::UniversalString, b"\x00\x00\x00A"),
+        );
+        assert_eq!(attr.as_str_lossy(), "A");
+        let attr = AttributeTypeAndValue::new(
+            oid! {2.5.4.3},
+            Any::from_tag_and_data(Tag::OctetString, b"\x01\xab"),
+        );
+        assert_eq!(attr.as_str_lossy(), "01AB");
+    }
+
+    #[test]
+    fn test_decode_directory_string() {
+        let decode = |tag, data| decode_directory_string(&Any::from_tag_and_data(tag, data));
+        assert_eq!(
+            decode(Tag::Utf8String, b"caf\xc3\xa9"),
+            Some("caf\u{e9}".to_owned())
+        );
+        assert_eq!(
+            decode(Tag::T61String, b"caf\xe9"),
+            Some("caf\u{e9}".to_owned())
+        );
+        assert_eq!(
+            decode(Tag::BmpString, b"\x00T\x00\xe9"),
+            Some("T\u{e9}".to_owned())
+        );
+        assert_eq!(decode(Tag::BmpString, b"\x00T\x00"), None);
+        assert_eq!(
+            decode(Tag::UniversalString, b"\x00\x00\x00A"),
+            Some("A".to_owned())
+        );
+        assert_eq!(decode(Tag::PrintableString, b"\xe9"), None);
+        assert_eq!(decode(Tag::OctetString, b"A"), None);
+    }
+
+    #[test]
+    fn test_x509_name_dc_domain() {
+        let dc = |s: &'static [u8]| {
+            RelativeDistinguishedName::new(vec![AttributeTypeAndValue::new(
+                OID_DOMAIN_COMPONENT,
+                Any::from_tag_and_data(Tag::Ia5String, s),
+            )])
+        };
+        let name: X509Name = vec![dc(b"com"), dc(b"example"), dc(b"corp")]
+            .into_iter()
+            .collect();
+        assert_eq!(name.dc_domain(), Some("corp.example.com".to_string()));
+        let name: X509Name = vec![].into_iter().collect();
+        assert_eq!(name.dc_domain(), None);
+    }
+
+    #[test]
+    fn test_x509_name_to_string_with_abbrevs() {
+        let jurisdiction_c = oid!(1.3.6 .1 .4 .1 .311 .60 .2 .1 .3);
+        let name: X509Name = vec![
+            RelativeDistinguishedName::new(vec![AttributeTypeAndValue::new(
+                jurisdiction_c.clone(),
+                Any::from_tag_and_data(Tag::PrintableString, b"FR"),
+            )]),
+            RelativeDistinguishedName::new(vec![AttributeTypeAndValue::new(
+                oid!(1.2.3 .4),
+                Any::from_tag_and_data(Tag::Utf8String, b"Test"),
+            )]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(name.to_string(), "msJurisdictionCountry=FR, 1.2.3.4=Test");
+        let mut abbrevs = HashMap::new();
+        abbrevs.insert(jurisdiction_c, "jurisdictionC");
+        assert_eq!(
+            name.to_string_with_abbrevs(&abbrevs, oid_registry()),
+            Ok("jurisdictionC=FR, 1.2.3.4=Test".to_string())
+        );
+    }
+
     #[test]
     fn test_x509_name() {
         let name = X509Name {
@@ -655,5 +1287,11 @@ mod tests {
             name.to_string(),
             "C=FR, ST=Some-State, O=Internet Widgits Pty Ltd, CN=Test1 + CN=Test2"
         );
+        let st: Vec<_> = name
+            .iter_state_or_province()
+            .map(|attr| attr.as_str())
+            .collect();
+        assert_eq!(st, vec![Ok("Some-State")]);
+        assert_eq!(name.iter_street_address().count(), 0);
     }
 }
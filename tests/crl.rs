@@ -0,0 +1,388 @@
+use ::time::Duration;
+use std::convert::TryFrom;
+use x509_parser::prelude::*;
+
+const CA_DATA: &[u8] = include_bytes!("../assets/ca_minimalcrl.der");
+const CRL_DATA: &[u8] = include_bytes!("../assets/minimal.crl");
+const EXAMPLE_CRL: &[u8] = include_bytes!("../assets/example.crl");
+const EMPTY_CRL: &[u8] = include_bytes!("../assets/empty.crl");
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+const TEST_CA_CRL: &[u8] = include_bytes!("../assets/test-ca.crl");
+const TEST_CA_ZERO_CRL: &[u8] = include_bytes!("../assets/test-ca-zero.crl");
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+
+#[test]
+fn read_crl_is_issued_by() {
+    let (_, ca) = X509Certificate::from_der(TEST_CA).expect("could not parse certificate");
+    let (_, leaf) = X509Certificate::from_der(TEST_LEAF).expect("could not parse certificate");
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).expect("could not parse revocation list");
+    assert!(crl.authority_key_identifier().is_some());
+    assert!(crl.is_issued_by(&ca));
+    assert!(!crl.is_issued_by(&leaf));
+
+    // CRL without AKI, from another CA
+    let (_, x509_ca) = X509Certificate::from_der(CA_DATA).expect("could not parse certificate");
+    let (_, crl) = parse_x509_crl(CRL_DATA).expect("could not parse revocation list");
+    assert!(crl.is_issued_by(&x509_ca));
+    assert!(!crl.is_issued_by(&ca));
+}
+
+#[test]
+fn read_crl_freshness() {
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).expect("could not parse revocation list");
+    let this_update = crl.last_update();
+    let next_update = crl.next_update().expect("missing nextUpdate");
+    let one_day = Duration::days(1);
+    assert!(crl.is_fresh_at(this_update, one_day));
+    assert!(crl.is_fresh_at((this_update + one_day).unwrap(), one_day));
+    assert!(!crl.is_fresh_at((this_update + Duration::days(2)).unwrap(), one_day));
+    let before = ASN1Time::from_timestamp(this_update.timestamp() - 1).unwrap();
+    assert!(!crl.is_fresh_at(before, one_day));
+    assert!(!crl.is_fresh_at((next_update + one_day).unwrap(), Duration::MAX));
+    assert!(!crl.is_stale_at(next_update));
+    assert!(crl.is_stale_at((next_update + Duration::seconds(1)).unwrap()));
+
+    // no nextUpdate: stale with the default policy
+    let (_, crl) = parse_x509_crl(CRL_DATA).expect("could not parse revocation list");
+    assert!(crl.next_update().is_none());
+    assert!(crl.is_stale());
+    assert!(!crl.is_fresh_at(ASN1Time::now(), Duration::MAX));
+}
+
+#[test]
+fn read_crl_next_update_policy() {
+    let fresh = NextUpdatePolicy::TreatAsFresh;
+    let strict = NextUpdatePolicy::RequireNextUpdate;
+    assert_eq!(NextUpdatePolicy::default(), strict);
+    let one_day = Duration::days(1);
+
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).expect("could not parse revocation list");
+    let this_update = crl.last_update();
+    assert_eq!(crl.effective_next_update(), crl.next_update());
+    assert!(crl.is_fresh_at_with_policy(this_update, one_day, strict));
+    assert!(!crl.is_stale_at_with_policy(this_update, strict));
+
+    // no nextUpdate
+    let (_, crl) = parse_x509_crl(CRL_DATA).expect("could not parse revocation list");
+    assert!(crl.effective_next_update().is_none());
+    assert!(!crl.is_stale_with_policy(fresh));
+    assert!(crl.is_stale_with_policy(strict));
+    assert!(crl.is_fresh_at_with_policy(ASN1Time::now(), Duration::MAX, fresh));
+    assert!(!crl.is_fresh_at_with_policy(ASN1Time::now(), Duration::days(365), fresh));
+    assert!(!crl.is_fresh_at_with_policy(ASN1Time::now(), Duration::MAX, strict));
+
+    // zero-length CRL: nextUpdate is equal to thisUpdate
+    let (_, crl) = parse_x509_crl(TEST_CA_ZERO_CRL).expect("could not parse revocation list");
+    let this_update = crl.last_update();
+    assert_eq!(crl.next_update(), Some(this_update));
+    assert!(crl.effective_next_update().is_none());
+    let later = (this_update + one_day).unwrap();
+    assert!(crl.is_stale_at(later));
+    assert!(!crl.is_stale_at_with_policy(later, fresh));
+    assert!(crl.is_fresh_at_with_policy(later, Duration::days(2), fresh));
+    assert!(!crl.is_fresh_at_with_policy(later, Duration::days(2), strict));
+    assert!(!crl.is_fresh_at_with_policy(this_update, one_day, strict));
+}
+
+#[test]
+fn read_crl_is_newer_than() {
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).expect("could not parse revocation list");
+    let (_, old_crl) = parse_x509_crl(CRL_DATA).expect("could not parse revocation list");
+    // no CRL number in old_crl: compare thisUpdate
+    assert!(crl.is_newer_than(&old_crl));
+    assert!(!old_crl.is_newer_than(&crl));
+    assert!(!crl.is_newer_than(&crl));
+    assert_eq!(crl.crl_number().and_then(|n| n.as_u128()), Some(2));
+
+    // CRL numbers take precedence over thisUpdate
+    let (_, crl3) = parse_x509_crl(EXAMPLE_CRL).expect("could not parse revocation list");
+    let (_, crl2) = parse_x509_crl(EMPTY_CRL).expect("could not parse revocation list");
+    assert!(crl3.last_update() < crl2.last_update());
+    assert!(crl3.is_newer_than(&crl2));
+    assert!(!crl2.is_newer_than(&crl3));
+}
+
+#[test]
+fn read_crl_contains_raw_serial() {
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).expect("could not parse revocation list");
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).expect("could not parse certificate");
+    assert!(crl.contains_raw_serial(leaf.raw_serial()));
+    assert!(crl.contains_raw_serial(&[0x00, 0x10, 0x01]));
+    assert!(!crl.contains_raw_serial(&[0x10, 0x02]));
+    assert!(!crl.contains_raw_serial(&[]));
+    let revoked = crl
+        .find_revoked_raw_serial(&[0x10, 0x01])
+        .expect("serial not found");
+    assert_eq!(revoked.serial_u128(), Some(0x1001));
+}
+
+#[test]
+fn read_crl_owned() {
+    let (_, crl) = parse_x509_crl(EXAMPLE_CRL).unwrap();
+    assert_eq!(crl.as_raw(), EXAMPLE_CRL);
+    let owned = CertificateRevocationListOwned::new(EXAMPLE_CRL.to_vec()).unwrap();
+    assert_eq!(Ok(owned.clone()), crl.to_owned());
+    assert_eq!(
+        Ok(owned.clone()),
+        CertificateRevocationListOwned::try_from(&crl)
+    );
+    let parsed = owned.crl().unwrap();
+    assert_eq!(parsed.as_raw(), crl.as_raw());
+    assert_eq!(
+        parsed.tbs_cert_list.revoked_certificates.len(),
+        crl.tbs_cert_list.revoked_certificates.len()
+    );
+    // a CRL built from a separately parsed TbsCertList has no encoding
+    let (_, tbs) = TbsCertList::from_der(crl.tbs_cert_list.as_ref()).unwrap();
+    let rebuilt = CertificateRevocationList::from_parts(
+        tbs,
+        crl.signature_algorithm.clone(),
+        crl.signature_value.clone(),
+    );
+    assert!(rebuilt.as_raw().is_empty());
+    assert_eq!(rebuilt.to_owned(), Err(X509Error::MissingEncoding));
+}
+
+#[test]
+fn read_crl_freshest_crl() {
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-freshest.crl")).unwrap();
+    let points = crl.freshest_crl().expect("missing freshest CRL");
+    assert_eq!(points.urls(), ["http://pki.example.com/test-ca-delta.crl"]);
+    assert_eq!(
+        points.to_string(),
+        "URI:http://pki.example.com/test-ca-delta.crl"
+    );
+    // not present
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).unwrap();
+    assert!(crl.freshest_crl().is_none());
+}
+
+#[test]
+fn read_crl_issuing_distribution_point() {
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-idp.crl")).unwrap();
+    let idp = crl
+        .issuing_distribution_point()
+        .expect("missing issuing distribution point");
+    match &idp.distribution_point {
+        Some(DistributionPointName::FullName(names)) => {
+            assert_eq!(
+                names,
+                &[GeneralName::URI("http://pki.example.com/test-ca.crl")]
+            )
+        }
+        dp => panic!("unexpected distribution point {:?}", dp),
+    }
+    assert!(idp.only_contains_user_certs);
+    assert!(!idp.only_contains_ca_certs);
+    assert!(!idp.indirect_crl);
+    assert!(!idp.only_contains_attribute_certs);
+    let reasons = idp.only_some_reasons.as_ref().expect("missing reasons");
+    assert!(reasons.key_compromise() && reasons.ca_compromise());
+    assert!(!reasons.superseded());
+    assert!(idp.is_partitioned());
+    assert_eq!(
+        idp.to_string(),
+        "URI:http://pki.example.com/test-ca.crl, Only User Certificates, \
+         Only Some Reasons: Key Compromise, CA Compromise"
+    );
+    // not present
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).unwrap();
+    assert!(crl.issuing_distribution_point().is_none());
+}
+
+#[test]
+fn read_crl_covers_certificate() {
+    let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    // no IDP: complete CRL for the certificates of the issuer
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).unwrap();
+    assert!(crl_covers_certificate(&crl, &leaf));
+    // the leaf is not issued by the CRL issuer
+    let (_, crl) = parse_x509_crl(CRL_DATA).unwrap();
+    assert!(!crl_covers_certificate(&crl, &leaf));
+    // IDP matching the CRL distribution point, only user certificates
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-idp-user.crl")).unwrap();
+    assert!(crl_covers_certificate(&crl, &leaf));
+    // the CA certificate is self-signed, but is not a user certificate
+    assert!(!crl_covers_certificate(&crl, &ca));
+    // same IDP, covering only some revocation reasons
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-idp.crl")).unwrap();
+    assert!(!crl_covers_certificate(&crl, &leaf));
+    // IDP matching the CRL distribution point, only attribute certificates
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-idp-attr.crl")).unwrap();
+    assert!(!crl_covers_certificate(&crl, &leaf));
+    // delta CRL
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-delta.crl")).unwrap();
+    assert!(crl.is_delta_crl());
+    assert!(!crl_covers_certificate(&crl, &leaf));
+    let (_, crl) = parse_x509_crl(TEST_CA_CRL).unwrap();
+    assert!(!crl.is_delta_crl());
+    // IDP with another distribution point
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-indirect.crl")).unwrap();
+    assert!(!crl_covers_certificate(&crl, &leaf));
+}
+
+#[test]
+fn read_crl_certificate_issuer() {
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-indirect.crl")).unwrap();
+    let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+    assert!(crl.issuing_distribution_point().unwrap().indirect_crl);
+    let entries: Vec<_> = crl
+        .iter_revoked_certificates_with_issuer()
+        .map(|(revoked, issuer)| (revoked.raw_serial_as_string(), issuer.map(|n| n.to_vec())))
+        .collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0], ("10:01".to_string(), None));
+    // the issuer of the second entry also applies to the third entry
+    let other = entries[1].1.as_ref().expect("missing certificate issuer");
+    assert!(matches!(
+        &other[..],
+        [GeneralName::DirectoryName(dn)] if dn.to_string() == "O=x509-parser, CN=Other CA"
+    ));
+    assert_eq!(entries[2].1.as_ref(), Some(other));
+
+    let revoked: Vec<_> = crl.iter_revoked_certificates().collect();
+    let (critical, names) = revoked[1].certificate_issuer().expect("missing extension");
+    assert!(critical);
+    assert_eq!(names.len(), 1);
+    assert!(revoked[0].certificate_issuer().is_none());
+    assert!(revoked[2].certificate_issuer().is_none());
+
+    // only the first entry was issued by the CRL issuer
+    assert!(crl
+        .find_revoked_for_issuer(&[0x10, 0x01], ca.subject())
+        .is_some());
+    assert!(crl
+        .find_revoked_for_issuer(&[0x30, 0x01], ca.subject())
+        .is_none());
+    assert!(crl.find_revoked_raw_serial(&[0x30, 0x01]).is_some());
+    match &other[0] {
+        GeneralName::DirectoryName(dn) => {
+            assert!(crl.find_revoked_for_issuer(&[0x30, 0x02], dn).is_some());
+            assert!(crl.find_revoked_for_issuer(&[0x10, 0x01], dn).is_none());
+        }
+        _ => unreachable!(),
+    }
+    // issuer names are compared after normalization (here, PrintableString and upper case)
+    let name = &[
+        0x30, 0x29, 0x31, 0x14, 0x30, 0x12, 0x06, 0x03, 0x55, 0x04, 0x0a, 0x13, 0x0b, 0x58, 0x35,
+        0x30, 0x39, 0x2d, 0x50, 0x41, 0x52, 0x53, 0x45, 0x52, 0x31, 0x11, 0x30, 0x0f, 0x06, 0x03,
+        0x55, 0x04, 0x03, 0x13, 0x08, 0x4f, 0x54, 0x48, 0x45, 0x52, 0x20, 0x43, 0x41,
+    ];
+    let (_, name) = X509Name::from_der(name).expect("could not parse name");
+    assert!(crl.find_revoked_for_issuer(&[0x30, 0x02], &name).is_some());
+    assert!(crl.find_revoked_for_issuer(&[0x10, 0x01], &name).is_none());
+}
+
+#[test]
+fn read_crl_match_inventory() {
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-indirect.crl")).unwrap();
+    let index = crl.serial_index();
+    assert_eq!(index.len(), 3);
+    // leading zeros are ignored
+    assert_eq!(
+        index.find(&[0x00, 0x10, 0x01]).map(|r| r.raw_serial()),
+        Some(&[0x10, 0x01][..])
+    );
+    assert!(index.find(&[0x20, 0x01]).is_none());
+
+    let serials: Vec<&[u8]> = vec![&[0x20, 0x01], &[0x30, 0x02], &[0x10, 0x01]];
+    let revoked = crl.match_revoked_serials(&serials);
+    let positions: Vec<_> = revoked.iter().map(|(n, _)| *n).collect();
+    assert_eq!(positions, [1, 2]);
+    assert_eq!(revoked[0].1.raw_serial(), &[0x30, 0x02]);
+
+    // 30:01 is listed, but for certificates of another issuer
+    let ders: &[&[u8]] = &[
+        include_bytes!("../assets/test-sia.der"),
+        include_bytes!("../assets/test-must-staple.der"),
+        TEST_LEAF,
+    ];
+    let certs: Vec<_> = ders
+        .iter()
+        .map(|der| parse_x509_certificate(der).unwrap().1)
+        .collect();
+    let revoked = crl.match_revoked_certificates(&certs);
+    assert_eq!(revoked.len(), 1);
+    assert_eq!(revoked[0].0, 2);
+    let serials: Vec<_> = certs.iter().map(|cert| cert.raw_serial()).collect();
+    assert_eq!(crl.match_revoked_serials(serials).len(), 2);
+}
+
+#[test]
+fn read_crl_entry_extensions() {
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-hold.crl")).unwrap();
+    let entries: Vec<_> = crl
+        .iter_revoked_certificates()
+        .map(|revoked| {
+            revoked
+                .entry_extensions()
+                .expect("invalid entry extensions")
+        })
+        .collect();
+    assert_eq!(entries.len(), 3);
+
+    // legacy `2.2.840.10040.2` arc
+    assert_eq!(entries[0].reason_code, Some(ReasonCode::CertificateHold));
+    assert_eq!(entries[0].hold_instruction, Some(HoldInstruction::Reject));
+    assert!(entries[0].invalidity_date.is_none());
+    assert!(entries[0].certificate_issuer.is_none());
+    assert!(entries[0].other.is_empty());
+
+    assert_eq!(entries[1].reason_code, Some(ReasonCode::KeyCompromise));
+    assert_eq!(
+        entries[1].invalidity_date.map(|date| date.timestamp()),
+        Some(1_764_547_200)
+    );
+    assert!(entries[1].hold_instruction.is_none());
+
+    assert_eq!(
+        entries[2].hold_instruction,
+        Some(HoldInstruction::CallIssuer)
+    );
+    assert_eq!(entries[2].other.len(), 1);
+    assert!(entries[2].has_unknown_critical());
+    assert!(!entries[0].has_unknown_critical());
+
+    let revoked = crl.find_revoked_raw_serial(&[0x50, 0x03]).unwrap();
+    let (critical, hold) = revoked.hold_instruction().unwrap();
+    assert!(!critical);
+    assert_eq!(hold.oid(), OID_HOLD_INSTRUCTION_CALL_ISSUER);
+    assert_eq!(
+        revoked
+            .find_extension(&oid_registry::OID_X509_EXT_HOLD_INSTRUCTION_CODE)
+            .unwrap()
+            .parsed_extension()
+            .to_string(),
+        "Call Issuer"
+    );
+
+    // entries of indirect CRLs
+    let (_, crl) = parse_x509_crl(include_bytes!("../assets/test-ca-indirect.crl")).unwrap();
+    let revoked = crl.find_revoked_raw_serial(&[0x30, 0x01]).unwrap();
+    let entry = revoked.entry_extensions().unwrap();
+    assert_eq!(
+        entry.certificate_issuer,
+        revoked.certificate_issuer().map(|(_, names)| names)
+    );
+}
+
+#[test]
+fn read_crl_snapshot() {
+    use x509_parser::snapshot::RevocationListSnapshot;
+
+    let (_, crl) =
+        parse_x509_crl(include_bytes!("../assets/test-ca.crl")).expect("could not parse CRL");
+    let snapshot = RevocationListSnapshot(&crl).to_string();
+    let expected_entries = "  revoked:
+    - serial: 10:01
+      date: 2026-10-17T07:20:16Z
+      extensions:
+        - reasonCode (2.5.29.21): keyCompromise
+  extensions:
+    - crlNumber (2.5.29.20): 2
+";
+    assert!(snapshot.starts_with("crl\n  version: 2\n"));
+    assert!(snapshot.contains(expected_entries));
+    assert_eq!(snapshot, RevocationListSnapshot(&crl).to_string());
+}
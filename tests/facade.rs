@@ -0,0 +1,52 @@
+use x509_parser::error::PEMError;
+use x509_parser::facade::*;
+
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+const TEST_CA_CRL: &[u8] = include_bytes!("../assets/test-ca.crl");
+
+#[test]
+fn test_facade_certificate() {
+    let cert = Certificate::parse_der(TEST_LEAF).expect("could not parse certificate");
+    assert_eq!(cert.as_der(), TEST_LEAF);
+    assert_eq!(cert.subject(), "O=x509-parser, CN=leaf.example.com");
+    assert_eq!(cert.issuer(), "O=x509-parser, CN=Test CRL CA");
+    assert_eq!(cert.serial(), "10:01");
+    assert!(cert.not_before() < cert.not_after());
+    assert!(!cert.is_ca());
+    assert_eq!(cert.dns_names(), ["leaf.example.com"]);
+    assert_eq!(cert.parsed().version().0, 2);
+
+    let pem = include_str!("../assets/certificate.pem");
+    let cert = Certificate::parse_pem(pem).expect("could not parse PEM certificate");
+    assert_eq!(cert.as_der(), include_bytes!("../assets/certificate.der"));
+    // the label must match the object type
+    assert!(matches!(
+        Certificate::parse_pem(include_str!("../assets/crls.pem")),
+        Err(PEMError::UnexpectedLabel(_))
+    ));
+    assert!(Certificate::parse_der(&TEST_LEAF[..100]).is_err());
+}
+
+#[test]
+fn test_facade_crl() {
+    let crl = Crl::parse_der(TEST_CA_CRL).expect("could not parse CRL");
+    assert_eq!(crl.issuer(), "O=x509-parser, CN=Test CRL CA");
+    assert!(crl.next_update().expect("missing next update") > crl.this_update());
+    assert_eq!(crl.revoked_serials(), ["10:01"]);
+    let leaf = Certificate::parse_der(TEST_LEAF).unwrap();
+    assert!(crl.is_revoked(&leaf));
+    let other = Certificate::parse_der(include_bytes!("../assets/test-sia.der")).unwrap();
+    assert!(!crl.is_revoked(&other));
+
+    let crl = Crl::parse_pem(include_bytes!("../assets/crls.pem")).expect("could not parse CRL");
+    assert_eq!(crl.parsed().issuer().to_string(), crl.issuer());
+}
+
+#[test]
+fn test_facade_csr() {
+    let csr = Csr::parse_pem(include_str!("../assets/test.csr")).expect("could not parse CSR");
+    assert!(!csr.subject().is_empty());
+    let csr2 = Csr::parse_der(csr.as_der()).unwrap();
+    assert_eq!(csr, csr2);
+    assert!(Csr::parse_der([0x30, 0x00]).is_err());
+}
@@ -0,0 +1,291 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use x509_parser::fetch::*;
+use x509_parser::prelude::*;
+
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+const TEST_FINAL: &[u8] = include_bytes!("../assets/test-final.der");
+const TEST_CRL: &[u8] = include_bytes!("../assets/test-ca.crl");
+#[cfg(feature = "verify")]
+const TEST_ZERO_CRL: &[u8] = include_bytes!("../assets/test-ca-zero.crl");
+const LETSENCRYPT_X3: &[u8] = include_bytes!("../assets/lets-encrypt-x3-cross-signed.der");
+const CERTS_P7B: &[u8] = include_bytes!("../assets/certs.p7b");
+
+const CRL_URI: &str = "http://pki.example.com/test-ca.crl";
+
+// Serve static files, and record the requested URIs
+#[derive(Default)]
+struct StaticFetcher {
+    files: HashMap<&'static str, &'static [u8]>,
+    requests: RefCell<Vec<String>>,
+    policy: NextUpdatePolicy,
+}
+
+impl StaticFetcher {
+    fn get(&self, uri: &str) -> Result<Vec<u8>, String> {
+        self.requests.borrow_mut().push(uri.to_string());
+        self.files
+            .get(uri)
+            .map(|data| data.to_vec())
+            .ok_or_else(|| format!("not found: {}", uri))
+    }
+}
+
+impl ChainFetcher for StaticFetcher {
+    type Error = String;
+
+    fn fetch_certificates(&self, uri: &str) -> Result<Vec<u8>, String> {
+        self.get(uri)
+    }
+}
+
+impl RevocationProvider for StaticFetcher {
+    type Error = String;
+
+    fn fetch_crl(&self, uri: &str) -> Result<Vec<u8>, String> {
+        self.get(uri)
+    }
+
+    fn fetch_ocsp(&self, uri: &str, _request: &[u8]) -> Result<Vec<u8>, String> {
+        self.get(uri)
+    }
+
+    fn next_update_policy(&self) -> NextUpdatePolicy {
+        self.policy
+    }
+}
+
+#[test]
+fn test_fetch_uris() {
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    assert_eq!(crl_uris(&leaf), [CRL_URI]);
+    assert!(ca_issuers_uris(&leaf).is_empty());
+    let (_, x3) = parse_x509_certificate(LETSENCRYPT_X3).unwrap();
+    assert_eq!(
+        ca_issuers_uris(&x3),
+        ["http://apps.identrust.com/roots/dstrootcax3.p7c"]
+    );
+    assert_eq!(ocsp_uris(&x3), ["http://isrg.trustid.ocsp.identrust.com"]);
+}
+
+#[test]
+fn test_parse_fetched_certificates() {
+    let certs = parse_fetched_certificates(TEST_CA);
+    assert_eq!(certs.len(), 1);
+    assert_eq!(certs[0].as_raw(), TEST_CA);
+    assert!(parse_fetched_certificates(CERTS_P7B).len() > 1);
+    assert!(parse_fetched_certificates(b"not a certificate").is_empty());
+}
+
+#[test]
+fn test_fetch_missing_issuers() {
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let (_, x3) = parse_x509_certificate(LETSENCRYPT_X3).unwrap();
+    let mut fetcher = StaticFetcher::default();
+    // the leaf has no caIssuers location
+    let fetched = fetch_missing_issuers(&fetcher, &[leaf], 4).unwrap();
+    assert!(fetched.is_empty());
+    assert!(fetcher.requests.borrow().is_empty());
+    // fetch errors are propagated
+    let res = fetch_missing_issuers(&fetcher, std::slice::from_ref(&x3), 4);
+    assert!(res.is_err());
+    // unrelated certificates are ignored
+    fetcher
+        .files
+        .insert("http://apps.identrust.com/roots/dstrootcax3.p7c", CERTS_P7B);
+    let fetched = fetch_missing_issuers(&fetcher, &[x3], 4).unwrap();
+    assert!(fetched.is_empty());
+    assert_eq!(fetcher.requests.borrow().len(), 2);
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn test_check_revocation_crl() {
+    let (_, issuer) = parse_x509_certificate(TEST_CA).unwrap();
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let (_, other) = parse_x509_certificate(TEST_FINAL).unwrap();
+    let mut provider = StaticFetcher::default();
+    assert!(check_revocation(&provider, &leaf, &issuer).is_err());
+    provider.files.insert(CRL_URI, TEST_CRL);
+    match check_revocation(&provider, &leaf, &issuer) {
+        Ok(RevocationStatus::Revoked { revocation_time }) => {
+            assert!(revocation_time <= ASN1Time::now())
+        }
+        res => panic!("unexpected result {:?}", res),
+    }
+    assert_eq!(
+        check_revocation(&provider, &other, &issuer),
+        Ok(RevocationStatus::Good)
+    );
+    // a CRL from another issuer is ignored
+    assert_eq!(
+        check_revocation(&provider, &leaf, &leaf),
+        Ok(RevocationStatus::Unknown)
+    );
+    // a CRL for another distribution point is ignored
+    provider
+        .files
+        .insert(CRL_URI, include_bytes!("../assets/test-ca-indirect.crl"));
+    assert_eq!(
+        check_revocation(&provider, &leaf, &issuer),
+        Ok(RevocationStatus::Unknown)
+    );
+    // partial CRLs are ignored: only some reasons, only attribute certificates, delta CRL
+    let partial: [&'static [u8]; 3] = [
+        include_bytes!("../assets/test-ca-idp.crl"),
+        include_bytes!("../assets/test-ca-idp-attr.crl"),
+        include_bytes!("../assets/test-ca-delta.crl"),
+    ];
+    for crl in partial.iter() {
+        provider.files.insert(CRL_URI, crl);
+        assert_eq!(
+            check_revocation(&provider, &leaf, &issuer),
+            Ok(RevocationStatus::Unknown)
+        );
+    }
+    // the same CRL without the reasons is used
+    provider
+        .files
+        .insert(CRL_URI, include_bytes!("../assets/test-ca-idp-user.crl"));
+    assert!(matches!(
+        check_revocation(&provider, &leaf, &issuer),
+        Ok(RevocationStatus::Revoked { .. })
+    ));
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn test_check_revocation_next_update_policy() {
+    let (_, issuer) = parse_x509_certificate(TEST_CA).unwrap();
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let mut provider = StaticFetcher::default();
+    // nextUpdate is equal to thisUpdate
+    provider.files.insert(CRL_URI, TEST_ZERO_CRL);
+    provider.policy = NextUpdatePolicy::TreatAsFresh;
+    assert!(matches!(
+        check_revocation(&provider, &leaf, &issuer),
+        Ok(RevocationStatus::Revoked { .. })
+    ));
+    provider.policy = NextUpdatePolicy::RequireNextUpdate;
+    assert_eq!(
+        check_revocation(&provider, &leaf, &issuer),
+        Ok(RevocationStatus::Unknown)
+    );
+    // a CRL with a valid nextUpdate is not affected by the policy
+    provider.files.insert(CRL_URI, TEST_CRL);
+    assert!(matches!(
+        check_revocation(&provider, &leaf, &issuer),
+        Ok(RevocationStatus::Revoked { .. })
+    ));
+
+    // providers require a usable nextUpdate by default
+    struct DefaultPolicy<'a>(&'a StaticFetcher);
+    impl RevocationProvider for DefaultPolicy<'_> {
+        type Error = String;
+
+        fn fetch_crl(&self, uri: &str) -> Result<Vec<u8>, String> {
+            self.0.get(uri)
+        }
+
+        fn fetch_ocsp(&self, uri: &str, _request: &[u8]) -> Result<Vec<u8>, String> {
+            self.0.get(uri)
+        }
+    }
+    provider.files.insert(CRL_URI, TEST_ZERO_CRL);
+    assert_eq!(
+        check_revocation(&DefaultPolicy(&provider), &leaf, &issuer),
+        Ok(RevocationStatus::Unknown)
+    );
+}
+
+#[cfg(not(feature = "verify"))]
+#[test]
+fn test_check_revocation_unverified() {
+    let (_, issuer) = parse_x509_certificate(TEST_CA).unwrap();
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let (_, other) = parse_x509_certificate(TEST_FINAL).unwrap();
+    let mut provider = StaticFetcher::default();
+    provider.files.insert(CRL_URI, TEST_CRL);
+    // the CRL signature cannot be verified, so the CRL is not used
+    assert_eq!(
+        check_revocation(&provider, &leaf, &issuer),
+        Ok(RevocationStatus::Unknown)
+    );
+    assert_eq!(
+        check_revocation(&provider, &other, &issuer),
+        Ok(RevocationStatus::Unknown)
+    );
+    assert_eq!(*provider.requests.borrow(), vec![CRL_URI, CRL_URI]);
+}
+
+#[cfg(feature = "async")]
+mod async_tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    // The futures used in these tests never wait, so they can be polled without a runtime
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    struct AsyncStaticFetcher(HashMap<&'static str, &'static [u8]>);
+
+    #[async_trait]
+    impl AsyncRevocationProvider for AsyncStaticFetcher {
+        type Error = String;
+
+        async fn fetch_crl(&self, uri: &str) -> Result<Vec<u8>, String> {
+            self.0
+                .get(uri)
+                .map(|data| data.to_vec())
+                .ok_or_else(|| format!("not found: {}", uri))
+        }
+
+        async fn fetch_ocsp(&self, uri: &str, _request: &[u8]) -> Result<Vec<u8>, String> {
+            Err(format!("not found: {}", uri))
+        }
+    }
+
+    #[async_trait]
+    impl AsyncChainFetcher for AsyncStaticFetcher {
+        type Error = String;
+
+        async fn fetch_certificates(&self, uri: &str) -> Result<Vec<u8>, String> {
+            Err(format!("not found: {}", uri))
+        }
+    }
+
+    #[test]
+    fn test_check_revocation_async() {
+        let (_, issuer) = parse_x509_certificate(TEST_CA).unwrap();
+        let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+        let mut files = HashMap::new();
+        files.insert(CRL_URI, TEST_CRL);
+        let provider = AsyncStaticFetcher(files);
+        let status = block_on(check_revocation_async(&provider, &leaf, &issuer));
+        if cfg!(feature = "verify") {
+            assert!(matches!(status, Ok(RevocationStatus::Revoked { .. })));
+        } else {
+            assert_eq!(status, Ok(RevocationStatus::Unknown));
+        }
+        let fetched = block_on(fetch_missing_issuers_async(&provider, &[leaf], 4));
+        assert_eq!(fetched.map(|certs| certs.len()), Ok(0));
+    }
+}
@@ -0,0 +1,49 @@
+use x509_parser::hostname::*;
+use x509_parser::prelude::*;
+
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+const TEST_V1_LEAF: &[u8] = include_bytes!("../assets/test-v1-leaf.der");
+
+#[test]
+fn test_verify_hostname_san() {
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let policy = HostnamePolicy::default();
+    assert_eq!(verify_hostname(&x509, "leaf.example.com", &policy), Ok(()));
+    assert_eq!(verify_hostname(&x509, "LEAF.Example.com.", &policy), Ok(()));
+    assert_eq!(
+        verify_hostname(&x509, "example.com", &policy),
+        Err(X509Error::HostnameMismatch)
+    );
+    assert_eq!(
+        verify_hostname(&x509, "127.0.0.1", &policy),
+        Err(X509Error::HostnameMismatch)
+    );
+    assert_eq!(
+        verify_hostname(&x509, "bücher.example.com", &policy),
+        Err(X509Error::InvalidHostname)
+    );
+    assert_eq!(
+        verify_hostname(&x509, "leaf..example.com", &policy),
+        Err(X509Error::InvalidHostname)
+    );
+    // the CN is not used when the certificate has DNS names
+    let policy = policy.with_cn_fallback(true);
+    assert_eq!(verify_hostname(&x509, "leaf.example.com", &policy), Ok(()));
+}
+
+#[test]
+fn test_verify_hostname_cn_fallback() {
+    // v1 certificate, without subject alternative names
+    let (_, x509) = parse_x509_certificate(TEST_V1_LEAF).unwrap();
+    let policy = HostnamePolicy::new();
+    assert_eq!(
+        verify_hostname(&x509, "leaf.example.com", &policy),
+        Err(X509Error::HostnameMismatch)
+    );
+    let policy = policy.with_cn_fallback(true);
+    assert_eq!(verify_hostname(&x509, "leaf.example.com", &policy), Ok(()));
+    assert_eq!(
+        verify_hostname(&x509, "other.example.com", &policy),
+        Err(X509Error::HostnameMismatch)
+    );
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "hash")]
+
+use x509_parser::pinning::*;
+use x509_parser::prelude::*;
+
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+
+const CA_PIN: &str = "cbb9PGWcxSnl00Sr9zusb9RAFFXkIP3Upv5xkh4g20w=";
+const LEAF_PIN: &str = "igP4ByReySysPQbgvHZQupByLdi6i6X3dHMIoU3oqgI=";
+const BACKUP_PIN: &str = "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=";
+
+fn chain() -> Vec<X509Certificate<'static>> {
+    vec![
+        parse_x509_certificate(TEST_LEAF).unwrap().1,
+        parse_x509_certificate(TEST_CA).unwrap().1,
+    ]
+}
+
+#[test]
+fn test_pins_match_chain() {
+    let chain = chain();
+    // a pin can match any certificate of the chain
+    for pin in &[LEAF_PIN, CA_PIN] {
+        let pins = PinSet::from_base64(&[pin, BACKUP_PIN]).unwrap();
+        assert_eq!(pins.len(), 2);
+        assert_eq!(validate_chain_pins(&chain, &pins), Ok(()));
+    }
+    let pins = PinSet::from_base64(&[CA_PIN, BACKUP_PIN]).unwrap();
+    let found = pins.find_match(&chain).expect("no match");
+    assert_eq!(found.as_raw(), TEST_CA);
+    // pins can be added from keys
+    let mut pins = PinSet::from_base64(&[BACKUP_PIN]).unwrap();
+    pins.add_spki(chain[0].public_key());
+    assert!(pins.contains(chain[0].public_key()));
+    assert_eq!(validate_chain_pins(&chain, &pins), Ok(()));
+}
+
+#[test]
+fn test_pins_mismatch() {
+    let chain = chain();
+    let pins = PinSet::from_base64(&[BACKUP_PIN]).unwrap();
+    assert_eq!(
+        validate_chain_pins(&chain, &pins),
+        Err(X509Error::PinMismatch)
+    );
+}
+
+#[test]
+fn test_pins_backup() {
+    let chain = chain();
+    // all pins match the chain: no backup pin
+    let pins = PinSet::from_base64(&[LEAF_PIN, CA_PIN]).unwrap();
+    assert!(!pins.has_backup_pin(&chain));
+    assert_eq!(
+        validate_chain_pins(&chain, &pins),
+        Err(X509Error::MissingBackupPin)
+    );
+    // the same pin is not added twice
+    let pins = PinSet::from_base64(&[CA_PIN, CA_PIN]).unwrap();
+    assert_eq!(pins.len(), 1);
+    assert_eq!(
+        validate_chain_pins(&chain, &PinSet::new()),
+        Err(X509Error::MissingBackupPin)
+    );
+}
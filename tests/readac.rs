@@ -0,0 +1,95 @@
+use x509_parser::attribute_certificate::*;
+use x509_parser::prelude::*;
+
+const TEST_AC: &[u8] = include_bytes!("../assets/test-ac.der");
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+const TEST_FINAL: &[u8] = include_bytes!("../assets/test-final.der");
+
+#[test]
+fn read_attribute_certificate() {
+    let (rem, ac) = AttributeCertificate::from_der(TEST_AC).expect("could not parse AC");
+    assert!(rem.is_empty());
+    assert_eq!(ac.acinfo.version, X509Version::V2);
+    assert_eq!(ac.raw_serial_as_string(), "20:01");
+    match ac.issuer() {
+        AttCertIssuer::V2Form {
+            base_certificate_id,
+            ..
+        } => assert!(base_certificate_id.is_none()),
+        issuer => panic!("unexpected issuer {:?}", issuer),
+    }
+    assert!(matches!(
+        ac.issuer().names(),
+        [GeneralName::DirectoryName(dn)] if dn.to_string() == "O=x509-parser, CN=Test CRL CA"
+    ));
+    let validity = &ac.acinfo.validity;
+    assert!(ac.is_valid_at(validity.not_before));
+    assert!(!ac.is_valid_at(ASN1Time::from_timestamp(0).unwrap()));
+    assert_eq!(ac.attributes().len(), 2);
+    assert!(ac.extensions().is_empty());
+}
+
+#[test]
+fn read_attribute_certificate_roles_groups() {
+    let (_, ac) = AttributeCertificate::from_der(TEST_AC).expect("could not parse AC");
+    let roles = ac.roles().expect("invalid roles");
+    assert_eq!(roles.len(), 1);
+    assert!(roles[0].role_authority.is_none());
+    assert_eq!(roles[0].role_name, GeneralName::URI("urn:role:admin"));
+    assert!(ac.has_role("urn:role:admin"));
+    assert!(!ac.has_role("urn:role:user"));
+
+    let groups = ac.groups().expect("invalid groups");
+    assert_eq!(groups.len(), 1);
+    assert_eq!(
+        groups[0].policy_authority,
+        Some(vec![GeneralName::URI("http://pki.example.com")])
+    );
+    assert_eq!(
+        groups[0].values,
+        [
+            IetfAttrValue::String("operators"),
+            IetfAttrValue::String("auditors")
+        ]
+    );
+    assert!(ac.has_group("auditors"));
+    assert!(!ac.has_group("admins"));
+}
+
+#[test]
+fn read_attribute_certificate_holder() {
+    let (_, ac) = AttributeCertificate::from_der(TEST_AC).expect("could not parse AC");
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let (_, other) = parse_x509_certificate(TEST_FINAL).unwrap();
+    let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+    let holder = ac.holder();
+    let base = holder
+        .base_certificate_id
+        .as_ref()
+        .expect("no base cert ID");
+    assert_eq!(base.raw_serial, &[0x10, 0x01]);
+    assert!(base.matches(&leaf));
+    assert!(holder.matches(&leaf));
+    // different serial number
+    assert!(!holder.matches(&other));
+    assert!(!holder.matches(&ca));
+
+    // holder identified by entity name only
+    let holder = Holder {
+        base_certificate_id: None,
+        ..holder.clone()
+    };
+    assert!(holder.matches(&leaf));
+    assert!(!holder.matches(&ca));
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn read_attribute_certificate_verify() {
+    let (_, ac) = AttributeCertificate::from_der(TEST_AC).expect("could not parse AC");
+    let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    assert!(ac.verify_signature(ca.public_key()).is_ok());
+    assert!(ac.verify_signature(leaf.public_key()).is_err());
+}
@@ -4,6 +4,7 @@ use der_parser::oid;
 use nom::Parser;
 use oid_registry::*;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use x509_parser::prelude::*;
 
 static IGCA_DER: &[u8] = include_bytes!("../assets/IGC_A.der");
@@ -12,6 +13,11 @@ static V1: &[u8] = include_bytes!("../assets/v1.der");
 static CRL_DER: &[u8] = include_bytes!("../assets/example.crl");
 static EMPTY_CRL_DER: &[u8] = include_bytes!("../assets/empty.crl");
 static MINIMAL_CRL_DER: &[u8] = include_bytes!("../assets/minimal.crl");
+static ISSUER_ALT_NAME: &[u8] = include_bytes!("../assets/issuer-alt-name.der");
+static ED25519_SPKI_PARAMS: &[u8] = include_bytes!("../assets/ed25519-spki-params.der");
+static NTDS_CA_SECURITY: &[u8] = include_bytes!("../assets/ntds-ca-security.der");
+static TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+static TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
 static DUPLICATE_VALUE_IN_AIA: &[u8] =
     include_bytes!("../assets/duplicate_value_in_authority_info_access.der");
 
@@ -30,6 +36,7 @@ fn test_x509_parser() {
             //
             let s = tbs_cert.raw_serial_as_string();
             assert_eq!(&s, "39:11:45:10:94");
+            assert_eq!(tbs_cert.serial_u128(), Some(0x39_1145_1094));
             //
             let expected_subject = "C=FR, ST=France, L=Paris, O=PM/SGDN, OU=DCSSI, CN=IGC/A, Email=igca@sgdn.pm.gouv.fr";
             assert_eq!(format!("{}", tbs_cert.subject), expected_subject);
@@ -166,6 +173,12 @@ fn test_version_v1() {
     assert_eq!(format!("{}", tbs_cert.issuer), "CN=marquee");
 }
 
+// The parsed CRLNumber extension, from its DER encoding
+fn crl_number(der: &'static [u8]) -> ParsedExtension<'static> {
+    let (_, num) = CRLNumber::from_der(der).expect("invalid CRL number");
+    ParsedExtension::CRLNumber(num)
+}
+
 #[test]
 fn test_crl_parse() {
     match parse_x509_crl(CRL_DER) {
@@ -201,7 +214,9 @@ fn test_crl_parse() {
 
             let revoked_certs = &tbs_cert_list.revoked_certificates;
             let revoked_cert_0 = &revoked_certs[0];
+            #[cfg(feature = "bigint")]
             assert_eq!(*revoked_cert_0.serial(), 0x147947u32.into());
+            assert_eq!(revoked_cert_0.serial_u128(), Some(0x147947));
             assert_eq!(
                 revoked_cert_0.revocation_date.to_datetime(),
                 revocation_date
@@ -225,7 +240,9 @@ fn test_crl_parse() {
             assert_eq!(revoked_cert_0.extensions(), &expected_extensions as &[_]);
 
             assert_eq!(revoked_certs.len(), 5);
+            #[cfg(feature = "bigint")]
             assert_eq!(revoked_certs[4].user_certificate, 1_341_771_u32.into());
+            assert_eq!(revoked_certs[4].serial_u128(), Some(1_341_771));
 
             let expected_extensions = vec![
                 X509Extension::new(
@@ -244,12 +261,7 @@ fn test_crl_parse() {
                         authority_cert_serial: None,
                     }),
                 ),
-                X509Extension::new(
-                    oid!(2.5.29 .20),
-                    false,
-                    &[2, 1, 3],
-                    ParsedExtension::CRLNumber(3u32.into()),
-                ),
+                X509Extension::new(oid!(2.5.29 .20), false, &[2, 1, 3], crl_number(&[2, 1, 3])),
             ];
             assert_eq!(tbs_cert_list.extensions(), &expected_extensions as &[_]);
 
@@ -267,12 +279,7 @@ fn test_crl_parse_empty() {
             assert!(cert.tbs_cert_list.revoked_certificates.is_empty());
 
             let expected_extensions = vec![
-                X509Extension::new(
-                    oid!(2.5.29 .20),
-                    false,
-                    &[2, 1, 2],
-                    ParsedExtension::CRLNumber(2u32.into()),
-                ),
+                X509Extension::new(oid!(2.5.29 .20), false, &[2, 1, 2], crl_number(&[2, 1, 2])),
                 X509Extension::new(
                     OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER,
                     false,
@@ -309,7 +316,9 @@ fn test_crl_parse_minimal() {
             let revoked_certificates = &crl.tbs_cert_list.revoked_certificates;
             assert_eq!(revoked_certificates.len(), 1);
             let revoked_cert_0 = &revoked_certificates[0];
+            #[cfg(feature = "bigint")]
             assert_eq!(*revoked_cert_0.serial(), 42u32.into());
+            assert_eq!(revoked_cert_0.serial_u128(), Some(42));
             assert_eq!(
                 revoked_cert_0.revocation_date.to_datetime(),
                 revocation_date
@@ -358,3 +367,1028 @@ fn test_x509_parser_no_ext() {
         assert_eq!(ext.parsed_extension(), &ParsedExtension::Unparsed);
     }
 }
+
+#[test]
+fn test_x509_parser_strict() {
+    // valid certificate
+    let mut parser = X509CertificateParser::new().with_strict(true);
+    assert!(parser.parse(IGCA_DER).is_ok());
+    // Ed25519 key with NULL parameters
+    let (_, x509) = parse_x509_certificate(ED25519_SPKI_PARAMS).expect("parsing failed");
+    assert!(x509.public_key().algorithm.has_forbidden_parameters());
+    assert!(!x509.signature_algorithm.has_forbidden_parameters());
+    assert_eq!(
+        parser.parse(ED25519_SPKI_PARAMS),
+        Err(nom::Err::Error(X509Error::InvalidAlgorithmIdentifier))
+    );
+}
+
+#[test]
+fn test_x509_signature_input() {
+    let (_, x509) = parse_x509_certificate(IGCA_DER).expect("parsing failed");
+    let (signed_data, signature_value, signature_algorithm) = x509.signature_input();
+    assert_eq!(signed_data, x509.tbs_certificate.as_ref());
+    assert_eq!(signature_value.unused_bits, 0);
+    assert_eq!(signature_value.data.len(), 256);
+    assert_eq!(signature_algorithm.algorithm, OID_PKCS1_SHA1WITHRSA);
+}
+
+#[test]
+fn test_x509_cow_detach() {
+    let buffer = IGCA_DER.to_vec();
+    let (_, x509) = parse_x509_certificate(&buffer).expect("parsing failed");
+    let subject = CowX509Name::from(x509.subject()).into_owned();
+    let spki = CowSubjectPublicKeyInfo::from(x509.public_key()).into_owned();
+    let extensions: Vec<_> = x509
+        .extensions()
+        .iter()
+        .map(|ext| CowX509Extension::from(ext).into_owned())
+        .collect();
+    let expected_subject = x509.subject().to_string();
+    drop(x509);
+    drop(buffer);
+
+    assert_eq!(subject.to_name().unwrap().to_string(), expected_subject);
+    assert_eq!(
+        spki.to_spki().unwrap().algorithm.algorithm,
+        OID_PKCS1_RSAENCRYPTION
+    );
+    let bc = extensions
+        .iter()
+        .find(|ext| ext.oid == OID_X509_EXT_BASIC_CONSTRAINTS)
+        .expect("missing basic constraints");
+    let ext = bc.to_extension();
+    assert!(matches!(
+        ext.parsed_extension(),
+        ParsedExtension::BasicConstraints(bc) if bc.ca
+    ));
+}
+
+#[test]
+fn test_x509_issuer_alternative_name() {
+    let (_, x509) = parse_x509_certificate(ISSUER_ALT_NAME).expect("parsing failed");
+    let ian = x509
+        .issuer_alternative_name()
+        .expect("invalid extension")
+        .expect("missing extension");
+    assert!(!ian.critical);
+    assert_eq!(
+        ian.value.general_names,
+        vec![
+            GeneralName::URI("http://pki.example.com/ca"),
+            GeneralName::RFC822Name("ca@example.com"),
+        ]
+    );
+    let (_, x509) = parse_x509_certificate(IGCA_DER).expect("parsing failed");
+    assert_eq!(x509.issuer_alternative_name(), Ok(None));
+}
+
+#[test]
+fn test_x509_parsed_extension_display() {
+    let (_, x509) = parse_x509_certificate(ISSUER_ALT_NAME).expect("parsing failed");
+    let ext = x509
+        .get_extension_unique(&OID_X509_EXT_ISSUER_ALT_NAME)
+        .expect("invalid extensions")
+        .expect("missing extension");
+    assert_eq!(
+        ext.parsed_extension().to_string(),
+        "URI:http://pki.example.com/ca, email:ca@example.com"
+    );
+    let (_, x509) = parse_x509_certificate(IGCA_DER).expect("parsing failed");
+    let lines: Vec<_> = x509
+        .extensions()
+        .iter()
+        .map(|ext| ext.parsed_extension().to_string())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            "CA:TRUE",
+            "Non Repudiation, Key Cert Sign, CRL Sign",
+            "Policy: 1.2.250.1.121.1.1.1",
+            "a3:05:2f:18:60:50:c2:89:0a:dd:2b:21:4f:ff:8e:4e:a8:30:31:36",
+            "keyid:a3:05:2f:18:60:50:c2:89:0a:dd:2b:21:4f:ff:8e:4e:a8:30:31:36",
+        ]
+    );
+}
+
+#[test]
+fn test_x509_remaining_lifetime() {
+    let (_, x509) = parse_x509_certificate(IGCA_DER).expect("parsing failed");
+    let validity = x509.validity();
+    // 2002-12-13 14:29:23 -> 2020-10-17 14:29:22
+    assert_eq!(
+        x509.lifetime(),
+        Some(::time::Duration::days(6518) - ::time::Duration::seconds(1))
+    );
+    let time = ASN1Time::from(datetime!(2020-09-17 14:29:22 UTC));
+    assert!(!validity.expires_within_at(::time::Duration::days(29), time));
+    assert!(validity.expires_within_at(::time::Duration::days(30), time));
+    // certificate has expired
+    assert!(x509.expires_within(::time::Duration::ZERO));
+    assert_eq!(x509.days_remaining(), None);
+}
+
+#[test]
+fn test_x509_ntds_ca_security() {
+    let (_, x509) = parse_x509_certificate(NTDS_CA_SECURITY).expect("parsing failed");
+    let ext = x509
+        .ntds_ca_security()
+        .expect("invalid extension")
+        .expect("missing extension");
+    assert!(!ext.critical);
+    assert_eq!(
+        x509.subject_sid(),
+        Ok(Some("S-1-5-21-1004336348-1177238915-682003330-1001"))
+    );
+    let (_, x509) = parse_x509_certificate(IGCA_DER).expect("parsing failed");
+    assert_eq!(x509.subject_sid(), Ok(None));
+}
+
+#[test]
+fn test_x509_ms_certificate_template() {
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/ms-certificate-template.der"))
+        .expect("parsing failed");
+    let name = x509
+        .enroll_cert_type()
+        .expect("invalid extension")
+        .expect("missing extension");
+    assert_eq!(name.value, "WebServer");
+    let template = x509
+        .certificate_template()
+        .expect("invalid extension")
+        .expect("missing extension");
+    assert!(!template.critical);
+    assert_eq!(
+        template.value.template_id,
+        oid!(1.3.6 .1 .4 .1 .311 .21 .8 .1234567 .7654321 .100)
+    );
+    assert_eq!(template.value.major_version, Some(100));
+    assert_eq!(template.value.minor_version, Some(4));
+    assert_eq!(
+        template.value.to_string(),
+        "Template=1.3.6.1.4.1.311.21.8.1234567.7654321.100, \
+         Major Version Number=100, Minor Version Number=4"
+    );
+    let (_, x509) = parse_x509_certificate(NTDS_CA_SECURITY).expect("parsing failed");
+    assert_eq!(x509.enroll_cert_type(), Ok(None));
+    assert_eq!(x509.certificate_template(), Ok(None));
+}
+
+#[test]
+fn test_x509_certificate_kind() {
+    let kind = |der| parse_x509_certificate(der).unwrap().1.certificate_kind();
+    assert_eq!(kind(IGCA_DER), CertificateKind::Root);
+    assert_eq!(kind(TEST_CA), CertificateKind::Root);
+    assert_eq!(kind(TEST_LEAF), CertificateKind::Leaf(LeafKind::TlsServer));
+    assert_eq!(
+        kind(NTDS_CA_SECURITY),
+        CertificateKind::Leaf(LeafKind::TlsClient)
+    );
+    assert_eq!(kind(V1), CertificateKind::Root);
+    assert_eq!(
+        kind(NO_EXTENSIONS_DER),
+        CertificateKind::Leaf(LeafKind::Unknown)
+    );
+    // CA:true, but keyUsage does not contain keyCertSign
+    let der = include_bytes!("../assets/test-ca-no-certsign.der");
+    let (_, x509) = parse_x509_certificate(der).unwrap();
+    assert!(x509.is_ca());
+    assert_eq!(
+        x509.certificate_kind(),
+        CertificateKind::Leaf(LeafKind::Unknown)
+    );
+}
+
+#[test]
+fn test_parse_public_key_pem() {
+    static PUBKEY_PEM: &[u8] = include_bytes!("../assets/public-key.pem");
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).expect("could not parse certificate");
+    let (_, pem) = parse_x509_pem(PUBKEY_PEM).expect("could not parse PEM");
+    assert_eq!(pem.label, "PUBLIC KEY");
+    let spki = pem
+        .parse_subject_public_key_info()
+        .expect("could not parse public key");
+    assert_eq!(&spki, x509.public_key());
+    assert_eq!(spki.raw, &pem.contents[..]);
+
+    let (rem, spki) = parse_subject_public_key_info(&pem.contents).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(spki.algorithm.algorithm, OID_KEY_TYPE_EC_PUBLIC_KEY);
+
+    // skip the SEQUENCE header of the SubjectPublicKeyInfo
+    let (rem, alg) = parse_algorithm_identifier(&pem.contents[2..]).unwrap();
+    assert_eq!(rem[0], 0x03); // BIT STRING
+    assert_eq!(alg, spki.algorithm);
+    assert!(parse_algorithm_identifier(&[0x30, 0x00]).is_err());
+}
+
+#[test]
+fn test_x509_constant_time_eq() {
+    let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    assert!(leaf.issuer().ct_eq(ca.subject()));
+    assert!(!leaf.subject().ct_eq(ca.subject()));
+    assert!(leaf.raw_serial_ct_eq(leaf.raw_serial()));
+    assert!(!leaf.raw_serial_ct_eq(ca.raw_serial()));
+}
+
+#[test]
+fn test_x509_certificate_owned() {
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    assert_eq!(x509.as_raw(), TEST_LEAF);
+    let owned = x509.to_owned().unwrap();
+    assert_eq!(Ok(owned.clone()), X509CertificateOwned::try_from(&x509));
+    // the owned certificate can outlive the input buffer and be moved to another thread
+    let subject = std::thread::spawn(move || owned.certificate().unwrap().subject().to_string())
+        .join()
+        .unwrap();
+    assert_eq!(subject, x509.subject().to_string());
+
+    let mut data = TEST_LEAF.to_vec();
+    data.extend_from_slice(&[0, 0]);
+    let owned = X509CertificateOwned::new(data).unwrap();
+    assert_eq!(owned.as_raw(), TEST_LEAF);
+    assert_eq!(owned.certificate().unwrap(), x509);
+    assert!(X509CertificateOwned::new(vec![0x30, 0x00]).is_err());
+
+    // a certificate built from a separately parsed TbsCertificate has no encoding
+    let (_, tbs) = TbsCertificate::from_der(x509.tbs_certificate.as_ref()).unwrap();
+    let rebuilt = X509Certificate::from_parts(
+        tbs,
+        x509.signature_algorithm.clone(),
+        x509.signature_value.clone(),
+    );
+    assert!(rebuilt.as_raw().is_empty());
+    assert_eq!(rebuilt.to_owned(), Err(X509Error::MissingEncoding));
+    assert_eq!(
+        X509CertificateOwned::try_from(&rebuilt),
+        Err(X509Error::MissingEncoding)
+    );
+}
+
+#[test]
+fn test_x509_eq_der() {
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let (_, ca) = parse_x509_certificate(TEST_CA).unwrap();
+    // parse a copy, so the certificates do not share the same buffer
+    let copy = TEST_LEAF.to_vec();
+    let (_, leaf2) = parse_x509_certificate(&copy).unwrap();
+    assert!(leaf.eq_der(&leaf2));
+    assert_eq!(leaf, leaf2);
+    assert!(!leaf.eq_der(&ca));
+    assert_ne!(leaf, ca);
+
+    // certificates built from parts have no encoding, and are never equal
+    let from_parts = |x509: &X509Certificate<'static>| {
+        X509Certificate::from_parts(
+            x509.tbs_certificate.clone(),
+            x509.signature_algorithm.clone(),
+            x509.signature_value.clone(),
+        )
+    };
+    let (leaf_parts, ca_parts) = (from_parts(&leaf), from_parts(&ca));
+    assert!(!leaf_parts.eq_der(&ca_parts));
+    assert!(!leaf_parts.eq_der(&leaf_parts));
+    assert!(!leaf_parts.eq_der(&leaf));
+    assert!(!leaf.eq_der(&leaf_parts));
+}
+
+#[test]
+fn test_x509_parse_hex() {
+    // contiguous hex, as from Wireshark "Copy as Hex Stream"
+    let hex = data_encoding::HEXLOWER.encode(TEST_LEAF);
+    let cert = parse_x509_hex(&hex).expect("parsing failed");
+    assert_eq!(cert.as_raw(), TEST_LEAF);
+    // colon-separated, wrapped on multiple lines (as printed by openssl)
+    let dump = format_serial(TEST_LEAF)
+        .as_bytes()
+        .chunks(45)
+        .map(|line| format!("    {}", std::str::from_utf8(line).unwrap()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let cert = parse_x509_hex(&dump).expect("parsing failed");
+    assert_eq!(
+        cert.certificate().unwrap().subject().to_string(),
+        "O=x509-parser, CN=leaf.example.com"
+    );
+    // invalid input
+    assert_eq!(parse_x509_hex("30:8g").unwrap_err(), X509Error::InvalidHex);
+    assert!(parse_x509_hex("3082").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_x509_serialize() {
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let v = serde_json::to_value(&x509).expect("serialization failed");
+    let tbs = &v["tbs_certificate"];
+    assert_eq!(tbs["version"], 2);
+    assert_eq!(tbs["serial"], x509.raw_serial_as_string());
+    assert_eq!(tbs["subject"], "O=x509-parser, CN=leaf.example.com");
+    assert_eq!(
+        tbs["validity"]["not_before"],
+        x509.validity().not_before.to_rfc3339()
+    );
+    assert_eq!(
+        tbs["subject_pki"]["algorithm"]["algorithm"],
+        "1.2.840.10045.2.1"
+    );
+    let extensions = tbs["extensions"].as_array().unwrap();
+    assert_eq!(extensions.len(), x509.extensions().len());
+    let san = extensions
+        .iter()
+        .find(|ext| ext["oid"] == "2.5.29.17")
+        .expect("no SAN extension");
+    assert_eq!(
+        san["parsed_extension"],
+        serde_json::json!(["DNSName(leaf.example.com)"])
+    );
+    let parsed = |oid: &str| {
+        extensions
+            .iter()
+            .find(|ext| ext["oid"] == oid)
+            .map(|ext| ext["parsed_extension"].clone())
+            .expect("missing extension")
+    };
+    assert_eq!(parsed("2.5.29.19")["ca"], false);
+    assert_eq!(
+        parsed("2.5.29.15"),
+        serde_json::json!(["Digital Signature"])
+    );
+    assert_eq!(parsed("2.5.29.37")["server_auth"], true);
+    assert_eq!(
+        parsed("2.5.29.35")["key_identifier"],
+        "125bcd17d4a10aa73cdfb16ab17fbac2adc096f8"
+    );
+    assert_eq!(
+        parsed("2.5.29.31")[0]["distribution_point"],
+        "URI:http://pki.example.com/test-ca.crl"
+    );
+}
+
+#[test]
+fn test_x509_report_json() {
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let report = x509.report();
+    assert_eq!(report.version, 3);
+    assert_eq!(report.subject, "O=x509-parser, CN=leaf.example.com");
+    assert_eq!(report.not_after, x509.validity().not_after.to_rfc3339());
+    assert!(report
+        .subject_alt_names
+        .contains(&"DNS:leaf.example.com".to_string()));
+    assert!(!report.is_ca);
+
+    let json: serde_json::Value = serde_json::from_str(&x509.to_json()).expect("invalid JSON");
+    assert_eq!(json["version"], 3);
+    assert_eq!(json["serial"], report.serial.as_str());
+    assert_eq!(json["issuer"], report.issuer.as_str());
+    assert_eq!(
+        json["public_key_algorithm"],
+        report.public_key_algorithm.as_str()
+    );
+    assert_eq!(
+        json["subject_alt_names"][0],
+        report.subject_alt_names[0].as_str()
+    );
+    assert_eq!(
+        json["key_usage"].as_array().unwrap().len(),
+        report.key_usage.len()
+    );
+    assert_eq!(json["is_ca"], false);
+    #[cfg(feature = "hash")]
+    assert_eq!(json["sha256_fingerprint"].as_str().unwrap().len(), 64);
+    #[cfg(not(feature = "hash"))]
+    assert!(json["sha256_fingerprint"].is_null());
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn test_x509_redacted_report() {
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let report = x509.report();
+    let redacted = x509.redacted_report(b"telemetry-salt");
+    // the structure, algorithms and validity are kept
+    assert_eq!(redacted.not_after, report.not_after);
+    assert_eq!(redacted.public_key_algorithm, report.public_key_algorithm);
+    assert_eq!(redacted.key_usage, report.key_usage);
+    // subject and issuer values are hashed
+    let attrs: Vec<_> = redacted.subject.split(", ").collect();
+    assert_eq!(attrs.len(), 2);
+    assert!(attrs[0].starts_with("O=hmac-sha256:") && attrs[1].starts_with("CN=hmac-sha256:"));
+    assert_eq!(attrs[1].len(), "CN=hmac-sha256:".len() + 16);
+    assert!(!redacted.subject.contains("leaf.example.com"));
+    assert!(redacted.issuer.starts_with("O=hmac-sha256:"));
+    assert!(!redacted.issuer.contains("Test CRL CA"));
+    // the issuer of a self-signed certificate is redacted like its subject
+    let (_, ca) = parse_x509_certificate(include_bytes!("../assets/test-ca.der")).unwrap();
+    let redacted_ca = ca.redacted_report(b"telemetry-salt");
+    assert_eq!(redacted_ca.issuer, redacted_ca.subject);
+    assert_eq!(redacted_ca.subject, redacted.issuer);
+    assert!(!redacted_ca.to_json().contains("x509-parser"));
+    // SAN values are dropped
+    assert_eq!(
+        redacted.subject_alt_names.len(),
+        report.subject_alt_names.len()
+    );
+    assert!(redacted
+        .subject_alt_names
+        .contains(&"DNS:<redacted>".to_string()));
+    assert_eq!(redacted.serial, "<redacted>");
+    assert!(redacted.sha256_fingerprint.is_none());
+    assert!(!redacted.to_json().contains("example.com"));
+    // hashes are stable for a given salt
+    assert_eq!(x509.redacted_report(b"telemetry-salt"), redacted);
+    assert_ne!(x509.redacted_report(b"other").subject, redacted.subject);
+}
+
+#[test]
+fn test_x509_certificate_text() {
+    use x509_parser::display::certificate_text;
+
+    // expected lines are taken from `openssl x509 -text -noout`
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    let text = certificate_text(&x509);
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines[0], "Certificate:");
+    assert!(lines.contains(&"        Serial Number: 4097 (0x1001)"));
+    assert!(lines.contains(&"            Not Before: Oct 17 07:20:16 2026 GMT"));
+    assert!(lines.contains(&"        Subject: O=x509-parser, CN=leaf.example.com"));
+    assert!(lines.contains(&"                NIST CURVE: P-256"));
+
+    let der = include_bytes!("../assets/certificate.der");
+    let (_, x509) = parse_x509_certificate(der).unwrap();
+    let text = certificate_text(&x509);
+    let expected = "            Authority Information Access: \n                OCSP - URI:http://ocsp.int-x3.letsencrypt.org\n                CA Issuers - URI:http://cert.int-x3.letsencrypt.org/\n";
+    assert!(text.contains(expected));
+    let expected = "                Policy: 1.3.6.1.4.1.44947.1.1.1\n                  CPS: http://cps.letsencrypt.org\n";
+    assert!(text.contains(expected));
+    assert!(text.contains("                    Timestamp : Jul 12 12:12:30.834 2019 GMT\n"));
+    assert!(text.contains("        Serial Number:\n            03:20:48:03:0b:bb:34:10:f9:09:3c:57:f2:cb:83:08:c8:05\n"));
+}
+
+#[test]
+fn test_x509_signed_certificate_timestamps() {
+    let der = include_bytes!("../assets/certificate.der");
+    let (_, x509) = parse_x509_certificate(der).unwrap();
+    let ext = x509
+        .signed_certificate_timestamps()
+        .expect("invalid SCT list")
+        .expect("missing SCT list");
+    assert!(!ext.critical);
+    let scts = ext.value;
+    assert_eq!(scts.len(), 2);
+    let sct = &scts[0];
+    assert_eq!(sct.version, CtVersion::V1);
+    assert_eq!(&sct.id.key_id[..4], &[0x29, 0x3c, 0x51, 0x96]);
+    assert_eq!(sct.timestamp, 1_562_933_550_834);
+    assert!(sct.extensions.0.is_empty());
+    // SHA-256 (4) with ECDSA (3)
+    assert_eq!(sct.signature.hash_alg_id, 4);
+    assert_eq!(sct.signature.sign_alg_id, 3);
+    assert_eq!(sct.signature.data.len(), 0x47);
+    assert_eq!(&scts[1].id.key_id[..4], &[0x6f, 0x53, 0x76, 0xac]);
+    // no SCT list
+    let (_, x509) = parse_x509_certificate(TEST_LEAF).unwrap();
+    assert_eq!(x509.signed_certificate_timestamps(), Ok(None));
+}
+
+#[test]
+fn test_x509_precertificate() {
+    let precert_der = include_bytes!("../assets/test-precert.der");
+    let final_der = include_bytes!("../assets/test-final.der");
+    let (_, precert) = parse_x509_certificate(precert_der).unwrap();
+    let (_, final_cert) = parse_x509_certificate(final_der).unwrap();
+    assert!(precert.is_precertificate());
+    assert!(!final_cert.is_precertificate());
+    let poison = precert
+        .get_extension_unique(&oids::OID_CT_PRECERT_POISON)
+        .unwrap()
+        .expect("missing poison extension");
+    assert!(poison.critical);
+    assert_eq!(poison.parsed_extension(), &ParsedExtension::CtPrecertPoison);
+    // a non-critical poison extension is ignored
+    let pattern = &[0x02, 0x04, 0x03, 0x01, 0x01, 0xff];
+    let pos = precert_der
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+        .expect("poison extension not found");
+    let mut der = precert_der.to_vec();
+    der[pos + 5] = 0x00;
+    let (_, non_critical) = parse_x509_certificate(&der).unwrap();
+    assert!(non_critical
+        .get_extension_unique(&oids::OID_CT_PRECERT_POISON)
+        .unwrap()
+        .is_some());
+    assert!(!non_critical.is_precertificate());
+
+    // the precertificate and the final certificate have the same TBS for CT logs
+    let tbs = precert.ct_tbs_certificate().unwrap();
+    assert_eq!(tbs, final_cert.ct_tbs_certificate().unwrap());
+    let (rem, parsed) = TbsCertificate::from_der(&tbs).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(parsed.extensions().len(), precert.extensions().len() - 1);
+    assert!(!parsed.is_precertificate());
+    assert_eq!(parsed.subject(), precert.subject());
+
+    // removing no extension gives the original encoding
+    assert_eq!(
+        precert.raw_without_extensions(&[]).unwrap(),
+        precert.tbs_certificate.as_ref()
+    );
+    // removing all extensions omits the extensions field
+    let oids: Vec<_> = precert
+        .extensions()
+        .iter()
+        .map(|ext| ext.oid.clone())
+        .collect();
+    let tbs = precert.raw_without_extensions(&oids).unwrap();
+    let (_, parsed) = TbsCertificate::from_der(&tbs).unwrap();
+    assert!(parsed.extensions().is_empty());
+}
+
+#[test]
+fn test_x509_precert_signing_cert() {
+    let (_, signer) =
+        parse_x509_certificate(include_bytes!("../assets/test-precert-signer.der")).unwrap();
+    let (_, precert) =
+        parse_x509_certificate(include_bytes!("../assets/test-precert-signed.der")).unwrap();
+    let (_, direct) = parse_x509_certificate(include_bytes!("../assets/test-precert.der")).unwrap();
+    assert!(signer.is_precert_signing_cert());
+    assert!(!precert.is_precert_signing_cert());
+    assert!(precert.is_precertificate());
+    assert_eq!(precert.issuer(), signer.subject());
+
+    // the issuer and authority key identifier are those of the final CA, so the TBS is the same
+    // as for a precertificate issued directly by the CA
+    let tbs = precert.ct_tbs_certificate_with_signer(&signer).unwrap();
+    assert_eq!(tbs, direct.ct_tbs_certificate().unwrap());
+    assert_ne!(tbs, precert.ct_tbs_certificate().unwrap());
+    let (_, parsed) = TbsCertificate::from_der(&tbs).unwrap();
+    assert_eq!(parsed.issuer(), signer.issuer());
+
+    // the signer must be a precertificate signing certificate
+    assert_eq!(
+        precert.ct_tbs_certificate_with_signer(&direct),
+        Err(X509Error::InvalidCertificate)
+    );
+}
+
+#[test]
+fn test_x509_crl_distribution_points() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    let crldp = cert
+        .crl_distribution_points()
+        .expect("invalid extension")
+        .expect("missing CRL distribution points");
+    assert!(!crldp.critical);
+    assert_eq!(crldp.value.len(), 1);
+    assert_eq!(crldp.value.urls(), ["http://pki.example.com/test-ca.crl"]);
+    assert!(crldp.value[0].reasons.is_none());
+    // not present
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-ca.der")).unwrap();
+    assert_eq!(cert.crl_distribution_points(), Ok(None));
+}
+
+#[test]
+fn test_x509_info_access() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-sia.der")).unwrap();
+    let aia = cert
+        .authority_info_access()
+        .expect("invalid extension")
+        .expect("missing AIA");
+    assert_eq!(aia.value.accessdescs.len(), 2);
+    let sia = cert
+        .subject_info_access()
+        .expect("invalid extension")
+        .expect("missing SIA");
+    assert!(!sia.critical);
+    let methods: Vec<_> = sia.value.iter().map(|ad| &ad.access_method).collect();
+    assert_eq!(
+        methods,
+        [
+            &oid_registry::OID_PKIX_ACCESS_DESCRIPTOR_CA_REPOSITORY,
+            &oid_registry::OID_PKIX_ACCESS_DESCRIPTOR_RPKI_MANIFEST,
+            &oid_registry::OID_PKIX_ACCESS_DESCRIPTOR_RPKI_NOTIFY,
+        ]
+    );
+    let map = sia.value.as_hashmap();
+    assert_eq!(
+        map[&oid_registry::OID_PKIX_ACCESS_DESCRIPTOR_CA_REPOSITORY],
+        [&GeneralName::URI("rsync://rpki.example.com/repo/")]
+    );
+    let ext = cert
+        .get_extension_unique(&oids::OID_PKIX_SUBJECT_INFO_ACCESS)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        ext.parsed_extension().to_string(),
+        "CA Repository - URI:rsync://rpki.example.com/repo/, \
+         RPKI Manifest - URI:rsync://rpki.example.com/repo/ca.mft, \
+         RPKI Notify - URI:https://rrdp.example.com/notification.xml"
+    );
+    // not present
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert_eq!(cert.subject_info_access(), Ok(None));
+}
+
+#[test]
+fn test_x509_freshest_crl() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-freshest.der")).unwrap();
+    let points = cert
+        .freshest_crl()
+        .expect("invalid extension")
+        .expect("missing freshest CRL");
+    assert!(!points.critical);
+    assert_eq!(
+        points.value.urls(),
+        ["http://pki.example.com/test-ca-delta.crl"]
+    );
+    // the CRL distribution points are distinct
+    let crldp = cert.crl_distribution_points().unwrap().unwrap();
+    assert_eq!(crldp.value.urls(), ["http://pki.example.com/test-ca.crl"]);
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert_eq!(cert.freshest_crl(), Ok(None));
+}
+
+#[test]
+fn test_x509_certificate_policy_qualifiers() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-policies.der")).unwrap();
+    let policies = cert
+        .certificate_policies()
+        .expect("invalid extension")
+        .expect("missing certificate policies");
+    assert!(!policies.critical);
+    assert_eq!(policies.value.len(), 1);
+    let policy = &policies.value[0];
+    assert_eq!(policy.policy_id, oid!(1.3.6 .1 .4 .1 .99999 .1));
+    assert_eq!(policy.cps_uris(), ["http://pki.example.com/cps"]);
+    let qualifiers = policy.policy_qualifiers.as_ref().unwrap();
+    assert_eq!(qualifiers.len(), 2);
+    assert_eq!(
+        qualifiers[0].parsed_qualifier(),
+        Ok(PolicyQualifier::CPSUri("http://pki.example.com/cps"))
+    );
+    assert_eq!(
+        policy.user_notices(),
+        [UserNotice {
+            notice_ref: Some(NoticeReference {
+                organization: "x509-parser".to_string(),
+                notice_numbers: vec![1, 2],
+            }),
+            explicit_text: Some("Test policy".to_string()),
+        }]
+    );
+    let text = x509_parser::display::certificate_text(&cert);
+    let expected = "                  CPS: http://pki.example.com/cps\n                  User Notice:\n                    Organization: x509-parser\n                    Numbers: 1, 2\n                    Explicit Text: Test policy\n";
+    assert!(text.contains(expected));
+    // not present
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert_eq!(cert.certificate_policies(), Ok(None));
+}
+
+#[test]
+fn test_x509_smime_capabilities() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-smime.der")).unwrap();
+    let caps = cert
+        .smime_capabilities()
+        .expect("invalid extension")
+        .expect("missing S/MIME capabilities");
+    assert!(!caps.critical);
+    let names: Vec<_> = caps.value.iter().map(|c| c.name()).collect();
+    assert_eq!(
+        names,
+        [
+            Some("aes-256-cbc"),
+            Some("aes-128-cbc"),
+            Some("des-ede3-cbc"),
+            Some("rc2-cbc")
+        ]
+    );
+    assert!(caps.value.contains(&OID_NIST_ENC_AES256_CBC));
+    // RC2 key length
+    let params = caps.value.capabilities[3].parameters.as_ref().unwrap();
+    assert_eq!(params.clone().u32(), Ok(128));
+    assert!(caps.value.capabilities[0].parameters.is_none());
+    let ext = cert
+        .get_extension_unique(&OID_PKCS9_SMIME_CAPABILITIES)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        ext.parsed_extension().to_string(),
+        "aes-256-cbc, aes-128-cbc, des-ede3-cbc, rc2-cbc"
+    );
+    // not present
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert_eq!(cert.smime_capabilities(), Ok(None));
+}
+
+#[test]
+fn test_x509_netscape_extensions() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-netscape.der")).unwrap();
+    let cert_type = cert
+        .netscape_cert_type()
+        .expect("invalid extension")
+        .expect("missing Netscape cert type");
+    assert!(!cert_type.critical);
+    assert!(cert_type.value.ssl_client() && cert_type.value.smime());
+    assert!(!cert_type.value.ssl_server() && !cert_type.value.ssl_ca());
+    assert_eq!(cert_type.value.to_string(), "SSL Client, S/MIME");
+    let comment = cert.netscape_comment().unwrap().unwrap();
+    assert_eq!(comment.value, "Legacy enterprise certificate");
+    let base_url = cert.netscape_base_url().unwrap().unwrap();
+    assert_eq!(base_url.value, "https://pki.example.com/netscape/");
+    assert!(cert
+        .extensions()
+        .iter()
+        .all(|ext| !ext.parsed_extension().unsupported()));
+    let text = x509_parser::display::certificate_text(&cert);
+    assert!(text.contains(
+        "            Netscape Base Url: \n                https://pki.example.com/netscape/\n"
+    ));
+    // not present
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert_eq!(cert.netscape_cert_type(), Ok(None));
+    assert_eq!(cert.netscape_base_url(), Ok(None));
+}
+
+#[test]
+fn test_x509_comments() {
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-comments.der")).unwrap();
+    let comment = cert.netscape_comment().unwrap().unwrap();
+    assert_eq!(comment.value, "  Extended Validation:   see the CPS  ");
+    // normalized, and the duplicate user notice is skipped
+    let comments: Vec<_> = cert.comments().collect();
+    assert_eq!(
+        comments,
+        [
+            "Extended Validation: see the CPS",
+            "Relying parties must check revocation"
+        ]
+    );
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-policies.der")).unwrap();
+    assert_eq!(cert.comments().collect::<Vec<_>>(), ["Test policy"]);
+    let (_, cert) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert_eq!(cert.comments().count(), 0);
+}
+
+#[test]
+fn test_x509_snapshot() {
+    use x509_parser::snapshot::{certificate_snapshot, CertificateSnapshot};
+
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der"))
+        .expect("could not parse certificate");
+    let expected = "certificate
+  version: 3
+  serial: 10:01
+  signature_algorithm: ecdsa-with-SHA256 (1.2.840.10045.4.3.2)
+  issuer: O=x509-parser, CN=Test CRL CA
+  not_before: 2026-10-17T07:20:16Z
+  not_after: 2036-10-14T07:20:16Z
+  subject: O=x509-parser, CN=leaf.example.com
+  public_key_algorithm: id-ecPublicKey (1.2.840.10045.2.1)
+  public_key: [65 bytes] 04:8f:d0:81:b6:8e:9a:a3..c5:bf:bc:2f:52:0a:b6:05
+  extensions:
+    - subjectKeyIdentifier (2.5.29.14): ca:2b:bc:8d:d4:92:9f:c1:67:96:26:fd:e7:f9:88:73:5f:4b:89:e3
+    - keyUsage (2.5.29.15) critical: digitalSignature
+    - subjectAltName (2.5.29.17): DNS:leaf.example.com
+    - basicConstraints (2.5.29.19): ca=false
+    - crlDistributionPoints (2.5.29.31): {name=[URI:http://pki.example.com/test-ca.crl]}
+    - authorityKeyIdentifier (2.5.29.35): keyid=12:5b:cd:17:d4:a1:0a:a7:3c:df:b1:6a:b1:7f:ba:c2:ad:c0:96:f8
+    - extendedKeyUsage (2.5.29.37): serverAuth
+  signature: [70 bytes] 30:44:02:20:74:2d:3e:b2..e8:85:d5:28:60:20:74:90
+";
+    assert_eq!(certificate_snapshot(&x509), expected);
+    // the alternate format prints blobs in full
+    let full = format!("{:#}", CertificateSnapshot(&x509));
+    assert!(full.contains("  public_key: [65 bytes] 04:8f:d0:81:b6:8e:9a:a3:"));
+    assert!(!full.contains(".."));
+}
+
+#[test]
+fn test_x509_ocsp_no_check() {
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-ocsp-responder.der"))
+        .expect("could not parse certificate");
+    assert!(x509.has_ocsp_no_check());
+    let ext = x509
+        .get_extension_unique(&x509_parser::oids::OID_PKIX_OCSP_NOCHECK)
+        .expect("duplicate extension")
+        .expect("missing extension");
+    assert!(!ext.critical);
+    assert_eq!(ext.parsed_extension(), &ParsedExtension::OcspNoCheck);
+    let eku = x509.extended_key_usage().unwrap().unwrap();
+    assert!(eku.value.ocsp_signing);
+    // other certificates do not have the extension
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert!(!x509.has_ocsp_no_check());
+}
+
+#[test]
+fn test_x509_can_sign_http_exchanges() {
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-sxg.der"))
+        .expect("could not parse certificate");
+    assert!(x509.can_sign_http_exchanges());
+    let ext = x509
+        .get_extension_unique(&x509_parser::oids::OID_CAN_SIGN_HTTP_EXCHANGES)
+        .expect("duplicate extension")
+        .expect("missing extension");
+    assert!(!ext.critical);
+    assert_eq!(
+        ext.parsed_extension(),
+        &ParsedExtension::CanSignHttpExchanges
+    );
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-leaf.der")).unwrap();
+    assert!(!x509.can_sign_http_exchanges());
+}
+
+#[test]
+fn test_x509_conversions() {
+    use std::convert::TryInto;
+
+    let der: &[u8] = include_bytes!("../assets/IGC_A.der");
+    let x509: X509Certificate = der.try_into().expect("could not parse certificate");
+    assert_eq!(x509.as_raw(), der);
+    // trailing data is rejected
+    let mut data = der.to_vec();
+    data.push(0);
+    assert_eq!(
+        X509Certificate::try_from(data.as_slice()).unwrap_err(),
+        X509Error::InvalidCertificate
+    );
+    assert_eq!(
+        X509CertificateOwned::try_from(data.clone()).unwrap_err(),
+        X509Error::InvalidCertificate
+    );
+    // unless the certificate is built using `new`
+    let owned = X509CertificateOwned::new(data).expect("could not parse certificate");
+    assert_eq!(owned.as_raw(), der);
+    let owned = X509CertificateOwned::try_from(der.to_vec()).expect("could not parse certificate");
+    assert_eq!(owned.as_raw(), der);
+
+    let pem = std::fs::read_to_string("assets/IGC_A.pem").unwrap();
+    let owned: X509CertificateOwned = pem.parse().expect("could not parse PEM certificate");
+    assert_eq!(owned.as_raw(), der);
+    let crl = std::fs::read_to_string("assets/crls.pem").unwrap();
+    assert!(matches!(
+        crl.parse::<X509CertificateOwned>(),
+        Err(PEMError::UnexpectedLabel(label)) if label == "X509 CRL"
+    ));
+    assert!("not a certificate".parse::<X509CertificateOwned>().is_err());
+}
+
+#[test]
+fn test_x509_admission() {
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-admission.der"))
+        .expect("could not parse certificate");
+    let ext = x509
+        .admission()
+        .expect("invalid extension")
+        .expect("missing extension");
+    assert!(!ext.critical);
+    let admission = ext.value;
+    assert_eq!(
+        admission.admission_authority,
+        Some(GeneralName::URI("https://www.kzbv.de"))
+    );
+    assert_eq!(admission.contents_of_admissions.len(), 1);
+    let admissions = &admission.contents_of_admissions[0];
+    assert!(admissions.admission_authority.is_none());
+    let naming_authority = admissions.naming_authority.as_ref().unwrap();
+    assert_eq!(naming_authority.id, Some(oid!(1.2.276 .0 .76 .3 .1 .91)));
+    assert_eq!(naming_authority.url, Some("https://www.gematik.de"));
+    assert!(naming_authority.text.is_none());
+
+    let infos: Vec<_> = admission.profession_infos().collect();
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[0].profession_items, ["Zahnärztin"]);
+    assert_eq!(infos[0].profession_oids, [oid!(1.2.276 .0 .76 .4 .31)]);
+    assert_eq!(infos[0].registration_number, Some("2-1234567890"));
+    assert!(infos[0].add_profession_info.is_none());
+    // BMPString item, and naming authority of the profession
+    let naming_authority = infos[1].naming_authority.as_ref().unwrap();
+    assert_eq!(naming_authority.text.as_deref(), Some("Bundesnotarkammer"));
+    assert_eq!(infos[1].profession_items, ["Notar"]);
+    assert!(infos[1].profession_oids.is_empty());
+    assert!(infos[1].registration_number.is_none());
+    assert_eq!(infos[1].add_profession_info, Some(&[1u8, 2][..]));
+    assert_eq!(
+        admission.to_string(),
+        "Authority: URI:https://www.kzbv.de; Professions: Zahnärztin (2-1234567890); Notar"
+    );
+}
+
+#[test]
+fn test_x509_v1_certificates() {
+    let (_, root) = parse_x509_certificate(V1).expect("could not parse certificate");
+    let (_, leaf) = parse_x509_certificate(include_bytes!("../assets/test-v1-leaf.der"))
+        .expect("could not parse certificate");
+    for x509 in &[&root, &leaf] {
+        assert_eq!(x509.version(), X509Version::V1);
+        assert!(x509.extensions().is_empty());
+        assert_eq!(x509.basic_constraints(), Ok(None));
+        assert_eq!(x509.key_usage(), Ok(None));
+        assert_eq!(x509.subject_alternative_name(), Ok(None));
+        assert_eq!(x509.extended_key_usage(), Ok(None));
+        assert!(!x509.is_ca());
+        assert!(!x509.assume_v1_ca(V1CaPolicy::default()));
+        assert!(x509.assume_v1_ca(V1CaPolicy::Always));
+    }
+    // legacy self-signed v1 roots
+    assert!(root.assume_v1_ca(V1CaPolicy::SelfIssued));
+    assert_eq!(root.certificate_kind(), CertificateKind::Root);
+    assert!(!leaf.assume_v1_ca(V1CaPolicy::SelfIssued));
+    assert_eq!(
+        leaf.certificate_kind(),
+        CertificateKind::Leaf(LeafKind::Unknown)
+    );
+    // the policy does not apply to v3 certificates without basic constraints
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/no_extensions.der")).unwrap();
+    assert!(!x509.assume_v1_ca(V1CaPolicy::Always));
+}
+
+#[test]
+fn test_x509_parse_stats() {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    static TICKS: AtomicU64 = AtomicU64::new(0);
+    fn clock() -> Duration {
+        Duration::from_millis(TICKS.fetch_add(5, Ordering::SeqCst))
+    }
+
+    let der = include_bytes!("../assets/test-leaf.der");
+    let mut input = der.to_vec();
+    input.extend_from_slice(&[0, 0]);
+    let (rem, (x509, stats)) = X509CertificateParser::new()
+        .with_clock(clock)
+        .parse_with_stats(&input)
+        .expect("could not parse certificate");
+    assert_eq!(rem, &[0, 0]);
+    assert_eq!(stats.bytes_consumed, der.len());
+    assert_eq!(stats.trailing_bytes, 2);
+    assert_eq!(stats.extensions, 7);
+    assert_eq!(stats.san_entries, 1);
+    // Certificate > TBSCertificate > Name > RDN > AttributeTypeAndValue > OID
+    assert_eq!(stats.max_depth, 6);
+    assert_eq!(stats.elapsed, Some(Duration::from_millis(5)));
+    let stats = ParseStats::from_certificate(&x509);
+    assert_eq!((stats.trailing_bytes, stats.elapsed), (0, None));
+    // without clock, and without parsing extensions
+    let (_, (_, stats)) = X509CertificateParser::new()
+        .with_deep_parse_extensions(false)
+        .parse_with_stats(der)
+        .unwrap();
+    assert_eq!((stats.extensions, stats.san_entries), (7, 0));
+    assert_eq!(stats.elapsed, None);
+}
+
+#[test]
+fn test_x509_smtp_utf8_mailbox() {
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-smtputf8.der"))
+        .expect("could not parse certificate");
+    let san = x509
+        .subject_alternative_name()
+        .expect("invalid extension")
+        .expect("missing extension");
+    let names = &san.value.general_names;
+    assert_eq!(names.len(), 2);
+    assert_eq!(names[1], GeneralName::SmtpUTF8Mailbox("用户@例子.广告"));
+    let emails: Vec<_> = names
+        .iter()
+        .filter_map(|name| name.email_address())
+        .collect();
+    assert_eq!(emails, ["ascii@example.com", "用户@例子.广告"]);
+    let ext = x509
+        .get_extension_unique(&OID_X509_EXT_SUBJECT_ALT_NAME)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        ext.parsed_extension().to_string(),
+        "email:ascii@example.com, othername:SmtpUTF8Mailbox:用户@例子.广告"
+    );
+}
+
+#[test]
+fn test_x509_user_principal_name() {
+    let (_, x509) = parse_x509_certificate(include_bytes!("../assets/test-upn.der"))
+        .expect("could not parse certificate");
+    let san = x509
+        .subject_alternative_name()
+        .expect("invalid extension")
+        .expect("missing extension");
+    let upn = san.value.general_names.iter().find_map(|name| match name {
+        GeneralName::UserPrincipalName(upn) => Some(*upn),
+        _ => None,
+    });
+    assert_eq!(upn, Some("jdoe@corp.example.com"));
+    // the UPN is not an email address
+    assert_eq!(san.value.general_names[0].email_address(), None);
+    let ext = x509
+        .get_extension_unique(&OID_X509_EXT_SUBJECT_ALT_NAME)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        ext.parsed_extension().to_string(),
+        "othername:UPN:jdoe@corp.example.com, email:jdoe@example.com"
+    );
+}
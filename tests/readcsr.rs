@@ -1,13 +1,16 @@
 use asn1_rs::Set;
 use oid_registry::{
-    OID_PKCS1_SHA256WITHRSA, OID_PKCS9_CHALLENGE_PASSWORD, OID_SIG_ECDSA_WITH_SHA256,
-    OID_X509_COMMON_NAME,
+    OID_PKCS1_SHA256WITHRSA, OID_PKCS9_CHALLENGE_PASSWORD, OID_PKCS9_SMIME_CAPABILITIES,
+    OID_SIG_ECDSA_WITH_SHA256, OID_X509_COMMON_NAME,
 };
+use std::convert::TryFrom;
 use x509_parser::prelude::*;
 
 const CSR_DATA_EMPTY_ATTRIB: &[u8] = include_bytes!("../assets/csr-empty-attributes.csr");
 const CSR_DATA: &[u8] = include_bytes!("../assets/test.csr");
 const CSR_CHALLENGE_PASSWORD: &[u8] = include_bytes!("../assets/csr-challenge-password.pem");
+const CSR_MS_ATTRIBUTES: &[u8] = include_bytes!("../assets/csr-ms-attributes.der");
+const CSR_SMIME_CAPABILITIES: &[u8] = include_bytes!("../assets/csr-smime-capabilities.der");
 #[test]
 fn read_csr_empty_attrib() {
     let (rem, csr) =
@@ -56,6 +59,20 @@ fn read_csr_with_san() {
     }
 }
 
+#[test]
+fn read_csr_snapshot() {
+    use x509_parser::snapshot::CertificationRequestSnapshot;
+
+    let der = pem::parse_x509_pem(CSR_DATA).unwrap().1;
+    let (_, csr) = X509CertificationRequest::from_der(&der.contents).expect("could not parse CSR");
+    let snapshot = CertificationRequestSnapshot(&csr).to_string();
+    let expected = "  requested_extensions:
+    - subjectAltName (2.5.29.17): DNS:test.rusticata.fr
+";
+    assert!(snapshot.starts_with("certification_request\n  version: 1\n"));
+    assert!(snapshot.contains(expected));
+}
+
 #[test]
 fn read_csr_with_challenge_password() {
     let der = pem::parse_x509_pem(CSR_CHALLENGE_PASSWORD).unwrap().1;
@@ -91,6 +108,22 @@ fn read_csr_with_challenge_password() {
         panic!("Parsed attribute is not a challenge password");
     }
 
+    // 3. Check: typed accessor
+    let challenge_password = cri
+        .challenge_password()
+        .expect("invalid attribute")
+        .expect("missing challenge password");
+    assert_eq!(challenge_password.0, "A challenge password");
+    let request = cri
+        .extension_request()
+        .expect("invalid attribute")
+        .expect("missing extension request");
+    assert!(!request.extensions.is_empty());
+    assert_eq!(cri.os_version(), Ok(None));
+    assert!(!challenge_password_attr.parsed_attribute().unsupported());
+    let dump = challenge_password_attr.debug_dump().expect("invalid DER");
+    assert!(dump.contains("A challenge password"), "{}", dump);
+
     // Make sure we can read requested extensions
     let extensions = csr
         .requested_extensions()
@@ -106,6 +139,102 @@ fn read_csr_with_challenge_password() {
     assert!(found_san);
 }
 
+#[test]
+fn read_csr_ms_attributes() {
+    let (rem, csr) =
+        X509CertificationRequest::from_der(CSR_MS_ATTRIBUTES).expect("could not parse CSR");
+    assert!(rem.is_empty());
+    let cri = &csr.certification_request_info;
+    assert_eq!(cri.attributes().len(), 3);
+
+    let attr = cri.find_attribute(&OID_MS_OS_VERSION).unwrap();
+    assert_eq!(
+        attr.parsed_attribute(),
+        &ParsedCriAttribute::OsVersion(OsVersion("10.0.19045.2"))
+    );
+
+    let attr = cri.find_attribute(&OID_MS_REQUEST_CLIENT_INFO).unwrap();
+    assert_eq!(
+        attr.parsed_attribute(),
+        &ParsedCriAttribute::RequestClientInfo(RequestClientInfo {
+            client_id: 5,
+            machine_name: "ws01.corp.example.com",
+            user_name: "CORP\\jdoe",
+            process_name: "certreq.exe",
+        })
+    );
+
+    assert_eq!(cri.os_version(), Ok(Some(&OsVersion("10.0.19045.2"))));
+    let info = cri
+        .request_client_info()
+        .unwrap()
+        .expect("missing client info");
+    assert_eq!(info.user_name, "CORP\\jdoe");
+    let renewal = cri
+        .renewal_certificate()
+        .unwrap()
+        .expect("missing certificate");
+    assert_eq!(renewal.0.raw_serial(), &[0x10, 0x01]);
+    assert_eq!(cri.challenge_password(), Ok(None));
+
+    let attr = cri.find_attribute(&OID_MS_RENEWAL_CERTIFICATE).unwrap();
+    if let ParsedCriAttribute::RenewalCertificate(RenewalCertificate(cert)) =
+        attr.parsed_attribute()
+    {
+        assert_eq!(
+            cert.subject().to_string(),
+            "O=x509-parser, CN=leaf.example.com"
+        );
+    } else {
+        panic!("unexpected attribute {:?}", attr.parsed_attribute());
+    }
+}
+
+#[test]
+fn read_csr_smime_capabilities() {
+    let (rem, csr) =
+        X509CertificationRequest::from_der(CSR_SMIME_CAPABILITIES).expect("could not parse CSR");
+    assert!(rem.is_empty());
+    let cri = &csr.certification_request_info;
+    let caps = cri
+        .smime_capabilities()
+        .unwrap()
+        .expect("missing S/MIME capabilities");
+    let names: Vec<_> = caps.iter().filter_map(|c| c.name()).collect();
+    assert_eq!(names, ["aes-256-cbc", "des-ede3-cbc", "rc2-cbc"]);
+    let attr = cri.find_attribute(&OID_PKCS9_SMIME_CAPABILITIES).unwrap();
+    assert!(!attr.parsed_attribute().unsupported());
+    assert_eq!(cri.challenge_password(), Ok(None));
+    // not present
+    let (_, csr) = X509CertificationRequest::from_der(CSR_MS_ATTRIBUTES).unwrap();
+    assert_eq!(
+        csr.certification_request_info.smime_capabilities(),
+        Ok(None)
+    );
+}
+
+#[test]
+fn read_csr_signature_input() {
+    let der = pem::parse_x509_pem(CSR_DATA).unwrap().1;
+    let (_, csr) = X509CertificationRequest::from_der(&der.contents).expect("could not parse CSR");
+    assert_eq!(csr.as_raw(), &der.contents[..]);
+    let (signed_data, signature_value, signature_algorithm) = csr.signature_input();
+    assert_eq!(signed_data, csr.certification_request_info.as_raw());
+    assert!(der.contents.len() > signed_data.len());
+    assert_eq!(signature_value, &csr.signature_value);
+    assert_eq!(signature_algorithm, &csr.signature_algorithm);
+
+    // verify using an external implementation
+    #[cfg(feature = "verify")]
+    x509_parser::verify::verify_signature(
+        &csr.certification_request_info.subject_pki,
+        signature_algorithm,
+        signature_value,
+        signed_data,
+    )
+    .expect("signature verification failed");
+}
+
 #[cfg(feature = "verify")]
 #[test]
 fn read_csr_verify() {
@@ -123,3 +252,26 @@ fn read_csr_verify() {
     let (_, csr) = X509CertificationRequest::from_der(&der.contents).expect("could not parse CSR");
     csr.verify_signature().unwrap_err();
 }
+
+#[test]
+fn read_csr_owned() {
+    let der = pem::parse_x509_pem(CSR_DATA).unwrap().1;
+    let (_, csr) = X509CertificationRequest::from_der(&der.contents).unwrap();
+    let owned = csr.to_owned().unwrap();
+    assert_eq!(
+        Ok(owned.clone()),
+        X509CertificationRequestOwned::try_from(&csr)
+    );
+    assert_eq!(owned.as_raw(), &der.contents[..]);
+    assert_eq!(owned.request().unwrap(), csr);
+    // a request built from a separately parsed CertificationRequestInfo has no encoding
+    let (_, info) =
+        X509CertificationRequestInfo::from_der(csr.certification_request_info.raw).unwrap();
+    let rebuilt = X509CertificationRequest::from_parts(
+        info,
+        csr.signature_algorithm.clone(),
+        csr.signature_value.clone(),
+    );
+    assert!(rebuilt.as_raw().is_empty());
+    assert_eq!(rebuilt.to_owned(), Err(X509Error::MissingEncoding));
+}
@@ -0,0 +1,110 @@
+use ::time::Duration;
+use x509_parser::ocsp::*;
+use x509_parser::prelude::*;
+
+const OCSP_RESPONSE: &[u8] = include_bytes!("../assets/ocsp-response.der");
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+
+#[test]
+fn read_ocsp_response() {
+    let (rem, response) = OCSPResponse::from_der(OCSP_RESPONSE).expect("could not parse response");
+    assert!(rem.is_empty());
+    assert!(response.is_successful());
+    let bytes = response.response_bytes.as_ref().expect("no response bytes");
+    assert_eq!(bytes.response_type, OID_PKIX_OCSP_BASIC);
+    let basic = response.basic_response().expect("no basic response");
+    assert_eq!(basic.tbs_response_data.version, X509Version::V1);
+    match basic.responder_id() {
+        ResponderID::ByName(name) => {
+            assert_eq!(name.to_string(), "O=x509-parser, CN=Test CRL CA")
+        }
+        id => panic!("unexpected responder id {:?}", id),
+    }
+    assert_eq!(basic.produced_at().timestamp(), 1_792_227_989);
+
+    let responses: Vec<_> = basic.iter_responses().collect();
+    assert_eq!(responses.len(), 3);
+    let statuses: Vec<_> = responses
+        .iter()
+        .map(|r| (r.cert_id.serial_u128(), r.cert_status.clone()))
+        .collect();
+    assert_eq!(statuses[1], (Some(0x1003), CertStatus::Good));
+    assert_eq!(statuses[2], (Some(0x1002), CertStatus::Unknown));
+    let single = responses[0];
+    assert_eq!(single.cert_id.raw_serial(), &[0x10, 0x01]);
+    assert_eq!(
+        single.cert_id.issuer_key_hash,
+        &[
+            0x12, 0x5b, 0xcd, 0x17, 0xd4, 0xa1, 0x0a, 0xa7, 0x3c, 0xdf, 0xb1, 0x6a, 0xb1, 0x7f,
+            0xba, 0xc2, 0xad, 0xc0, 0x96, 0xf8
+        ]
+    );
+    match &single.cert_status {
+        CertStatus::Revoked(info) => {
+            assert_eq!(info.revocation_reason, Some(ReasonCode::KeyCompromise));
+        }
+        status => panic!("unexpected status {:?}", status),
+    }
+    let next_update = single.next_update.expect("no next update");
+    assert_eq!(
+        next_update.timestamp() - single.this_update.timestamp(),
+        7 * 86400
+    );
+
+    // the responder certificate is embedded
+    assert_eq!(basic.certificates().len(), 1);
+    assert_eq!(basic.certs[0].as_raw(), TEST_CA);
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn read_ocsp_response_verify() {
+    let (_, ca) = X509Certificate::from_der(TEST_CA).expect("could not parse certificate");
+    let (_, response) = OCSPResponse::from_der(OCSP_RESPONSE).expect("could not parse response");
+    let basic = response.basic_response().expect("no basic response");
+    assert!(basic.verify_signature(ca.public_key()).is_ok());
+}
+
+#[test]
+fn ocsp_cache_policy() {
+    let (_, response) = OCSPResponse::from_der(OCSP_RESPONSE).expect("could not parse response");
+    let basic = response.basic_response().expect("no basic response");
+    let single = basic.iter_responses().next().expect("no single response");
+    let this_update = single.this_update;
+    let at = |hours: i64| (this_update + Duration::hours(hours)).unwrap();
+
+    // the response is valid for 7 days
+    let policy = OcspCachePolicy::new();
+    assert_eq!(policy.cache_duration(single, at(24)), Duration::days(6));
+    assert_eq!(policy.cache_duration(single, at(8 * 24)), Duration::ZERO);
+    let policy = policy.with_max_age(Duration::hours(12));
+    assert_eq!(policy.cache_duration(single, at(24)), Duration::hours(12));
+    assert_eq!(
+        policy.cache_duration(single, at(7 * 24 - 1)),
+        Duration::hours(1)
+    );
+    // must-staple: refresh halfway through the validity period
+    let policy = OcspCachePolicy {
+        must_staple: true,
+        ..OcspCachePolicy::default()
+    };
+    assert_eq!(policy.cache_duration(single, at(24)), Duration::hours(60));
+    assert_eq!(policy.cache_duration(single, at(4 * 24)), Duration::ZERO);
+    // without nextUpdate, the default lifetime is used
+    let mut single = single.clone();
+    single.next_update = None;
+    let policy = OcspCachePolicy::new();
+    assert_eq!(
+        policy.cache_duration(&single, this_update),
+        Duration::hours(1)
+    );
+}
+
+#[test]
+fn ocsp_cache_policy_must_staple() {
+    let (_, cert) = X509Certificate::from_der(include_bytes!("../assets/test-must-staple.der"))
+        .expect("could not parse certificate");
+    assert!(OcspCachePolicy::for_certificate(&cert).must_staple);
+    let (_, cert) = X509Certificate::from_der(TEST_CA).expect("could not parse certificate");
+    assert!(!OcspCachePolicy::for_certificate(&cert).must_staple);
+}
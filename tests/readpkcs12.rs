@@ -0,0 +1,64 @@
+use x509_parser::pkcs12::*;
+use x509_parser::prelude::*;
+
+const TEST_P12: &[u8] = include_bytes!("../assets/test.p12");
+const TEST_P12_NOCRYPT: &[u8] = include_bytes!("../assets/test-nocrypt.p12");
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+const TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+
+#[test]
+fn read_pkcs12_unencrypted() {
+    let (rem, pfx) = parse_pkcs12(TEST_P12_NOCRYPT).expect("could not parse PKCS#12");
+    assert!(rem.is_empty());
+    assert_eq!(pfx.version, 3);
+    assert!(pfx.mac_data.is_some());
+    assert!(!pfx.has_encrypted_content());
+    let certs: Vec<_> = pfx.iter_certificates().map(|cert| cert.as_raw()).collect();
+    assert_eq!(certs, [TEST_LEAF, TEST_CA]);
+    // first bag is the leaf certificate, with a friendly name
+    let bag = pfx.iter_safe_bags().next().expect("missing bag");
+    assert_eq!(bag.bag_id, OID_PKCS12_CERT_BAG);
+    assert_eq!(bag.friendly_name().as_deref(), Some("leaf"));
+    // the key bag is kept in raw form
+    assert_eq!(pfx.iter_safe_bags().count(), 3);
+}
+
+#[test]
+fn read_pkcs12_encrypted() {
+    let (rem, pfx) = parse_pkcs12(TEST_P12).expect("could not parse PKCS#12");
+    assert!(rem.is_empty());
+    assert!(pfx.has_encrypted_content());
+    // certificates are encrypted
+    assert_eq!(pfx.iter_certificates().count(), 0);
+    match &pfx.auth_safe[0] {
+        AuthenticatedSafeContent::EncryptedData(info) => {
+            assert_eq!(info.content_type, oid_registry::OID_PKCS7_ID_DATA);
+            assert!(!info.encrypted_content.is_empty());
+        }
+        _ => panic!("unexpected content type"),
+    }
+}
+
+#[cfg(feature = "decrypt")]
+#[test]
+fn read_pkcs12_decrypt() {
+    let (_, pfx) = parse_pkcs12(TEST_P12).expect("could not parse PKCS#12");
+    let certs = pfx
+        .decrypt_certificates("x509-parser")
+        .expect("decryption failed");
+    assert_eq!(certs.len(), 2);
+    assert_eq!(certs[0].as_raw(), TEST_LEAF);
+    assert_eq!(certs[1].as_raw(), TEST_CA);
+    // same result for unencrypted contents
+    let (_, pfx) = parse_pkcs12(TEST_P12_NOCRYPT).expect("could not parse PKCS#12");
+    let certs = pfx.decrypt_certificates("").expect("decryption failed");
+    assert_eq!(certs.len(), 2);
+}
+
+#[cfg(feature = "decrypt")]
+#[test]
+fn read_pkcs12_decrypt_wrong_password() {
+    let (_, pfx) = parse_pkcs12(TEST_P12).expect("could not parse PKCS#12");
+    let res = pfx.decrypt_certificates("wrong password");
+    assert_eq!(res.unwrap_err(), X509Error::PKCS12DecryptionError);
+}
@@ -0,0 +1,40 @@
+use x509_parser::pem::parse_x509_pem;
+use x509_parser::pkcs7::*;
+use x509_parser::prelude::*;
+
+const CERTS_P7B: &[u8] = include_bytes!("../assets/certs.p7b");
+const TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+const TEST_CA_CRL: &[u8] = include_bytes!("../assets/test-ca.crl");
+
+#[test]
+fn read_pkcs7_certs_only() {
+    let (rem, signed_data) = parse_pkcs7_signed_data(CERTS_P7B).expect("could not parse PKCS#7");
+    assert!(rem.is_empty());
+    assert!(signed_data.is_certs_only());
+    assert_eq!(signed_data.version, 1);
+    assert_eq!(signed_data.content_type, oid_registry::OID_PKCS7_ID_DATA);
+    let subjects: Vec<_> = signed_data
+        .iter_certificates()
+        .map(|cert| cert.subject().to_string())
+        .collect();
+    assert_eq!(
+        subjects,
+        [
+            "O=x509-parser, CN=Test CRL CA",
+            "O=x509-parser, CN=leaf.example.com"
+        ]
+    );
+    assert_eq!(signed_data.certificates[0].as_raw(), TEST_CA);
+    assert_eq!(signed_data.crls.len(), 1);
+    assert_eq!(signed_data.crls[0].as_raw(), TEST_CA_CRL);
+}
+
+#[test]
+fn read_pkcs7_pem() {
+    let mut pem_data = String::from("-----BEGIN PKCS7-----\n");
+    pem_data.push_str(&data_encoding::BASE64.encode(CERTS_P7B));
+    pem_data.push_str("\n-----END PKCS7-----\n");
+    let (_, pem) = parse_x509_pem(pem_data.as_bytes()).expect("invalid PEM");
+    let signed_data = pem.parse_pkcs7().expect("could not parse PKCS#7");
+    assert_eq!(signed_data.certificates.len(), 2);
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "hash")]
+
+use x509_parser::prelude::*;
+use x509_parser::store::{sha256_fingerprint, StoreCounts, X509Store};
+
+static TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+static TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+static IGC_A: &[u8] = include_bytes!("../assets/IGC_A.der");
+
+#[test]
+fn test_store_insert_unique() {
+    let (_, ca) = parse_x509_certificate(TEST_CA).expect("could not parse certificate");
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).expect("could not parse certificate");
+    let mut store = X509Store::new();
+    assert!(store.is_empty());
+    assert!(store.insert_unique(&leaf));
+    assert!(store.insert_unique(&ca));
+    // duplicates are ignored, whatever the type of the certificate
+    assert!(!store.insert_unique(&ca));
+    let owned = X509CertificateOwned::new(TEST_CA.to_vec()).unwrap();
+    assert!(!store.insert_unique(owned));
+    assert_eq!(store.len(), 2);
+    assert!(store.contains(&leaf));
+    let fingerprint = sha256_fingerprint(TEST_CA);
+    assert_eq!(store.get(&fingerprint).map(|c| c.as_raw()), Some(TEST_CA));
+    // iteration is sorted by fingerprint
+    let fingerprints: Vec<_> = store.iter().map(|(fp, _)| *fp).collect();
+    assert!(fingerprints.windows(2).all(|w| w[0] < w[1]));
+
+    let removed = store
+        .remove(&fingerprint)
+        .expect("certificate was not found");
+    assert_eq!(removed.as_raw(), TEST_CA);
+    assert!(store.get(&fingerprint).is_none());
+    assert!(store.insert_unique(removed));
+
+    // certificates without an encoding are not added
+    let (_, tbs) = TbsCertificate::from_der(leaf.tbs_certificate.as_ref()).unwrap();
+    let rebuilt = X509Certificate::from_parts(
+        tbs,
+        leaf.signature_algorithm.clone(),
+        leaf.signature_value.clone(),
+    );
+    assert!(!store.insert_unique(&rebuilt));
+    assert_eq!(store.len(), 2);
+}
+
+#[test]
+fn test_store_len_by_kind() {
+    let mut store = X509Store::new();
+    for der in &[TEST_CA, TEST_LEAF, IGC_A] {
+        let owned = X509CertificateOwned::new(der.to_vec()).unwrap();
+        assert!(store.insert_unique(owned));
+    }
+    let expected = StoreCounts {
+        roots: 2,
+        intermediates: 0,
+        leaves: 1,
+    };
+    assert_eq!(store.len_by_kind(), expected);
+}
+
+#[test]
+fn test_store_snapshot() {
+    use x509_parser::error::X509Error;
+
+    let mut store = X509Store::new();
+    for der in &[TEST_CA, TEST_LEAF, IGC_A] {
+        let owned = X509CertificateOwned::new(der.to_vec()).unwrap();
+        assert!(store.insert_unique(owned));
+    }
+    let snapshot = store.save_snapshot();
+    assert!(snapshot.starts_with(b"X509STOR\x01\x00\x00\x00\x03"));
+    let loaded = X509Store::load_snapshot(&snapshot).expect("could not load snapshot");
+    assert_eq!(loaded, store);
+    assert_eq!(loaded.len_by_kind(), store.len_by_kind());
+    let fingerprint = sha256_fingerprint(TEST_LEAF);
+    let leaf = loaded.get(&fingerprint).expect("certificate was not found");
+    assert_eq!(leaf.certificate().unwrap().as_raw(), TEST_LEAF);
+    assert_eq!(
+        X509Store::load_snapshot(&X509Store::new().save_snapshot()),
+        Ok(X509Store::new())
+    );
+
+    // truncated, trailing data, unknown version, corrupted certificate
+    let invalid = Err(X509Error::InvalidStoreSnapshot);
+    assert_eq!(
+        X509Store::load_snapshot(&snapshot[..snapshot.len() - 1]),
+        invalid
+    );
+    let mut data = snapshot.clone();
+    data.push(0);
+    assert_eq!(X509Store::load_snapshot(&data), invalid);
+    let mut data = snapshot.clone();
+    data[8] = 2;
+    assert_eq!(X509Store::load_snapshot(&data), invalid);
+    let mut data = snapshot;
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    assert_eq!(X509Store::load_snapshot(&data), invalid);
+}
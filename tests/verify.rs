@@ -33,3 +33,101 @@ fn test_signature_verification_ed25519() {
     eprintln!("Verification: {:?}", res);
     assert!(res.is_ok());
 }
+
+static TEST_CA: &[u8] = include_bytes!("../assets/test-ca.der");
+static TEST_LEAF: &[u8] = include_bytes!("../assets/test-leaf.der");
+
+#[test]
+fn test_verification_cache() {
+    use x509_parser::error::X509Error;
+    use x509_parser::verify::VerificationCache;
+
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).expect("could not parse certificate");
+    let (_, ca) = parse_x509_certificate(TEST_CA).expect("could not parse certificate");
+    let mut cache = VerificationCache::new();
+    let chain = vec![leaf.clone(), ca.clone()];
+    assert!(cache.verify_chain_signatures(&chain).is_ok());
+    assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    // a second chain sharing the same CA
+    assert!(cache.verify_chain_signatures(&chain).is_ok());
+    assert_eq!((cache.hits(), cache.misses()), (2, 2));
+    // failures are cached too
+    let res = cache.verify_signature(&ca, leaf.public_key());
+    assert_eq!(res, Err(X509Error::SignatureVerificationError));
+    let res = cache.verify_signature(&ca, leaf.public_key());
+    assert_eq!(res, Err(X509Error::SignatureVerificationError));
+    assert_eq!((cache.hits(), cache.misses()), (3, 3));
+
+    let constraints = cache.constraints(&ca);
+    assert!(constraints.is_ca);
+    assert_eq!(constraints.key_cert_sign, Some(true));
+    assert!(constraints.extensions_valid);
+    assert!(!cache.constraints(&leaf).is_ca);
+    assert_eq!(cache.constraints(&ca), constraints);
+    assert_eq!(cache.len(), 5);
+    cache.clear();
+    assert!(cache.is_empty());
+
+    // the error locates the certificate in the chain
+    let err = cache
+        .verify_chain_signatures(&[ca.clone(), leaf.clone()])
+        .unwrap_err();
+    assert_eq!(err.error, X509Error::SignatureVerificationError);
+    assert_eq!(err.chain_index, Some(0));
+    assert_eq!(err.expected, Some(ca.issuer().to_string()));
+    assert_eq!(err.found, Some(leaf.subject().to_string()));
+    assert!(err
+        .explanation()
+        .starts_with("Certificate #0 of the chain: "));
+}
+
+#[test]
+fn test_verification_cache_from_parts() {
+    use x509_parser::certificate::TbsCertificate;
+    use x509_parser::prelude::{FromDer, X509Certificate};
+    use x509_parser::verify::VerificationCache;
+
+    // certificates built from a separately parsed TBS certificate have no encoding
+    fn from_parts<'a>(cert: &X509Certificate<'a>) -> X509Certificate<'a> {
+        let (tbs, signature_value, signature_algorithm) = cert.signature_input();
+        let (_, tbs_certificate) = TbsCertificate::from_der(tbs).expect("could not parse TBS");
+        X509Certificate::from_parts(
+            tbs_certificate,
+            signature_algorithm.clone(),
+            signature_value.clone(),
+        )
+    }
+
+    let (_, leaf) = parse_x509_certificate(TEST_LEAF).expect("could not parse certificate");
+    let (_, ca) = parse_x509_certificate(TEST_CA).expect("could not parse certificate");
+    let (leaf, ca) = (from_parts(&leaf), from_parts(&ca));
+    assert!(leaf.as_raw().is_empty() && ca.as_raw().is_empty());
+    let mut cache = VerificationCache::new();
+    assert!(cache.verify_signature(&leaf, ca.public_key()).is_ok());
+    // the self-signed CA verifies with its own key, but not the leaf
+    assert!(cache.verify_signature(&ca, ca.public_key()).is_ok());
+    assert!(cache.verify_signature(&leaf, leaf.public_key()).is_err());
+    assert_eq!((cache.hits(), cache.misses()), (0, 3));
+    assert!(cache.constraints(&ca).is_ca);
+    assert!(!cache.constraints(&leaf).is_ca);
+    assert_eq!((cache.hits(), cache.misses()), (0, 5));
+    assert!(cache.verify_chain_signatures(&[leaf, ca]).is_ok());
+    assert_eq!(cache.hits(), 2);
+}
+
+#[test]
+fn test_verification_cache_v1_ca_policy() {
+    use x509_parser::certificate::V1CaPolicy;
+    use x509_parser::verify::VerificationCache;
+
+    let (_, leaf) = parse_x509_certificate(include_bytes!("../assets/test-v1-leaf.der"))
+        .expect("could not parse certificate");
+    let (_, ca) = parse_x509_certificate(TEST_CA).expect("could not parse certificate");
+    let mut cache = VerificationCache::new();
+    assert!(cache.verify_chain_signatures(&[leaf.clone(), ca]).is_ok());
+    assert!(!cache.constraints(&leaf).is_ca);
+    let mut cache = VerificationCache::new().with_v1_ca_policy(V1CaPolicy::Always);
+    assert!(cache.constraints(&leaf).is_ca);
+    cache.clear();
+    assert!(cache.constraints(&leaf).is_ca);
+}